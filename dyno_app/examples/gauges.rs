@@ -1,23 +1,18 @@
 use dynotest_app::widgets::{Gauge, GaugeTypes};
-use eframe::epaint::Vec2;
+use eframe::egui::ViewportBuilder;
 
 fn main() {
     let native_options = eframe::NativeOptions {
-        transparent: false,
-        maximized: false,
-        fullscreen: false,
-        always_on_top: true,
-        initial_window_size: Some(Vec2 {
-            x: 720_f32,
-            y: 480_f32,
-        }),
-        resizable: false,
+        viewport: ViewportBuilder::default()
+            .with_always_on_top()
+            .with_inner_size([720_f32, 480_f32])
+            .with_resizable(false),
         ..Default::default()
     };
     eframe::run_native(
         "Image Viewer",
         native_options,
-        Box::new(|cc| Box::new(ExampleGauge::new(cc))),
+        Box::new(|cc| Ok(Box::new(ExampleGauge::new(cc)))),
     )
     .expect("ERROR: Failed 'eframe::run_native'");
 }
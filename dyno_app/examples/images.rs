@@ -2,19 +2,18 @@ use dynotest_app::widgets::images::Img;
 
 fn main() {
     let native_options = eframe::NativeOptions {
-        transparent: false,
-        maximized: false,
-        fullscreen: false,
-        always_on_top: true,
-        initial_window_size: Some(eframe::egui::Vec2 {
-            x: 720_f32,
-            y: 480_f32,
-        }),
-        resizable: false,
+        viewport: eframe::egui::ViewportBuilder::default()
+            .with_always_on_top()
+            .with_inner_size([720_f32, 480_f32])
+            .with_resizable(false),
         ..Default::default()
     };
-    eframe::run_native("Image Viewer", native_options, Box::new(ExampleImages::new))
-        .expect("ERROR: Failed 'eframe::run_native'");
+    eframe::run_native(
+        "Image Viewer",
+        native_options,
+        Box::new(|cc| Ok(ExampleImages::new(cc))),
+    )
+    .expect("ERROR: Failed 'eframe::run_native'");
 }
 
 struct ExampleImages {
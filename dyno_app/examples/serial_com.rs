@@ -22,9 +22,11 @@ fn main() {
         })
     });
 
-    let Some(mut serial_service) = service::SerialService::new() else { return };
+    let Some(mut serial_service) = service::SerialService::new() else {
+        return;
+    };
     let (tx, rx) = unbounded();
-    let handle = match serial_service.start(tx) {
+    let handle = match serial_service.start(service::SerialSettings::default(), tx) {
         Ok(k) => k,
         Err(err) => {
             eprintln!("ERROR: Failed to start serial service - {err}");
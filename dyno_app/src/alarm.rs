@@ -0,0 +1,564 @@
+//! alarm rules that is checked on every data of the running service, e.g. the engine temperature
+//! is too high or the roller is overspeed. the alarm is raised once until the condition is
+//! cleared, the raised alarm is logged into the annotations of the run.
+
+use derive_more::Display;
+use dyno_core::{
+    i18n::{tr, tr_attr},
+    serde,
+    types::Data,
+    DynoConfig,
+};
+use eframe::egui::{Checkbox, ComboBox, DragValue, Grid, TextEdit, Ui};
+
+use crate::widgets::PlotChannel;
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum AlarmCondition {
+    Above(f64),
+    Below(f64),
+    /// the value change faster than this per second, rising or falling
+    RateAbove(f64),
+    /// the sensor report a fault (e.g. the thermocouple is unplugged), or the value is outside
+    /// of the range the sensor can read
+    SensorFault {
+        min: f64,
+        max: f64,
+    },
+}
+
+impl AlarmCondition {
+    /// message key of every kind
+    const KINDS: [&'static str; 4] = [
+        "alarm-above",
+        "alarm-below",
+        "alarm-rate-above",
+        "alarm-sensor-fault",
+    ];
+
+    const fn kind(&self) -> &'static str {
+        match self {
+            Self::Above(_) => Self::KINDS[0],
+            Self::Below(_) => Self::KINDS[1],
+            Self::RateAbove(_) => Self::KINDS[2],
+            Self::SensorFault { .. } => Self::KINDS[3],
+        }
+    }
+
+    /// condition of `kind` with the limit of `self` if it has one
+    fn with_kind(self, kind: &str) -> Self {
+        let limit = match self {
+            Self::Above(limit) | Self::Below(limit) | Self::RateAbove(limit) => limit,
+            Self::SensorFault { max, .. } => max,
+        };
+        match kind {
+            "alarm-above" => Self::Above(limit),
+            "alarm-below" => Self::Below(limit),
+            "alarm-rate-above" => Self::RateAbove(limit),
+            _ => Self::SensorFault {
+                min: 0.,
+                max: limit,
+            },
+        }
+    }
+
+    /// `rate` is the change per second from the previous data, `None` on the first data
+    pub fn is_met(&self, value: f64, rate: Option<f64>) -> bool {
+        match *self {
+            Self::Above(limit) => value > limit,
+            Self::Below(limit) => value < limit,
+            Self::RateAbove(limit) => rate.is_some_and(|rate| rate.abs() > limit),
+            Self::SensorFault { min, max } => !value.is_finite() || value < min || value > max,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub const ALL: [Self; 3] = [Self::Info, Self::Warning, Self::Critical];
+
+    /// translated name of the severity
+    pub fn label(self) -> String {
+        tr(match self {
+            Self::Info => "severity-info",
+            Self::Warning => "severity-warning",
+            Self::Critical => "severity-critical",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default, crate = "serde")]
+pub struct AlarmActions {
+    pub toast: bool,
+    /// audible alert on the default output device, see [`play_sound`]
+    pub sound: bool,
+    /// blink the border of the window and request the attention of the window
+    pub bell: bool,
+    /// flash the dashboard tiles of the channel
+    pub flash: bool,
+    /// end the recording of the run, `stop` of the older config is read as this one
+    #[serde(alias = "stop")]
+    pub stop_recording: bool,
+    /// send the stop command to the device, the recording keep going
+    pub stop_device: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct AlarmRule {
+    pub name: String,
+    pub enabled: bool,
+    pub channel: PlotChannel,
+    pub condition: AlarmCondition,
+    pub severity: Severity,
+    pub actions: AlarmActions,
+}
+
+impl AlarmRule {
+    fn new(
+        name: impl Into<String>,
+        channel: PlotChannel,
+        condition: AlarmCondition,
+        severity: Severity,
+        actions: AlarmActions,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            enabled: true,
+            channel,
+            condition,
+            severity,
+            actions,
+        }
+    }
+
+    /// value of the channel in `data` if the condition is met
+    pub fn check(&self, prev: Option<&Data>, data: &Data) -> Option<f64> {
+        if matches!(self.condition, AlarmCondition::SensorFault { .. })
+            && self.channel.is_fault(data)
+        {
+            return Some(self.channel.value(data));
+        }
+        let value = self.channel.value(data);
+        let rate = prev
+            .filter(|prev| data.timestamp > prev.timestamp)
+            .map(|prev| {
+                let seconds = (data.timestamp - prev.timestamp) as f64 / 1000.;
+                (value - self.channel.value(prev)) / seconds
+            });
+        self.condition.is_met(value, rate).then_some(value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, crate = "serde")]
+pub struct AlarmSettings {
+    pub enabled: bool,
+    pub rules: Vec<AlarmRule>,
+}
+
+impl Default for AlarmSettings {
+    fn default() -> Self {
+        use AlarmCondition::*;
+        use PlotChannel::*;
+
+        let all = AlarmActions {
+            toast: true,
+            sound: true,
+            bell: true,
+            flash: true,
+            stop_recording: false,
+            stop_device: false,
+        };
+        Self {
+            enabled: true,
+            rules: vec![
+                AlarmRule::new(
+                    tr("alarm-engine-temp-high"),
+                    Temp(0),
+                    Above(100.),
+                    Severity::Warning,
+                    AlarmActions {
+                        sound: false,
+                        bell: false,
+                        ..all
+                    },
+                ),
+                AlarmRule::new(
+                    tr("alarm-engine-overheat"),
+                    Temp(0),
+                    Above(115.),
+                    Severity::Critical,
+                    all,
+                ),
+                AlarmRule::new(
+                    tr("alarm-thermocouple-fault"),
+                    Temp(0),
+                    SensorFault {
+                        min: -20.,
+                        max: 400.,
+                    },
+                    Severity::Warning,
+                    AlarmActions {
+                        toast: true,
+                        ..Default::default()
+                    },
+                ),
+                AlarmRule::new(
+                    tr("alarm-roller-overspeed"),
+                    Speed,
+                    Above(200.),
+                    Severity::Critical,
+                    AlarmActions {
+                        stop_recording: true,
+                        stop_device: true,
+                        ..all
+                    },
+                ),
+            ],
+        }
+    }
+}
+
+impl AlarmSettings {
+    pub fn ui(&mut self, ui: &mut Ui, config: &DynoConfig) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.enabled, tr("alarm-enable"));
+            if ui.button(format!("➕ {}", tr("alarm-add-rule"))).clicked() {
+                self.rules.push(AlarmRule::new(
+                    tr("alarm-new-rule"),
+                    PlotChannel::Speed,
+                    AlarmCondition::Above(0.),
+                    Severity::Warning,
+                    AlarmActions {
+                        toast: true,
+                        ..Default::default()
+                    },
+                ));
+            }
+            if ui.button(tr("action-reset")).clicked() {
+                *self = Self::default();
+            }
+        });
+        let mut removed = None;
+        Grid::new("dyno_alarm_rules_grid")
+            .num_columns(6)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong(tr("alarm-rule"));
+                ui.strong(tr("alarm-channel"));
+                ui.strong(tr("alarm-condition"));
+                ui.strong(tr("alarm-severity"));
+                ui.strong(tr("alarm-actions"));
+                ui.end_row();
+                for (idx, rule) in self.rules.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add(Checkbox::without_text(&mut rule.enabled));
+                        ui.add(TextEdit::singleline(&mut rule.name).desired_width(160.));
+                    });
+                    ComboBox::from_id_source(("dyno_alarm_channel", idx))
+                        .selected_text(rule.channel.name(config))
+                        .show_ui(ui, |ui| {
+                            for channel in PlotChannel::all(config) {
+                                ui.selectable_value(
+                                    &mut rule.channel,
+                                    channel,
+                                    channel.name(config),
+                                );
+                            }
+                        });
+                    ui.horizontal(|ui| {
+                        ComboBox::from_id_source(("dyno_alarm_condition", idx))
+                            .width(90.)
+                            .selected_text(tr(rule.condition.kind()))
+                            .show_ui(ui, |ui| {
+                                for kind in AlarmCondition::KINDS {
+                                    if ui
+                                        .selectable_label(rule.condition.kind() == kind, tr(kind))
+                                        .clicked()
+                                    {
+                                        rule.condition = rule.condition.with_kind(kind);
+                                    }
+                                }
+                            });
+                        match &mut rule.condition {
+                            AlarmCondition::Above(limit) | AlarmCondition::Below(limit) => {
+                                ui.add(DragValue::new(limit).suffix(rule.channel.unit()));
+                            }
+                            AlarmCondition::RateAbove(limit) => {
+                                ui.add(
+                                    DragValue::new(limit)
+                                        .suffix(format!("{}/s", rule.channel.unit())),
+                                );
+                            }
+                            AlarmCondition::SensorFault { min, max } => {
+                                ui.add(DragValue::new(min));
+                                ui.label("-");
+                                ui.add(DragValue::new(max).suffix(rule.channel.unit()));
+                            }
+                        }
+                    });
+                    ComboBox::from_id_source(("dyno_alarm_severity", idx))
+                        .width(80.)
+                        .selected_text(rule.severity.label())
+                        .show_ui(ui, |ui| {
+                            for severity in Severity::ALL {
+                                ui.selectable_value(&mut rule.severity, severity, severity.label());
+                            }
+                        });
+                    ui.horizontal(|ui| {
+                        let actions = &mut rule.actions;
+                        ui.checkbox(&mut actions.toast, tr("alarm-toast"));
+                        ui.checkbox(&mut actions.sound, tr("alarm-sound"))
+                            .on_hover_text(tr_attr("alarm-sound", "hint"));
+                        ui.checkbox(&mut actions.bell, tr("alarm-bell"))
+                            .on_hover_text(tr_attr("alarm-bell", "hint"));
+                        ui.checkbox(&mut actions.flash, tr("alarm-flash"));
+                        ui.checkbox(&mut actions.stop_recording, tr("alarm-stop-recording"))
+                            .on_hover_text(tr_attr("alarm-stop-recording", "hint"));
+                        ui.checkbox(&mut actions.stop_device, tr("alarm-stop-device"))
+                            .on_hover_text(tr_attr("alarm-stop-device", "hint"));
+                    });
+                    if ui.small_button("🗑").clicked() {
+                        removed = Some(idx);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(idx) = removed {
+            self.rules.remove(idx);
+        }
+    }
+}
+
+/// alarm that is raised by the data at `timestamp`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlarmEvent {
+    pub timestamp: i64,
+    pub name: String,
+    pub channel: PlotChannel,
+    pub severity: Severity,
+    pub value: f64,
+    pub actions: AlarmActions,
+}
+
+impl AlarmEvent {
+    pub fn message(&self) -> String {
+        dyno_core::tr!(
+            "alarm-message",
+            severity = self.severity.label(),
+            name = self.name.as_str(),
+            value = format!("{:.1}", self.value),
+            unit = self.channel.unit(),
+        )
+    }
+}
+
+/// beep on the default output device without blocking the ui, three times for
+/// [`Severity::Critical`]. failing to open the device is only logged
+pub fn play_sound(severity: Severity) {
+    use rodio::{source::SineWave, OutputStream, Sink, Source};
+    use std::time::Duration;
+
+    let beeps = match severity {
+        Severity::Critical => 3,
+        _ => 1,
+    };
+    let spawned = std::thread::Builder::new()
+        .name("dyno_alarm_sound".to_owned())
+        .spawn(move || {
+            // the stream must outlive the sink until the end of the sound
+            let (_stream, handle) = match OutputStream::try_default() {
+                Ok(ok) => ok,
+                Err(err) => return dyno_core::log::warn!("no audio output for alarm: {err}"),
+            };
+            let sink = match Sink::try_new(&handle) {
+                Ok(ok) => ok,
+                Err(err) => return dyno_core::log::warn!("failed playing alarm: {err}"),
+            };
+            for _ in 0..beeps {
+                let beep = SineWave::new(880.)
+                    .take_duration(Duration::from_millis(250))
+                    .amplify(0.4);
+                sink.append(beep);
+                sink.append(
+                    rodio::source::Zero::<f32>::new(1, 48_000)
+                        .take_duration(Duration::from_millis(150)),
+                );
+            }
+            sink.sleep_until_end();
+        });
+    if let Err(err) = spawned {
+        dyno_core::log::warn!("failed playing alarm: {err}");
+    }
+}
+
+/// state of the rules of the running service
+#[derive(Debug, Default)]
+pub struct AlarmEngine {
+    /// the rule at the same index is raised and not cleared yet
+    raised: Vec<bool>,
+    last: Option<Data>,
+    /// channels of the raised rules with [`AlarmActions::flash`]
+    flashing: Vec<PlotChannel>,
+}
+
+impl AlarmEngine {
+    /// forget the previous data and the raised alarm, e.g. the recording is started
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    #[inline]
+    pub fn flashing(&self) -> &[PlotChannel] {
+        &self.flashing
+    }
+
+    /// check `data` against every rule, return the alarms that is newly raised
+    pub fn check(&mut self, settings: &AlarmSettings, data: &Data) -> Vec<AlarmEvent> {
+        let prev = self.last.replace(*data);
+        self.raised.resize(settings.rules.len(), false);
+        self.flashing.clear();
+        if !settings.enabled {
+            self.raised.fill(false);
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        for (rule, raised) in settings.rules.iter().zip(self.raised.iter_mut()) {
+            let value = rule.check(prev.as_ref(), data).filter(|_| rule.enabled);
+            match (value, *raised) {
+                (Some(value), false) => events.push(AlarmEvent {
+                    timestamp: data.timestamp,
+                    name: rule.name.clone(),
+                    channel: rule.channel,
+                    severity: rule.severity,
+                    value,
+                    actions: rule.actions,
+                }),
+                (None, true) => dyno_core::log::info!("alarm `{}` is cleared", rule.name),
+                _ => {}
+            }
+            *raised = value.is_some();
+            if *raised && rule.actions.flash && !self.flashing.contains(&rule.channel) {
+                self.flashing.push(rule.channel);
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::{
+        types::{DynotestData, RawSerialData, RawTemp, RawTemps},
+        uom::si::{f64::*, temperature_interval::degree_celsius},
+    };
+
+    fn data(timestamp: i64, temp: f64) -> Data {
+        let mut data = Data {
+            timestamp,
+            ..Default::default()
+        };
+        data.temps[0] = TemperatureInterval::new::<degree_celsius>(temp);
+        data
+    }
+
+    #[test]
+    fn alarm_conditions() {
+        assert!(AlarmCondition::Above(10.).is_met(11., None));
+        assert!(!AlarmCondition::Below(10.).is_met(11., None));
+        assert!(!AlarmCondition::RateAbove(5.).is_met(0., None));
+        assert!(AlarmCondition::RateAbove(5.).is_met(0., Some(-6.)));
+        let fault = AlarmCondition::SensorFault { min: 0., max: 10. };
+        assert!(fault.is_met(f64::NAN, None));
+        assert!(fault.is_met(11., None));
+        assert!(!fault.is_met(5., None));
+
+        let rule = AlarmRule::new(
+            "rate",
+            PlotChannel::Temp(0),
+            AlarmCondition::RateAbove(10.),
+            Severity::Info,
+            AlarmActions::default(),
+        );
+        // 6 °C in 500 ms
+        assert_eq!(rule.check(Some(&data(0, 20.)), &data(500, 26.)), Some(26.));
+        assert_eq!(rule.check(Some(&data(0, 20.)), &data(1_000, 26.)), None);
+    }
+
+    #[test]
+    fn alarm_actions_config() {
+        // saved before the actions is splitted
+        let actions: AlarmActions =
+            toml::from_str("toast = true\nsound = true\nstop = true").unwrap();
+        let expected = AlarmActions {
+            toast: true,
+            sound: true,
+            stop_recording: true,
+            ..Default::default()
+        };
+        assert_eq!(actions, expected);
+    }
+
+    #[test]
+    fn alarm_engine() {
+        let settings = AlarmSettings::default();
+        let mut engine = AlarmEngine::default();
+        assert!(engine.check(&settings, &data(0, 90.)).is_empty());
+
+        let events = engine.check(&settings, &data(250, 120.));
+        let names = events.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["Engine temperature high", "Engine overheat"]);
+        assert_eq!(
+            events[1].message(),
+            "ALARM Critical: Engine overheat (120.0 °C)"
+        );
+        assert_eq!(engine.flashing(), [PlotChannel::Temp(0)]);
+
+        // raised once until cleared
+        assert!(engine.check(&settings, &data(500, 121.)).is_empty());
+        assert!(engine.check(&settings, &data(750, 90.)).is_empty());
+        assert!(engine.flashing().is_empty());
+        assert_eq!(engine.check(&settings, &data(1_000, 101.)).len(), 1);
+
+        let disabled = AlarmSettings {
+            enabled: false,
+            ..settings
+        };
+        assert!(engine.check(&disabled, &data(1_250, 500.)).is_empty());
+    }
+
+    #[test]
+    fn alarm_thermocouple_fault() {
+        let frame = |raw: RawTemp| RawSerialData {
+            raw_temps: RawTemps::from_array([raw]),
+            ..RawSerialData::new()
+        };
+        let mut buffer = DynotestData::default();
+        // 80 °C, then the thermocouple is unplugged
+        buffer.push_from_raw_serial_data(frame(RawTemp::max6675(320 << 3)));
+        buffer.push_from_raw_serial_data(frame(RawTemp::fault(Default::default())));
+        let (good, faulted) = (buffer[buffer.len() - 2], buffer[buffer.len() - 1]);
+        assert!(!good.is_temp_fault(0) && faulted.is_temp_fault(0));
+        // the last good reading is kept, so only the fault flag raise the alarm
+        assert_eq!(faulted.temps[0], good.temps[0]);
+
+        let settings = AlarmSettings::default();
+        let mut engine = AlarmEngine::default();
+        assert!(engine.check(&settings, &good).is_empty());
+        let events = engine.check(&settings, &faulted);
+        let names = events.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["Thermocouple fault"]);
+    }
+}
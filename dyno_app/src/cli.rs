@@ -0,0 +1,460 @@
+//! headless mode, record and manage the runs without the window, e.g. on a small linux box next
+//! to the dyno that upload the runs later. the window is opened when there is no subcommand,
+//! with the file arguments opened like the dropped files
+//!
+//! ```text
+//! dynotest_app before.dyno after.dyno
+//! dynotest_app record run.dyno --duration 60 --until "rpm_engine<1500"
+//! dynotest_app convert run.dyno run.xlsx
+//! dynotest_app upload run.dyno
+//! ```
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use clap::{Args, Parser, Subcommand};
+use dyno_core::{
+    crossbeam_channel::{unbounded, RecvTimeoutError},
+    report::RunSummary,
+    tokio,
+    types::{Data, DynotestData},
+    CsvSaver, DynoConfig, DynoErr, DynoResult, DynoSaver, ExcelSaver,
+};
+
+use crate::{
+    config::ApplicationConfig,
+    paths::DynoPaths,
+    service::{
+        get_dyno_port, get_dyno_ports, ApiService, Outbox, PendingUpload, PortInfo, SerialService,
+    },
+    session::{open_runs, DynoRun, DynoSession},
+    state::DynoFileType,
+    widgets::PlotChannel,
+    AsyncMsg, PACKAGE_INFO,
+};
+
+#[derive(Debug, Parser)]
+#[command(name = "dynotest_app", version, about = "DynoTests Polije")]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// run files to open in the window, more than one file is opened as runs to be compared
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// record from the device until the duration, Ctrl-C or the `--until` condition
+    Record(RecordArgs),
+    /// convert the run between the `.dyno`, `.csv`, `.xlsx` and `.dynosession` format
+    ///
+    /// the format is chosen by the file extension, the active run of the session is converted
+    Convert { input: PathBuf, output: PathBuf },
+    /// queue the runs to be saved to the active server and send every queued run
+    ///
+    /// the login of the app is used. the runs that failed stay queued, the app send them later
+    Upload {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// list the connected dyno devices, the first one is used by `record`
+    ListPorts,
+    /// print the peaks of the runs, every run of the session file
+    Summary {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct RecordArgs {
+    /// the run file, the format is chosen by the extension
+    pub output: PathBuf,
+    /// port name of the device, the first connected device if not set
+    #[arg(long, conflicts_with = "replay")]
+    pub port: Option<String>,
+    /// replay the `.dynocap` capture file instead of the device
+    #[arg(long)]
+    pub replay: Option<PathBuf>,
+    /// also capture the raw bytes of the device to this file
+    #[arg(long)]
+    pub capture: Option<PathBuf>,
+    /// stop after this many seconds
+    #[arg(long)]
+    pub duration: Option<f64>,
+    /// stop at the end of the segment, e.g. `rpm_engine<1500`
+    ///
+    /// `rpm_engine<1500` stop when the engine rpm fall below 1500 after it has been above.
+    /// channel is one of speed, rpm_engine, rpm_roda, torque, power or temp0..temp3
+    #[arg(long)]
+    pub until: Option<StopCondition>,
+}
+
+/// end of the segment of a run, the condition is only checked after the channel has been on the
+/// other side, so the recording is not stopped before the run is started
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StopCondition {
+    pub channel: PlotChannel,
+    pub below: bool,
+    pub value: f64,
+    armed: bool,
+}
+
+impl StopCondition {
+    /// `true` at the end of the segment
+    pub fn update(&mut self, data: &Data) -> bool {
+        let value = self.channel.value(data);
+        let met = match self.below {
+            true => value < self.value,
+            false => value > self.value,
+        };
+        if !met {
+            self.armed = true;
+        }
+        met && self.armed
+    }
+}
+
+impl FromStr for StopCondition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (idx, below) = s
+            .find(['<', '>'])
+            .map(|idx| (idx, s[idx..].starts_with('<')))
+            .ok_or("expected `channel<value` or `channel>value`")?;
+        let channel = match s[..idx].trim().to_lowercase().as_str() {
+            "speed" => PlotChannel::Speed,
+            "rpm_engine" | "rpm" => PlotChannel::RpmEngine,
+            "rpm_roda" => PlotChannel::RpmRoda,
+            "torque" => PlotChannel::Torque,
+            "power" | "horsepower" => PlotChannel::Power,
+            temp => match temp.strip_prefix("temp").map(str::parse::<usize>) {
+                Some(Ok(idx)) if idx < dyno_core::types::MAX_TEMP_CHANNELS => {
+                    PlotChannel::Temp(idx)
+                }
+                _ => return Err(format!("unknown channel `{temp}`")),
+            },
+        };
+        let value = s[idx + 1..]
+            .trim()
+            .parse()
+            .map_err(|err| format!("invalid value - {err}"))?;
+        Ok(Self {
+            channel,
+            below,
+            value,
+            armed: false,
+        })
+    }
+}
+
+/// run the subcommand, the tokio runtime must be entered
+pub fn run(command: Command) -> DynoResult<()> {
+    match command {
+        Command::Record(args) => record(args),
+        Command::Convert { input, output } => {
+            let format = DynoFileType::from_path(&output)?;
+            let data = match DynoFileType::from_path(&input)? {
+                DynoFileType::Session => DynoSession::open(&input)?.buffer().clone(),
+                _ => open_runs(&input)?.remove(0).data,
+            };
+            save(data, &output, format)?;
+            println!("converted `{}` to `{}`", input.display(), output.display());
+            Ok(())
+        }
+        Command::Upload { files } => upload(&files),
+        Command::ListPorts => {
+            let ports = get_dyno_ports()?;
+            if ports.is_empty() {
+                println!("there is no dyno device connected");
+            }
+            for port in ports {
+                println!(
+                    "{}\t{:04x}:{:04x}\t{}",
+                    port.display_name(),
+                    port.vid,
+                    port.pid,
+                    port.product.as_deref().unwrap_or("-")
+                );
+            }
+            Ok(())
+        }
+        Command::Summary { files } => {
+            for file in files {
+                for run in open_runs(&file)? {
+                    print_summary(&format!("{} - {}", file.display(), run.name), &run.data);
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// the paths and the configs of the app, the same files that the window use
+fn load_config() -> DynoResult<(DynoPaths, ApplicationConfig, DynoConfig)> {
+    let paths = DynoPaths::new(PACKAGE_INFO.app_name)?;
+    let app_config = paths
+        .get_config::<ApplicationConfig>("app_config.toml")
+        .unwrap_or_default();
+    let config = paths
+        .get_config::<DynoConfig>("config.toml")
+        .unwrap_or_default();
+    Ok((paths, app_config, config))
+}
+
+fn save(data: DynotestData, path: &Path, format: DynoFileType) -> DynoResult<()> {
+    let dropped = data.annotations().len();
+    if dropped > 0 && !format.keeps_annotations() {
+        eprintln!(
+            "warning: {}",
+            dyno_core::tr!(
+                "save-drops-annotations",
+                format = format.to_string(),
+                count = dropped
+            )
+        );
+    }
+    match format {
+        DynoFileType::Session => {
+            let mut session = DynoSession::new(data.config.clone());
+            *session.active_mut() = DynoRun::with_data("Run 1", data);
+            session.save(path)
+        }
+        DynoFileType::Dyno => data.save_dyno_from_path(path),
+        DynoFileType::Csv => data.save_csv_from_path(path),
+        DynoFileType::Excel => data.save_excel_from_path(path),
+    }
+}
+
+fn print_summary(title: &str, data: &DynotestData) {
+    let summary = RunSummary::from_data(data);
+    println!("{title}");
+    println!("  data     : {}", data.len());
+    println!("  duration : {:.1} s", summary.duration_ms as f64 / 1000.);
+    println!(
+        "  power    : {:.2} HP @ {:.0} rpm",
+        summary.max_power, summary.max_power_rpm
+    );
+    println!(
+        "  torque   : {:.2} Nm @ {:.0} rpm",
+        summary.max_torque, summary.max_torque_rpm
+    );
+    println!("  speed    : {:.1} km/h", summary.max_speed);
+    println!("  odo      : {:.3} km", summary.odo_km);
+}
+
+fn record(args: RecordArgs) -> DynoResult<()> {
+    let format = DynoFileType::from_path(&args.output)?;
+    let (_, app_config, config) = load_config()?;
+    let mut serial = match (&args.replay, &args.port) {
+        (Some(replay), _) => SerialService::replay(replay.clone(), 1.0),
+        (None, Some(port)) => {
+            let info = get_dyno_ports()?
+                .into_iter()
+                .find(|info| &info.port_name == port)
+                .unwrap_or_else(|| PortInfo::virtual_port(port));
+            SerialService::with_port(info)
+        }
+        (None, None) => SerialService::with_port(
+            get_dyno_port()?
+                .ok_or_else(|| DynoErr::service_error("There is no dyno device connected"))?,
+        ),
+    };
+    serial.set_capture(args.capture.clone());
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    tokio::spawn({
+        let interrupted = interrupted.clone();
+        async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                interrupted.store(true, Ordering::Relaxed);
+            }
+        }
+    });
+
+    let mut data = DynotestData::new(config);
+    data.start();
+    let (tx, rx) = unbounded();
+    let handle = serial.start(app_config.serial.clone(), tx)?;
+    eprintln!(
+        "recording from `{}` to `{}`, press Ctrl-C to stop",
+        serial.get_info().display_name(),
+        args.output.display()
+    );
+
+    let deadline = args
+        .duration
+        .map(|secs| Instant::now() + Duration::from_secs_f64(secs.max(0.)));
+    let mut until = args.until;
+    let reason = loop {
+        if interrupted.load(Ordering::Relaxed) {
+            break "interrupted";
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            break "duration is reached";
+        }
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(AsyncMsg::OnSerialData(raw)) => {
+                data.push_from_raw_serial_data(raw);
+                if until
+                    .as_mut()
+                    .is_some_and(|until| until.update(data.last()))
+                {
+                    break "end of the segment";
+                }
+            }
+            Ok(AsyncMsg::OnSerialConfig(device)) => {
+                data.config.apply_device_config(&device);
+                eprintln!("loaded config from device `{}`", device.name);
+            }
+            Ok(AsyncMsg::OnSerialDisconnected(err)) => {
+                eprintln!("device disconnected, waiting to reconnect - {err}");
+            }
+            Ok(AsyncMsg::OnSerialReconnected(info, gap)) => {
+                eprintln!(
+                    "device reconnected on `{}` after {:.1}s",
+                    info.display_name(),
+                    gap.as_secs_f32()
+                );
+                data.record_gap(dyno_core::chrono::Duration::from_std(gap).unwrap_or_default());
+            }
+            Ok(AsyncMsg::OnError(err)) => eprintln!("error: {err}"),
+            Ok(_) => {}
+            // the service is stopped, e.g. the end of the replay or failed to start streaming
+            Err(RecvTimeoutError::Timeout) if !serial.is_open() => break "device is stopped",
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break "device is stopped",
+        }
+    };
+    serial.stop();
+    // wait the stop command is sent and the capture is flushed
+    if let Err(err) = tokio::runtime::Handle::current().block_on(handle) {
+        dyno_core::log::error!("serial service is panicked - {err}");
+    }
+
+    eprintln!("recording is stopped, {reason}");
+    print_summary(&args.output.display().to_string(), &data);
+    save(data, &args.output, format)
+}
+
+fn upload(files: &[PathBuf]) -> DynoResult<()> {
+    let (paths, app_config, _) = load_config()?;
+    let mut outbox = Outbox::open(paths.get_data_dir_folder("Outbox"));
+    for file in files {
+        for DynoRun { name, data, .. } in open_runs(file)? {
+            let start = data.start_time().naive_utc();
+            let stop = start + data.total_time();
+            let upload = PendingUpload::new(&data, start, stop)?;
+            outbox.push(&upload)?;
+            println!("queued `{name}` of `{}`", file.display());
+        }
+    }
+
+    let profile = app_config.server.profile();
+    let mut api = ApiService::new(&profile)
+        .ok_or_else(|| DynoErr::service_error(format!("Invalid server `{}`", profile.url)))?;
+    api.set_store(profile.token_store(&paths.data_local_dir));
+    let (tx, rx) = unbounded();
+    api.restore(tx.clone());
+    if !api.is_logined() {
+        return Err(DynoErr::unauthorized_error(format!(
+            "Not logged in to `{}`, login in the app first. {} runs stay queued",
+            profile.base_url(),
+            outbox.len()
+        )));
+    }
+
+    let items = outbox.ready(Instant::now());
+    for item in &items {
+        api.upload(item, tx.clone());
+    }
+    let mut waiting = items.len();
+    while waiting > 0 {
+        let Ok(msg) = rx.recv() else {
+            break;
+        };
+        let AsyncMsg::OnApiUpload(checksum, result) = msg else {
+            continue;
+        };
+        waiting -= 1;
+        let short = &checksum[..8.min(checksum.len())];
+        match result {
+            Ok(id) => {
+                println!("saved {short} to the server with id {id}");
+                outbox.done(&checksum);
+            }
+            Err(err) => {
+                eprintln!("failed to save {short} - {err}");
+                outbox.failed(&checksum, err, Instant::now());
+            }
+        }
+    }
+    api.detach();
+    match outbox.len() {
+        0 => Ok(()),
+        queued => Err(DynoErr::service_error(format!(
+            "{queued} runs stay queued, the app send them later"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::uom::si::{angular_velocity::revolution_per_minute, f64::AngularVelocity};
+
+    fn engine_rpm(rpm: f64) -> Data {
+        Data {
+            rpm_engine: AngularVelocity::new::<revolution_per_minute>(rpm),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cli_stop_condition() {
+        let mut until = "rpm_engine < 1500".parse::<StopCondition>().unwrap();
+        assert_eq!(until.channel, PlotChannel::RpmEngine);
+        assert!(until.below);
+        // idle before the run is not the end of the segment
+        assert!(!until.update(&engine_rpm(900.)));
+        assert!(!until.update(&engine_rpm(4000.)));
+        assert!(until.update(&engine_rpm(1200.)));
+
+        assert_eq!(
+            "temp2>80".parse::<StopCondition>().unwrap().channel,
+            PlotChannel::Temp(2)
+        );
+        assert!("rpm_engine".parse::<StopCondition>().is_err());
+        assert!("temp9>80".parse::<StopCondition>().is_err());
+        assert!("speed>fast".parse::<StopCondition>().is_err());
+    }
+
+    #[test]
+    fn cli_convert() {
+        let dir = std::env::temp_dir().join(format!("dynotest_cli_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut data = DynotestData::new(DynoConfig::default());
+        for rpm in [1000., 3000., 2000.] {
+            data.push(engine_rpm(rpm));
+        }
+
+        for name in ["run.dyno", "run.csv", "run.xlsx", "run.dynosession"] {
+            let path = dir.join(name);
+            save(data.clone(), &path, DynoFileType::from_path(&path).unwrap()).unwrap();
+            let runs = open_runs(&path).unwrap();
+            assert_eq!(runs.len(), 1, "{name}");
+            assert_eq!(runs[0].data.len(), data.len(), "{name}");
+        }
+        assert!(DynoFileType::from_path(Path::new("run.txt")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,14 +1,20 @@
 use eframe::{
+    egui::{IconData, ViewportBuilder, WindowLevel},
     epaint::{Pos2, Vec2},
-    IconData, NativeOptions, Theme,
+    NativeOptions, Theme,
 };
 
 use crate::{
+    alarm::AlarmSettings,
     assets::ICO_LOGO,
     open_option_icon, row_label_value,
-    widgets::{DisplayStylePreset, DynoWidgets},
+    service::{SerialSettings, ServerSettings},
+    widgets::{DashboardSettings, DisplayStylePreset, DynoWidgets, PlotSettings},
+};
+use dyno_core::{
+    i18n::{self, tr, tr_attr, Language},
+    serde,
 };
-use dyno_core::serde;
 
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -17,6 +23,25 @@ pub struct ApplicationConfig {
     pub segment_display_style: DisplayStylePreset,
     pub app_options: AppOptions,
     pub show_startup: bool,
+    #[serde(default)]
+    pub serial: SerialSettings,
+    /// endpoints of the server, the active one is connected
+    #[serde(default)]
+    pub server: ServerSettings,
+    #[serde(default)]
+    pub report: ReportSettings,
+    /// channels, axes and styles of the realtime plot
+    #[serde(default)]
+    pub plot: PlotSettings,
+    /// tiles of the left and right panel
+    #[serde(default)]
+    pub dashboard: DashboardSettings,
+    /// threshold rules checked on every data while recording
+    #[serde(default)]
+    pub alarms: AlarmSettings,
+    /// language of the ui, the messages is in `dyno_core/locales`
+    #[serde(default)]
+    pub language: Language,
 }
 
 impl ApplicationConfig {
@@ -26,19 +51,80 @@ impl ApplicationConfig {
         }
     }
     pub fn draw(&mut self, ui: &mut eframe::egui::Ui) {
-        ui.checkbox(&mut self.show_startup, "Show Startup Window");
+        if ui
+            .combobox_from_iter(
+                tr("language"),
+                &mut self.language,
+                Language::ALL.into_iter(),
+            )
+            .on_hover_text(tr_attr("language", "hint"))
+            .changed()
+        {
+            i18n::set_language(self.language);
+        }
+        ui.checkbox(&mut self.show_startup, tr("option-show-startup"));
         ui.separator();
         self.app_options.ui(ui);
 
         let iter = self.segment_display_style.get_iter();
         ui.combobox_from_iter(
-            "Style for SevenSegment",
+            tr("option-segment-style"),
             &mut self.segment_display_style,
             iter,
         );
     }
 }
 
+/// header of the printed dyno sheet
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default, crate = "serde")]
+pub struct ReportSettings {
+    pub workshop: String,
+    pub address: String,
+    pub operator: String,
+}
+
+impl Default for ReportSettings {
+    fn default() -> Self {
+        Self {
+            workshop: "DynoTests Polije".to_owned(),
+            address: "Politeknik Negeri Jember".to_owned(),
+            operator: String::new(),
+        }
+    }
+}
+
+impl ReportSettings {
+    pub fn ui(&mut self, ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
+        use eframe::egui::{Grid, TextEdit};
+
+        Grid::new("dyno_report_settings_grid")
+            .num_columns(2)
+            .spacing([40.0, 4.0])
+            .show(ui, |ui| {
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut self.workshop),
+                    "report-workshop"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut self.address),
+                    "report-address"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut self.operator),
+                    "report-operator"
+                );
+            })
+            .response
+    }
+}
+
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(default, crate = "serde")]
@@ -67,51 +153,43 @@ pub struct AppOptions {
 impl AppOptions {
     pub fn ui(&mut self, ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
         use eframe::egui::{Grid, RichText};
-        ui.heading("Application Options Setting");
+        ui.heading(tr("option-heading"));
 
         let ui_grid_appoptions = |ui: &mut eframe::egui::Ui| {
             row_label_value!(
                 ui => ui.optional_value_widget(&mut self.icon_path, |ui, value| {
                     let text = RichText::new(value.to_string())
                         .background_color(ui.visuals().extreme_bg_color);
-                    let response = ui.link(text).on_hover_text("Left Click to Edit");
+                    let response = ui.link(text).on_hover_text(tr("path-click-edit"));
                     if response.clicked() {
                         if let Some(path) =
-                            crate::widgets::DynoFileManager::pick_folder("Change Path", &value)
+                            crate::widgets::DynoFileManager::pick_folder(&tr("path-change"), &value)
                         {
                             *value = path.display().to_string();
                         }
                     }
                     response
                 }),
-                "Icon Path",
-                "icon for aplication, default to icon thath saved memory to (embed into app)"
+                "option-icon-path"
             );
             ui.end_row();
             row_label_value!(ui => ui.toggle(&mut self.always_on_top),
-                    "AlwaysOnTop",
-                    "Aplication Config, Always on top window config parameters");
+                    "option-always-on-top");
             row_label_value!(ui => ui.toggle(&mut self.maximized),
-                    "Maximize",
-                    "Aplication Config, Maximize window config parameters");
+                    "option-maximize");
             ui.end_row();
             row_label_value!(ui => ui.toggle(&mut self.decorated),
-                    "Decorated",
-                    "Aplication Config, Decorated window config parameters");
+                    "option-decorated");
             row_label_value!(ui => ui.toggle(&mut self.fullscreen),
-                    "Fullscreen",
-                    "Aplication Config, Fullscreen window config parameters");
+                    "option-fullscreen");
             ui.end_row();
             row_label_value!(ui => ui.toggle(&mut self.drag_and_drop_support),
-                    "Drag and Drop",
-                    "Aplication Config, Drag and Drop Support window config parameters");
+                    "option-drag-and-drop");
             row_label_value!(ui => ui.toggle(&mut self.resizable),
-                    "Resizeable",
-                    "Aplication Config, Resizeable Support window config parameters");
+                    "option-resizable");
             ui.end_row();
             row_label_value!(ui => ui.toggle(&mut self.follow_system_theme),
-                    "Follow System Theme",
-                    "Aplication Config, Follow System Theme window config parameters");
+                    "option-follow-theme");
         };
 
         Grid::new("_grid_config_edit")
@@ -181,12 +259,15 @@ impl AppOptions {
     pub fn startup_opt(&self) -> NativeOptions {
         let s = self.clone().into_native_options();
         NativeOptions {
-            always_on_top: true,
-            resizable: false,
-            initial_window_size: Some(Vec2 {
-                x: 720f32,
-                y: 480f32,
-            }),
+            viewport: s
+                .viewport
+                .clone()
+                .with_window_level(WindowLevel::AlwaysOnTop)
+                .with_resizable(false)
+                .with_inner_size(Vec2 {
+                    x: 720f32,
+                    y: 480f32,
+                }),
             ..s
         }
     }
@@ -217,23 +298,39 @@ impl AppOptions {
             icon_path,
         } = self;
 
-        let icon_data = match icon_path.map(std::path::PathBuf::from) {
+        let icon_data: Option<IconData> = match icon_path.map(std::path::PathBuf::from) {
             Some(path) if path.exists() => open_option_icon!(path),
             _ => ICO_LOGO.as_ref().map(|(icon, _)| icon.clone()),
         };
 
+        let mut viewport = ViewportBuilder::default()
+            .with_maximized(maximized)
+            .with_decorations(decorated)
+            .with_fullscreen(fullscreen)
+            .with_drag_and_drop(drag_and_drop_support)
+            .with_resizable(resizable)
+            .with_transparent(transparent);
+        if always_on_top {
+            viewport = viewport.with_window_level(WindowLevel::AlwaysOnTop);
+        }
+        if let Some(pos) = initial_window_pos {
+            viewport = viewport.with_position(pos);
+        }
+        if let Some(size) = initial_window_size {
+            viewport = viewport.with_inner_size(size);
+        }
+        if let Some(size) = min_window_size {
+            viewport = viewport.with_min_inner_size(size);
+        }
+        if let Some(size) = max_window_size {
+            viewport = viewport.with_max_inner_size(size);
+        }
+        if let Some(icon) = icon_data {
+            viewport = viewport.with_icon(icon);
+        }
+
         eframe::NativeOptions {
-            always_on_top,
-            maximized,
-            decorated,
-            fullscreen,
-            drag_and_drop_support,
-            initial_window_pos,
-            initial_window_size,
-            min_window_size,
-            max_window_size,
-            resizable,
-            transparent,
+            viewport,
             vsync,
             multisampling,
             depth_buffer,
@@ -241,7 +338,6 @@ impl AppOptions {
             follow_system_theme,
             default_theme,
             run_and_return,
-            icon_data,
             ..Default::default()
         }
     }
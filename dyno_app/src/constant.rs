@@ -8,7 +8,7 @@ macro_rules! get_assets {
 pub const APP_KEY: &str = "dynotest-app";
 
 pub mod assets {
-    use dyno_core::lazy_static;
+    use lazy_static;
 
     macro_rules! declare_static_ico {
         ($($name: ident => $file_name: literal),* $(,)?) => {$(
@@ -38,7 +38,7 @@ pub mod assets {
         )*};
     }
 
-    use eframe::IconData;
+    use eframe::egui::IconData;
     lazy_static::lazy_static! {
         pub static ref ICO_LOGO: Option<(IconData, [u32; 2])> = declare_static_ico!(ICO_LOGO  => "icons/polije.ico");
         pub static ref ICO_ERROR: Option<(IconData, [u32; 2])> = declare_static_ico!(ICO_ERROR => "icons/error.ico");
@@ -157,6 +157,12 @@ impl PackageInfo<'static> {
     }
 }
 
+impl Default for PackageInfo<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub static PACKAGE_INFO: PackageInfo<'static> = PackageInfo::new();
 
 // ----------------------------------------------------------------------------
@@ -1,38 +1,47 @@
 use crate::{
+    alarm::{self, AlarmEngine, AlarmEvent, Severity},
     config::ApplicationConfig,
-    paths::DynoPaths,
+    journal::{self, Journal},
+    paths::{file_name_timestamp, DynoPaths},
+    procedure::{Procedure, ProcedureRunner, StepStarted},
+    registry::{VehicleRegistry, VehicleRun},
     row_label_value,
-    service::{ApiService, PortInfo, SerialService},
-    state::{DynoFileType, DynoState, OperatorData},
-    toast_error, toast_info, toast_success,
-    widgets::{
-        button::ButtonExt, segment_display::SegmentedDisplay, DynoFileManager, Gauge, RealtimePlot,
+    service::{
+        ApiService, LinkReport, LiveStream, Outbox, PendingUpload, PortEvent, PortInfo,
+        PortManager, SerialService, CAPTURE_EXTENSION,
     },
+    session::{open_runs, DynoRun, DynoSession, SESSION_EXTENSION},
+    state::{DynoFileType, DynoState, OperatorData},
+    toast_error, toast_info, toast_success, toast_warn,
+    widgets::{button::ButtonExt, tiles_ui, DashValues, DynoFileManager, RealtimePlot},
     windows::{open_server::OpenServerWindow, WSIdx, WindowStack},
     AsyncMsg,
 };
 use dyno_core::{
     asyncify,
-    chrono::{NaiveDateTime, Utc},
+    chrono::{self, Utc},
     crossbeam_channel::{unbounded, Receiver, Sender},
-    ignore_err, log, serde, BufferData, CompresedSaver, CsvSaver, Data, DynoConfig, ExcelSaver,
+    i18n::{tr, tr_attr},
+    ignore_err, log,
+    model::vehicle::Vehicle,
+    serde,
+    types::{Data, DynotestData, MotorInfo, MAX_TEMP_CHANNELS},
+    uom::si::{
+        angular_velocity::revolution_per_minute, power::horsepower_metric,
+        temperature_interval::degree_celsius, torque::newton_meter, velocity::kilometer_per_hour,
+    },
+    CsvSaver, DynoConfig, DynoErr, DynoSaver, ExcelSaver,
 };
 use eframe::egui::*;
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
 };
 
-#[cfg_attr(debug_assertions, derive(Debug))]
-#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Default)]
-#[serde(crate = "serde")]
-enum PanelSetting {
-    #[default]
-    Generic,
-    Config,
-    Style,
-}
-
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(crate = "serde")]
 pub struct DynoControl {
@@ -40,16 +49,40 @@ pub struct DynoControl {
     pub app_config: ApplicationConfig,
     pub config: DynoConfig,
 
+    /// runs of the current customer visit, the data is recorded to the selected run
     #[serde(skip)]
     #[serde(default)]
-    buffer: BufferData,
+    session: DynoSession,
 
     #[serde(skip)]
-    #[serde(default = "SerialService::new")]
+    #[serde(default)]
     serial: Option<SerialService>,
 
+    #[serde(default)]
+    ports: PortManager,
+
+    #[serde(skip)]
+    #[serde(default)]
+    serial_lost: bool,
+
+    #[serde(skip)]
+    #[serde(default)]
+    link_report: Option<LinkReport>,
+
+    /// write the raw serial traffic to `.dynocap` in the cache folder
+    #[serde(default)]
+    capture_serial: bool,
+
+    #[serde(default = "default_replay_speed")]
+    replay_speed: f32,
+
+    /// path of pseudo-terminal fed by a simulator
+    #[serde(skip)]
+    #[serde(default)]
+    virtual_port: String,
+
     #[serde(skip)]
-    #[serde(default = "ApiService::new")]
+    #[serde(default)]
     api_service: Option<ApiService>,
 
     plots: RealtimePlot,
@@ -59,21 +92,71 @@ pub struct DynoControl {
     async_channels: (Sender<AsyncMsg>, Receiver<AsyncMsg>),
 
     #[serde(skip)]
-    start_time: u64,
+    #[serde(default)]
+    loadings: Arc<AtomicBool>,
 
     #[serde(skip)]
-    pub start: Option<NaiveDateTime>,
+    #[serde(default)]
+    buffer_saved: bool,
 
+    /// autosave of the unsaved session, started in [`Self::init`]
     #[serde(skip)]
-    pub stop: Option<NaiveDateTime>,
+    #[serde(default)]
+    journal: Option<Journal>,
 
+    /// session restored from the journal of the previous app, and the journal files
     #[serde(skip)]
     #[serde(default)]
-    loadings: Arc<AtomicBool>,
+    recovered: Option<(DynoSession, Vec<std::path::PathBuf>)>,
 
+    /// rules of `app_config.alarms` checked on every data of the running service
     #[serde(skip)]
     #[serde(default)]
-    buffer_saved: bool,
+    alarms: AlarmEngine,
+
+    /// ring the bell and request attention of the window on the next frame
+    #[serde(skip)]
+    #[serde(default)]
+    alarm_attention: bool,
+
+    /// the border of the window is blinking until this `ctx` time
+    #[serde(skip)]
+    #[serde(default)]
+    alarm_bell: Option<f64>,
+
+    /// procedures loaded from the config dir in [`Self::init`]
+    #[serde(skip)]
+    #[serde(default)]
+    procedures: Vec<Procedure>,
+
+    #[serde(skip)]
+    #[serde(default)]
+    procedure: Option<ProcedureRunner>,
+
+    /// vehicles and customers, loaded from the data dir in [`Self::init`]
+    #[serde(skip)]
+    #[serde(default)]
+    registry: VehicleRegistry,
+
+    /// runs waiting to be saved to the server, opened from the data dir in [`Self::init`]
+    #[serde(skip)]
+    #[serde(default)]
+    outbox: Outbox,
+
+    /// stream of the recording to the server, spawned with the api in [`Self::start_api`]
+    #[serde(skip)]
+    #[serde(default)]
+    live: Option<LiveStream>,
+
+    /// dropped files or the file arguments, opened by [`OperatorData::OpenPaths`]
+    #[serde(skip)]
+    #[serde(default)]
+    pending_open: Vec<PathBuf>,
+}
+
+#[inline]
+const fn default_replay_speed() -> f32 {
+    1.0
 }
 
 impl Default for DynoControl {
@@ -82,16 +165,30 @@ impl Default for DynoControl {
             paths: Default::default(),
             app_config: Default::default(),
             config: Default::default(),
-            buffer: Default::default(),
+            session: Default::default(),
             serial: Default::default(),
+            ports: Default::default(),
+            serial_lost: Default::default(),
+            link_report: Default::default(),
+            capture_serial: Default::default(),
+            replay_speed: default_replay_speed(),
+            virtual_port: Default::default(),
             api_service: Default::default(),
             plots: Default::default(),
             async_channels: unbounded(),
-            start_time: Default::default(),
-            start: Default::default(),
-            stop: Default::default(),
             loadings: Default::default(),
             buffer_saved: Default::default(),
+            journal: Default::default(),
+            recovered: Default::default(),
+            alarms: Default::default(),
+            alarm_attention: Default::default(),
+            alarm_bell: Default::default(),
+            procedures: Default::default(),
+            procedure: Default::default(),
+            registry: Default::default(),
+            outbox: Default::default(),
+            live: Default::default(),
+            pending_open: Default::default(),
         }
     }
 }
@@ -109,6 +206,7 @@ impl DynoControl {
                 dyno_core::log::error!("{err}");
                 Default::default()
             });
+        dyno_core::i18n::set_language(app_config.language);
 
         let config = paths
             .get_config::<DynoConfig>("config.toml")
@@ -116,64 +214,226 @@ impl DynoControl {
                 dyno_core::log::error!("Failed to get DynoTests Configuration file ({err})");
                 Default::default()
             });
+        let api_service = ApiService::new(&app_config.server.profile());
 
         Self {
+            session: DynoSession::new(config.clone()),
             app_config,
             config,
             paths,
-            buffer: BufferData::new(),
             plots: RealtimePlot::new(),
             buffer_saved: true,
             async_channels: unbounded(),
-            api_service: ApiService::new(),
-            serial: SerialService::new(),
+            api_service,
             ..Default::default()
         }
     }
 
     pub fn init(&mut self) {
-        match self.api() {
-            Some(api) => api.set_active(self.config.clone(), self.tx().clone()),
-            None => self.reconnect_api(),
+        let journal_dir = self.paths.get_cache_dir_folder("Journal");
+        let leftovers = journal::leftovers(&journal_dir);
+        let recovered = leftovers
+            .iter()
+            .find_map(|path| journal::recover(path).ok().flatten());
+        match recovered {
+            Some(session) => self.recovered = Some((session, leftovers)),
+            None => journal::remove_all(&leftovers),
+        }
+        self.journal = Some(Journal::spawn(journal_dir));
+        self.reload_procedures();
+        self.registry = self
+            .paths
+            .get_bin(VehicleRegistry::FILE)
+            .unwrap_or_else(|err| {
+                log::warn!("Failed to load the vehicle registry - {err}");
+                Default::default()
+            });
+        self.outbox = Outbox::open(self.paths.get_data_dir_folder("Outbox"));
+        if !self.outbox.is_empty() {
+            log::info!(
+                "{} runs is waiting to be saved to the server",
+                self.outbox.len()
+            );
         }
-        if self.serial.is_none() {
-            self.reconnect_serial();
+
+        match self.api_service.take() {
+            Some(api) => self.start_api(api),
+            None => self.reconnect_api(),
         }
+        self.reconnect_serial();
     }
     pub fn deinit(&mut self) {
+        // only stop refreshing, the saved login is restored on the next start. the tokens is
+        // revoked only when the operator logout
         if let Some(api) = self.api() {
-            api.logout(self.tx().clone());
+            api.detach();
             api.set_non_active(self.tx().clone());
         }
+        if self.is_buffer_saved() {
+            self.clear_journal();
+        }
+        // join the writer thread, so the last entries is flushed
+        self.journal = None;
+    }
+
+    /// write the whole session to the journal, called after the runs is changed
+    fn snapshot_journal(&self) {
+        if let Some(journal) = &self.journal {
+            journal.snapshot(&self.session);
+        }
+    }
+
+    fn clear_journal(&self) {
+        if let Some(journal) = &self.journal {
+            journal.clear();
+        }
+    }
+
+    /// session left by the previous app that was not closed properly
+    #[inline]
+    pub fn recovered(&self) -> Option<&DynoSession> {
+        self.recovered.as_ref().map(|(session, _)| session)
+    }
+
+    /// replace the current session with the recovered session
+    pub fn restore_recovered(&mut self) {
+        if self.is_recording() {
+            toast_warn!("{}", tr("toast-stop-before-restore"));
+            return;
+        }
+        if let Some((session, leftovers)) = self.recovered.take() {
+            self.session = session;
+            self.buffer_saved = false;
+            self.snapshot_journal();
+            journal::remove_all(&leftovers);
+            toast_success!(
+                "{}",
+                dyno_core::tr!("toast-session-restored", count = self.session.runs().len())
+            );
+        }
+    }
+
+    pub fn discard_recovered(&mut self) {
+        if let Some((_, leftovers)) = self.recovered.take() {
+            journal::remove_all(&leftovers);
+        }
+    }
+
+    /// operator quit without saving the session, the journal is not needed anymore
+    pub fn discard_journal(&mut self) {
+        self.clear_journal();
     }
 
     #[inline(always)]
     pub fn last_buffer(&self) -> Data {
-        self.buffer.last().clone()
+        *self.session.buffer().last()
     }
 
+    /// data of the selected run
     #[allow(unused)]
     #[inline(always)]
-    pub fn buffer(&self) -> &'_ BufferData {
-        &self.buffer
+    pub fn buffer(&self) -> &'_ DynotestData {
+        self.session.buffer()
+    }
+
+    #[inline(always)]
+    pub fn buffer_mut(&mut self) -> &'_ mut DynotestData {
+        self.session.buffer_mut()
+    }
+
+    #[inline(always)]
+    pub fn session(&self) -> &'_ DynoSession {
+        &self.session
     }
 
     #[inline(always)]
-    pub fn buffer_mut(&mut self) -> &'_ mut BufferData {
-        &mut self.buffer
+    pub fn session_mut(&mut self) -> &'_ mut DynoSession {
+        &mut self.session
     }
 
+    /// true if the serial service is recording to the selected run
     #[inline]
-    pub fn start_time(&self) -> String {
-        let seconds = (self.start_time / 1000) % 60;
-        let minutes = (self.start_time / (1000 * 60)) % 60;
-        let hours = (self.start_time / (1000 * 60 * 60)) % 24;
-        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    pub fn is_recording(&self) -> bool {
+        self.serial.as_ref().is_some_and(SerialService::is_open)
     }
 
-    // mark return saved if buffer is already saved or buffer is empty
-    pub const fn is_buffer_saved(&self) -> bool {
-        self.buffer_saved || self.buffer.is_empty()
+    /// start new session, the current session must be saved first
+    pub fn new_session(&mut self) {
+        if self.is_recording() {
+            toast_warn!("{}", tr("toast-stop-before-new-session"));
+            return;
+        }
+        self.session = DynoSession::new(self.config.clone());
+        self.buffer_saved = true;
+        self.clear_journal();
+    }
+
+    /// add new empty run to the session and select it
+    pub fn new_run(&mut self) {
+        if self.is_recording() {
+            toast_warn!("{}", tr("toast-stop-before-new-run"));
+            return;
+        }
+        self.session.add_run(self.config.clone());
+        self.snapshot_journal();
+    }
+
+    /// open the dropped files or the file arguments. one session file replace the session (the
+    /// unsaved session is confirmed first), else every file is opened as new runs to be compared
+    pub fn open_paths(&mut self, paths: Vec<PathBuf>, state: &mut DynoState) {
+        if self.is_recording() {
+            toast_warn!("{}", tr("toast-stop-before-open"));
+            return;
+        }
+        let (paths, unknown): (Vec<_>, Vec<_>) = paths
+            .into_iter()
+            .partition(|path| DynoFileType::from_path(path).is_ok());
+        for path in unknown {
+            toast_warn!(
+                "{}",
+                dyno_core::tr!("toast-unknown-format", path = path.display().to_string())
+            );
+        }
+        if !paths.is_empty() {
+            self.pending_open = paths;
+            state.set_operator(OperatorData::OpenPaths);
+        }
+    }
+
+    /// the pending files replace the session
+    fn is_session_pending(&self) -> bool {
+        matches!(
+            self.pending_open.as_slice(),
+            [path] if matches!(DynoFileType::from_path(path), Ok(DynoFileType::Session))
+        )
+    }
+
+    /// the operator cancel opening the pending files
+    pub fn cancel_open(&mut self) {
+        self.pending_open.clear();
+    }
+
+    #[inline]
+    pub fn has_pending_open(&self) -> bool {
+        !self.pending_open.is_empty()
+    }
+
+    /// remove the run at `idx`, the recorded run can't be removed
+    pub fn remove_run(&mut self, idx: usize) {
+        if self.is_recording() && idx == self.session.active_index() {
+            toast_warn!("{}", tr("toast-stop-before-remove-run"));
+            return;
+        }
+        if self.session.remove(idx).is_some() {
+            self.buffer_saved = false;
+            self.snapshot_journal();
+        }
+    }
+
+    #[inline]
+    // mark return saved if session is already saved or every run is empty
+    pub fn is_buffer_saved(&self) -> bool {
+        self.buffer_saved || self.session.is_empty()
     }
 
     #[inline]
@@ -192,23 +452,172 @@ impl DynoControl {
 
     #[inline]
     pub fn reconnect_api(&mut self) {
-        if let Some(api) = ApiService::new() {
-            toast_success!("SUCCES! connected to Api Endpoint: {}", api.url);
-            api.set_active(self.config.clone(), self.tx().clone());
-            self.api_service = Some(api);
+        if let Some(api) = ApiService::new(&self.app_config.server.profile()) {
+            toast_success!(
+                "{}",
+                dyno_core::tr!("toast-api-connected", url = api.url.to_string())
+            );
+            if let Some(old) = self.api() {
+                old.detach();
+                if old.url != api.url {
+                    old.set_non_active(self.tx().clone());
+                }
+            }
+            self.start_api(api);
         }
     }
 
-    #[inline]
+    /// restore the saved login of the server, and tell the server this dyno is active
+    fn start_api(&mut self, mut api: ApiService) {
+        let profile = self.app_config.server.profile();
+        api.set_store(profile.token_store(&self.paths.data_local_dir));
+        api.set_active(self.config.clone(), self.tx().clone());
+        api.restore(self.tx().clone());
+        self.live = profile.live_stream.then(|| LiveStream::spawn(&api));
+        self.api_service = Some(api);
+    }
+
+    /// check the health of the active server profile, without connecting to it
+    pub fn test_server(&mut self) {
+        if let Some(api) = ApiService::new(&self.app_config.server.profile()) {
+            self.set_loading();
+            api.check_health(self.tx().clone());
+        }
+    }
+
+    /// connect to the port picked by the user, or the first port if not connected
     pub fn reconnect_serial(&mut self) {
-        if let Some(serial) = SerialService::new() {
-            toast_success!(
-                "SUCCES! connected to Serial: [{}]:[{}-{}]",
-                serial.info.port_name,
-                serial.info.vid,
-                serial.info.pid
-            );
-            self.serial = Some(serial);
+        self.ports.refresh();
+        match self.ports.preferred().cloned() {
+            Some(info) => self.connect_serial(info),
+            None => toast_error!("{}", tr("toast-no-port")),
+        }
+    }
+
+    fn connect_serial(&mut self, info: PortInfo) {
+        if self.serial.as_ref().is_some_and(SerialService::is_open) {
+            return;
+        }
+        toast_success!(
+            "{}",
+            dyno_core::tr!(
+                "toast-serial-connected",
+                port = info.display_name(),
+                vid = info.vid.to_string(),
+                pid = info.pid.to_string(),
+            )
+        );
+        self.serial = Some(SerialService::with_port(info));
+    }
+
+    /// handshake with the device using [`ApplicationConfig::serial`], the result is shown in
+    /// the settings window
+    pub fn test_serial_link(&mut self) {
+        if self.serial.as_ref().is_some_and(SerialService::is_open) {
+            toast_warn!("{}", tr("toast-stop-before-test-link"));
+            return;
+        }
+        let info = match &self.serial {
+            Some(serial) => Some(serial.info.clone()),
+            None => {
+                self.ports.refresh();
+                self.ports.preferred().cloned()
+            }
+        };
+        let Some(info) = info else {
+            toast_error!("{}", tr("toast-no-port"));
+            return;
+        };
+
+        let settings = self.app_config.serial.clone();
+        let tx = self.tx().clone();
+        self.link_report = None;
+        self.set_loading();
+        dyno_core::tokio::spawn(async move {
+            match crate::service::test_link(&info.port_name, &settings).await {
+                Ok(report) => ignore_err!(tx.send(AsyncMsg::OnSerialLinkTest(report))),
+                Err(err) => ignore_err!(tx.send(AsyncMsg::error(err))),
+            }
+        });
+    }
+
+    #[inline]
+    pub fn link_report(&self) -> Option<&LinkReport> {
+        self.link_report.as_ref()
+    }
+
+    /// replace the serial service with replaying the capture file at `path`
+    pub fn open_replay(&mut self, path: std::path::PathBuf) {
+        if self.serial.as_ref().is_some_and(SerialService::is_open) {
+            toast_warn!("{}", tr("toast-stop-before-replay"));
+            return;
+        }
+        let serial = SerialService::replay(path, self.replay_speed);
+        toast_info!(
+            "{}",
+            dyno_core::tr!(
+                "toast-capture-loaded",
+                port = serial.info.port_name.as_str()
+            )
+        );
+        self.serial = Some(serial);
+    }
+
+    fn serial_capture_menu(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.capture_serial, tr("capture-raw"))
+            .on_hover_text(tr_attr("capture-raw", "hint"));
+        ui.horizontal(|ui| {
+            ui.label(tr("capture-replay-speed"));
+            ui.add(
+                DragValue::new(&mut self.replay_speed)
+                    .range(0.0..=100.0)
+                    .speed(0.1)
+                    .suffix("x"),
+            )
+            .on_hover_text(tr_attr("capture-replay-speed", "hint"));
+        });
+        if ui.button(tr("capture-replay")).clicked() {
+            let dir = self.paths.get_cache_dir_folder("Captures");
+            if let Some(path) = DynoFileManager::pick_capture(dir) {
+                self.open_replay(path);
+            }
+            ui.close_menu();
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.virtual_port)
+                    .hint_text("/dev/pts/N")
+                    .desired_width(120.),
+            )
+            .on_hover_text(tr_attr("capture-virtual-port", "hint"));
+            if ui.button(tr("capture-virtual-port")).clicked() && !self.virtual_port.is_empty() {
+                self.connect_serial(PortInfo::virtual_port(self.virtual_port.clone()));
+                ui.close_menu();
+            }
+        });
+    }
+
+    /// watch the attached and detached ports, the running service reconnect by itself
+    fn poll_ports(&mut self) {
+        for event in self.ports.poll() {
+            match event {
+                PortEvent::Attached(info) => {
+                    if self.serial.is_none() && self.ports.preferred() == Some(&info) {
+                        self.connect_serial(info);
+                    }
+                }
+                PortEvent::Detached(info) => match &self.serial {
+                    Some(serial) if !serial.is_open() && serial.info == info => {
+                        toast_warn!(
+                            "{}",
+                            dyno_core::tr!("toast-port-detached", port = info.display_name())
+                        );
+                        self.serial = None;
+                    }
+                    _ => {}
+                },
+            }
         }
     }
 
@@ -225,16 +634,106 @@ impl DynoControl {
 impl DynoControl {
     #[inline]
     pub fn on_pos_render(&mut self, window_stack: &mut WindowStack, state: &mut DynoState) {
+        if self.recovered.is_some() {
+            window_stack.set_open(WSIdx::Recovery, true);
+        }
+        self.poll_outbox();
         if let Ok(msg) = self.async_channels.1.try_recv() {
             match msg {
                 AsyncMsg::OnSerialData(serial_data) => {
-                    self.start_time += serial_data.period as u64;
-                    self.buffer.push_from_serial(&mut self.config, serial_data);
+                    let run = self.session.active_mut();
+                    run.elapsed_ms += run.data.config.delta_ms as u64;
+                    run.data.push_from_raw_serial_data(serial_data);
                     self.buffer_saved = false;
+                    if let Some(journal) = &self.journal {
+                        journal.data(&self.session);
+                    }
+                    let data = *self.session.buffer().last();
+                    if let Some(live) = &self.live {
+                        live.push(data);
+                    }
+                    for event in self.alarms.check(&self.app_config.alarms, &data) {
+                        self.on_alarm(event);
+                    }
+                    if let Some(runner) = &mut self.procedure {
+                        let started = runner.update(&data);
+                        self.on_steps(started);
+                    }
+                }
+                AsyncMsg::OnSerialDisconnected(err) => {
+                    toast_error!(
+                        "{}",
+                        dyno_core::tr!("toast-serial-disconnected", error = err.to_string())
+                    );
+                    self.serial_lost = true;
+                }
+                AsyncMsg::OnSerialReconnected(info, gap) => {
+                    toast_success!(
+                        "{}",
+                        dyno_core::tr!(
+                            "toast-serial-reconnected",
+                            port = info.display_name(),
+                            gap = format!("{:.1}", gap.as_secs_f32()),
+                        )
+                    );
+                    if let Some(serial) = &mut self.serial {
+                        serial.info = info;
+                    }
+                    let gap = chrono::Duration::from_std(gap).unwrap_or_default();
+                    let run = self.session.active_mut();
+                    run.data.record_gap(gap);
+                    run.elapsed_ms += gap.num_milliseconds().unsigned_abs();
+                    if let Some(journal) = &self.journal {
+                        journal.gap(&self.session, gap);
+                    }
+                    self.serial_lost = false;
+                }
+                AsyncMsg::OnSerialLinkTest(report) => {
+                    match &report {
+                        LinkReport::Connected { .. } => toast_success!("{report}"),
+                        _ => toast_error!("{report}"),
+                    }
+                    self.link_report = Some(report);
+                    self.unset_loading();
+                }
+                AsyncMsg::OnSerialConfig(device) => {
+                    self.config.apply_device_config(&device);
+                    self.session
+                        .buffer_mut()
+                        .config
+                        .apply_device_config(&device);
+                    self.snapshot_journal();
+                    toast_info!(
+                        "{}",
+                        dyno_core::tr!("toast-device-config", device = device.name.as_str())
+                    );
                 }
                 AsyncMsg::OnOpenBuffer(buffer) => {
-                    self.buffer = *buffer;
+                    let name = self.session.next_run_name();
+                    self.session.push_run(DynoRun::with_data(name, *buffer));
                     self.buffer_saved = false;
+                    self.snapshot_journal();
+                    self.unset_loading();
+                }
+                AsyncMsg::OnOpenRuns(runs) => {
+                    toast_info!(
+                        "{}",
+                        dyno_core::tr!("toast-opened-runs", count = runs.len())
+                    );
+                    for run in runs {
+                        self.session.push_run(run);
+                    }
+                    self.buffer_saved = false;
+                    self.snapshot_journal();
+                }
+                AsyncMsg::OnOpenSession(session) => {
+                    self.session = *session;
+                    self.buffer_saved = true;
+                    self.clear_journal();
+                    toast_info!(
+                        "{}",
+                        dyno_core::tr!("toast-opened-session", count = self.session.runs().len())
+                    );
                     self.unset_loading();
                 }
                 AsyncMsg::OnError(err) => {
@@ -243,14 +742,22 @@ impl DynoControl {
                 }
                 AsyncMsg::OnSavedBuffer(()) => {
                     self.buffer_saved = true;
+                    // still recording, the journal start again from the saved session
+                    match self.is_recording() {
+                        true => self.snapshot_journal(),
+                        false => self.clear_journal(),
+                    }
                     if state.quitable() {
                         state.set_quit(true);
+                    } else if !self.pending_open.is_empty() {
+                        // saved from the confirmation of the dropped files, open them now
+                        state.set_operator(OperatorData::OpenPaths);
                     }
                     self.unset_loading();
                 }
                 AsyncMsg::OnCheckHealthApi(s) => {
                     if s.is_success() {
-                        toast_success!("API Check Health is Success");
+                        toast_success!("{}", tr("toast-api-healthy"));
                     }
                     self.unset_loading();
                 }
@@ -262,7 +769,38 @@ impl DynoControl {
                     }
                     self.unset_loading();
                 }
-                AsyncMsg::OnApiLogin | AsyncMsg::OnApiRegister => {
+                AsyncMsg::OnApiUpload(checksum, result) => match result {
+                    Ok(id) => {
+                        self.outbox.done(&checksum);
+                        toast_success!("{}", dyno_core::tr!("toast-upload-saved", id = id));
+                    }
+                    Err(err) => {
+                        let attempts = self.outbox.failed(&checksum, err.clone(), Instant::now());
+                        log::warn!("Failed to save the run to the server ({attempts}x) - {err}");
+                        if attempts == 1 {
+                            toast_warn!(
+                                "{}",
+                                dyno_core::tr!("toast-upload-failed", error = err.to_string())
+                            );
+                        }
+                    }
+                },
+                AsyncMsg::OnApiSyncVehicle(pushed, remote) => {
+                    self.registry.merge(&pushed, remote);
+                    self.save_registry();
+                    log::info!("Vehicle registry is synced, {} pushed", pushed.len());
+                    self.unset_loading();
+                }
+                AsyncMsg::OnApiLogin => {
+                    window_stack.set_swap_open(WSIdx::Auth);
+                    self.sync_registry();
+                    self.unset_loading();
+                }
+                AsyncMsg::OnApiRestore => {
+                    toast_info!("{}", tr("toast-login-restored"));
+                    self.sync_registry();
+                }
+                AsyncMsg::OnApiRegister => {
                     window_stack.set_swap_open(WSIdx::Auth);
                     self.unset_loading();
                 }
@@ -273,19 +811,422 @@ impl DynoControl {
             // if buffer is saved and operator want to save, do save the buffer, or if buffer
             // already saved, ignore the operator
             (OperatorData::SaveFile(tp), false) => self.on_save(tp),
-            // if buffer is saved and operator want to open file, do open the file to buffer,
-            // or is buffer unsaved but operator want ot open file, show popup to save buffer first
-            (OperatorData::OpenFile(tp), true) => self.on_open(tp),
-            (OperatorData::OpenFile(_), false) => {
+            // opening a session replace every run, show popup to save the session first if
+            // it's unsaved. other file is opened as new run, so nothing is lost
+            (OperatorData::OpenFile(DynoFileType::Session), false) => {
+                window_stack.set_open(WSIdx::ConfirmUnsaved, true)
+            }
+            (OperatorData::OpenFile(tp), _) => self.on_open(tp),
+            (OperatorData::OpenPaths, false) if self.is_session_pending() => {
                 window_stack.set_open(WSIdx::ConfirmUnsaved, true)
             }
+            (OperatorData::OpenPaths, _) => self.on_open_paths(),
             _ => {}
         }
     }
+    /// run the actions of the raised alarm, the alarm is logged into the annotations of the run
+    fn on_alarm(&mut self, event: AlarmEvent) {
+        let message = event.message();
+        log::warn!("{message}");
+        if event.actions.toast {
+            match event.severity {
+                Severity::Info => toast_info!("{message}"),
+                Severity::Warning => toast_warn!("{message}"),
+                Severity::Critical => toast_error!("{message}"),
+            }
+        }
+        if event.actions.sound {
+            alarm::play_sound(event.severity);
+        }
+        self.alarm_attention |= event.actions.bell;
+
+        self.session.buffer_mut().annotate(event.timestamp, message);
+        self.buffer_saved = false;
+        self.snapshot_journal();
+
+        match (event.actions.stop_recording, event.actions.stop_device) {
+            (true, stop_device) => self.stop_recording(stop_device),
+            (false, true) => {
+                if let Some(serial) = self.serial.as_ref().filter(|serial| serial.is_open()) {
+                    serial.stop_device();
+                    toast_warn!("{}", tr("toast-alarm-device-stopped"));
+                }
+            }
+            (false, false) => {}
+        }
+    }
+
+    #[inline]
+    pub fn procedures(&self) -> &[Procedure] {
+        &self.procedures
+    }
+
+    pub fn reload_procedures(&mut self) {
+        self.procedures = Procedure::load_dir(self.paths.get_config_dir_folder(Procedure::FOLDER));
+    }
+
+    /// procedure that is running on the selected run
+    #[inline]
+    pub fn procedure(&self) -> Option<&ProcedureRunner> {
+        self.procedure.as_ref()
+    }
+
+    /// start the procedure at `idx`, the first step is started with the next data
+    pub fn start_procedure(&mut self, idx: usize) {
+        if let Some(procedure) = self.procedures.get(idx) {
+            self.procedure = Some(ProcedureRunner::new(procedure.clone()));
+        }
+    }
+
+    /// confirm or skip the current step of the procedure
+    pub fn next_step(&mut self) {
+        let timestamp = self.session.buffer().last().timestamp;
+        if let Some(started) = self.procedure.as_mut().and_then(|p| p.next(timestamp)) {
+            self.on_steps(vec![started]);
+        }
+    }
+
+    pub fn stop_procedure(&mut self) {
+        self.procedure = None;
+    }
+
+    /// log the started steps into the annotations and the tags of the run
+    fn on_steps(&mut self, started: Vec<StepStarted>) {
+        let Some(runner) = &self.procedure else {
+            return;
+        };
+        if started.is_empty() {
+            return;
+        }
+        let procedure = runner.procedure();
+        let run = self.session.active_mut();
+        for StepStarted { index, timestamp } in started {
+            let text = match procedure.steps.get(index) {
+                Some(step) => {
+                    if let Some(tag) = step.tag.as_ref().filter(|tag| !run.tags.contains(tag)) {
+                        run.tags.push(tag.clone());
+                    }
+                    toast_info!(
+                        "{}",
+                        dyno_core::tr!(
+                            "toast-procedure-step",
+                            number = index + 1,
+                            name = step.name.as_str(),
+                            prompt = step.prompt.as_str(),
+                        )
+                    );
+                    dyno_core::tr!(
+                        "annotation-procedure-step",
+                        number = index + 1,
+                        total = procedure.steps.len(),
+                        name = step.name.as_str(),
+                    )
+                }
+                None => {
+                    toast_success!(
+                        "{}",
+                        dyno_core::tr!("toast-procedure-done", name = procedure.name.as_str())
+                    );
+                    dyno_core::tr!("annotation-procedure-done", name = procedure.name.as_str())
+                }
+            };
+            run.data.annotate(timestamp, text);
+        }
+        self.buffer_saved = false;
+        self.snapshot_journal();
+    }
+
+    /// stop the running serial service like the stop button, the device is only stopped when
+    /// `stop_device`
+    fn stop_recording(&mut self, stop_device: bool) {
+        let Some(serial) = self.serial.as_ref().filter(|serial| serial.is_open()) else {
+            return;
+        };
+        match stop_device {
+            true => serial.stop(),
+            false => serial.stop_reading(),
+        }
+        self.stop_live();
+        self.serial_lost = false;
+        self.session.active_mut().stop = Some(Utc::now().naive_utc());
+        self.snapshot_journal();
+        self.record_vehicle_run();
+        toast_warn!("{}", tr("toast-alarm-stopped"));
+    }
+
+    /// queue the selected run to be saved to the server, sent when logged in
+    pub fn queue_upload(&mut self) {
+        let run = self.session.active();
+        let upload = PendingUpload::new(
+            &run.data,
+            run.start.unwrap_or_default(),
+            run.stop.unwrap_or_default(),
+        );
+        match upload.and_then(|upload| self.outbox.push(&upload)) {
+            Ok(()) => toast_info!(
+                "{}",
+                dyno_core::tr!("toast-upload-queued", name = run.name.as_str())
+            ),
+            Err(err) => toast_error!(
+                "{}",
+                dyno_core::tr!("toast-upload-queue-failed", error = err.to_string())
+            ),
+        }
+        self.poll_outbox();
+    }
+
+    #[inline]
+    pub fn outbox(&self) -> &Outbox {
+        &self.outbox
+    }
+
+    /// send the queued uploads that is due, nothing is sent while logged out
+    fn poll_outbox(&mut self) {
+        let Some(api) = self.api_service.as_ref().filter(|api| api.is_logined()) else {
+            return;
+        };
+        for item in self.outbox.ready(Instant::now()) {
+            api.upload(&item, self.async_channels.0.clone());
+        }
+    }
+
+    #[inline]
+    fn stop_live(&mut self) {
+        if let Some(live) = &mut self.live {
+            live.stop();
+        }
+    }
+
+    fn live_status(&self, ui: &mut Ui) {
+        let Some(live) = self.live.as_ref().filter(|live| live.is_streaming()) else {
+            return;
+        };
+        let (color, hover) = match live.is_connected() {
+            true => (Color32::GREEN, "live-streaming"),
+            false => (Color32::YELLOW, "live-connecting"),
+        };
+        ui.label(
+            RichText::new(format!("📡 {}", tr("live-status")))
+                .small()
+                .color(color),
+        )
+        .on_hover_text(tr(hover));
+    }
+
+    fn outbox_status(&mut self, ui: &mut Ui) {
+        if self.outbox.is_empty() {
+            return;
+        }
+        let logined = self.api().is_some_and(ApiService::is_logined);
+        let sending = self.outbox.items().iter().filter(|i| i.sending).count();
+        let total = self.outbox.len();
+        let text = match (logined, sending) {
+            (false, _) => dyno_core::tr!("outbox-queued-logout", count = total),
+            (true, 0) => dyno_core::tr!("outbox-queued", count = total),
+            (true, _) => dyno_core::tr!("outbox-sending", sending = sending, count = total),
+        };
+        let response = ui
+            .add(
+                Label::new(
+                    RichText::new(format!("⬆ {text}"))
+                        .small()
+                        .color(Color32::YELLOW),
+                )
+                .sense(Sense::click()),
+            )
+            .on_hover_ui(|ui| {
+                let now = Instant::now();
+                if let Some(next) = self.outbox.next_retry(now).filter(|d| !d.is_zero()) {
+                    ui.label(dyno_core::tr!(
+                        "outbox-next-retry",
+                        seconds = next.as_secs()
+                    ));
+                }
+                for item in self.outbox.items() {
+                    let status = match (&item.error, item.sending) {
+                        (_, true) => tr("outbox-item-sending"),
+                        (Some(err), false) => dyno_core::tr!(
+                            "outbox-item-failed",
+                            attempts = item.attempts,
+                            error = err.to_string(),
+                        ),
+                        (None, false) => tr("outbox-item-waiting"),
+                    };
+                    ui.small(format!(
+                        "{}: {status}",
+                        &item.checksum[..8.min(item.checksum.len())]
+                    ));
+                }
+            });
+        if response.clicked() {
+            self.outbox.retry_now();
+        }
+    }
+
+    #[inline]
+    pub fn registry(&self) -> &VehicleRegistry {
+        &self.registry
+    }
+
+    fn save_registry(&self) {
+        let saved = std::fs::create_dir_all(&self.paths.data_dir)
+            .map_err(From::from)
+            .and_then(|()| self.paths.set_bin(&self.registry, VehicleRegistry::FILE));
+        if let Err(err) = saved {
+            toast_error!(
+                "{}",
+                dyno_core::tr!("toast-registry-save-failed", error = err.to_string())
+            );
+        }
+    }
+
+    /// push the queued vehicles to the server, the queue is kept while logged out
+    pub fn sync_registry(&self) {
+        match self.api() {
+            Some(api) if api.is_logined() => {
+                self.set_loading();
+                api.sync_vehicle(self.registry.outbox(), self.tx().clone());
+            }
+            _ => log::debug!(
+                "Not logged in, {} vehicles stay queued",
+                self.registry.pending()
+            ),
+        }
+    }
+
+    pub fn save_vehicle(&mut self, vehicle: Vehicle) {
+        self.registry.upsert(vehicle);
+        self.save_registry();
+        self.sync_registry();
+    }
+
+    pub fn remove_vehicle(&mut self, uuid: &str) {
+        if self.registry.remove(uuid) {
+            if self.session.info.vehicle_id.as_deref() == Some(uuid) {
+                self.session.info.vehicle_id = None;
+            }
+            self.save_registry();
+            self.sync_registry();
+        }
+    }
+
+    /// new vehicle from the customer of the session and the active motor info
+    pub fn vehicle_from_session(&self) -> Vehicle {
+        let info = &self.session.info;
+        Vehicle {
+            uuid: uuid::Uuid::new_v4().to_string(),
+            make: info.vehicle.clone(),
+            plate_number: info.plate_number.clone(),
+            customer: info.customer.clone(),
+            phone: info.phone.clone(),
+            motor_info: self.config.motor_info,
+            ..Default::default()
+        }
+    }
+
+    /// link the session to the vehicle, the customer and the motor info is filled from it
+    pub fn use_vehicle(&mut self, uuid: &str) {
+        let Some(vehicle) = self.registry.get(uuid) else {
+            return;
+        };
+        let info = &mut self.session.info;
+        info.vehicle_id = Some(vehicle.uuid.clone());
+        info.vehicle = vehicle.title();
+        info.plate_number = vehicle.plate_number.clone();
+        info.customer = vehicle.customer.clone();
+        info.phone = vehicle.phone.clone();
+        self.config.motor_info = vehicle.motor_info;
+        let run = self.session.active_mut();
+        if run.data.is_empty() {
+            run.data.config.motor_info = vehicle.motor_info;
+        }
+        toast_info!(
+            "{}",
+            dyno_core::tr!("toast-vehicle-linked", vehicle = vehicle.title())
+        );
+        self.snapshot_journal();
+    }
+
+    /// add the active run to the history of the linked vehicle
+    fn record_vehicle_run(&mut self) {
+        let Some(uuid) = self.session.info.vehicle_id.clone() else {
+            return;
+        };
+        let run = VehicleRun::new(&self.session, self.session.active());
+        self.registry.record_run(&uuid, run);
+        self.save_registry();
+    }
+
+    /// save the dyno sheet of every recorded run in the session, the runs is drawn as overlay
+    pub fn export_report(&mut self) {
+        use dyno_core::{
+            report::{DynoReport, ReportHeader, ReportInfo},
+            tokio,
+        };
+
+        if self.session.is_empty() {
+            toast_warn!("{}", tr("toast-report-empty"));
+            return;
+        }
+        let session = self.session.clone();
+        let settings = self.app_config.report.clone();
+        let loadings = self.loadings.clone();
+        let tx = self.async_channels.0.clone();
+        let dirpath = self.paths.get_data_dir_folder("Reports");
+        tokio::spawn(async move {
+            loadings.store(true, Ordering::Relaxed);
+            let file_name = format!("dynosheet_{}.svg", Utc::now().timestamp());
+            match DynoFileManager::save_report_async(file_name, dirpath).await {
+                Some(file) => {
+                    let saved = asyncify!(move || {
+                        let header = ReportHeader {
+                            workshop: settings.workshop,
+                            address: settings.address,
+                            logo_png: Some(crate::get_assets!("logo-512x512.png").to_vec()),
+                        };
+                        let info = ReportInfo {
+                            customer: session.info.customer.clone(),
+                            vehicle: session.info.vehicle.clone(),
+                            plate_number: session.info.plate_number.clone(),
+                            operator: settings.operator,
+                            notes: session.notes.clone(),
+                        };
+                        let mut report = DynoReport::new(header, info);
+                        for run in session.runs() {
+                            report.add_run(&run.name, &run.data);
+                        }
+                        report.save_svg(file.path())?;
+                        Ok(file.path().display().to_string())
+                    });
+                    match saved {
+                        Ok(path) => ignore_err!(tx.send(AsyncMsg::message(dyno_core::tr!(
+                            "toast-report-saved",
+                            path = path
+                        )))),
+                        Err(err) => ignore_err!(tx.send(AsyncMsg::OnError(err))),
+                    }
+                }
+                None => dyno_core::log::debug!("FileManager ppick file canceled"),
+            }
+            loadings.store(false, Ordering::Relaxed);
+        });
+    }
+
     pub fn on_save(&mut self, tp: DynoFileType) {
         use dyno_core::tokio;
 
-        let buffer = self.buffer.clone();
+        let dropped = self.session.buffer().annotations().len();
+        if dropped > 0 && !tp.keeps_annotations() {
+            toast_warn!(
+                "{}",
+                dyno_core::tr!(
+                    "save-drops-annotations",
+                    format = tp.to_string(),
+                    count = dropped
+                )
+            );
+        }
+        let session = self.session.clone();
         let loadings = self.loadings.clone();
         let tx = self.async_channels.0.clone();
 
@@ -293,21 +1234,39 @@ impl DynoControl {
         tokio::spawn(async move {
             loadings.store(true, Ordering::Relaxed);
             match tp {
-                DynoFileType::Dyno => {
-                    match DynoFileManager::save_binaries_async(
-                        format!("dynotest_{}.dyno", Utc::now().timestamp()),
+                DynoFileType::Session => {
+                    match DynoFileManager::save_session_async(
+                        format!("dynotest_{}.{SESSION_EXTENSION}", Utc::now().timestamp()),
                         dirpath,
                     )
                     .await
                     {
-                        Some(file) => match asyncify!(move || buffer.compress_to_path(file.path()))
-                        {
+                        Some(file) => match asyncify!(move || session.save(file.path())) {
                             Ok(()) => ignore_err!(tx.send(AsyncMsg::OnSavedBuffer(()))),
                             Err(err) => ignore_err!(tx.send(AsyncMsg::OnError(err))),
                         },
                         None => dyno_core::log::debug!("FileManager ppick file canceled"),
                     }
                 }
+                DynoFileType::Dyno => {
+                    match DynoFileManager::save_binaries_async(
+                        format!("dynotest_{}.dyno", Utc::now().timestamp()),
+                        dirpath,
+                    )
+                    .await
+                    {
+                        Some(file) => {
+                            match asyncify!(move || session
+                                .buffer()
+                                .save_dyno_from_path(file.path()))
+                            {
+                                Ok(()) => ignore_err!(tx.send(AsyncMsg::OnSavedBuffer(()))),
+                                Err(err) => ignore_err!(tx.send(AsyncMsg::OnError(err))),
+                            }
+                        }
+                        None => dyno_core::log::debug!("FileManager ppick file canceled"),
+                    }
+                }
                 DynoFileType::Csv => {
                     match DynoFileManager::save_csv_async(
                         format!("dynotest_{}.csv", Utc::now().timestamp()),
@@ -316,7 +1275,10 @@ impl DynoControl {
                     .await
                     {
                         Some(file) => {
-                            match asyncify!(move || buffer.save_csv_from_path(file.path())) {
+                            match asyncify!(move || session
+                                .buffer()
+                                .save_csv_from_path(file.path()))
+                            {
                                 Ok(()) => ignore_err!(tx.send(AsyncMsg::OnSavedBuffer(()))),
                                 Err(err) => ignore_err!(tx.send(AsyncMsg::OnError(err))),
                             }
@@ -330,11 +1292,13 @@ impl DynoControl {
                 )
                 .await
                 {
-                    Some(file) => match asyncify!(move || buffer.save_excel_from_path(file.path()))
-                    {
-                        Ok(()) => ignore_err!(tx.send(AsyncMsg::OnSavedBuffer(()))),
-                        Err(err) => ignore_err!(tx.send(AsyncMsg::OnError(err))),
-                    },
+                    Some(file) => {
+                        match asyncify!(move || session.buffer().save_excel_from_path(file.path()))
+                        {
+                            Ok(()) => ignore_err!(tx.send(AsyncMsg::OnSavedBuffer(()))),
+                            Err(err) => ignore_err!(tx.send(AsyncMsg::OnError(err))),
+                        }
+                    }
                     None => dyno_core::log::debug!("FileManager ppick file canceled"),
                 },
             };
@@ -352,9 +1316,16 @@ impl DynoControl {
         tokio::spawn(async move {
             loadings.store(true, Ordering::Relaxed);
             match tp {
+                DynoFileType::Session => match DynoFileManager::pick_session_async(dirpath).await {
+                    Some(file) => match asyncify!(move || DynoSession::open(file.path())) {
+                        Ok(session) => ignore_err!(tx.send(AsyncMsg::open_session(session))),
+                        Err(err) => ignore_err!(tx.send(AsyncMsg::error(err))),
+                    },
+                    None => dyno_core::log::debug!("FileManager ppick file canceled"),
+                },
                 DynoFileType::Dyno => match DynoFileManager::pick_binaries_async(dirpath).await {
                     Some(file) => {
-                        match asyncify!(move || BufferData::decompress_from_path(file.path())) {
+                        match asyncify!(move || DynotestData::open_dyno_from_path(file.path())) {
                             Ok(data) => ignore_err!(tx.send(AsyncMsg::open_buffer(data))),
                             Err(err) => ignore_err!(tx.send(AsyncMsg::error(err))),
                         }
@@ -363,7 +1334,7 @@ impl DynoControl {
                 },
                 DynoFileType::Csv => match DynoFileManager::pick_csv_async(dirpath).await {
                     Some(file) => {
-                        match asyncify!(move || BufferData::open_csv_from_path(file.path())) {
+                        match asyncify!(move || DynotestData::open_csv_from_path(file.path())) {
                             Ok(data) => ignore_err!(tx.send(AsyncMsg::open_buffer(data))),
                             Err(err) => ignore_err!(tx.send(AsyncMsg::error(err))),
                         }
@@ -372,7 +1343,7 @@ impl DynoControl {
                 },
                 DynoFileType::Excel => match DynoFileManager::pick_excel_async(dirpath).await {
                     Some(file) => {
-                        match asyncify!(move || BufferData::open_excel_from_path(file.path())) {
+                        match asyncify!(move || DynotestData::open_excel_from_path(file.path())) {
                             Ok(data) => ignore_err!(tx.send(AsyncMsg::open_buffer(data))),
                             Err(err) => ignore_err!(tx.send(AsyncMsg::error(err))),
                         }
@@ -383,6 +1354,34 @@ impl DynoControl {
             loadings.store(false, Ordering::Relaxed);
         });
     }
+
+    /// open the pending files, see [`Self::open_paths`]
+    pub fn on_open_paths(&mut self) {
+        use dyno_core::tokio;
+
+        let replace_session = self.is_session_pending();
+        let paths = std::mem::take(&mut self.pending_open);
+        let tx = self.async_channels.0.clone();
+        let loadings = self.loadings.clone();
+
+        tokio::spawn(async move {
+            loadings.store(true, Ordering::Relaxed);
+            for path in paths {
+                let name = path.display().to_string();
+                let msg = match replace_session {
+                    true => asyncify!(move || DynoSession::open(path)).map(AsyncMsg::open_session),
+                    false => asyncify!(move || open_runs(&path)).map(AsyncMsg::OnOpenRuns),
+                };
+                ignore_err!(tx.send(msg.unwrap_or_else(|err| {
+                    AsyncMsg::error(DynoErr::new(
+                        err.kind(),
+                        dyno_core::tr!("toast-open-failed", name = name, error = err.to_string()),
+                    ))
+                })));
+            }
+            loadings.store(false, Ordering::Relaxed);
+        });
+    }
 }
 
 impl DynoControl {
@@ -393,67 +1392,116 @@ impl DynoControl {
         window_stack: &mut WindowStack,
         state: &mut DynoState,
     ) {
-        ui.menu_button("File", |menu_ui| {
+        ui.menu_button(tr("menu-file"), |menu_ui| {
             if menu_ui.open_button().clicked() {
                 log::debug!("Open Button menu clicked");
-                state.set_operator(OperatorData::OpenFile(DynoFileType::Dyno));
+                state.set_operator(OperatorData::OpenFile(DynoFileType::Session));
             }
-            menu_ui.menu_button("Open As..", |submenu_ui| {
-                if submenu_ui.button("Csv File").clicked() {
+            menu_ui.menu_button(tr("menu-open-as"), |submenu_ui| {
+                if submenu_ui.button(tr("file-session")).clicked() {
+                    state.set_operator(OperatorData::OpenFile(DynoFileType::Session));
+                    log::debug!("Open as Session file submenu clicked");
+                }
+                if submenu_ui.button(tr("file-csv")).clicked() {
                     state.set_operator(OperatorData::OpenFile(DynoFileType::Csv));
                     log::debug!("Open as Csv file submenu clicked");
                 }
-                if submenu_ui.button("Excel File").clicked() {
+                if submenu_ui.button(tr("file-excel")).clicked() {
                     state.set_operator(OperatorData::OpenFile(DynoFileType::Excel));
                     log::debug!("Open as Excel file submenu clicked");
                 }
-                if submenu_ui.button("Binaries File").clicked() {
+                if submenu_ui.button(tr("file-binaries")).clicked() {
                     state.set_operator(OperatorData::OpenFile(DynoFileType::Dyno));
                     log::debug!("Open as Binaries file submenu clicked");
                 }
             });
             if menu_ui.save_button().clicked() {
                 log::debug!("Save file menu clicked");
-                state.set_operator(OperatorData::SaveFile(DynoFileType::Dyno));
+                state.set_operator(OperatorData::SaveFile(DynoFileType::Session));
             }
-            menu_ui.menu_button("Save As..", |submenu_ui| {
-                if submenu_ui.button("Csv File").clicked() {
+            menu_ui.menu_button(tr("menu-save-as"), |submenu_ui| {
+                if submenu_ui.button(tr("file-session")).clicked() {
+                    log::debug!("Save as Session file submenu clicked");
+                    state.set_operator(OperatorData::SaveFile(DynoFileType::Session));
+                }
+                if submenu_ui.button(tr("file-csv")).clicked() {
                     log::debug!("Save as Csv file submenu clicked");
                     state.set_operator(OperatorData::SaveFile(DynoFileType::Csv));
                 }
-                if submenu_ui.button("Excel File").clicked() {
+                if submenu_ui.button(tr("file-excel")).clicked() {
                     log::debug!("Save as Excel file submenu clicked");
                     state.set_operator(OperatorData::SaveFile(DynoFileType::Excel));
                 }
-                if submenu_ui.button("Binaries File").clicked() {
+                if submenu_ui.button(tr("file-binaries")).clicked() {
                     log::debug!("Save as Binaries file submenu clicked");
                     state.set_operator(OperatorData::SaveFile(DynoFileType::Dyno));
                 }
             });
-            if menu_ui.button("Quit").clicked() {
+            if menu_ui
+                .button(tr("menu-export-sheet"))
+                .on_hover_text(tr_attr("menu-export-sheet", "hint"))
+                .clicked()
+            {
+                menu_ui.close_menu();
+                self.export_report();
+            }
+            menu_ui.menu_button(tr("menu-serial-capture"), |submenu_ui| {
+                self.serial_capture_menu(submenu_ui)
+            });
+            if menu_ui.button(tr("menu-quit")).clicked() {
                 log::debug!("Exit submenu clicked");
                 window_stack.set_open(WSIdx::ConfirmQuit, true);
             }
         });
-        ui.menu_button("View", |submenu_ui| {
-            submenu_ui.checkbox(state.show_bottom_panel_mut(), "Bottom Panel");
-            submenu_ui.checkbox(state.show_left_panel_mut(), "Left Panel");
+        ui.menu_button(tr("menu-view"), |submenu_ui| {
+            submenu_ui.checkbox(state.show_bottom_panel_mut(), tr("view-bottom-panel"));
+            submenu_ui.checkbox(state.show_left_panel_mut(), tr("view-left-panel"));
             if submenu_ui
-                .checkbox(state.show_logger_window_mut(), "Logger Window")
+                .checkbox(state.show_logger_window_mut(), tr("view-logger-window"))
                 .changed()
             {
                 window_stack.set_open(WSIdx::Logger, state.show_logger_window())
             }
         });
-        if ui.button("Config").clicked() {
+        if ui.button(tr("menu-session")).clicked() {
+            log::debug!("Session submenu clicked");
+            window_stack.set_swap_open(WSIdx::Session);
+        }
+        if ui.button(tr("menu-vehicles")).clicked() {
+            log::debug!("Vehicles submenu clicked");
+            window_stack.set_swap_open(WSIdx::Vehicles);
+        }
+        if ui.button(tr("menu-procedure")).clicked() {
+            log::debug!("Procedure submenu clicked");
+            window_stack.set_swap_open(WSIdx::Procedure);
+        }
+        ui.menu_button(tr("menu-server"), |menu_ui| {
+            if self.app_config.server.select_ui(menu_ui) {
+                self.reconnect_api();
+            }
+            menu_ui.separator();
+            if menu_ui.button(tr("menu-edit-servers")).clicked() {
+                window_stack.set_swap_open(WSIdx::Setting);
+                menu_ui.close_menu();
+            }
+        });
+        ui.menu_button(tr("menu-dashboard"), |menu_ui| {
+            self.app_config.dashboard.select_ui(menu_ui);
+            menu_ui.separator();
+            if menu_ui.button(tr("menu-edit-layouts")).clicked() {
+                window_stack.set_swap_open(WSIdx::Dashboard);
+                menu_ui.close_menu();
+            }
+        });
+        if ui.button(tr("menu-config")).clicked() {
             log::debug!("Config submenu clicked");
             window_stack.set_swap_open(WSIdx::Setting);
         }
-        if ui.button("Help").clicked() {
+        if ui.button(tr("menu-help")).clicked() {
             log::debug!("Help submenu clicked");
             window_stack.set_swap_open(WSIdx::Help);
         }
-        if ui.button("About").clicked() {
+        if ui.button(tr("menu-about")).clicked() {
             log::debug!("About submenu clicked");
             window_stack.set_swap_open(WSIdx::About);
         }
@@ -462,23 +1510,23 @@ impl DynoControl {
             eframe::egui::widgets::global_dark_light_mode_switch(rtl_ui);
             match &self.api_service {
                 Some(api) if api.is_logined() => {
-                    if rtl_ui.button("Logout").clicked() {
+                    if rtl_ui.button(tr("menu-logout")).clicked() {
                         log::info!("Logout button clicked");
                         api.logout(self.tx().clone());
                     }
-                    if rtl_ui.button("Open from Server").clicked() {
+                    if rtl_ui.button(tr("menu-open-server")).clicked() {
                         log::info!("Opening Window Open Project from server...");
                         window_stack.set_swap_open(WSIdx::OpenServer);
                     }
-                    if rtl_ui.button("Save to Server").clicked() {
+                    if rtl_ui.button(tr("menu-save-server")).clicked() {
                         log::info!("Opening Window Save Project to to server...");
                         window_stack.set_swap_open(WSIdx::SaveServer);
                     }
                 }
                 _ => {
                     if rtl_ui
-                        .button("Login")
-                        .on_hover_text("login first to access server, like saving data to server.")
+                        .button(tr("menu-login"))
+                        .on_hover_text(tr_attr("menu-login", "hint"))
                         .clicked()
                     {
                         log::info!("Login bottom clicked");
@@ -490,74 +1538,143 @@ impl DynoControl {
     }
 
     #[inline(always)]
+    /// select the run to display and record, disabled while recording
+    fn run_selector(&mut self, ui: &mut Ui) {
+        let recording = self.is_recording();
+        ui.add_enabled_ui(!recording, |ui| {
+            let mut selected = self.session.active_index();
+            ComboBox::from_id_source("dyno_run_selector")
+                .selected_text(self.session.active().name.as_str())
+                .show_ui(ui, |ui| {
+                    for (idx, run) in self.session.runs().iter().enumerate() {
+                        ui.selectable_value(&mut selected, idx, run.name.as_str());
+                    }
+                })
+                .response
+                .on_hover_text(tr("status-run-selector"));
+            if selected != self.session.active_index() {
+                self.session.select(selected);
+            }
+            if ui
+                .small_button("➕")
+                .on_hover_text(tr("status-new-run"))
+                .clicked()
+            {
+                self.new_run();
+            }
+        });
+        ui.separator();
+    }
+
     pub fn bottom_status(&mut self, ui: &mut Ui) {
-        let layout_ui_status = |ltr_ui: &mut Ui| match &mut self.serial {
-            Some(serial) => {
-                let serial_open = serial.is_open();
-                let (status, color) = if serial_open {
-                    ("STATUS: Running", Color32::BLUE)
-                } else {
-                    ("STATUS: Connected", Color32::GREEN)
-                };
-                let PortInfo {
-                    port_name,
-                    vid,
-                    pid,
-                    ..
-                }: &PortInfo = serial.get_info();
-                Label::new(RichText::new(status).color(color))
-                    .ui(ltr_ui)
-                    .on_hover_text(format!("PORT INFO: [{port_name}] ({vid}:{pid})"));
-                ltr_ui.separator();
-                let btn_start = ltr_ui
-                    .small_play_button()
-                    .on_hover_text("Click to Start the Service");
-                let btn_stop = ltr_ui
-                    .small_stop_button()
-                    .on_hover_text("Click to Stop/Pause the Service");
-                let btn_reset = ltr_ui
-                    .small_reset_button()
-                    .on_hover_text("Click to Stop and Reset recorded data buffer");
-                match (
-                    btn_start.clicked(),
-                    btn_stop.clicked(),
-                    btn_reset.clicked(),
-                    serial_open,
-                ) {
-                    (true, _, _, false) => {
-                        if let Err(err) = serial.start(self.async_channels.0.clone()) {
-                            toast_error!("Serial Service Failed to start - {err}")
+        let layout_ui_status = |ltr_ui: &mut Ui| {
+            self.port_selector(ltr_ui);
+            self.run_selector(ltr_ui);
+            match &mut self.serial {
+                Some(serial) => {
+                    let serial_open = serial.is_open();
+                    let (status, color) = match (serial_open, self.serial_lost) {
+                        (true, _) if serial.is_replay() => ("status-replaying", Color32::BLUE),
+                        (true, true) => ("status-reconnecting", Color32::YELLOW),
+                        (true, false) => ("status-running", Color32::BLUE),
+                        (false, _) => ("status-connected", Color32::GREEN),
+                    };
+                    let info: &PortInfo = serial.get_info();
+                    let PortInfo { vid, pid, .. } = info;
+                    let port_name = info.display_name();
+                    Label::new(RichText::new(tr(status)).color(color))
+                        .ui(ltr_ui)
+                        .on_hover_text(dyno_core::tr!(
+                            "status-port-info",
+                            port = port_name,
+                            vid = vid.to_string(),
+                            pid = pid.to_string(),
+                        ));
+                    ltr_ui.separator();
+                    let btn_start = ltr_ui.small_play_button().on_hover_text(tr("status-start"));
+                    let btn_stop = ltr_ui.small_stop_button().on_hover_text(tr("status-stop"));
+                    let btn_reset = ltr_ui
+                        .small_reset_button()
+                        .on_hover_text(tr("status-reset"));
+                    match (
+                        btn_start.clicked(),
+                        btn_stop.clicked(),
+                        btn_reset.clicked(),
+                        serial_open,
+                    ) {
+                        (true, _, _, false) => {
+                            serial.set_capture(self.capture_serial.then(|| {
+                                self.paths
+                                    .get_cache_dir_folder("Captures")
+                                    .join(file_name_timestamp(CAPTURE_EXTENSION))
+                            }));
+                            match serial.start(
+                                self.app_config.serial.clone(),
+                                self.async_channels.0.clone(),
+                            ) {
+                                Ok(_) => {
+                                    // the run is marked started only after the serial service is running
+                                    let run = self.session.active_mut();
+                                    if run.data.is_empty() {
+                                        run.data.config = self.config.clone();
+                                        run.data.start();
+                                    }
+                                    run.start = Some(Utc::now().naive_utc());
+                                    self.alarms.reset();
+                                    self.snapshot_journal();
+                                    let logined = self
+                                        .api_service
+                                        .as_ref()
+                                        .is_some_and(ApiService::is_logined);
+                                    if let Some(live) = self.live.as_mut().filter(|_| logined) {
+                                        live.start(&self.session.active().name);
+                                    }
+                                }
+                                Err(err) => toast_error!(
+                                    "{}",
+                                    dyno_core::tr!(
+                                        "toast-serial-start-failed",
+                                        error = err.to_string()
+                                    )
+                                ),
+                            }
                         }
-                        self.start = Some(Utc::now().naive_utc());
-                    }
-                    (_, true, _, true) => {
-                        serial.stop();
-                        self.stop = Some(Utc::now().naive_utc());
-                    }
-                    (_, _, true, true) => {
-                        serial.stop();
-                        self.buffer.clean();
+                        (_, true, _, true) => {
+                            serial.stop();
+                            self.stop_live();
+                            self.serial_lost = false;
+                            self.session.active_mut().stop = Some(Utc::now().naive_utc());
+                            self.snapshot_journal();
+                            self.record_vehicle_run();
+                        }
+                        (_, _, true, true) => {
+                            serial.stop();
+                            self.stop_live();
+                            self.serial_lost = false;
+                            let run = self.session.active_mut();
+                            run.data.clear();
+                            run.elapsed_ms = 0;
+                            self.snapshot_journal();
+                        }
+                        (_, _, true, _) => {
+                            let run = self.session.active_mut();
+                            run.data.clear();
+                            run.elapsed_ms = 0;
+                            self.snapshot_journal();
+                        }
+                        _ => {}
                     }
-                    (_, _, true, _) => self.buffer.clean(),
-                    _ => {}
                 }
-            }
-            None => {
-                Label::new(RichText::new("STATUS: Not Initialize / Connected").color(Color32::RED))
-                    .sense(Sense::union(Sense::click(), Sense::hover()))
-                    .ui(ltr_ui)
-                    .on_hover_text(
-                        "PORT INFO: [NO PORT DETECTED] (XX:XX), click to try Initialize the port",
-                    );
-                if ltr_ui.button("\u{1F50C} Try Reconnect").clicked() {
-                    if let Some(serial) = SerialService::new() {
-                        toast_success!(
-                            "SUCCES! connected to [{}] - [{}:{}]",
-                            serial.info.port_name,
-                            serial.info.vid,
-                            serial.info.pid
-                        );
-                        self.serial = Some(serial);
+                None => {
+                    Label::new(RichText::new(tr("status-not-connected")).color(Color32::RED))
+                        .sense(Sense::union(Sense::click(), Sense::hover()))
+                        .ui(ltr_ui)
+                        .on_hover_text(tr_attr("status-not-connected", "hint"));
+                    if ltr_ui
+                        .button(format!("\u{1F50C} {}", tr("status-reconnect")))
+                        .clicked()
+                    {
+                        self.reconnect_serial();
                     }
                 }
             }
@@ -565,106 +1682,114 @@ impl DynoControl {
         ui.with_layout(Layout::left_to_right(Align::Center), layout_ui_status);
         ui.separator();
         ui.with_layout(Layout::right_to_left(Align::Center), |rtl_ui| {
-            rtl_ui.small(format!("Active Info: {}", self.config.motor_type));
+            self.outbox_status(rtl_ui);
+            self.live_status(rtl_ui);
+            let MotorInfo {
+                cc,
+                kind,
+                cylinder,
+                stroke,
+                transmition,
+            } = self.config.motor_info;
+            rtl_ui.small(dyno_core::tr!(
+                "status-motor-info",
+                kind = kind.to_string(),
+                cc = cc.to_string(),
+                stroke = stroke.to_string(),
+                cylinder = cylinder.to_string(),
+                transmition = transmition.to_string(),
+            ));
         });
     }
 
+    fn port_selector(&mut self, ui: &mut Ui) {
+        let running = self.serial.as_ref().is_some_and(SerialService::is_open);
+        let current = self.serial.as_ref().map(|serial| serial.info.clone());
+        let mut picked = None;
+        ui.add_enabled_ui(!running, |ui| {
+            ComboBox::from_id_source("dyno_serial_port_selector")
+                .selected_text(
+                    current
+                        .as_ref()
+                        .map_or_else(|| tr("status-no-port"), PortInfo::display_name),
+                )
+                .show_ui(ui, |ui| {
+                    for port in self.ports.ports() {
+                        let PortInfo {
+                            vid, pid, product, ..
+                        } = port;
+                        if ui
+                            .selectable_label(current.as_ref() == Some(port), port.display_name())
+                            .on_hover_text(format!(
+                                "{} ({vid}:{pid})",
+                                product
+                                    .clone()
+                                    .unwrap_or_else(|| tr("status-unknown-device"))
+                            ))
+                            .clicked()
+                        {
+                            picked = Some(port.clone());
+                        }
+                    }
+                });
+        })
+        .response
+        .on_disabled_hover_text(tr("status-port-locked"));
+
+        if let Some(port) = picked {
+            self.ports.select(&port);
+            self.serial = None;
+            self.connect_serial(port);
+        }
+    }
+
     pub fn left_panel(&mut self, ui: &mut Ui) {
-        let Data {
-            speed,
-            torque,
-            horsepower,
-            rpm_roda,
-            rpm_engine,
-            temp,
-            odo,
-            percepatan_sudut,
-            percepatan_roller,
-            ..
-        } = self.buffer.last();
+        let buffer = self.session.buffer();
+        let last = buffer.last();
+        let speed = last.speed.get::<kilometer_per_hour>();
+        let rpm_engine = last.rpm_engine.get::<revolution_per_minute>();
+        let rpm_roda = last.rpm_roda.get::<revolution_per_minute>();
+        let torque = last.torque.get::<newton_meter>();
+        let horsepower = last.horsepower.get::<horsepower_metric>();
+        let temp = last.temps[0].get::<degree_celsius>();
+
+        let speed_text = format!("{speed:.2} km/h");
+        let rpm_engine_text = format!("{rpm_engine:.0} rpm");
+        let rpm_roda_text = format!("{rpm_roda:.0} rpm");
+        let torque_text = format!("{torque:.2} Nm");
+        let horsepower_text = format!("{horsepower:.2} HP");
+        let odo_text = format!("{:.3} km", buffer.odo_km());
+        let temp_text = format!("{temp:.1} °C");
+        let temps_text = (0..buffer.config.temp_channels.len().min(MAX_TEMP_CHANNELS))
+            .map(|idx| {
+                format!(
+                    "{}: {:.1} °C",
+                    buffer.config.temp_channel_name(idx),
+                    last.temps[idx].get::<degree_celsius>()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
 
         let grid_ui = |grid_ui: &mut Ui| {
-            row_label_value!(
-                grid_ui,
-                speed,
-                "Speed",
-                "calculated from rotational speed distance of the roller in dynotests chasis"
-            );
-            row_label_value!(
-                grid_ui,
-                rpm_engine,
-                "Rpm Engine",
-                "calculated from rpm counter driver in dynotests chasis",
-            );
+            row_label_value!(grid_ui, speed_text, "data-speed");
+            row_label_value!(grid_ui, odo_text, "data-odo",);
             grid_ui.end_row();
-            row_label_value!(
-                grid_ui,
-                rpm_engine,
-                "Rpm ENgine (Mesin)",
-                "calculated from rotational engine from rpm driver sensor in dynotests chasis",
-            );
-            row_label_value!(
-                grid_ui,
-                rpm_roda,
-                "Rpm Tire (Roda)",
-                "calculated from rotational of the roller in dynotests",
-            );
+            row_label_value!(grid_ui, rpm_engine_text, "data-rpm-engine",);
+            row_label_value!(grid_ui, rpm_roda_text, "data-rpm-roda",);
             grid_ui.end_row();
-            row_label_value!(
-                grid_ui,
-                torque,
-                "Torque",
-                "calculated from rotational speed of the roller in dynotests chasis",
-            );
-            row_label_value!(
-                grid_ui,
-                horsepower,
-                "HorsePower",
-                "calculated from rotational speed of the roller in dynotests chasis",
-            );
-            grid_ui.end_row();
-            row_label_value!(
-                grid_ui,
-                odo,
-                "ODO (Jarak Tempuh)",
-                r#"
-Distance Traveled / Jarak Tempuh calculated distance 
-from rotational rounds of the roller in dynotests chasis
-                "#,
-            );
-            row_label_value!(
-                grid_ui,
-                temp,
-                "Engine Temperature",
-                "calculated from thermocouple sensor driver in dynotests chasis",
-            );
+            row_label_value!(grid_ui, torque_text, "data-torque",);
+            row_label_value!(grid_ui, horsepower_text, "data-horsepower",);
             grid_ui.end_row();
-            row_label_value!(
-                grid_ui,
-                percepatan_sudut,
-                "Angular Velocity",
-                "calculated from angular rotational roller in dynotests chasis",
-            );
-            row_label_value!(
-                grid_ui,
-                percepatan_roller,
-                "Roller Velocity",
-                "calculated Roller velocity in dynotests chasis",
-            );
+            row_label_value!(grid_ui, temp_text, "data-engine-temp",);
+            row_label_value!(grid_ui, temps_text, "data-temp-channels",);
         };
-        ui.columns(2, |uis| {
-            uis[0].add(Gauge::speed(*speed).diameter(uis[0].available_width()));
-            uis[1].add(Gauge::rpm_engine(*rpm_engine).diameter(uis[1].available_width()));
-        });
-        CollapsingHeader::new("Gauges Other")
-            .id_source("dyno_gauges_other_collapse_id")
-            .show(ui, |ui| {
-                ui.columns(3, |uis| {
-                    uis[0].add(Gauge::horsepower(*horsepower).diameter(uis[0].available_width()));
-                    uis[1].add(Gauge::rpm_roda(*rpm_roda).diameter(uis[1].available_width()));
-                    uis[2].add(Gauge::torque(*torque).diameter(uis[2].available_width()));
-                });
-            });
+        tiles_ui(
+            ui,
+            &self.app_config.dashboard.layout().left,
+            &self.dash_values(),
+            self.app_config.segment_display_style,
+        );
         ui.vertical_centered(|ui| {
             Grid::new("dyno_left_values_grid_id")
                 .num_columns(4)
@@ -674,46 +1799,65 @@ from rotational rounds of the roller in dynotests chasis
         });
     }
 
+    /// last data of the selected run for the dashboard tiles
+    fn dash_values(&self) -> DashValues<'_> {
+        let run = self.session.active();
+        DashValues {
+            data: run.data.last(),
+            config: run.config(),
+            odo_km: run.data.odo_km(),
+            elapsed_ms: run.elapsed_ms,
+            alarms: self.alarms.flashing(),
+        }
+    }
+
     pub fn right_panel(&mut self, ui: &mut Ui) {
-        let Data {
-            speed,
-            rpm_engine,
-            odo,
-            ..
-        } = self.buffer.last();
-        const MULTPL_WIDTH: f32 = 0.19;
-        const HEADING_SEGMENTS: [&str; 4] =
-            ["Speed (km/h)", "RPM x 1000", "ODO (km)", "Time (HH:MM:SS)"];
-        let value_segments = [
-            format!("{:7.2}", speed.value()),
-            format!("{:7.2}", rpm_engine.value() * 0.001),
-            format!("{:7.2}", odo.value()),
-            self.start_time(),
-        ];
-        let iter_segmented_ui = |(idx, segment_ui): (usize, &mut Ui)| {
-            segment_ui.group(|uigroup_inner| {
-                uigroup_inner.vertical_centered(|uivert_inner| {
-                    uivert_inner.strong(HEADING_SEGMENTS[idx]);
-                    let digit_height = uivert_inner.available_width() * MULTPL_WIDTH;
-                    SegmentedDisplay::dyno_seven_segment(&value_segments[idx])
-                        .style_preset(self.app_config.segment_display_style)
-                        .digit_height(digit_height)
-                        .ui(uivert_inner);
-                });
-            });
-        };
-        ui.columns(4, |segments_ui| {
-            segments_ui
-                .iter_mut()
-                .enumerate()
-                .for_each(iter_segmented_ui);
-        });
+        tiles_ui(
+            ui,
+            &self.app_config.dashboard.layout().right,
+            &self.dash_values(),
+            self.app_config.segment_display_style,
+        );
         ui.separator();
-        self.plots.ui(ui, &self.buffer);
+        let run = self.session.active_mut();
+        let annotations = run.data.annotations().len();
+        self.plots.ui(ui, &mut run.data, &mut self.app_config.plot);
+        if run.data.annotations().len() != annotations {
+            self.buffer_saved = false;
+            self.snapshot_journal();
+        }
     }
 
     #[inline]
     pub fn handle_states(&mut self, ctx: &Context) {
+        self.poll_ports();
+        ctx.request_repaint_after(PortManager::POLL_INTERVAL);
+
+        const BELL_SECONDS: f64 = 1.5;
+        let now = ctx.input(|i| i.time);
+        if std::mem::take(&mut self.alarm_attention) {
+            self.alarm_bell = Some(now + BELL_SECONDS);
+            ctx.send_viewport_cmd(ViewportCommand::RequestUserAttention(
+                UserAttentionType::Critical,
+            ));
+        }
+        match self.alarm_bell {
+            Some(until) if now < until => {
+                // visual bell, the border of the window is blinking red
+                if (now * 8.) as i64 % 2 == 0 {
+                    ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("dyno_alarm_bell")))
+                        .rect_stroke(
+                            ctx.screen_rect().shrink(3.),
+                            Rounding::ZERO,
+                            Stroke::new(6.0_f32, Color32::RED),
+                        );
+                }
+                ctx.request_repaint();
+            }
+            Some(_) => self.alarm_bell = None,
+            None => {}
+        }
+
         if self.loadings.load(Ordering::Relaxed) {
             ctx.layer_painter(LayerId::new(
                 Order::Background,
@@ -724,7 +1868,7 @@ from rotational rounds of the roller in dynotests chasis
                 0.0,
                 Color32::from_black_alpha(192),
             );
-            Area::new("dyno_global_loading_spinner")
+            Area::new(Id::new("dyno_global_loading_spinner"))
                 .order(Order::Foreground)
                 .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
                 .show(ctx, |ui| {
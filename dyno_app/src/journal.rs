@@ -0,0 +1,374 @@
+//! append-only journal of the unsaved session in the cache dir, so the recorded data is not lost
+//! when the app crashed. the journal start with a snapshot of the whole session, then every data
+//! from the serial is appended as small entry, a new snapshot truncate the journal.
+//!
+//! entry layout: `[len: u32 LE][postcard JournalEntry]`, a torn entry at the end (crash in the
+//! middle of a write) is ignored when the journal is restored.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use dyno_core::{
+    chrono,
+    crossbeam_channel::{unbounded, RecvTimeoutError, Sender},
+    serde,
+    types::Data,
+    DynoResult,
+};
+
+use crate::{paths::file_name_timestamp, session::DynoSession};
+
+pub const JOURNAL_EXTENSION: &str = "dynojournal";
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum JournalEntry {
+    Snapshot(Box<DynoSession>),
+    Data {
+        run: usize,
+        elapsed_ms: u64,
+        data: Data,
+    },
+    Gap {
+        run: usize,
+        elapsed_ms: u64,
+        gap_ms: i64,
+    },
+}
+
+impl JournalEntry {
+    /// apply the entry to `session`, entry of unknown run is ignored
+    fn apply(self, session: &mut Option<DynoSession>) {
+        match (self, session.as_mut()) {
+            (Self::Snapshot(snapshot), _) => *session = Some(*snapshot),
+            (
+                Self::Data {
+                    run,
+                    elapsed_ms,
+                    data,
+                },
+                Some(session),
+            ) => {
+                if let Some(run) = session.run_mut(run) {
+                    run.data.push(data);
+                    run.elapsed_ms = elapsed_ms;
+                }
+            }
+            (
+                Self::Gap {
+                    run,
+                    elapsed_ms,
+                    gap_ms,
+                },
+                Some(session),
+            ) => {
+                if let Some(run) = session.run_mut(run) {
+                    run.data.record_gap(chrono::Duration::milliseconds(gap_ms));
+                    run.elapsed_ms = elapsed_ms;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn write_entry<W: Write>(writer: &mut W, entry: &JournalEntry) -> DynoResult<()> {
+    let bytes = postcard::to_stdvec(entry)?;
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// read the entries until the end or the first torn entry, then rebuild the session
+pub fn restore<R: Read>(mut reader: R) -> Option<DynoSession> {
+    let mut session = None;
+    let mut len = [0u8; 4];
+    let mut bytes = Vec::new();
+    while reader.read_exact(&mut len).is_ok() {
+        // the length of a torn entry can be garbage, only read what is left in the journal
+        // instead of allocating the length up front
+        let len = u32::from_le_bytes(len) as usize;
+        bytes.clear();
+        match reader.by_ref().take(len as u64).read_to_end(&mut bytes) {
+            Ok(read) if read == len => {}
+            _ => {
+                dyno_core::log::warn!("Journal entry is truncated, the rest is ignored");
+                break;
+            }
+        }
+        match postcard::from_bytes::<JournalEntry>(&bytes) {
+            Ok(entry) => entry.apply(&mut session),
+            Err(err) => {
+                dyno_core::log::warn!("Journal entry is corrupted, the rest is ignored - {err}");
+                break;
+            }
+        }
+    }
+    session
+}
+
+/// session in the journal at `path`, `None` if there is no recorded data
+pub fn recover(path: impl AsRef<Path>) -> DynoResult<Option<DynoSession>> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(restore(reader).filter(|session| !session.is_empty()))
+}
+
+/// journals in `dir` that is left by the previous app, the newest first
+pub fn leftovers(dir: impl AsRef<Path>) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut journals = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == JOURNAL_EXTENSION)
+        })
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .collect::<Vec<_>>();
+    journals.sort_by(|(left, _), (right, _)| right.cmp(left));
+    journals.into_iter().map(|(_, path)| path).collect()
+}
+
+fn remove_journal(path: &Path) {
+    match fs::remove_file(path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => dyno_core::log::warn!("Failed to remove journal `{}` - {err}", path.display()),
+    }
+}
+
+/// remove the journal files at `paths`
+pub fn remove_all(paths: &[PathBuf]) {
+    paths.iter().for_each(|path| remove_journal(path));
+}
+
+enum Command {
+    Entry(JournalEntry),
+    Clear,
+}
+
+/// journal writer of the running app, the file is written in background thread
+pub struct Journal {
+    path: PathBuf,
+    tx: Option<Sender<Command>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Journal {
+    /// buffered entries are written to the file at least once every this interval
+    pub const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+    /// start the writer thread, the file in `dir` is created on the first snapshot
+    pub fn spawn(dir: impl AsRef<Path>) -> Self {
+        let path = dir.as_ref().join(file_name_timestamp(JOURNAL_EXTENSION));
+        let (tx, rx) = unbounded();
+        let thread_path = path.clone();
+        let handle = std::thread::Builder::new()
+            .name("dyno-journal".to_owned())
+            .spawn(move || {
+                let mut writer: Option<BufWriter<File>> = None;
+                loop {
+                    let result = match rx.recv_timeout(Self::FLUSH_INTERVAL) {
+                        Ok(Command::Entry(entry)) => Self::write(&thread_path, &mut writer, &entry),
+                        Ok(Command::Clear) => {
+                            writer = None;
+                            remove_journal(&thread_path);
+                            Ok(())
+                        }
+                        Err(RecvTimeoutError::Timeout) => writer
+                            .as_mut()
+                            .map_or(Ok(()), |w| w.flush().map_err(From::from)),
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
+                    if let Err(err) = result {
+                        dyno_core::log::error!("Failed to write journal - {err}");
+                    }
+                }
+                if let Some(Err(err)) = writer.as_mut().map(Write::flush) {
+                    dyno_core::log::error!("Failed to flush journal - {err}");
+                }
+            })
+            .map_err(|err| dyno_core::log::error!("Failed to spawn journal thread - {err}"))
+            .ok();
+        Self {
+            path,
+            tx: Some(tx),
+            handle,
+        }
+    }
+
+    fn write(
+        path: &Path,
+        writer: &mut Option<BufWriter<File>>,
+        entry: &JournalEntry,
+    ) -> DynoResult<()> {
+        if let JournalEntry::Snapshot(_) = entry {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let w = writer.insert(BufWriter::new(File::create(path)?));
+            write_entry(w, entry)?;
+            return w.flush().map_err(From::from);
+        }
+        // data before the first snapshot has no run to apply to
+        match writer {
+            Some(w) => write_entry(w, entry),
+            None => Ok(()),
+        }
+    }
+
+    #[inline(always)]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    fn send(&self, command: Command) {
+        if let Some(tx) = &self.tx {
+            dyno_core::ignore_err!(tx.send(command));
+        }
+    }
+
+    /// replace the journal with the whole `session`
+    pub fn snapshot(&self, session: &DynoSession) {
+        self.send(Command::Entry(JournalEntry::Snapshot(Box::new(
+            session.clone(),
+        ))));
+    }
+
+    /// append the last data of the selected run
+    pub fn data(&self, session: &DynoSession) {
+        self.send(Command::Entry(JournalEntry::Data {
+            run: session.active_index(),
+            elapsed_ms: session.active().elapsed_ms,
+            data: *session.buffer().last(),
+        }));
+    }
+
+    pub fn gap(&self, session: &DynoSession, gap: chrono::Duration) {
+        self.send(Command::Entry(JournalEntry::Gap {
+            run: session.active_index(),
+            elapsed_ms: session.active().elapsed_ms,
+            gap_ms: gap.num_milliseconds(),
+        }));
+    }
+
+    /// remove the journal, e.g. the session is saved
+    pub fn clear(&self) {
+        self.send(Command::Clear);
+    }
+}
+
+impl Drop for Journal {
+    fn drop(&mut self) {
+        // disconnect the channel to stop the thread, then wait the last flush
+        drop(self.tx.take());
+        if let Some(handle) = self.handle.take() {
+            dyno_core::ignore_err!(handle.join());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::DynoConfig;
+
+    fn data(timestamp: i64) -> Data {
+        Data {
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn journal_restore() {
+        let mut session = DynoSession::new(DynoConfig::default());
+        session.info.customer = "customer".to_owned();
+        let mut bytes = Vec::new();
+        write_entry(
+            &mut bytes,
+            &JournalEntry::Snapshot(Box::new(session.clone())),
+        )
+        .unwrap();
+        for (idx, timestamp) in [1_000, 1_250, 1_500].into_iter().enumerate() {
+            let entry = JournalEntry::Data {
+                run: 0,
+                elapsed_ms: idx as u64 * 250,
+                data: data(timestamp),
+            };
+            write_entry(&mut bytes, &entry).unwrap();
+        }
+        // unknown run is ignored
+        let entry = JournalEntry::Data {
+            run: 3,
+            elapsed_ms: 0,
+            data: data(0),
+        };
+        write_entry(&mut bytes, &entry).unwrap();
+        let complete = bytes.len();
+        let entry = JournalEntry::Gap {
+            run: 0,
+            elapsed_ms: 2_000,
+            gap_ms: 1_500,
+        };
+        write_entry(&mut bytes, &entry).unwrap();
+
+        let restored = restore(bytes.as_slice()).unwrap();
+        assert_eq!(restored.info, session.info);
+        assert_eq!(restored.runs().len(), 1);
+        assert_eq!(restored.buffer().len(), 3);
+        assert_eq!(restored.buffer().gaps().len(), 1);
+        assert_eq!(restored.active().elapsed_ms, 2_000);
+
+        // crashed in the middle of writing the gap
+        let restored = restore(&bytes[..complete + 3]).unwrap();
+        assert!(restored.buffer().gaps().is_empty());
+        assert_eq!(restored.active().elapsed_ms, 500);
+
+        assert!(restore(&bytes[..2]).is_none());
+
+        // torn length of the last entry
+        let mut torn = bytes[..complete].to_vec();
+        torn.extend_from_slice(&u32::MAX.to_le_bytes());
+        torn.extend_from_slice(&[0; 16]);
+        let restored = restore(torn.as_slice()).unwrap();
+        assert_eq!(restored.buffer().len(), 3);
+    }
+
+    #[test]
+    fn journal_writer() {
+        let dir = std::env::temp_dir().join(format!("dynotest_journal_{}", std::process::id()));
+        let mut session = DynoSession::new(DynoConfig::default());
+
+        let journal = Journal::spawn(&dir);
+        // not recorded, there is no snapshot yet
+        session.buffer_mut().push(data(500));
+        journal.data(&session);
+        journal.snapshot(&session);
+        session.buffer_mut().push(data(1_000));
+        journal.data(&session);
+        let path = journal.path().to_path_buf();
+        drop(journal);
+
+        assert_eq!(leftovers(&dir), std::slice::from_ref(&path));
+        let recovered = recover(&path).unwrap().unwrap();
+        assert_eq!(recovered.buffer().len(), 2);
+
+        let journal = Journal::spawn(dir.join("cleared"));
+        journal.snapshot(&session);
+        journal.clear();
+        drop(journal);
+        assert!(leftovers(dir.join("cleared")).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,15 +1,21 @@
 mod constant;
 
+pub mod alarm;
+pub mod cli;
 pub mod config;
 pub mod control;
+pub mod journal;
 pub mod paths;
+pub mod procedure;
+pub mod registry;
 pub mod service;
+pub mod session;
 pub mod state;
 pub mod widgets;
 pub mod windows;
 
 pub use constant::*;
-use dyno_core::{lazy_static, log, DynoErr, DynoResult, FileAction, LoggerBuilder};
+use dyno_core::{log, DynoErr, DynoResult, FileAction, LoggerBuilder};
 
 pub const COLOR_BLUE_DYNO: eframe::epaint::Color32 = eframe::epaint::Color32::from_rgb(0, 204, 255);
 pub const COLOR_BLUE_DYNO_DARK: eframe::epaint::Color32 =
@@ -21,6 +27,22 @@ lazy_static::lazy_static! {
     pub static ref TOAST_MSG: eframe::epaint::mutex::Mutex<widgets::toast::Toasts> = eframe::epaint::mutex::Mutex::new(widgets::toast::Toasts::new());
 }
 
+/// static name of the value, used on combobox and selectable labels
+pub trait AsStr<'s> {
+    fn as_str(&self) -> &'s str;
+}
+
+impl AsStr<'static> for dyno_core::model::role::Roles {
+    #[inline]
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Admin => "Admin",
+            Self::User => "User",
+            Self::Guest => "Guest",
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub enum PanelId {
     Top,
@@ -62,14 +84,30 @@ pub fn init_logger(file: impl AsRef<std::path::Path>) -> DynoResult<()> {
 #[derive(Debug, Clone)]
 pub enum AsyncMsg {
     OnSavedBuffer(()),
-    OnCheckHealthApi(dyno_core::reqwest::StatusCode),
-    OnSerialData(dyno_core::SerialData),
+    OnCheckHealthApi(reqwest::StatusCode),
+    OnSerialData(dyno_core::types::RawSerialData),
+    OnSerialConfig(dyno_core::types::DeviceConfig),
+    OnSerialDisconnected(DynoErr),
+    OnSerialReconnected(service::PortInfo, std::time::Duration),
+    OnSerialLinkTest(service::LinkReport),
     OnMessage(String),
     OnError(DynoErr),
     OnApiLogin,
+    /// logined with the saved tokens
+    OnApiRestore,
     OnApiRegister,
-    OnApiLoadDyno(Vec<dyno_core::dynotests::DynoTest>),
-    OnOpenBuffer(Box<dyno_core::BufferData>),
+    OnApiLoadDyno(Vec<dyno_core::model::dynotests::DynoTest>),
+    /// checksum of the queued upload and the id of the saved dyno
+    OnApiUpload(String, DynoResult<i64>),
+    /// pushed vehicles and every vehicle of the server
+    OnApiSyncVehicle(
+        Vec<dyno_core::model::vehicle::Vehicle>,
+        Vec<dyno_core::model::vehicle::Vehicle>,
+    ),
+    OnOpenBuffer(Box<dyno_core::types::DynotestData>),
+    OnOpenSession(Box<session::DynoSession>),
+    /// runs of the dropped files, added to the session
+    OnOpenRuns(Vec<session::DynoRun>),
 }
 
 impl AsyncMsg {
@@ -78,15 +116,15 @@ impl AsyncMsg {
         Self::OnSavedBuffer(())
     }
     #[inline]
-    pub const fn serial_data(inner: dyno_core::SerialData) -> Self {
+    pub const fn serial_data(inner: dyno_core::types::RawSerialData) -> Self {
         Self::OnSerialData(inner)
     }
     #[inline]
-    pub const fn check_health(inner: dyno_core::reqwest::StatusCode) -> Self {
+    pub const fn check_health(inner: reqwest::StatusCode) -> Self {
         Self::OnCheckHealthApi(inner)
     }
     #[inline]
-    pub const fn on_load_dyno(data: Vec<dyno_core::dynotests::DynoTest>) -> Self {
+    pub const fn on_load_dyno(data: Vec<dyno_core::model::dynotests::DynoTest>) -> Self {
         Self::OnApiLoadDyno(data)
     }
     #[inline]
@@ -94,10 +132,18 @@ impl AsyncMsg {
         Self::OnError(inner.into())
     }
     #[inline]
-    pub fn open_buffer(inner: dyno_core::BufferData) -> Self {
+    pub fn api_error(inner: reqwest::Error) -> Self {
+        Self::OnError(DynoErr::api_error(inner))
+    }
+    #[inline]
+    pub fn open_buffer(inner: dyno_core::types::DynotestData) -> Self {
         Self::OnOpenBuffer(Box::new(inner))
     }
     #[inline]
+    pub fn open_session(inner: session::DynoSession) -> Self {
+        Self::OnOpenSession(Box::new(inner))
+    }
+    #[inline]
     pub fn message(inner: impl ToString) -> Self {
         Self::OnMessage(inner.to_string())
     }
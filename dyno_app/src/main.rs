@@ -1,10 +1,10 @@
 // #![warn(clippy::all, rust_2018_idioms)]
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
-use dyno_core::{ignore_err, serde, tokio};
+use dyno_core::{i18n::tr, ignore_err, serde, tokio};
 use dynotest_app::{
-    control::DynoControl, init_logger, msg_dialog_err, state::DynoState, windows::WindowStack,
-    PanelId, APP_KEY, PACKAGE_INFO, TOAST_MSG,
+    cli::Cli, control::DynoControl, init_logger, msg_dialog_err, state::DynoState,
+    windows::WindowStack, PanelId, APP_KEY, PACKAGE_INFO, TOAST_MSG,
 };
 use eframe::egui::*;
 
@@ -32,23 +32,26 @@ impl Default for Applications {
 
 impl Applications {
     #[allow(clippy::new_ret_no_self)]
-    pub fn run(control: DynoControl) {
+    pub fn run(control: DynoControl, files: Vec<std::path::PathBuf>) {
         let log_dir = control.paths.get_cache_dir_file("logs/log.log");
         ignore_err!(init_logger(log_dir));
         let opt = control.app_config.app_options.main_window_opt();
         let app_creator: eframe::AppCreator = Box::new(|cc| {
-            Box::new(
-                cc.storage
-                    .and_then(|s| eframe::get_value::<Self>(s, APP_KEY).map(Self::init))
-                    .unwrap_or_else(|| {
-                        Self {
-                            window_stack: WindowStack::new(),
-                            control,
-                            ..Default::default()
-                        }
-                        .init()
-                    }),
-            )
+            let mut app = cc
+                .storage
+                .and_then(|s| eframe::get_value::<Self>(s, APP_KEY).map(Self::init))
+                .unwrap_or_else(|| {
+                    Self {
+                        window_stack: WindowStack::new(),
+                        control,
+                        ..Default::default()
+                    }
+                    .init()
+                });
+            if !files.is_empty() {
+                app.control.open_paths(files, &mut app.state);
+            }
+            Ok(Box::new(app))
         });
 
         if let Err(err) = eframe::run_native(PACKAGE_INFO.app_name, opt, app_creator) {
@@ -69,6 +72,50 @@ impl Applications {
     pub fn deinit(&mut self) {
         self.control.deinit();
     }
+
+    // check the closing of the main window, ask confirmation when buffer is not saved
+    fn allow_close(&mut self) -> bool {
+        use dynotest_app::windows::WSIdx::{ConfirmQuit, ConfirmUnsaved};
+        if !self.control.is_buffer_saved() && !self.state.quit() {
+            self.state.set_quitable(true);
+            self.window_stack.set_open(ConfirmUnsaved, true);
+            return false;
+        }
+        if self.state.quitable() {
+            true
+        } else {
+            self.window_stack.set_open(ConfirmQuit, true);
+            false
+        }
+    }
+
+    /// open the files dropped into the window, the hint is shown while the files is hovered
+    fn dropped_files(&mut self, ctx: &Context) {
+        let (hovered, dropped) = ctx.input(|i| {
+            let dropped = i
+                .raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone());
+            (!i.raw.hovered_files.is_empty(), dropped.collect::<Vec<_>>())
+        });
+        if hovered {
+            let painter =
+                ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("dyno_drop_files")));
+            let rect = ctx.screen_rect();
+            painter.rect_filled(rect, 0.0, Color32::from_black_alpha(192));
+            painter.text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                tr("drop-files"),
+                TextStyle::Heading.resolve(&ctx.style()),
+                Color32::WHITE,
+            );
+        }
+        if !dropped.is_empty() {
+            self.control.open_paths(dropped, &mut self.state);
+        }
+    }
 }
 
 impl Applications {
@@ -79,7 +126,7 @@ impl Applications {
         TopBottomPanel::top(PanelId::Top).show(ctx, |ui| {
             menu::bar(ui, |uibar| {
                 use dynotest_app::assets::POLIJE_LOGO_PNG as IMG;
-                uibar.image(IMG.texture_id(uibar.ctx()), IMG.size_vec2());
+                uibar.image((IMG.texture_id(uibar.ctx()), IMG.size_vec2()));
                 uibar.heading("DynoTests Polije");
                 uibar.separator();
                 self.control
@@ -104,7 +151,7 @@ impl Applications {
 }
 
 impl eframe::App for Applications {
-    fn update(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         for window in self.window_stack.iter_mut() {
             if window.is_open() {
                 window.show_window(ctx, &mut self.control, &mut self.state)
@@ -114,34 +161,21 @@ impl eframe::App for Applications {
 
         self.control.handle_states(ctx);
         self.main_panels_draw(ctx);
+        self.dropped_files(ctx);
 
+        if ctx.input(|i| i.viewport().close_requested()) && !self.allow_close() {
+            ctx.send_viewport_cmd(ViewportCommand::CancelClose);
+        }
         if self.state.quit() {
-            frame.close();
+            ctx.send_viewport_cmd(ViewportCommand::Close);
         }
-    }
 
-    fn clear_color(&self, visuals: &Visuals) -> [f32; 4] {
-        visuals.window_fill().to_normalized_gamma_f32()
-    }
-
-    fn post_rendering(&mut self, _window_size_px: [u32; 2], _frame: &eframe::Frame) {
         self.control
             .on_pos_render(&mut self.window_stack, &mut self.state);
     }
 
-    fn on_close_event(&mut self) -> bool {
-        use dynotest_app::windows::WSIdx::{ConfirmQuit, ConfirmUnsaved};
-        if !self.control.is_buffer_saved() && !self.state.quit() {
-            self.state.set_quitable(true);
-            self.window_stack.set_open(ConfirmUnsaved, true);
-            return false;
-        }
-        if self.state.quitable() {
-            true
-        } else {
-            self.window_stack.set_open(ConfirmQuit, true);
-            false
-        }
+    fn clear_color(&self, visuals: &Visuals) -> [f32; 4] {
+        visuals.window_fill().to_normalized_gamma_f32()
     }
 
     fn auto_save_interval(&self) -> std::time::Duration {
@@ -159,6 +193,7 @@ impl eframe::App for Applications {
 }
 
 fn main() {
+    let cli = <Cli as clap::Parser>::parse();
     let rt = tokio::runtime::Runtime::new().expect("Unable to create tokio's Runtime");
     // Enter the runtime so that `tokio::spawn` is available immediately.
     let _enter = rt.enter();
@@ -173,6 +208,15 @@ fn main() {
         })
     });
 
+    // headless mode, see `dynotest_app::cli`
+    if let Some(command) = cli.command {
+        if let Err(err) = dynotest_app::cli::run(command) {
+            eprintln!("ERROR: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let control = DynoControl::new();
-    Applications::run(control)
+    Applications::run(control, cli.files)
 }
@@ -5,7 +5,8 @@ use std::{
 };
 
 use crate::widgets::DynoFileManager;
-use dyno_core::{chrono::Local, paste::paste, serde, toml, CompresedSaver, DynoErr, DynoResult};
+use dyno_core::{chrono::Local, i18n::tr, serde, CompresedSaver, DynoErr, DynoResult};
+use paste::paste;
 use serde::{de::DeserializeOwned, Serialize};
 
 macro_rules! dyno_paths {
@@ -168,12 +169,12 @@ impl IndexMut<usize> for DynoPaths {
 
 impl DynoPaths {
     pub const PATHS_NAME: [&str; 6] = [
-        r#"PROJECT PATH"#,
-        r#"CACHE PATH"#,
-        r#"CONFIG PATH"#,
-        r#"DATA PATH"#,
-        r#"DATA LOCAL PATH"#,
-        r#"PREFERENCE PATH"#,
+        "path-project",
+        "path-cache",
+        "path-config",
+        "path-data",
+        "path-data-local",
+        "path-preference",
     ];
 
     #[inline]
@@ -188,7 +189,7 @@ impl DynoPaths {
         }
         fs::read_to_string(file).map_or_else(
             |err| Err(From::from(err)),
-            |x| toml::from_str(&x).map_err(From::from),
+            |x| toml::from_str(&x).map_err(DynoErr::serialize_error),
         )
     }
 
@@ -198,14 +199,14 @@ impl DynoPaths {
         S: Serialize,
     {
         let file = self.get_config_dir_file(filename);
-        let data = toml::to_string(&config)?;
+        let data = toml::to_string(&config).map_err(DynoErr::serialize_error)?;
         fs::write(file, data.as_bytes()).map_err(From::from)
     }
 
     #[inline]
     pub fn get_bin<D>(&self, filename: &'_ str) -> DynoResult<D>
     where
-        D: CompresedSaver,
+        D: CompresedSaver + DeserializeOwned,
     {
         let file = self.get_data_dir_file(filename);
         if !file.exists() {
@@ -214,7 +215,7 @@ impl DynoPaths {
                 f = file.display()
             )));
         }
-        D::decompress_from_path(file).map_err(From::from)
+        D::decompress_from_path(file)
     }
 
     #[inline]
@@ -223,7 +224,7 @@ impl DynoPaths {
         S: CompresedSaver,
     {
         let file = self.get_data_dir_file(filename);
-        S::compress_to_path(&config, file).map_err(From::from)
+        S::compress_to_path(&config, file)
     }
 
     #[inline]
@@ -241,22 +242,22 @@ impl DynoPaths {
     pub fn draw(&mut self, ui: &mut eframe::egui::Ui, edit: &mut bool) {
         use eframe::egui::{Grid, Link, TextEdit};
         ui.add_space(50.0);
-        ui.add(TextEdit::singleline(&mut self.name).hint_text("app dir name"));
+        ui.add(TextEdit::singleline(&mut self.name).hint_text(tr("path-app-dir")));
         ui.add_space(20.0);
-        ui.checkbox(edit, "Edit Paths Config");
+        ui.checkbox(edit, tr("path-edit"));
         ui.add_space(20.0);
         Grid::new("dyno_setting_paths")
             .num_columns(2)
             .striped(true)
             .show(ui, |ui| {
                 for (i, paths) in self.as_slice_mut().into_iter().enumerate() {
-                    ui.label(Self::PATHS_NAME[i])
-                        .on_hover_text("Click on the path in the right to edit the paths");
+                    ui.label(tr(Self::PATHS_NAME[i]))
+                        .on_hover_text(tr("path-edit-hint"));
                     let links = ui
                         .add_enabled(*edit, Link::new(paths.to_string_lossy()))
-                        .on_hover_text("Click to Edit");
+                        .on_hover_text(tr("path-click-edit"));
                     if links.clicked() {
-                        if let Some(p) = DynoFileManager::pick_folder("Change Path", &paths) {
+                        if let Some(p) = DynoFileManager::pick_folder(&tr("path-change"), &paths) {
                             *paths = p;
                         }
                     }
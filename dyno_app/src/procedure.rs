@@ -0,0 +1,422 @@
+//! guided test procedure, the steps of the SOP (warm up, pull, coast down) as data. the runner
+//! walk the operator through the steps and detect the completion from the live data, every step
+//! is logged into the annotations of the run when it's started.
+//!
+//! procedures is loaded from `*.toml` in the `Procedures` folder of the config dir, e.g.
+//!
+//! ```toml
+//! name = "Power Pull"
+//! [[steps]]
+//! name = "Warm up"
+//! prompt = "Run the engine idle until warm"
+//! tag = "warm-up"
+//! condition = { kind = "above", channel = { Temp = 0 }, value = 60.0 }
+//! ```
+
+use std::{fs, path::Path};
+
+use dyno_core::{i18n::tr, serde, tr, types::Data, DynoErr, DynoResult};
+
+use crate::widgets::PlotChannel;
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case", crate = "serde")]
+pub enum StepCondition {
+    /// the operator confirm the step
+    Manual,
+    Above {
+        channel: PlotChannel,
+        value: f64,
+    },
+    Below {
+        channel: PlotChannel,
+        value: f64,
+    },
+    /// the channel stay in `value ± tolerance` for `seconds`
+    Hold {
+        channel: PlotChannel,
+        value: f64,
+        tolerance: f64,
+        seconds: f64,
+    },
+    /// wait for `seconds` since the step is started
+    Duration {
+        seconds: f64,
+    },
+}
+
+impl std::fmt::Display for StepCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match *self {
+            Self::Manual => tr("step-manual"),
+            Self::Above { channel, value } => {
+                tr!("step-above", value = value, unit = channel.unit())
+            }
+            Self::Below { channel, value } => {
+                tr!("step-below", value = value, unit = channel.unit())
+            }
+            Self::Hold {
+                channel,
+                value,
+                tolerance,
+                seconds,
+            } => tr!(
+                "step-hold",
+                value = value,
+                tolerance = tolerance,
+                unit = channel.unit(),
+                seconds = seconds,
+            ),
+            Self::Duration { seconds } => tr!("step-duration", seconds = seconds),
+        };
+        f.write_str(&text)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct ProcedureStep {
+    pub name: String,
+    /// instruction that is showed to the operator
+    #[serde(default)]
+    pub prompt: String,
+    pub condition: StepCondition,
+    /// added to the tags of the run when the step is started
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+impl ProcedureStep {
+    fn new(name: &str, prompt: &str, condition: StepCondition, tag: Option<&str>) -> Self {
+        Self {
+            name: name.to_owned(),
+            prompt: prompt.to_owned(),
+            condition,
+            tag: tag.map(ToOwned::to_owned),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct Procedure {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub steps: Vec<ProcedureStep>,
+}
+
+impl Procedure {
+    pub const FOLDER: &'static str = "Procedures";
+
+    /// warm up, pull in 3rd gear and coast down, like the SOP of the dyno
+    pub fn standard() -> Self {
+        use PlotChannel::*;
+        use StepCondition::*;
+
+        Self {
+            name: "Standard Power Pull".to_owned(),
+            description: "warm up, one full throttle pull in 3rd gear and coast down".to_owned(),
+            steps: vec![
+                ProcedureStep::new(
+                    "Warm up",
+                    "Run the engine until it reach the working temperature",
+                    Above {
+                        channel: Temp(0),
+                        value: 60.,
+                    },
+                    Some("warm-up"),
+                ),
+                ProcedureStep::new(
+                    "Select 3rd gear",
+                    "Shift to the 3rd gear, then confirm",
+                    Manual,
+                    None,
+                ),
+                ProcedureStep::new(
+                    "Hold 2000 rpm",
+                    "Hold the engine steady at 2000 rpm",
+                    Hold {
+                        channel: RpmEngine,
+                        value: 2_000.,
+                        tolerance: 150.,
+                        seconds: 3.,
+                    },
+                    None,
+                ),
+                ProcedureStep::new(
+                    "WOT",
+                    "Open the throttle fully until 9000 rpm",
+                    Above {
+                        channel: RpmEngine,
+                        value: 9_000.,
+                    },
+                    Some("pull"),
+                ),
+                ProcedureStep::new(
+                    "Coast down",
+                    "Close the throttle and let the roller slow down",
+                    Below {
+                        channel: Speed,
+                        value: 10.,
+                    },
+                    Some("coast-down"),
+                ),
+            ],
+        }
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> DynoResult<Self> {
+        let text = fs::read_to_string(path)?;
+        let procedure = toml::from_str::<Self>(&text).map_err(DynoErr::serialize_error)?;
+        if procedure.steps.is_empty() {
+            return Err(DynoErr::input_output_error(format!(
+                "Procedure `{}` has no step",
+                procedure.name
+            )));
+        }
+        Ok(procedure)
+    }
+
+    /// procedures in `dir` sorted by name, the standard procedure is written to `dir` as example
+    /// if the folder is not exists yet
+    pub fn load_dir(dir: impl AsRef<Path>) -> Vec<Self> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            let written = fs::create_dir_all(dir)
+                .map_err(DynoErr::from)
+                .and_then(|()| {
+                    let text = toml::to_string_pretty(&Self::standard())
+                        .map_err(DynoErr::serialize_error)?;
+                    fs::write(dir.join("standard.toml"), text).map_err(From::from)
+                });
+            if let Err(err) = written {
+                dyno_core::log::warn!("Failed to write example procedure - {err}");
+            }
+        }
+        let Ok(entries) = fs::read_dir(dir) else {
+            return vec![Self::standard()];
+        };
+        let mut procedures = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| {
+                Self::open(&path)
+                    .map_err(|err| {
+                        dyno_core::log::warn!("Invalid procedure `{}` - {err}", path.display())
+                    })
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        if procedures.is_empty() {
+            procedures.push(Self::standard());
+        }
+        procedures.sort_by(|left, right| left.name.cmp(&right.name));
+        procedures
+    }
+}
+
+/// the step at `index` is started at `timestamp` of the data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StepStarted {
+    pub index: usize,
+    pub timestamp: i64,
+}
+
+/// state of the procedure that is running
+#[derive(Clone, Debug)]
+pub struct ProcedureRunner {
+    procedure: Procedure,
+    step: usize,
+    /// timestamp of the data when the current step is started, `None` before the first data
+    step_start: Option<i64>,
+    /// timestamp since the channel is in the range of [`StepCondition::Hold`]
+    hold_since: Option<i64>,
+    last_timestamp: i64,
+}
+
+impl ProcedureRunner {
+    pub fn new(procedure: Procedure) -> Self {
+        Self {
+            procedure,
+            step: 0,
+            step_start: None,
+            hold_since: None,
+            last_timestamp: 0,
+        }
+    }
+
+    #[inline]
+    pub fn procedure(&self) -> &Procedure {
+        &self.procedure
+    }
+
+    #[inline]
+    pub fn step_index(&self) -> usize {
+        self.step
+    }
+
+    /// current step, `None` if every step is done
+    #[inline]
+    pub fn current(&self) -> Option<&ProcedureStep> {
+        self.procedure.steps.get(self.step)
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.step >= self.procedure.steps.len()
+    }
+
+    /// progress of the hold or duration of the current step in `0..=1`
+    pub fn step_progress(&self) -> Option<f32> {
+        let elapsed = |since: Option<i64>| Some((self.last_timestamp - since?) as f64 / 1000.);
+        let progress = match self.current()?.condition {
+            StepCondition::Hold { seconds, .. } => elapsed(self.hold_since).unwrap_or(0.) / seconds,
+            StepCondition::Duration { seconds } => elapsed(self.step_start)? / seconds,
+            _ => return None,
+        };
+        Some(progress.clamp(0., 1.) as f32)
+    }
+
+    fn advance(&mut self, timestamp: i64) -> StepStarted {
+        self.step += 1;
+        self.step_start = Some(timestamp);
+        self.hold_since = None;
+        StepStarted {
+            index: self.step,
+            timestamp,
+        }
+    }
+
+    /// check the completion of the current step with the live `data`, return the steps that is
+    /// started, the index equal to the number of steps means the procedure is finished
+    pub fn update(&mut self, data: &Data) -> Vec<StepStarted> {
+        let timestamp = data.timestamp;
+        self.last_timestamp = timestamp;
+        let mut started = Vec::new();
+        if self.step_start.is_none() {
+            self.step_start = Some(timestamp);
+            started.push(StepStarted {
+                index: self.step,
+                timestamp,
+            });
+        }
+        let Some(step) = self.current() else {
+            return started;
+        };
+        let done = match step.condition {
+            StepCondition::Manual => false,
+            StepCondition::Above { channel, value } => channel.value(data) > value,
+            StepCondition::Below { channel, value } => channel.value(data) < value,
+            StepCondition::Hold {
+                channel,
+                value,
+                tolerance,
+                seconds,
+            } => {
+                if (channel.value(data) - value).abs() <= tolerance {
+                    let since = *self.hold_since.get_or_insert(timestamp);
+                    (timestamp - since) as f64 >= seconds * 1000.
+                } else {
+                    self.hold_since = None;
+                    false
+                }
+            }
+            StepCondition::Duration { seconds } => self
+                .step_start
+                .is_some_and(|start| (timestamp - start) as f64 >= seconds * 1000.),
+        };
+        if done {
+            started.push(self.advance(timestamp));
+        }
+        started
+    }
+
+    /// confirm or skip the current step at `timestamp`
+    pub fn next(&mut self, timestamp: i64) -> Option<StepStarted> {
+        (!self.is_finished()).then(|| self.advance(timestamp.max(self.last_timestamp)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::uom::si::{
+        angular_velocity::revolution_per_minute, f64::*, temperature_interval::degree_celsius,
+    };
+
+    fn data(timestamp: i64, temp: f64, rpm: f64) -> Data {
+        let mut data = Data {
+            timestamp,
+            rpm_engine: AngularVelocity::new::<revolution_per_minute>(rpm),
+            ..Default::default()
+        };
+        data.temps[0] = TemperatureInterval::new::<degree_celsius>(temp);
+        data
+    }
+
+    #[test]
+    fn procedure_runner() {
+        let mut runner = ProcedureRunner::new(Procedure::standard());
+        let started = |index, timestamp| StepStarted { index, timestamp };
+
+        assert_eq!(runner.update(&data(0, 30., 1_000.)), [started(0, 0)]);
+        assert!(runner.update(&data(250, 59., 1_000.)).is_empty());
+        assert_eq!(runner.update(&data(500, 61., 1_000.)), [started(1, 500)]);
+        // manual step
+        assert!(runner.update(&data(750, 61., 1_000.)).is_empty());
+        assert_eq!(runner.next(600), Some(started(2, 750)));
+
+        // hold is restarted when the rpm leave the tolerance
+        assert!(runner.update(&data(1_000, 61., 2_050.)).is_empty());
+        assert!(runner.update(&data(2_000, 61., 2_500.)).is_empty());
+        assert!(runner.update(&data(3_000, 61., 1_900.)).is_empty());
+        assert_eq!(runner.step_progress(), Some(0.));
+        assert!(runner.update(&data(5_000, 61., 2_100.)).is_empty());
+        assert_eq!(
+            runner.update(&data(6_000, 61., 2_000.)),
+            [started(3, 6_000)]
+        );
+
+        assert_eq!(
+            runner.update(&data(7_000, 61., 9_500.)),
+            [started(4, 7_000)]
+        );
+        assert_eq!(runner.update(&data(8_000, 61., 0.)), [started(5, 8_000)]);
+        assert!(runner.is_finished());
+        assert_eq!(runner.next(9_000), None);
+    }
+
+    #[test]
+    fn procedure_toml() {
+        let dir = std::env::temp_dir().join(format!("dynotest_procedure_{}", std::process::id()));
+        let procedures = Procedure::load_dir(&dir);
+        assert_eq!(procedures, [Procedure::standard()]);
+        assert!(dir.join("standard.toml").exists());
+
+        let text = r#"
+name = "Coast Down"
+[[steps]]
+name = "Coast"
+condition = { kind = "duration", seconds = 5.0 }
+[[steps]]
+name = "Done"
+condition = { kind = "manual" }
+"#;
+        fs::write(dir.join("coast.toml"), text).unwrap();
+        fs::write(dir.join("broken.toml"), "name = 1").unwrap();
+        let procedures = Procedure::load_dir(&dir);
+        let names = procedures
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Coast Down", "Standard Power Pull"]);
+        assert_eq!(
+            procedures[0].steps[0].condition,
+            StepCondition::Duration { seconds: 5. }
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,247 @@
+//! local registry of the vehicles and the customers, saved in the data dir. the vehicle that is
+//! changed while offline is queued in the outbox and pushed on the next sync with the server
+
+use std::collections::BTreeMap;
+
+use dyno_core::{
+    chrono::{DateTime, NaiveDateTime, Utc},
+    model::vehicle::Vehicle,
+    report::RunSummary,
+    serde,
+};
+
+use crate::session::{DynoRun, DynoSession};
+
+/// summary of the run recorded for the vehicle
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct VehicleRun {
+    /// `created` of the session, the session and the run name identify the run
+    pub session: DateTime<Utc>,
+    pub run: String,
+    pub tags: Vec<String>,
+    pub start: Option<NaiveDateTime>,
+    pub elapsed_ms: u64,
+    /// unit [HP]
+    pub max_power: f64,
+    /// unit [NM]
+    pub max_torque: f64,
+    /// unit [KMpH]
+    pub max_speed: f64,
+}
+
+impl VehicleRun {
+    pub fn new(session: &DynoSession, run: &DynoRun) -> Self {
+        let summary = RunSummary::from_data(&run.data);
+        Self {
+            session: session.created,
+            run: run.name.clone(),
+            tags: run.tags.clone(),
+            start: run.start,
+            elapsed_ms: run.elapsed_ms,
+            max_power: summary.max_power,
+            max_torque: summary.max_torque,
+            max_speed: summary.max_speed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct VehicleRegistry {
+    vehicles: Vec<Vehicle>,
+    /// uuid of the vehicles changed since the last sync
+    outbox: Vec<String>,
+    /// runs recorded for the vehicle, only kept in this machine
+    history: BTreeMap<String, Vec<VehicleRun>>,
+    last_sync: Option<DateTime<Utc>>,
+}
+
+impl VehicleRegistry {
+    /// file name in the data dir
+    pub const FILE: &'static str = "vehicles.bin";
+
+    /// vehicles that is not deleted
+    pub fn vehicles(&self) -> impl Iterator<Item = &Vehicle> {
+        self.vehicles.iter().filter(|v| !v.deleted)
+    }
+
+    /// vehicles matching `query`, see [`Vehicle::matches`]
+    pub fn search<'a>(&'a self, query: &'a str) -> impl Iterator<Item = &'a Vehicle> {
+        self.vehicles().filter(move |v| v.matches(query))
+    }
+
+    pub fn get(&self, uuid: &str) -> Option<&Vehicle> {
+        self.vehicles().find(|v| v.uuid == uuid)
+    }
+
+    /// add or replace the vehicle with the same uuid, and queue it for the next sync
+    pub fn upsert(&mut self, mut vehicle: Vehicle) {
+        vehicle.updated_at = Utc::now();
+        let uuid = vehicle.uuid.clone();
+        match self.vehicles.iter_mut().find(|v| v.uuid == uuid) {
+            Some(old) => *old = vehicle,
+            None => self.vehicles.push(vehicle),
+        }
+        self.queue(uuid);
+    }
+
+    /// mark the vehicle as deleted, the history of the runs is kept
+    pub fn remove(&mut self, uuid: &str) -> bool {
+        let Some(vehicle) = self
+            .vehicles
+            .iter_mut()
+            .find(|v| v.uuid == uuid && !v.deleted)
+        else {
+            return false;
+        };
+        vehicle.deleted = true;
+        vehicle.updated_at = Utc::now();
+        self.queue(uuid.to_owned());
+        true
+    }
+
+    fn queue(&mut self, uuid: String) {
+        if !self.outbox.contains(&uuid) {
+            self.outbox.push(uuid);
+        }
+    }
+
+    /// vehicles waiting to be pushed to the server, deleted vehicle included
+    pub fn outbox(&self) -> Vec<Vehicle> {
+        self.vehicles
+            .iter()
+            .filter(|v| self.outbox.contains(&v.uuid))
+            .cloned()
+            .collect()
+    }
+
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.outbox.len()
+    }
+
+    #[inline]
+    pub fn last_sync(&self) -> Option<DateTime<Utc>> {
+        self.last_sync
+    }
+
+    /// apply the result of the sync. the `pushed` vehicle that is not changed again while syncing
+    /// leave the outbox, and the `remote` vehicle replace the older local vehicle
+    pub fn merge(&mut self, pushed: &[Vehicle], remote: Vec<Vehicle>) {
+        self.outbox.retain(|uuid| {
+            let local = self.vehicles.iter().find(|v| &v.uuid == uuid);
+            !pushed.iter().any(|p| Some(p) == local)
+        });
+        for vehicle in remote {
+            match self.vehicles.iter_mut().find(|v| v.uuid == vehicle.uuid) {
+                Some(local)
+                    if local.updated_at < vehicle.updated_at
+                        && !self.outbox.contains(&vehicle.uuid) =>
+                {
+                    *local = vehicle
+                }
+                Some(_) => {}
+                None => self.vehicles.push(vehicle),
+            }
+        }
+        self.last_sync = Some(Utc::now());
+    }
+
+    /// add the run to the history of the vehicle, the same run recorded again is replaced
+    pub fn record_run(&mut self, uuid: &str, run: VehicleRun) {
+        let history = self.history.entry(uuid.to_owned()).or_default();
+        match history
+            .iter_mut()
+            .find(|r| r.session == run.session && r.run == run.run)
+        {
+            Some(old) => *old = run,
+            None => history.push(run),
+        }
+    }
+
+    pub fn history(&self, uuid: &str) -> &[VehicleRun] {
+        self.history.get(uuid).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::{chrono::Duration, DynoConfig};
+
+    fn vehicle(uuid: &str, plate_number: &str, customer: &str) -> Vehicle {
+        Vehicle {
+            uuid: uuid.to_owned(),
+            make: "Honda".to_owned(),
+            model: "Beat".to_owned(),
+            plate_number: plate_number.to_owned(),
+            customer: customer.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn registry_search() {
+        let mut registry = VehicleRegistry::default();
+        registry.upsert(vehicle("a", "P 1234 AB", "Budi"));
+        registry.upsert(vehicle("b", "N 42 XY", "Sari"));
+        assert_eq!(registry.search("").count(), 2);
+        assert_eq!(registry.search(" p 1234").next().unwrap().uuid, "a");
+        assert_eq!(registry.search("sari").next().unwrap().uuid, "b");
+        assert_eq!(registry.search("beat").count(), 2);
+
+        assert!(registry.remove("a"));
+        assert!(!registry.remove("a"));
+        assert!(registry.get("a").is_none());
+        assert_eq!(registry.search("").count(), 1);
+        // deletion is queued too
+        assert_eq!(registry.outbox().len(), 2);
+
+        let session = DynoSession::new(DynoConfig::default());
+        let mut run = VehicleRun::new(&session, session.active());
+        registry.record_run("b", run.clone());
+        run.elapsed_ms = 1000;
+        registry.record_run("b", run);
+        assert_eq!(registry.history("b").len(), 1);
+        assert_eq!(registry.history("b")[0].elapsed_ms, 1000);
+        assert!(registry.history("a").is_empty());
+    }
+
+    #[test]
+    fn registry_sync() {
+        let mut registry = VehicleRegistry::default();
+        registry.upsert(vehicle("a", "P 1", "Budi"));
+        registry.upsert(vehicle("b", "P 2", "Sari"));
+        let pushed = registry.outbox();
+        assert_eq!(registry.pending(), 2);
+
+        // `b` is changed again while the sync is running
+        let mut b = registry.get("b").cloned().unwrap();
+        b.phone = "0812".to_owned();
+        registry.upsert(b);
+
+        let mut remote_a = pushed[0].clone();
+        remote_a.updated_at -= Duration::minutes(1);
+        remote_a.customer = "old".to_owned();
+        let mut remote_b = pushed[1].clone();
+        remote_b.updated_at += Duration::minutes(1);
+        remote_b.customer = "server".to_owned();
+        let remote_c = Vehicle {
+            updated_at: Utc::now(),
+            ..vehicle("c", "P 3", "Andi")
+        };
+        registry.merge(&pushed, vec![remote_a, remote_b, remote_c]);
+
+        assert_eq!(registry.outbox().len(), 1);
+        assert_eq!(registry.outbox()[0].uuid, "b");
+        assert!(registry.last_sync().is_some());
+        // the older server record is ignored, the queued record is not replaced
+        assert_eq!(registry.get("a").unwrap().customer, "Budi");
+        assert_eq!(registry.get("b").unwrap().customer, "Sari");
+        assert_eq!(registry.get("c").unwrap().customer, "Andi");
+
+        registry.merge(&registry.outbox(), vec![]);
+        assert_eq!(registry.pending(), 0);
+    }
+}
@@ -0,0 +1,524 @@
+//! stream of the running record to the `/ws` of the server, the live page of the web show it.
+//! the samples is sent in batch, and kept while reconnecting so the viewers only miss the data
+//! when the server is gone for long
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use dyno_core::{
+    chrono::Utc,
+    log,
+    model::live::LiveEvent,
+    serde,
+    tokio::{
+        self,
+        sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        time::MissedTickBehavior,
+    },
+    types::Data,
+    DynoErr, DynoResult,
+};
+use futures::{SinkExt, StreamExt};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{ring, CryptoProvider},
+    pki_types::{pem::PemObject, CertificateDer, ServerName, UnixTime},
+    ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+use tokio_tungstenite::{
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{header, HeaderValue, StatusCode},
+        Error as WsError, Message,
+    },
+    Connector, MaybeTlsStream, WebSocketStream,
+};
+
+use super::{ApiService, ServerProfile, APP_USER_AGENT};
+
+type Socket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+const BATCH_INTERVAL: Duration = Duration::from_millis(250);
+const RECONNECT_MIN: Duration = Duration::from_millis(500);
+const RECONNECT_MAX: Duration = Duration::from_secs(10);
+/// frames kept while disconnected, about 2 minutes of batches
+const MAX_PENDING: usize = 512;
+
+/// delay before connecting again, doubled on every failed attempt
+fn reconnect_delay(attempts: u32) -> Duration {
+    RECONNECT_MIN
+        .saturating_mul(1 << attempts.saturating_sub(1).min(8))
+        .min(RECONNECT_MAX)
+}
+
+/// `http://host` to `ws://host/ws`, `https` to `wss`
+fn ws_url(base_url: &str) -> String {
+    format!("{}/ws", base_url.replacen("http", "ws", 1))
+}
+
+enum LiveCmd {
+    Start(String),
+    Data(Data),
+    Stop,
+}
+
+/// text frame of the websocket, the live page read the samples as JSON `Vec<Data>`
+#[derive(serde::Serialize)]
+#[serde(crate = "serde", untagged)]
+enum Frame {
+    Event(LiveEvent),
+    Samples(Vec<Data>),
+}
+
+/// handle of the streaming task, the task stop when this is dropped
+#[derive(Debug)]
+pub struct LiveStream {
+    tx: UnboundedSender<LiveCmd>,
+    streaming: bool,
+    connected: Arc<AtomicBool>,
+}
+
+impl LiveStream {
+    pub fn spawn(api: &ApiService) -> Self {
+        let (tx, rx) = unbounded_channel();
+        let connected = Arc::new(AtomicBool::new(false));
+        tokio::spawn(stream(api.clone(), rx, connected.clone()));
+        Self {
+            tx,
+            streaming: false,
+            connected,
+        }
+    }
+
+    /// start streaming the run named `run`, the viewers reset the chart
+    pub fn start(&mut self, run: &str) {
+        self.streaming = self.tx.send(LiveCmd::Start(run.to_owned())).is_ok();
+    }
+
+    /// queue the sample for the next batch, ignored when not streaming
+    #[inline]
+    pub fn push(&self, data: Data) {
+        if self.streaming {
+            let _ = self.tx.send(LiveCmd::Data(data));
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if std::mem::take(&mut self.streaming) {
+            let _ = self.tx.send(LiveCmd::Stop);
+        }
+    }
+
+    #[inline]
+    pub fn is_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for LiveStream {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+async fn stream(api: ApiService, mut rx: UnboundedReceiver<LiveCmd>, connected: Arc<AtomicBool>) {
+    let mut socket: Option<Socket> = None;
+    let mut running: Option<String> = None;
+    // start marker of the run that the server received, the frames after it belong to it
+    let mut announced: Option<LiveEvent> = None;
+    let mut pending = VecDeque::<Frame>::new();
+    let mut batch = Vec::<Data>::new();
+    let mut attempts = 0u32;
+    let mut retry_at = Instant::now();
+    let mut ticker = tokio::time::interval(BATCH_INTERVAL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            cmd = rx.recv() => match cmd {
+                Some(LiveCmd::Start(run)) => {
+                    flush_batch(&mut batch, &mut pending);
+                    pending.push_back(Frame::Event(LiveEvent::Start {
+                        run: run.clone(),
+                        user_id: user_id(&api),
+                        start: Utc::now(),
+                    }));
+                    running = Some(run);
+                }
+                Some(LiveCmd::Data(data)) => batch.push(data),
+                Some(LiveCmd::Stop) => {
+                    flush_batch(&mut batch, &mut pending);
+                    if let Some(run) = running.take() {
+                        pending.push_back(Frame::Event(LiveEvent::Stop {
+                            run,
+                            user_id: user_id(&api),
+                            stop: Utc::now(),
+                        }));
+                    }
+                }
+                None => break,
+            },
+            msg = next_message(&mut socket) => match msg {
+                // the broadcast of the other clients, ping is answered by the socket
+                Some(Ok(Message::Close(_))) | None => socket = None,
+                Some(Ok(_)) => {}
+                Some(Err(err)) => {
+                    log::warn!("Live stream is disconnected - {err}");
+                    socket = None;
+                }
+            },
+            _ = ticker.tick() => {
+                flush_batch(&mut batch, &mut pending);
+                if pending.is_empty() {
+                    // nothing to send after the run is stopped, the connection is closed
+                    if running.is_none() {
+                        if let Some(mut ws) = socket.take() {
+                            let _ = ws.close(None).await;
+                        }
+                    }
+                } else if socket.is_none() && Instant::now() >= retry_at {
+                    match connect(&api).await {
+                        Ok(ws) => {
+                            attempts = 0;
+                            socket = Some(ws);
+                            // the viewer that join on the reconnect know the run of the samples
+                            if let Some(start) = &announced {
+                                if !matches!(pending.front(), Some(Frame::Event(LiveEvent::Start { .. }))) {
+                                    pending.push_front(Frame::Event(start.clone()));
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            attempts += 1;
+                            retry_at = Instant::now() + reconnect_delay(attempts);
+                            log::warn!("Failed to connect the live stream ({attempts}) - {err}");
+                        }
+                    }
+                }
+                if let Some(ws) = &mut socket {
+                    if let Err(err) = send_pending(ws, &mut pending, &mut announced).await {
+                        log::warn!("Live stream is disconnected - {err}");
+                        socket = None;
+                    }
+                }
+            }
+        }
+        connected.store(socket.is_some(), Ordering::Relaxed);
+    }
+
+    // the stop marker of the dropped stream is still sent
+    flush_batch(&mut batch, &mut pending);
+    if let Some(mut ws) = socket {
+        let _ = send_pending(&mut ws, &mut pending, &mut announced).await;
+        let _ = ws.close(None).await;
+    }
+    connected.store(false, Ordering::Relaxed);
+}
+
+fn user_id(api: &ApiService) -> i64 {
+    api.token_session
+        .lock()
+        .as_ref()
+        .map(|tok| tok.user_id)
+        .unwrap_or_default()
+}
+
+fn flush_batch(batch: &mut Vec<Data>, pending: &mut VecDeque<Frame>) {
+    if !batch.is_empty() {
+        pending.push_back(Frame::Samples(std::mem::take(batch)));
+    }
+    // the oldest samples is dropped first, the start and stop marker is kept so the viewer
+    // still reset the chart on the next run
+    while pending.len() > MAX_PENDING {
+        let oldest = pending
+            .iter()
+            .position(|frame| matches!(frame, Frame::Samples(_)))
+            .unwrap_or(0);
+        pending.remove(oldest);
+    }
+}
+
+async fn send_pending(
+    ws: &mut Socket,
+    pending: &mut VecDeque<Frame>,
+    announced: &mut Option<LiveEvent>,
+) -> DynoResult<()> {
+    while let Some(frame) = pending.front() {
+        let text = serde_json::to_string(frame).map_err(DynoErr::encoding_error)?;
+        ws.send(Message::Text(text))
+            .await
+            .map_err(DynoErr::service_error)?;
+        match pending.pop_front() {
+            Some(Frame::Event(start @ LiveEvent::Start { .. })) => *announced = Some(start),
+            Some(Frame::Event(LiveEvent::Stop { .. })) => *announced = None,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// next message of the socket, never ready when there is no socket
+async fn next_message(socket: &mut Option<Socket>) -> Option<Result<Message, WsError>> {
+    match socket {
+        Some(ws) => ws.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// open the websocket with the access token, refreshed once if the server reject it
+async fn connect(api: &ApiService) -> DynoResult<Socket> {
+    let profile = &api.profile;
+    api.authorized(move |token| handshake(profile, token)).await
+}
+
+async fn handshake(profile: &ServerProfile, token: String) -> DynoResult<Socket> {
+    let mut request = ws_url(profile.base_url())
+        .into_client_request()
+        .map_err(DynoErr::validation_error)?;
+    let headers = request.headers_mut();
+    let bearer = HeaderValue::from_str(&format!("Bearer {token}"))
+        .map_err(|err| DynoErr::validation_error(format!("Invalid access token - {err}")))?;
+    headers.insert(header::AUTHORIZATION, bearer);
+    headers.insert(header::USER_AGENT, HeaderValue::from_static(APP_USER_AGENT));
+
+    let connecting =
+        tokio_tungstenite::connect_async_tls_with_config(request, None, true, connector(profile)?);
+    match tokio::time::timeout(Duration::from_millis(profile.timeout_ms), connecting).await {
+        Ok(Ok((socket, _resp))) => Ok(socket),
+        Ok(Err(WsError::Http(resp))) if resp.status() == StatusCode::UNAUTHORIZED => Err(
+            DynoErr::unauthorized_error("Live stream is rejected by the server"),
+        ),
+        Ok(Err(err)) => Err(DynoErr::service_error(err)),
+        Err(_) => Err(DynoErr::service_error(
+            "Live stream connection is timed out",
+        )),
+    }
+}
+
+/// tls of the `wss` server with the same options as the http client of the profile
+fn connector(profile: &ServerProfile) -> DynoResult<Option<Connector>> {
+    if !profile.base_url().starts_with("https") {
+        return Ok(None);
+    }
+    let provider = Arc::new(ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(DynoErr::service_error)?;
+    let config = if profile.verify_tls {
+        let mut roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        if let Some(ca_file) = &profile.ca_file {
+            for cert in CertificateDer::pem_file_iter(ca_file).map_err(DynoErr::filesystem_error)? {
+                let cert = cert.map_err(DynoErr::validation_error)?;
+                roots.add(cert).map_err(DynoErr::validation_error)?;
+            }
+        }
+        builder.with_root_certificates(roots)
+    } else {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerify(provider)))
+    };
+    Ok(Some(Connector::Rustls(Arc::new(
+        config.with_no_client_auth(),
+    ))))
+}
+
+/// accept every certificate, only the signature is checked. used when `verify_tls` is off
+#[derive(Debug)]
+struct NoVerify(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerify {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::{
+        model::TokenDetails,
+        tokio::{net::TcpListener, sync::oneshot},
+    };
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build tokio runtime")
+            .block_on(fut)
+    }
+
+    #[allow(clippy::result_large_err)] // the callback type of tungstenite
+    async fn accept(listener: &TcpListener) -> WebSocketStream<tokio::net::TcpStream> {
+        let (stream, _) = listener.accept().await.unwrap();
+        let check_auth = |req: &Request, resp: Response| {
+            assert_eq!(req.uri().path(), "/ws");
+            assert_eq!(
+                req.headers().get(header::AUTHORIZATION).unwrap(),
+                "Bearer token"
+            );
+            Ok(resp)
+        };
+        tokio_tungstenite::accept_hdr_async(stream, check_auth)
+            .await
+            .unwrap()
+    }
+
+    async fn next_text(ws: &mut WebSocketStream<tokio::net::TcpStream>) -> serde_json::Value {
+        loop {
+            match ws.next().await.unwrap().unwrap() {
+                Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                Message::Close(_) => panic!("closed before the next frame"),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn live_reconnect_delay() {
+        assert_eq!(reconnect_delay(1), RECONNECT_MIN);
+        assert_eq!(reconnect_delay(2), RECONNECT_MIN * 2);
+        assert_eq!(reconnect_delay(100), RECONNECT_MAX);
+        assert_eq!(ws_url("http://127.0.0.1:8080"), "ws://127.0.0.1:8080/ws");
+        assert_eq!(
+            ws_url("https://dyno.example.com"),
+            "wss://dyno.example.com/ws"
+        );
+    }
+
+    #[test]
+    fn live_flush_batch_keep_start() {
+        let start = LiveEvent::Start {
+            run: "run".to_owned(),
+            user_id: 1,
+            start: Utc::now(),
+        };
+        let mut pending = VecDeque::from([Frame::Event(start.clone())]);
+        for _ in 0..MAX_PENDING + 10 {
+            flush_batch(&mut vec![Data::default()], &mut pending);
+        }
+        assert_eq!(pending.len(), MAX_PENDING);
+        assert!(matches!(
+            pending.front(),
+            Some(Frame::Event(LiveEvent::Start { .. }))
+        ));
+        assert!(pending
+            .iter()
+            .skip(1)
+            .all(|frame| matches!(frame, Frame::Samples(_))));
+    }
+
+    #[test]
+    fn live_stream_reconnect() {
+        block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let profile = ServerProfile {
+                url: format!("http://{}", listener.local_addr().unwrap()),
+                ..Default::default()
+            };
+            let api = ApiService::new(&profile).unwrap();
+            api.set_token(TokenDetails {
+                token: Some("token".to_owned()),
+                user_id: 7,
+                ..Default::default()
+            });
+
+            let (closed_tx, closed_rx) = oneshot::channel();
+            let server = tokio::spawn(async move {
+                // the first connection is lost after the start marker
+                let mut ws = accept(&listener).await;
+                let first = next_text(&mut ws).await;
+                ws.close(None).await.unwrap();
+                closed_tx.send(()).unwrap();
+
+                let mut ws = accept(&listener).await;
+                let mut frames = vec![];
+                loop {
+                    let frame = next_text(&mut ws).await;
+                    let stop = frame["event"] == "stop";
+                    frames.push(frame);
+                    if stop {
+                        break (first, frames);
+                    }
+                }
+            });
+
+            let mut live = LiveStream::spawn(&api);
+            live.start("Run 1");
+            closed_rx.await.unwrap();
+            tokio::time::sleep(BATCH_INTERVAL).await;
+            for _ in 0..3 {
+                live.push(Data::default());
+            }
+            live.stop();
+            live.push(Data::default());
+
+            let (first, frames) = server.await.unwrap();
+            assert_eq!(first["event"], "start");
+            assert_eq!(first["run"], "Run 1");
+            assert_eq!(first["user_id"], 7);
+            // the run is announced again on the new connection
+            assert_eq!(frames[0], first);
+            let samples: usize = frames[1..frames.len() - 1]
+                .iter()
+                .map(|frame| frame.as_array().unwrap().len())
+                .sum();
+            assert_eq!(samples, 3);
+            assert_eq!(frames.last().unwrap()["run"], "Run 1");
+        });
+    }
+}
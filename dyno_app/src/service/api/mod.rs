@@ -1,68 +1,106 @@
 mod dyno;
+mod live;
+mod outbox;
+mod settings;
+mod token_store;
 mod user;
+mod vehicle;
 
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 use dyno_core::{
-    chrono::NaiveDateTime,
+    chrono::Utc,
     crossbeam_channel::Sender,
-    crypto::TokenDetails,
-    dynotests::DynoTestDataInfo,
-    ignore_err,
-    reqwest::{
-        header::{HeaderMap, HeaderValue},
-        multipart, Client, Response,
+    i18n::tr,
+    ignore_err, log,
+    model::{
+        users::{UserLogin, UserRegistration},
+        vehicle::Vehicle,
+        TokenDetails,
     },
-    tokio,
-    users::{UserLogin, UserRegistration},
-    BufferData, DynoConfig, DynoErr,
+    tokio, DynoConfig, DynoErr, DynoResult, ErrKind,
 };
 use eframe::epaint::mutex::Mutex;
+use reqwest::{Client, Response, StatusCode};
+
+pub use live::LiveStream;
+pub use outbox::{Outbox, PendingUpload, UploadItem};
+pub use settings::{ServerProfile, ServerSettings};
+use token_store::TokenStore;
 
 use crate::{toast_error, AsyncMsg};
 
 static APP_USER_AGENT: &str = concat!("Dyno/Desktop-", env!("CARGO_PKG_VERSION"),);
 
+const NOT_LOGIN: &str = "You are not Login, please Login first.";
+/// the token is refreshed this long before it expire
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+/// refresh interval when the server not send the expiry
+const REFRESH_FALLBACK: Duration = Duration::from_secs(10 * 60);
+/// wait before trying again when the server is not reachable
+const REFRESH_RETRY: Duration = Duration::from_secs(30);
+
+/// error of the failed request, `401` become [`ErrKind::Unauthorized`] so the request is retried
+/// with the refreshed token
+fn response_error(err: reqwest::Error) -> DynoErr {
+    match err.status() {
+        Some(StatusCode::UNAUTHORIZED) => DynoErr::unauthorized_error(err),
+        _ => DynoErr::api_error(err),
+    }
+}
+
+/// time until the token that expire at `expires_in` (timestamp in ms) should be refreshed
+fn refresh_delay(expires_in: Option<i64>, now_ms: i64) -> Duration {
+    match expires_in {
+        Some(expires_in) => Duration::from_millis(expires_in.saturating_sub(now_ms).max(0) as _)
+            .saturating_sub(REFRESH_MARGIN),
+        None => REFRESH_FALLBACK,
+    }
+}
+
 #[derive(Clone)]
 pub struct ApiService {
     pub url: String,
     pub client: Client,
+    profile: ServerProfile,
     logined: Arc<AtomicBool>,
     token_session: Arc<Mutex<Option<TokenDetails>>>,
+    /// the saved login, `None` keep the login only in memory
+    store: Option<TokenStore>,
+    /// changed on every login and logout, the old refresher stop when it is changed
+    generation: Arc<AtomicUsize>,
+    /// one refresh at a time, the refresh token is valid only once
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl ApiService {
-    pub fn new() -> Option<Self> {
-        let url = std::env::var("DYNO_SERVER_URL").unwrap_or_else(|err| {
-            dyno_core::log::error!(
-                "Failed to Get DYNO_SERVER_URL from EnvVar, defaulting to [localhost:8000] - {err}"
-            );
-            "http://127.0.0.1:3000".to_owned()
-        });
-        let client = match Client::builder()
-            .default_headers({
-                let mut headers = HeaderMap::new();
-                headers.insert("AppDyno", HeaderValue::from_static("Desktop"));
-                headers
-            })
-            .user_agent(APP_USER_AGENT)
-            .build()
-            .map_err(DynoErr::service_error)
-        {
+    pub fn new(profile: &ServerProfile) -> Option<Self> {
+        let client = match profile.client() {
             Ok(ok) => ok,
             Err(err) => {
-                toast_error!("Failed to create Api Client - {err}");
+                toast_error!(
+                    "{}",
+                    dyno_core::tr!("toast-api-client-failed", error = err.to_string())
+                );
                 return None;
             }
         };
         Some(Self {
-            url,
+            url: profile.base_url().to_owned(),
             client,
+            profile: profile.clone(),
             logined: Arc::new(AtomicBool::new(false)),
             token_session: Default::default(),
+            store: None,
+            generation: Default::default(),
+            refresh_lock: Default::default(),
         })
     }
 
@@ -77,6 +115,35 @@ impl ApiService {
         lock.as_ref().and_then(|tok| tok.token.clone())
     }
 
+    /// save the login to `store`, see [`ApiService::restore`]
+    pub fn set_store(&mut self, store: TokenStore) {
+        self.store = Some(store);
+    }
+
+    fn set_token(&self, details: TokenDetails) {
+        if let Some(store) = &self.store {
+            if let Err(err) = store.save(&details) {
+                log::error!("Failed to save the login - {err}");
+            }
+        }
+        *self.token_session.lock() = Some(details);
+        self.logined.store(true, Ordering::Relaxed);
+    }
+
+    fn clear_token(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.logined.store(false, Ordering::Relaxed);
+        *self.token_session.lock() = None;
+        if let Some(store) = &self.store {
+            store.clear();
+        }
+    }
+
+    /// stop refreshing the token of this service, the saved login is kept
+    pub fn detach(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
     fn api_url(&self, url: impl AsRef<str>) -> String {
         format!("{}/api{}", self.url, url.as_ref())
     }
@@ -86,6 +153,112 @@ impl ApiService {
     }
 }
 
+impl ApiService {
+    /// exchange the refresh token for new tokens, return the new access token.
+    /// `stale` is the token that is rejected, if it is already refreshed by the other request
+    /// the current token is returned
+    async fn refresh(&self, stale: &str) -> DynoResult<String> {
+        let _guard = self.refresh_lock.lock().await;
+        let current = self.token_session.lock().clone();
+        let Some(current) = current else {
+            return Err(DynoErr::api_error(NOT_LOGIN));
+        };
+        if let Some(token) = current.token.filter(|token| token != stale) {
+            return Ok(token);
+        }
+        let expired = || DynoErr::unauthorized_error("Login is expired, please Login again.");
+        let refresh_token = current.refresh_token.ok_or_else(expired)?;
+        let url = self.api_url("/auth/refresh");
+        match user::refresh(url, self.client.clone(), refresh_token).await {
+            Ok(details) => {
+                let token = details.token.clone().ok_or_else(expired)?;
+                self.set_token(details);
+                Ok(token)
+            }
+            Err(err) if err.kind() == ErrKind::Unauthorized => {
+                self.clear_token();
+                Err(expired())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// run the `request` with the access token, it is tried once more with the refreshed token
+    /// when the server reject the token
+    async fn authorized<T, F, Fut>(&self, request: F) -> DynoResult<T>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = DynoResult<T>>,
+    {
+        let token = self
+            .get_token()
+            .ok_or_else(|| DynoErr::api_error(NOT_LOGIN))?;
+        match request(token.clone()).await {
+            Err(err) if err.kind() == ErrKind::Unauthorized => {
+                log::info!("Access token is rejected, refreshing - {err}");
+                let token = self.refresh(&token).await?;
+                request(token).await
+            }
+            result => result,
+        }
+    }
+
+    /// refresh the token before it expire, until the next login or logout
+    fn spawn_refresher(&self, tx: Sender<AsyncMsg>) {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        let this = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let expires_in = this
+                    .token_session
+                    .lock()
+                    .as_ref()
+                    .and_then(|tok| tok.expires_in);
+                let delay = refresh_delay(expires_in, Utc::now().timestamp_millis());
+                tokio::time::sleep(delay).await;
+                if this.generation.load(Ordering::Relaxed) != generation || !this.is_logined() {
+                    break;
+                }
+                let Some(token) = this.get_token() else {
+                    break;
+                };
+                match this.refresh(&token).await {
+                    Ok(_) => log::debug!("Access token is refreshed"),
+                    Err(err) if err.kind() == ErrKind::Unauthorized => {
+                        ignore_err!(tx.send(AsyncMsg::error(err)));
+                        break;
+                    }
+                    Err(err) => {
+                        log::warn!("Failed to refresh the access token, retrying - {err}");
+                        tokio::time::sleep(REFRESH_RETRY).await;
+                    }
+                }
+            }
+        });
+    }
+
+    /// login with the saved tokens, [`AsyncMsg::OnApiRestore`] is sent when there is one.
+    /// the expired access token is refreshed by the refresher
+    pub fn restore(&self, tx: Sender<AsyncMsg>) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        match store.load() {
+            Ok(Some(details)) if details.refresh_token.is_some() => {
+                *self.token_session.lock() = Some(details);
+                self.logined.store(true, Ordering::Relaxed);
+                self.spawn_refresher(tx.clone());
+                ignore_err!(tx.send(AsyncMsg::OnApiRestore));
+            }
+            Ok(_) => {}
+            Err(err) => {
+                log::warn!("Failed to load the saved login - {err}");
+                store.clear();
+            }
+        }
+    }
+}
+
 impl ApiService {
     pub fn check_health(&self, tx: Sender<AsyncMsg>) {
         let client = self.client.clone();
@@ -117,7 +290,7 @@ impl ApiService {
                 .and_then(Response::error_for_status)
                 .map_err(DynoErr::service_error)
             {
-                Ok(_resp) => tx.send(AsyncMsg::message("Success Connecting to API Server!")),
+                Ok(_resp) => tx.send(AsyncMsg::message(tr("toast-api-active"))),
                 Err(err) => tx.send(AsyncMsg::error(err)),
             }
         };
@@ -135,7 +308,7 @@ impl ApiService {
                 .and_then(Response::error_for_status)
                 .map_err(DynoErr::service_error)
             {
-                Ok(_resp) => tx.send(AsyncMsg::message("Success Disconnecting to API Server!")),
+                Ok(_resp) => tx.send(AsyncMsg::message(tr("toast-api-non-active"))),
                 Err(err) => tx.send(AsyncMsg::error(err)),
             }
         };
@@ -143,16 +316,13 @@ impl ApiService {
     }
 
     pub fn login(&self, login: UserLogin, tx: Sender<AsyncMsg>) {
-        let client = self.client.clone();
-        let logined = self.logined.clone();
-        let token_session = self.token_session.clone();
+        let this = self.clone();
         let url = self.api_url("/auth/login");
         tokio::spawn(async move {
-            match user::user_login(url, client, login).await {
+            match user::user_login(url, this.client.clone(), login).await {
                 Ok(resp) => {
-                    logined.store(true, Ordering::Relaxed);
-                    let mut token_lock = token_session.lock();
-                    *token_lock = Some(resp.payload);
+                    this.set_token(resp.payload);
+                    this.spawn_refresher(tx.clone());
                     ignore_err!(tx.send(AsyncMsg::OnApiLogin));
                 }
                 Err(err) => ignore_err!(tx.send(err)),
@@ -173,123 +343,100 @@ impl ApiService {
     }
 
     pub fn logout(&self, tx: Sender<AsyncMsg>) {
-        let token = match self.get_token() {
-            Some(tok) => tok,
-            None => {
-                ignore_err!(tx.send(AsyncMsg::error(DynoErr::api_error(
-                    "You are not Login, please Login first."
-                ))));
-                return;
-            }
-        };
-
-        let logined = self.logined.clone();
-        let token_session = self.token_session.clone();
-        let client = self.client.clone();
+        if !self.is_logined() {
+            ignore_err!(tx.send(AsyncMsg::error(DynoErr::api_error(NOT_LOGIN))));
+            return;
+        }
+        let this = self.clone();
         let url = self.api_url("/auth/logout");
         tokio::spawn(async move {
-            match user::user_logout(url, client, token).await {
-                Ok(ok) => {
-                    logined.store(false, Ordering::Relaxed);
-                    {
-                        let mut token_lock = token_session.lock();
-                        *token_lock = None;
-                    }
-                    ignore_err!(tx.send(ok))
+            let result = this
+                .authorized(|token| user::user_logout(url.clone(), this.client.clone(), token))
+                .await;
+            match result {
+                Ok(()) => {
+                    this.clear_token();
+                    ignore_err!(tx.send(AsyncMsg::message(tr("toast-logout"))))
                 }
-                Err(err) => ignore_err!(tx.send(err)),
+                Err(err) => ignore_err!(tx.send(AsyncMsg::error(err))),
             }
         });
     }
 }
 
 impl ApiService {
-    pub fn save_dyno(
-        &self,
-        data: BufferData,
-        config: DynoConfig,
-        start: NaiveDateTime,
-        stop: NaiveDateTime,
-        tx: Sender<AsyncMsg>,
-    ) {
-        let token = match self.get_token() {
-            Some(tok) => tok,
-            None => {
-                ignore_err!(tx.send(AsyncMsg::error(DynoErr::api_error(
-                    "You are not Login, please Login first."
-                ))));
-                return;
-            }
-        };
-
-        let client = self.client.clone();
+    /// send the queued upload, the result is sent as [`AsyncMsg::OnApiUpload`] with the checksum
+    pub fn upload(&self, item: &UploadItem, tx: Sender<AsyncMsg>) {
+        let this = self.clone();
+        let checksum = item.checksum.clone();
         let url = self.api_url("/dyno");
-
+        let path = item.path.clone();
         tokio::spawn(async move {
-            let (file_part, checksum_hex) = match dyno::get_data_part(data).await {
-                Ok(ok) => ok,
-                Err(err) => {
-                    ignore_err!(tx.send(AsyncMsg::error(err)));
-                    return;
-                }
-            };
-            let config_data = DynoTestDataInfo {
-                checksum_hex,
-                config,
-                start,
-                stop,
-            };
-            let info_part = match dyno::get_info_part(config_data).await {
-                Ok(ok) => ok,
-                Err(err) => {
-                    ignore_err!(tx.send(AsyncMsg::error(err)));
-                    return;
-                }
-            };
-            let multiparts = multipart::Form::new()
-                .part("data", file_part)
-                .part("info", info_part);
-
-            match dyno::save(url, client, token, multiparts).await {
-                Ok(ok) => ignore_err!(tx.send(ok)),
-                Err(err) => ignore_err!(tx.send(err)),
-            }
+            let result = this
+                .authorized(|token| {
+                    dyno::upload(url.clone(), this.client.clone(), token, path.clone())
+                })
+                .await;
+            ignore_err!(tx.send(AsyncMsg::OnApiUpload(checksum, result)));
         });
     }
 
     pub fn get_dyno(&self, tx: Sender<AsyncMsg>) {
-        let token = match self.get_token() {
-            Some(tok) => tok,
-            None => {
-                ignore_err!(tx.send(AsyncMsg::error(DynoErr::api_error(
-                    "You are not Login, please Login first."
-                ))));
-                return;
-            }
-        };
-        let client = self.client.clone();
+        let this = self.clone();
         let url = self.api_url("/dyno?all=true");
         tokio::spawn(async move {
-            let result = dyno::get(url, client, token).await;
-            ignore_err!(tx.send(result));
+            let result = this
+                .authorized(|token| dyno::get(url.clone(), this.client.clone(), token))
+                .await;
+            ignore_err!(tx.send(result.map_or_else(AsyncMsg::error, AsyncMsg::on_load_dyno)));
         });
     }
 
     pub fn load_dyno_file(&self, url: String, checksum: String, tx: Sender<AsyncMsg>) {
-        let token = match self.get_token() {
-            Some(tok) => tok,
-            None => {
-                ignore_err!(tx.send(AsyncMsg::error(DynoErr::api_error(
-                    "You are not Login, please Login first."
-                ))));
-                return;
-            }
-        };
-        let client = self.client.clone();
+        let this = self.clone();
         let url = self.data_url(url);
         tokio::spawn(async move {
-            let result = dyno::load_file(url, client, token, checksum).await;
-            ignore_err!(tx.send(result));
+            let result = this
+                .authorized(|token| {
+                    dyno::load_file(url.clone(), this.client.clone(), token, checksum.clone())
+                })
+                .await;
+            ignore_err!(tx.send(result.map_or_else(AsyncMsg::error, AsyncMsg::open_buffer)));
         });
     }
+
+    /// push the `changed` vehicles and get every vehicle of the server back
+    pub fn sync_vehicle(&self, changed: Vec<Vehicle>, tx: Sender<AsyncMsg>) {
+        let this = self.clone();
+        let url = self.api_url("/vehicle/sync");
+        tokio::spawn(async move {
+            let pushed = changed.as_slice();
+            let result = this
+                .authorized(|token| vehicle::sync(url.clone(), this.client.clone(), token, pushed))
+                .await;
+            let msg = match result {
+                Ok(remote) => AsyncMsg::OnApiSyncVehicle(changed, remote),
+                Err(err) => AsyncMsg::error(err),
+            };
+            ignore_err!(tx.send(msg));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_refresh_delay() {
+        let now = 1_000_000;
+        assert_eq!(refresh_delay(None, now), REFRESH_FALLBACK);
+        assert_eq!(
+            refresh_delay(Some(now + 5 * 60 * 1000), now),
+            Duration::from_secs(4 * 60)
+        );
+        // expiring soon or already expired is refreshed now
+        assert_eq!(refresh_delay(Some(now + 1000), now), Duration::ZERO);
+        assert_eq!(refresh_delay(Some(now - 1000), now), Duration::ZERO);
+    }
 }
@@ -0,0 +1,306 @@
+//! persistent queue of the runs saved to the server. the upload is written to the data dir
+//! first, then sent while logged in and retried with backoff until the server accept it. the
+//! server keep one dyno for the same checksum, so sending the same upload twice is harmless
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use dyno_core::{
+    chrono::NaiveDateTime, crypto::checksum_from_bytes, model::dynotests::DynoTestDataInfo, serde,
+    types::DynotestData, CompresedSaver, DynoErr, DynoResult,
+};
+
+pub const UPLOAD_EXTENSION: &str = "dynoupload";
+
+const BACKOFF_MIN: Duration = Duration::from_secs(5);
+const BACKOFF_MAX: Duration = Duration::from_secs(300);
+
+/// content of the upload file, the parts of the multipart request
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct PendingUpload {
+    /// compressed [`DynotestData`]
+    pub data: Vec<u8>,
+    pub info: DynoTestDataInfo<'static>,
+}
+
+impl PendingUpload {
+    /// the upload is tagged with the config the run is recorded with, not the current one
+    pub fn new(data: &DynotestData, start: NaiveDateTime, stop: NaiveDateTime) -> DynoResult<Self> {
+        let data_config = data.config.clone();
+        let data = data.compress()?;
+        let checksum = checksum_from_bytes(&data);
+        Ok(Self {
+            data,
+            info: DynoTestDataInfo {
+                checksum_hex: checksum.into(),
+                config: data_config,
+                start: start.and_utc(),
+                stop: stop.and_utc(),
+            },
+        })
+    }
+}
+
+/// delay before the next try, doubled on every failed attempt
+pub fn backoff(attempts: u32) -> Duration {
+    BACKOFF_MIN
+        .saturating_mul(1 << attempts.saturating_sub(1).min(16))
+        .min(BACKOFF_MAX)
+}
+
+#[derive(Debug, Clone)]
+pub struct UploadItem {
+    pub path: PathBuf,
+    pub checksum: String,
+    pub attempts: u32,
+    pub retry_at: Instant,
+    /// the request is not finished yet
+    pub sending: bool,
+    pub error: Option<DynoErr>,
+}
+
+impl UploadItem {
+    fn new(path: PathBuf, checksum: String) -> Self {
+        Self {
+            path,
+            checksum,
+            attempts: 0,
+            retry_at: Instant::now(),
+            sending: false,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Outbox {
+    dir: PathBuf,
+    items: Vec<UploadItem>,
+}
+
+impl Outbox {
+    /// queue of the upload files left in `dir`, so the upload resume after restarting the app
+    pub fn open(dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        let mut items = fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.extension().is_some_and(|ext| ext == UPLOAD_EXTENSION))
+                    .filter_map(|path| {
+                        let checksum = path.file_stem()?.to_str()?.to_owned();
+                        Some(UploadItem::new(path, checksum))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        items.sort_by(|a, b| a.path.cmp(&b.path));
+        Self { dir, items }
+    }
+
+    /// write the upload to the queue dir, the same data is queued once
+    pub fn push(&mut self, upload: &PendingUpload) -> DynoResult<()> {
+        let checksum = upload.info.checksum_hex.to_string();
+        if self.items.iter().any(|item| item.checksum == checksum) {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir).map_err(DynoErr::filesystem_error)?;
+        let path = self.dir.join(format!("{checksum}.{UPLOAD_EXTENSION}"));
+        upload.compress_to_path(&path)?;
+        self.items.push(UploadItem::new(path, checksum));
+        Ok(())
+    }
+
+    #[inline]
+    pub fn items(&self) -> &[UploadItem] {
+        &self.items
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// uploads that can be sent at `now`, they are marked as sending
+    pub fn ready(&mut self, now: Instant) -> Vec<UploadItem> {
+        self.items
+            .iter_mut()
+            .filter(|item| !item.sending && item.retry_at <= now)
+            .map(|item| {
+                item.sending = true;
+                item.clone()
+            })
+            .collect()
+    }
+
+    /// the server accepted the upload, the file is removed
+    pub fn done(&mut self, checksum: &str) {
+        if let Some(idx) = self.items.iter().position(|item| item.checksum == checksum) {
+            let item = self.items.remove(idx);
+            if let Err(err) = fs::remove_file(&item.path) {
+                dyno_core::log::error!("Failed to remove `{}` - {err}", item.path.display());
+            }
+        }
+    }
+
+    /// schedule the next try of the failed upload, return the number of the failed attempts
+    pub fn failed(&mut self, checksum: &str, err: DynoErr, now: Instant) -> u32 {
+        let Some(item) = self.items.iter_mut().find(|item| item.checksum == checksum) else {
+            return 0;
+        };
+        item.attempts += 1;
+        item.retry_at = now + backoff(item.attempts);
+        item.sending = false;
+        item.error = Some(err);
+        item.attempts
+    }
+
+    /// try every waiting upload on the next poll
+    pub fn retry_now(&mut self) {
+        let now = Instant::now();
+        for item in &mut self.items {
+            item.retry_at = now;
+        }
+    }
+
+    /// time until the next waiting upload is tried
+    pub fn next_retry(&self, now: Instant) -> Option<Duration> {
+        self.items
+            .iter()
+            .filter(|item| !item.sending)
+            .map(|item| item.retry_at.saturating_duration_since(now))
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::api::dyno;
+    use dyno_core::{
+        tokio::{
+            self,
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        },
+        types::Data,
+    };
+
+    const UNAVAILABLE: &str =
+        "HTTP/1.1 503 Service Unavailable\r\nconnection: close\r\ncontent-length: 0\r\n\r\n";
+    const SAVED: &str = "HTTP/1.1 200 OK\r\nconnection: close\r\ncontent-type: application/json\r\ncontent-length: 34\r\n\r\n{\"payload\":7,\"status\":\"success\"}\r\n";
+
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build tokio runtime")
+            .block_on(fut)
+    }
+
+    /// answer every request with the next response, return the head of the received requests
+    async fn mock_server(
+        responses: &'static [&'static str],
+    ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut requests = vec![];
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![];
+                let mut chunk = [0u8; 4096];
+                let head_end = loop {
+                    let len = stream.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..len]);
+                    if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break pos + 4;
+                    }
+                };
+                let head = String::from_utf8_lossy(&buffer[..head_end]).to_lowercase();
+                let body_len = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .and_then(|len| len.trim().parse::<usize>().ok())
+                    .unwrap_or_default();
+                while buffer.len() < head_end + body_len {
+                    let len = stream.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..len]);
+                }
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+                requests.push(head);
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn outbox_backoff() {
+        assert_eq!(backoff(1), BACKOFF_MIN);
+        assert_eq!(backoff(2), BACKOFF_MIN * 2);
+        assert_eq!(backoff(3), BACKOFF_MIN * 4);
+        assert_eq!(backoff(100), BACKOFF_MAX);
+    }
+
+    #[test]
+    fn outbox_upload_retry() {
+        let dir = std::env::temp_dir().join(format!("dynotest_outbox_{}", std::process::id()));
+        let mut data = DynotestData::default();
+        data.push(Data::default());
+        data.config.motor_info.cc = 150;
+        let upload =
+            PendingUpload::new(&data, NaiveDateTime::default(), NaiveDateTime::default()).unwrap();
+        assert_eq!(upload.info.config, data.config);
+
+        let mut outbox = Outbox::open(&dir);
+        assert!(outbox.is_empty());
+        outbox.push(&upload).unwrap();
+        outbox.push(&upload).unwrap();
+        assert_eq!(outbox.len(), 1);
+        // restarted app
+        let mut outbox = Outbox::open(&dir);
+        assert_eq!(outbox.len(), 1);
+
+        block_on(async {
+            let (url, server) = mock_server(&[UNAVAILABLE, SAVED]).await;
+            let url = format!("{url}/api/dyno");
+            let client = reqwest::Client::new();
+
+            let now = Instant::now();
+            let item = outbox.ready(now).remove(0);
+            assert!(outbox.ready(now).is_empty());
+            let err = dyno::upload(&url, client.clone(), "token", item.path.clone())
+                .await
+                .unwrap_err();
+            assert_eq!(outbox.failed(&item.checksum, err, now), 1);
+            assert!(outbox.ready(now).is_empty());
+            assert_eq!(outbox.next_retry(now), Some(BACKOFF_MIN));
+
+            let item = outbox.ready(now + BACKOFF_MIN).remove(0);
+            let id = dyno::upload(&url, client, "token", item.path.clone()).await;
+            assert_eq!(id.unwrap(), 7);
+            outbox.done(&item.checksum);
+            assert!(outbox.is_empty());
+            assert!(!item.path.exists());
+
+            let requests = server.await.unwrap();
+            assert_eq!(requests.len(), 2);
+            assert!(requests[1].starts_with("post /api/dyno"));
+            assert!(requests[1].contains("authorization: bearer token"));
+            assert!(requests[1].contains("multipart/form-data"));
+        });
+        assert!(Outbox::open(&dir).is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,265 @@
+//! named endpoints of the server, the app connect to the active one and can switch without
+//! restarting. `DYNO_SERVER_URL` is only the url of the default profile
+
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use dyno_core::{i18n::tr, serde, DynoErr, DynoResult};
+use eframe::egui::{Button, ComboBox, DragValue, Grid, TextEdit, Ui};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Certificate, Client,
+};
+
+use super::{token_store::TokenStore, APP_USER_AGENT};
+use crate::{row_label_value, widgets::DynoWidgets};
+
+/// same as the default port of the backend
+const DEFAULT_URL: &str = "http://127.0.0.1:8080";
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, crate = "serde")]
+pub struct ServerProfile {
+    pub name: String,
+    pub url: String,
+    /// check the certificate of the `https` server, turn off only for testing
+    pub verify_tls: bool,
+    /// PEM file of the certificate authority, for the server with a self signed certificate
+    pub ca_file: Option<PathBuf>,
+    /// timeout of every request, the upload of a long run included
+    pub timeout_ms: u64,
+    /// stream the recording to the live page of the server while logged in
+    pub live_stream: bool,
+}
+
+impl Default for ServerProfile {
+    fn default() -> Self {
+        Self {
+            name: "Default".to_owned(),
+            url: std::env::var("DYNO_SERVER_URL").unwrap_or_else(|_| DEFAULT_URL.to_owned()),
+            verify_tls: true,
+            ca_file: None,
+            timeout_ms: 30_000,
+            live_stream: true,
+        }
+    }
+}
+
+impl ServerProfile {
+    /// url without the trailing slash, the api path is appended to it
+    pub fn base_url(&self) -> &str {
+        self.url.trim().trim_end_matches('/')
+    }
+
+    pub fn client(&self) -> DynoResult<Client> {
+        let url = reqwest::Url::parse(self.base_url())
+            .map_err(|err| DynoErr::validation_error(format!("Invalid server url - {err}")))?;
+        let mut headers = HeaderMap::new();
+        headers.insert("AppDyno", HeaderValue::from_static("Desktop"));
+        let mut builder = Client::builder()
+            .default_headers(headers)
+            .user_agent(APP_USER_AGENT)
+            .timeout(Duration::from_millis(self.timeout_ms))
+            .danger_accept_invalid_certs(!self.verify_tls);
+        if let Some(ca_file) = self.ca_file.as_ref().filter(|_| url.scheme() == "https") {
+            let pem = std::fs::read(ca_file).map_err(DynoErr::filesystem_error)?;
+            let cert = Certificate::from_pem(&pem).map_err(DynoErr::validation_error)?;
+            builder = builder.add_root_certificate(cert);
+        }
+        builder.build().map_err(DynoErr::service_error)
+    }
+
+    /// saved login of this server, every server has its own login
+    pub fn token_store(&self, root: impl AsRef<Path>) -> TokenStore {
+        let dir = self
+            .base_url()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        TokenStore::new(root.as_ref().join("Sessions").join(dir))
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        Grid::new("dyno_server_profile_grid")
+            .num_columns(2)
+            .spacing([40.0, 4.0])
+            .show(ui, |ui| {
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut self.url).hint_text(DEFAULT_URL),
+                    "server-url"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui => ui.toggle(&mut self.verify_tls),
+                    "server-verify-tls"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui => ui.optional_value_widget(&mut self.ca_file, |ui, value| {
+                        let response = ui
+                            .link(value.display().to_string())
+                            .on_hover_text(tr("path-click-change"));
+                        if response.clicked() {
+                            if let Some(path) = crate::widgets::DynoFileManager::pick_file(
+                                &tr("server-ca-pick"),
+                                value.parent().unwrap_or(value),
+                                &[("PEM Certificate", &["pem", "crt"])],
+                            ) {
+                                *value = path;
+                            }
+                        }
+                        response
+                    }),
+                    "server-ca-file"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    DragValue::new(&mut self.timeout_ms)
+                        .range(1000..=600_000)
+                        .suffix(" ms"),
+                    "server-timeout"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui => ui.toggle(&mut self.live_stream),
+                    "server-live-stream"
+                );
+            });
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, crate = "serde")]
+pub struct ServerSettings {
+    pub active: usize,
+    /// never empty
+    pub profiles: Vec<ServerProfile>,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            active: 0,
+            profiles: vec![ServerProfile::default()],
+        }
+    }
+}
+
+impl ServerSettings {
+    /// active profile, the default profile if there is no profile
+    pub fn profile(&self) -> Cow<'_, ServerProfile> {
+        match self.profiles.get(self.active) {
+            Some(profile) => Cow::Borrowed(profile),
+            None => Cow::Owned(ServerProfile::default()),
+        }
+    }
+
+    fn new_name(&self, base: &str) -> String {
+        (1..)
+            .map(|n| match n {
+                1 => base.to_owned(),
+                n => format!("{base} {n}"),
+            })
+            .find(|name| self.profiles.iter().all(|profile| &profile.name != name))
+            .unwrap_or_default()
+    }
+
+    /// radio of every profile, return `true` if the other profile is selected
+    pub fn select_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        for (idx, profile) in self.profiles.iter().enumerate() {
+            if ui
+                .radio(idx == self.active, &profile.name)
+                .on_hover_text(profile.base_url())
+                .clicked()
+            {
+                changed = idx != self.active;
+                self.active = idx;
+                ui.close_menu();
+            }
+        }
+        changed
+    }
+
+    /// edit the profiles, the active profile is edited. the changes is used after reconnecting
+    pub fn ui(&mut self, ui: &mut Ui) {
+        if self.profiles.is_empty() {
+            *self = Self::default();
+        }
+        self.active = self.active.min(self.profiles.len() - 1);
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source("dyno_server_profile")
+                .selected_text(self.profiles[self.active].name.as_str())
+                .show_ui(ui, |ui| {
+                    for (idx, profile) in self.profiles.iter().enumerate() {
+                        ui.selectable_value(&mut self.active, idx, profile.name.as_str());
+                    }
+                });
+            ui.add(TextEdit::singleline(&mut self.profiles[self.active].name).desired_width(140.));
+            if ui.button(tr("action-duplicate")).clicked() {
+                let mut profile = self.profiles[self.active].clone();
+                profile.name = self.new_name(&profile.name);
+                self.profiles.push(profile);
+                self.active = self.profiles.len() - 1;
+            }
+            if ui
+                .add_enabled(self.profiles.len() > 1, Button::new(tr("action-delete")))
+                .clicked()
+            {
+                self.profiles.remove(self.active);
+                self.active = self.active.saturating_sub(1);
+            }
+        });
+        self.profiles[self.active].ui(ui);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_profile_client() {
+        let profile = ServerProfile {
+            url: "https://dyno.example.com/".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(profile.base_url(), "https://dyno.example.com");
+        assert!(profile.client().is_ok());
+
+        let invalid = ServerProfile {
+            url: "not a url".to_owned(),
+            ..Default::default()
+        };
+        assert!(invalid.client().is_err());
+        let missing_ca = ServerProfile {
+            ca_file: Some("/nonexistent/ca.pem".into()),
+            ..profile.clone()
+        };
+        assert!(missing_ca.client().is_err());
+
+        // the login of the other server is saved in the other dir
+        let local = ServerProfile {
+            name: "Local".to_owned(),
+            url: DEFAULT_URL.to_owned(),
+            ..Default::default()
+        };
+        assert_ne!(
+            format!("{:?}", profile.token_store("root")),
+            format!("{:?}", local.token_store("root"))
+        );
+        let mut settings = ServerSettings {
+            active: 3,
+            profiles: vec![local, profile],
+        };
+        assert_eq!(settings.profile().name, "Default");
+        assert_eq!(settings.new_name("Local"), "Local 2");
+        settings.active = 1;
+        assert_eq!(settings.profile().base_url(), "https://dyno.example.com");
+    }
+}
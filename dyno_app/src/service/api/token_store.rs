@@ -0,0 +1,139 @@
+//! tokens of the logged in user saved encrypted with ChaCha20-Poly1305, so the login survive
+//! restarting the app. the key is a random file next to the tokens, it keep the tokens away from
+//! the copied data dir and the backups, not from someone with access to the user account.
+//!
+//! token file layout: `[nonce: 12 bytes][encrypted postcard TokenDetails]`
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use dyno_core::{model::TokenDetails, DynoErr, DynoResult};
+
+const KEY_FILE: &str = "session.key";
+const TOKEN_FILE: &str = "session.token";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Clone)]
+pub struct TokenStore {
+    dir: PathBuf,
+}
+
+impl TokenStore {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    fn cipher(&self, create: bool) -> DynoResult<Option<ChaCha20Poly1305>> {
+        let path = self.dir.join(KEY_FILE);
+        if let Ok(key) = fs::read(&path) {
+            if key.len() == 32 {
+                return Ok(Some(ChaCha20Poly1305::new(Key::from_slice(&key))));
+            }
+        }
+        // a new key make the saved token unreadable, the user login again
+        if !create {
+            return Ok(None);
+        }
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        fs::create_dir_all(&self.dir).map_err(DynoErr::filesystem_error)?;
+        write_private(&path, &key)?;
+        Ok(Some(ChaCha20Poly1305::new(&key)))
+    }
+
+    pub fn save(&self, token: &TokenDetails) -> DynoResult<()> {
+        let Some(cipher) = self.cipher(true)? else {
+            return Ok(());
+        };
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let plain = postcard::to_stdvec(token)?;
+        let encrypted = cipher
+            .encrypt(&nonce, plain.as_slice())
+            .map_err(|err| DynoErr::encoding_error(format!("Failed to encrypt token - {err}")))?;
+        let mut bytes = nonce.to_vec();
+        bytes.extend(encrypted);
+        write_private(self.dir.join(TOKEN_FILE), &bytes)
+    }
+
+    /// saved tokens, `None` if nothing is saved
+    pub fn load(&self) -> DynoResult<Option<TokenDetails>> {
+        let Ok(bytes) = fs::read(self.dir.join(TOKEN_FILE)) else {
+            return Ok(None);
+        };
+        let Some(cipher) = self.cipher(false)? else {
+            return Ok(None);
+        };
+        if bytes.len() < NONCE_LEN {
+            return Err(DynoErr::encoding_error("Saved token is truncated"));
+        }
+        let (nonce, encrypted) = bytes.split_at(NONCE_LEN);
+        let plain = cipher
+            .decrypt(Nonce::from_slice(nonce), encrypted)
+            .map_err(|err| DynoErr::encoding_error(format!("Failed to decrypt token - {err}")))?;
+        postcard::from_bytes(&plain).map(Some).map_err(From::from)
+    }
+
+    pub fn clear(&self) {
+        let path = self.dir.join(TOKEN_FILE);
+        if path.exists() {
+            if let Err(err) = fs::remove_file(&path) {
+                dyno_core::log::error!("Failed to remove `{}` - {err}", path.display());
+            }
+        }
+    }
+}
+
+/// write the file readable only by the user on unix
+fn write_private(path: impl AsRef<Path>, bytes: &[u8]) -> DynoResult<()> {
+    use std::io::Write;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(bytes))
+        .map_err(DynoErr::filesystem_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_store_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("dynotest_token_{}", std::process::id()));
+        let store = TokenStore::new(&dir);
+        assert_eq!(store.load().unwrap(), None);
+
+        let token = TokenDetails {
+            token: Some("access".to_owned()),
+            token_uuid: "uuid".to_owned(),
+            user_id: 1,
+            expires_in: Some(1000),
+            refresh_token: Some("refresh".to_owned()),
+        };
+        store.save(&token).unwrap();
+        assert_eq!(store.load().unwrap().as_ref(), Some(&token));
+        let saved = fs::read(dir.join(TOKEN_FILE)).unwrap();
+        assert!(!saved.windows(7).any(|w| w == b"refresh"));
+
+        // tampered file is rejected
+        let mut tampered = saved.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        fs::write(dir.join(TOKEN_FILE), tampered).unwrap();
+        assert!(store.load().is_err());
+
+        store.clear();
+        assert_eq!(store.load().unwrap(), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,10 +1,14 @@
 use crate::AsyncMsg;
 use dyno_core::{
-    crypto::TokenDetails,
-    reqwest::{Client, IntoUrl, Response},
-    users::{UserLogin, UserRegistration},
-    ApiResponse,
+    model::{
+        users::{UserLogin, UserRegistration},
+        ApiResponse, RefreshRequest, TokenDetails,
+    },
+    DynoErr, DynoResult,
 };
+use reqwest::{Client, IntoUrl, Response};
+
+use super::response_error;
 
 pub async fn user_login(
     url: impl IntoUrl,
@@ -17,12 +21,12 @@ pub async fn user_login(
         .send()
         .await
         .and_then(Response::error_for_status)
-        .map_err(AsyncMsg::error)
+        .map_err(AsyncMsg::api_error)
     {
         Ok(resp) => resp
             .json::<ApiResponse<TokenDetails>>()
             .await
-            .map_err(AsyncMsg::error),
+            .map_err(AsyncMsg::api_error),
         Err(err) => Err(err),
     }
 }
@@ -38,12 +42,12 @@ pub async fn user_register(
         .send()
         .await
         .and_then(Response::error_for_status)
-        .map_err(AsyncMsg::error)
+        .map_err(AsyncMsg::api_error)
     {
         Ok(resp) => resp
             .json::<ApiResponse<i32>>()
             .await
-            .map_err(AsyncMsg::error),
+            .map_err(AsyncMsg::api_error),
         Err(err) => Err(err),
     }
 }
@@ -52,16 +56,32 @@ pub async fn user_logout(
     url: impl IntoUrl,
     client: Client,
     token: impl std::fmt::Display,
-) -> Result<AsyncMsg, AsyncMsg> {
-    match client
+) -> DynoResult<()> {
+    client
         .get(url)
         .bearer_auth(token)
         .send()
         .await
         .and_then(Response::error_for_status)
-        .map_err(AsyncMsg::error)
-    {
-        Ok(_resp) => Ok(AsyncMsg::message("Logout is Success!")),
-        Err(err) => Err(err),
-    }
+        .map(|_resp| ())
+        .map_err(response_error)
+}
+
+/// exchange the refresh token for new tokens, the used refresh token is not valid anymore
+pub async fn refresh(
+    url: impl IntoUrl,
+    client: Client,
+    refresh_token: String,
+) -> DynoResult<TokenDetails> {
+    client
+        .post(url)
+        .json(&RefreshRequest { refresh_token })
+        .send()
+        .await
+        .and_then(Response::error_for_status)
+        .map_err(response_error)?
+        .json::<ApiResponse<TokenDetails>>()
+        .await
+        .map(|resp| resp.payload)
+        .map_err(DynoErr::api_error)
 }
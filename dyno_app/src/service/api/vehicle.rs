@@ -0,0 +1,34 @@
+use dyno_core::{
+    model::{
+        vehicle::{Vehicle, VehicleSync},
+        ApiResponse,
+    },
+    DynoErr, DynoResult,
+};
+use reqwest::{Client, IntoUrl, Response};
+
+use super::response_error;
+
+/// push the `changed` vehicles, return every vehicle of the server
+pub async fn sync(
+    url: impl IntoUrl,
+    client: Client,
+    token: impl std::fmt::Display,
+    changed: &[Vehicle],
+) -> DynoResult<Vec<Vehicle>> {
+    let body = VehicleSync {
+        changed: changed.to_vec(),
+    };
+    client
+        .post(url)
+        .json(&body)
+        .bearer_auth(token)
+        .send()
+        .await
+        .and_then(Response::error_for_status)
+        .map_err(response_error)?
+        .json::<ApiResponse<Vec<Vehicle>>>()
+        .await
+        .map(|resp| resp.payload)
+        .map_err(DynoErr::api_error)
+}
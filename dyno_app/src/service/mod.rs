@@ -4,8 +4,11 @@ mod serial;
 // pub use api::*;
 
 pub use serial::{
-    ports::{get_dyno_port, PortInfo},
-    SerialService,
+    capture::{CaptureReader, CaptureRecord, CaptureWriter, CAPTURE_EXTENSION},
+    manager::{PortEvent, PortManager},
+    ports::{get_dyno_port, get_dyno_ports, PortInfo},
+    settings::{LinkParam, LinkReport, SerialSettings},
+    test_link, SerialService,
 };
 
-pub use api::ApiService;
+pub use api::{ApiService, LiveStream, Outbox, PendingUpload, ServerSettings};
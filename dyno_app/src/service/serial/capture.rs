@@ -0,0 +1,234 @@
+//! raw serial traffic capture (`.dynocap`), every chunk read from the port is written with the
+//! host timestamp, so a session on site can be replayed later without the hardware.
+//!
+//! layout: [`MAGIC`], start of the capture in unix milliseconds (`i64` LE), then records of
+//! elapsed microseconds since the start (`u64` LE), length (`u32` LE) and the bytes
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use dyno_core::{
+    chrono::{DateTime, Utc},
+    crossbeam_channel::Sender,
+    tokio::time::sleep_until,
+    types::MAX_FRAME_SIZE,
+    DynoErr, DynoResult,
+};
+
+use crate::AsyncMsg;
+
+pub const CAPTURE_EXTENSION: &str = "dynocap";
+
+/// magic bytes, the last byte is the format version
+const MAGIC: &[u8; 8] = b"DYNOCAP\x01";
+
+/// single chunk of bytes as it is read from the port
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureRecord {
+    /// time since the capture is started
+    pub elapsed: Duration,
+    pub bytes: Vec<u8>,
+}
+
+pub struct CaptureWriter<W: Write = BufWriter<File>> {
+    writer: W,
+    start: Instant,
+}
+
+impl CaptureWriter {
+    /// create the capture file, the parent folder is created if not exists
+    pub fn create(path: impl AsRef<Path>) -> DynoResult<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> CaptureWriter<W> {
+    pub fn new(mut writer: W) -> DynoResult<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&Utc::now().timestamp_millis().to_le_bytes())?;
+        Ok(Self {
+            writer,
+            start: Instant::now(),
+        })
+    }
+
+    pub fn record(&mut self, bytes: &[u8]) -> DynoResult<()> {
+        let elapsed = self.start.elapsed().as_micros() as u64;
+        self.writer.write_all(&elapsed.to_le_bytes())?;
+        self.writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        self.writer.write_all(bytes)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> DynoResult<()> {
+        self.writer.flush().map_err(From::from)
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+pub struct CaptureReader<R: Read = BufReader<File>> {
+    reader: R,
+    started: DateTime<Utc>,
+}
+
+impl CaptureReader {
+    pub fn open(path: impl AsRef<Path>) -> DynoResult<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn new(mut reader: R) -> DynoResult<Self> {
+        let mut magic = [0u8; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(DynoErr::input_output_error(
+                "not a dynotests serial capture file, or unsupported version",
+            ));
+        }
+        let mut millis = [0u8; 8];
+        reader.read_exact(&mut millis)?;
+        let started = DateTime::from_timestamp_millis(i64::from_le_bytes(millis))
+            .ok_or_else(|| DynoErr::input_output_error("invalid capture start time"))?;
+        Ok(Self { reader, started })
+    }
+
+    /// host time when the capture is started
+    #[inline]
+    pub fn started(&self) -> DateTime<Utc> {
+        self.started
+    }
+
+    fn read_record(&mut self) -> std::io::Result<CaptureRecord> {
+        let mut elapsed = [0u8; 8];
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut elapsed)?;
+        self.reader.read_exact(&mut len)?;
+        // every record is one read of the port into a `MAX_FRAME_SIZE` chunk, the longer one is a
+        // corrupt length and must not be allocated
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MAX_FRAME_SIZE {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("capture record of {len} bytes is corrupt"),
+            ));
+        }
+        let mut bytes = vec![0u8; len];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(CaptureRecord {
+            elapsed: Duration::from_micros(u64::from_le_bytes(elapsed)),
+            bytes,
+        })
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = DynoResult<CaptureRecord>;
+
+    /// the last record may be cut when the app is crashed while capturing, stop there
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_record() {
+            Ok(record) => Some(Ok(record)),
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => None,
+            Err(err) => Some(Err(err.into())),
+        }
+    }
+}
+
+/// feed the capture into `tx` through the same decoding as the real device, `speed` is the
+/// multiplier of the recorded timing (`2.0` twice faster), not positive means without delay
+pub async fn replay<R: Read>(
+    capture: CaptureReader<R>,
+    speed: f32,
+    tx: &Sender<AsyncMsg>,
+    running: &AtomicBool,
+) -> DynoResult<()> {
+    let start = dyno_core::tokio::time::Instant::now();
+    let mut buffer = Vec::new();
+    for record in capture {
+        if !running.load(Ordering::Relaxed) {
+            break;
+        }
+        let CaptureRecord { elapsed, bytes } = record?;
+        if speed > 0.0 {
+            sleep_until(start + elapsed.div_f32(speed)).await;
+        }
+        buffer.extend_from_slice(&bytes);
+        super::dispatch_frames(&mut buffer, tx);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::{
+        crossbeam_channel::unbounded,
+        tokio,
+        types::{RawSerialData, RawTemp, RawTemps, SerialResponse},
+    };
+
+    #[test]
+    fn serial_capture_replay() {
+        let data = RawSerialData {
+            pulse_enc: 10,
+            pulse_rpm: 20,
+            raw_temps: RawTemps::from_array([RawTemp::max6675(420)]),
+        };
+        let frame = postcard::to_stdvec_cobs(&SerialResponse::Data(data)).unwrap();
+
+        let mut writer = CaptureWriter::new(Vec::new()).unwrap();
+        writer.record(&frame).unwrap();
+        // frame splitted in two read
+        writer.record(&frame[..3]).unwrap();
+        writer.record(&frame[3..]).unwrap();
+        let mut bytes = writer.into_inner();
+        // crashed in the middle of a record
+        bytes.extend_from_slice(&[0x01, 0x02]);
+
+        let records = CaptureReader::new(bytes.as_slice())
+            .unwrap()
+            .collect::<DynoResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].bytes, frame);
+        assert!(records[0].elapsed <= records[2].elapsed);
+
+        let (tx, rx) = unbounded();
+        let running = AtomicBool::new(true);
+        let capture = CaptureReader::new(bytes.as_slice()).unwrap();
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(replay(capture, 0.0, &tx, &running))
+            .unwrap();
+        let received = rx
+            .try_iter()
+            .filter(|msg| matches!(msg, AsyncMsg::OnSerialData(received) if *received == data))
+            .count();
+        assert_eq!(received, 2);
+
+        assert!(CaptureReader::new(&b"NOTACAPTURE12345"[..]).is_err());
+
+        // corrupt length is an error, not an allocation of 4 GiB
+        let mut bytes = CaptureWriter::new(Vec::new()).unwrap().into_inner();
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        let mut capture = CaptureReader::new(bytes.as_slice()).unwrap();
+        assert!(capture.next().unwrap().is_err());
+    }
+}
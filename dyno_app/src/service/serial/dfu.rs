@@ -0,0 +1,115 @@
+//! firmware update over the serial link, the protocol is handled by [`DfuUpdater`]
+
+use std::{
+    io::{ErrorKind, Read, Write},
+    time::{Duration, Instant},
+};
+
+use dyno_core::{
+    dfu::{DfuLink, DfuProgress, DfuResult, DfuUpdateError, DfuUpdater, FirmwareImage},
+    types::{SerialCommand, SerialResponse, MAX_FRAME_SIZE},
+    DynoErr, DynoResult,
+};
+use serialport::SerialPort;
+
+use super::{ports, settings::SerialSettings, PortInfo};
+
+/// blocking COBS framed link, used only while updating so the streaming service must be stopped
+pub struct SerialDfuLink {
+    port: Box<dyn SerialPort>,
+    buffer: Vec<u8>,
+}
+
+impl SerialDfuLink {
+    /// longer than erasing the update partition in the device
+    const TIMEOUT: Duration = Duration::from_secs(3);
+
+    pub fn open(port_name: &str, settings: &SerialSettings) -> DynoResult<Self> {
+        let port = settings
+            .builder(port_name)
+            .timeout(Duration::from_millis(100))
+            .open()
+            .map_err(|err| DynoErr::service_error(format!("Error On Opening Port - ({err})")))?;
+        Ok(Self {
+            port,
+            buffer: Vec::with_capacity(MAX_FRAME_SIZE * 2),
+        })
+    }
+}
+
+impl DfuLink for SerialDfuLink {
+    fn send(&mut self, command: &SerialCommand) -> DfuResult<()> {
+        let frame = postcard::to_stdvec_cobs(command)
+            .map_err(|err| DfuUpdateError::Link(err.to_string()))?;
+        self.port
+            .write_all(&frame)
+            .map_err(|err| DfuUpdateError::Link(err.to_string()))
+    }
+
+    fn recv(&mut self) -> DfuResult<Option<SerialResponse>> {
+        let deadline = Instant::now() + Self::TIMEOUT;
+        let mut chunk = [0u8; MAX_FRAME_SIZE];
+        loop {
+            while let Some(mut frame) = super::take_frame(&mut self.buffer) {
+                match postcard::from_bytes_cobs(&mut frame) {
+                    Ok(response) => return Ok(Some(response)),
+                    Err(err) => dyno_core::log::warn!("dropping invalid frame: {err}"),
+                }
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            match self.port.read(&mut chunk) {
+                Ok(len) => self.buffer.extend_from_slice(&chunk[..len]),
+                Err(err) if err.kind() == ErrorKind::TimedOut => continue,
+                Err(err) => return Err(DfuUpdateError::Link(err.to_string())),
+            }
+        }
+    }
+}
+
+/// upload `image` to the device at `info`, wait the device reconnected and confirm it is
+/// running the new firmware
+pub fn update_firmware(
+    info: &PortInfo,
+    settings: &SerialSettings,
+    image: &FirmwareImage,
+    allow_downgrade: bool,
+    progress: impl FnMut(DfuProgress),
+) -> DynoResult<()> {
+    const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    let to_dyno_err = |err: DfuUpdateError| DynoErr::service_error(err.to_string());
+    {
+        let mut link = SerialDfuLink::open(&info.port_name, settings)?;
+        DfuUpdater::new(&mut link)
+            .update(image, allow_downgrade, progress)
+            .map_err(to_dyno_err)?;
+    }
+
+    // the bootloader verify and copy the image before the firmware is booted,
+    // a native USB board is enumerated again so find it by the serial number
+    let deadline = Instant::now() + RECONNECT_TIMEOUT;
+    std::thread::sleep(Duration::from_secs(2));
+    loop {
+        let port = ports::get_dyno_ports()?.into_iter().find(|port| {
+            match (&info.serial_number, &port.serial_number) {
+                (Some(old), Some(new)) => old == new,
+                _ => port.port_name == info.port_name,
+            }
+        });
+        if let Some(port) = port {
+            if let Ok(mut link) = SerialDfuLink::open(&port.port_name, settings) {
+                return DfuUpdater::new(&mut link)
+                    .confirm(image)
+                    .map_err(to_dyno_err);
+            }
+        }
+        if Instant::now() >= deadline {
+            return Err(DynoErr::service_error(
+                "device is not reconnected after the firmware update",
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
@@ -11,7 +11,7 @@ use std::{
 mod os_prelude {
     pub use super::tokio::io::unix::AsyncFd;
     pub use futures::ready;
-    pub use serialport::{BreakDuration, TTYPort};
+    pub use serialport::TTYPort;
 }
 
 #[cfg(windows)]
@@ -25,6 +25,24 @@ mod os_prelude {
 
 #[cfg(unix)]
 type PortType = TTYPort;
+
+/// `TTYPort` wait with `poll` for its timeout before read/write, with zero timeout it
+/// return `TimedOut` instead of blocking, map it to `WouldBlock` so the readiness is cleared
+#[cfg(unix)]
+fn nonblocking<T>(result: IoResult<T>) -> IoResult<T> {
+    result.map_err(|err| match err.kind() {
+        std::io::ErrorKind::TimedOut => std::io::ErrorKind::WouldBlock.into(),
+        _ => err,
+    })
+}
+
+#[cfg(unix)]
+fn new_async_fd(mut port: TTYPort) -> DynoResult<AsyncFd<TTYPort>> {
+    use serialport::SerialPort;
+    port.set_timeout(std::time::Duration::ZERO)
+        .map_err(|err| DynoErr::service_error(format!("{err}")))?;
+    AsyncFd::new(port).map_err(|err| DynoErr::service_error(format!("{err}")))
+}
 #[cfg(windows)]
 type PortType = COMPort;
 
@@ -48,7 +66,7 @@ impl SerialStream {
         #[cfg(unix)]
         {
             Ok(Self {
-                inner: AsyncFd::new(port)?,
+                inner: new_async_fd(port)?,
             })
         }
 
@@ -73,10 +91,10 @@ impl SerialStream {
             .map_err(|err| DynoErr::service_error(format!("Error On Pairing TTYPort - ({err})")))?;
 
         let master = SerialStream {
-            inner: AsyncFd::new(master).map_err(|err| DynoErr::service_error(format!("{err}")))?,
+            inner: new_async_fd(master)?,
         };
         let slave = SerialStream {
-            inner: AsyncFd::new(slave).map_err(|err| DynoErr::service_error(format!("{err}")))?,
+            inner: new_async_fd(slave)?,
         };
         Ok((master, slave))
     }
@@ -99,7 +117,7 @@ impl SerialStream {
     pub fn try_read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
         #[cfg(unix)]
         {
-            self.inner.get_mut().read(buf)
+            nonblocking(self.inner.get_mut().read(buf))
         }
         #[cfg(windows)]
         {
@@ -115,7 +133,7 @@ impl SerialStream {
     pub fn try_write(&mut self, buf: &[u8]) -> IoResult<usize> {
         #[cfg(unix)]
         {
-            self.inner.get_mut().write(buf)
+            nonblocking(self.inner.get_mut().write(buf))
         }
         #[cfg(windows)]
         {
@@ -176,7 +194,8 @@ impl AsyncRead for SerialStream {
         loop {
             let mut guard = ready!(self.inner.poll_read_ready_mut(cx))?;
 
-            match guard.try_io(|inner| inner.get_mut().read(buf.initialize_unfilled())) {
+            match guard.try_io(|inner| nonblocking(inner.get_mut().read(buf.initialize_unfilled())))
+            {
                 Ok(Ok(bytes_read)) => {
                     buf.advance(bytes_read);
                     return Poll::Ready(Ok(()));
@@ -217,7 +236,7 @@ impl AsyncWrite for SerialStream {
         loop {
             let mut guard = ready!(self.inner.poll_write_ready_mut(cx))?;
 
-            match guard.try_io(|inner| inner.get_mut().write(buf)) {
+            match guard.try_io(|inner| nonblocking(inner.get_mut().write(buf))) {
                 Ok(result) => return Poll::Ready(result),
                 Err(_would_block) => continue,
             }
@@ -445,6 +464,7 @@ mod io {
 /// This trait adds one method to SerialPortBuilder:
 ///
 /// - open_native_async
+///
 /// This method mirrors the `open_native` method of SerialPortBuilder
 pub trait SerialPortBuilderExt {
     /// Open a platform-specific interface to the port with the specified settings
@@ -460,7 +480,7 @@ impl SerialPortBuilderExt for serialport::SerialPortBuilder {
 
 pub fn open_async<'a>(
     path: impl Into<std::borrow::Cow<'a, str>>,
-    baud_rate: u32,
+    settings: &super::settings::SerialSettings,
 ) -> DynoResult<SerialStream> {
-    serialport::new(path, baud_rate).open_native_async()
+    settings.builder(path).open_native_async()
 }
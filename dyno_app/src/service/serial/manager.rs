@@ -0,0 +1,144 @@
+//! list and watch the connected dynotests device, hot-plug is detected by polling
+//! [`serialport::available_ports`] because there is no portable notification for it
+
+use std::time::{Duration, Instant};
+
+use dyno_core::serde;
+
+use super::ports::{self, PortInfo};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortEvent {
+    Attached(PortInfo),
+    Detached(PortInfo),
+}
+
+#[derive(Debug, Default, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+#[serde(default)]
+pub struct PortManager {
+    /// key of the port picked by the user, see [`PortInfo::key`]
+    selected: Option<String>,
+
+    #[serde(skip)]
+    ports: Vec<PortInfo>,
+
+    #[serde(skip)]
+    last_poll: Option<Instant>,
+}
+
+impl PortManager {
+    pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    #[inline(always)]
+    pub fn ports(&self) -> &[PortInfo] {
+        &self.ports
+    }
+
+    /// remember `info` as the port to use, also after the app restarted
+    #[inline]
+    pub fn select(&mut self, info: &PortInfo) {
+        self.selected = Some(info.key().to_owned());
+    }
+
+    #[inline]
+    pub fn is_selected(&self, info: &PortInfo) -> bool {
+        self.selected.as_deref() == Some(info.key())
+    }
+
+    /// the selected port if it's connected, otherwise the first port found
+    pub fn preferred(&self) -> Option<&PortInfo> {
+        self.selected
+            .as_deref()
+            .and_then(|key| self.ports.iter().find(|port| port.key() == key))
+            .or_else(|| self.ports.first())
+    }
+
+    /// poll the connected ports, at most once every [`Self::POLL_INTERVAL`]
+    pub fn poll(&mut self) -> Vec<PortEvent> {
+        if self
+            .last_poll
+            .is_some_and(|last| last.elapsed() < Self::POLL_INTERVAL)
+        {
+            return Vec::new();
+        }
+        self.refresh()
+    }
+
+    /// poll the connected ports now
+    pub fn refresh(&mut self) -> Vec<PortEvent> {
+        self.last_poll = Some(Instant::now());
+        match ports::get_dyno_ports() {
+            Ok(ports) => self.update(ports),
+            Err(err) => {
+                dyno_core::log::warn!("{err}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// replace the list of ports, return what is detached and attached since the last update
+    pub fn update(&mut self, ports: Vec<PortInfo>) -> Vec<PortEvent> {
+        let detached = self
+            .ports
+            .iter()
+            .filter(|old| !ports.contains(old))
+            .cloned()
+            .map(PortEvent::Detached);
+        let attached = ports
+            .iter()
+            .filter(|new| !self.ports.contains(new))
+            .cloned()
+            .map(PortEvent::Attached);
+        let events = detached.chain(attached).collect();
+        self.ports = ports;
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn port(port_name: &str, serial_number: Option<&str>) -> PortInfo {
+        PortInfo {
+            port_name: port_name.to_owned(),
+            vid: dyno_core::types::USB_VID,
+            pid: dyno_core::types::USB_PID,
+            serial_number: serial_number.map(ToOwned::to_owned),
+            product: None,
+        }
+    }
+
+    #[test]
+    fn port_manager_hotplug() {
+        let board_a = port("/dev/ttyACM0", Some("A"));
+        let board_b = port("/dev/ttyACM1", Some("B"));
+        let mut manager = PortManager::default();
+
+        assert_eq!(
+            manager.update(vec![board_a.clone(), board_b.clone()]),
+            vec![
+                PortEvent::Attached(board_a.clone()),
+                PortEvent::Attached(board_b.clone())
+            ]
+        );
+        assert_eq!(manager.preferred(), Some(&board_a));
+        manager.select(&board_b);
+        assert_eq!(manager.preferred(), Some(&board_b));
+
+        // replugged, the port name changed but still the selected board
+        let board_b_replug = port("/dev/ttyACM2", Some("B"));
+        assert_eq!(
+            manager.update(vec![board_a.clone()]),
+            vec![PortEvent::Detached(board_b)]
+        );
+        assert_eq!(manager.preferred(), Some(&board_a));
+        assert_eq!(
+            manager.update(vec![board_a, board_b_replug.clone()]),
+            vec![PortEvent::Attached(board_b_replug.clone())]
+        );
+        assert!(manager.is_selected(&board_b_replug));
+        assert_eq!(manager.preferred(), Some(&board_b_replug));
+    }
+}
@@ -1,133 +1,679 @@
 #![allow(dead_code)]
+pub mod capture;
+pub mod dfu;
 mod impl_serial;
+pub mod manager;
 pub mod ports;
+pub mod settings;
 
+use capture::{CaptureReader, CaptureWriter};
 use dyno_core::{
     crossbeam_channel::Sender,
+    i18n::tr,
     ignore_err,
     tokio::{
-        io::{AsyncBufReadExt, BufReader, ErrorKind as IOEK},
+        io::{AsyncReadExt, AsyncWriteExt, ErrorKind as IOEK},
         task::JoinHandle,
+        time::{sleep, timeout},
     },
-    DynoErr, DynoResult, SerialData,
+    types::{
+        BootStatus, DfuCommand, DfuResponse, FirmwareVersion, SerialCommand, SerialDataInit,
+        SerialResponse, MAX_FRAME_SIZE,
+    },
+    DynoErr, DynoResult,
 };
+use manager::PortManager;
 use ports::PortInfo;
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
+use settings::{LinkReport, SerialSettings};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
 use crate::{toast_error, AsyncMsg};
 
-use self::impl_serial::open_async;
+use self::impl_serial::{open_async, SerialStream};
+
+/// take one COBS frame from the buffer, include the `0x00` delimiter
+pub(crate) fn take_frame(buffer: &mut Vec<u8>) -> Option<Vec<u8>> {
+    let end = buffer.iter().position(|&b| b == 0)?;
+    Some(buffer.drain(..=end).collect())
+}
+
+async fn send_command(port: &mut SerialStream, command: &SerialCommand) -> DynoResult<()> {
+    let frame = postcard::to_stdvec_cobs(command)?;
+    port.write_all(&frame).await.map_err(From::from)
+}
+
+/// request the device config, then start streaming with the period stored in the device
+async fn start_streaming(port: &mut SerialStream) -> DynoResult<()> {
+    send_command(port, &SerialCommand::GetConfig).await?;
+    send_command(port, &SerialCommand::Start(SerialDataInit::DEVICE_DEFAULT)).await
+}
+
+/// ping the device with [`DfuCommand::GetVersion`], `None` if there is no valid answer in `wait`
+async fn handshake(
+    port: &mut SerialStream,
+    wait: Duration,
+) -> DynoResult<Option<(FirmwareVersion, BootStatus)>> {
+    send_command(port, &SerialCommand::Dfu(DfuCommand::GetVersion)).await?;
+    let deadline = Instant::now() + wait;
+    let mut buffer: Vec<u8> = Vec::with_capacity(MAX_FRAME_SIZE * 2);
+    let mut chunk = [0u8; MAX_FRAME_SIZE];
+    loop {
+        while let Some(mut frame) = take_frame(&mut buffer) {
+            match postcard::from_bytes_cobs::<SerialResponse>(&mut frame) {
+                Ok(SerialResponse::Dfu(DfuResponse::Version { version, status })) => {
+                    return Ok(Some((version, status)))
+                }
+                Ok(response) => dyno_core::log::debug!("ignoring serial response: {response}"),
+                // garbage is expected when the baud rate or parity is not match
+                Err(err) => dyno_core::log::debug!("dropping invalid frame: {err}"),
+            }
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match timeout(remaining, port.read(&mut chunk)).await {
+            Err(_) => return Ok(None),
+            Ok(Ok(len)) => buffer.extend_from_slice(&chunk[..len]),
+            Ok(Err(err)) if matches!(err.kind(), IOEK::TimedOut | IOEK::Interrupted) => {}
+            Ok(Err(err)) => return Err(err.into()),
+        }
+    }
+}
+
+/// check the link to the device at `port_name`, when the device is not answering, find which
+/// parameter of `settings` is not match with the device. the service must not be running
+pub async fn test_link(port_name: &str, settings: &SerialSettings) -> DynoResult<LinkReport> {
+    settings::diagnose(settings, |candidate| async move {
+        let mut port = open_async(port_name, &candidate)?;
+        handshake(&mut port, candidate.read_timeout()).await
+    })
+    .await
+}
+
+/// why [`read_frames`] is returned
+#[derive(Debug)]
+enum StreamEnd {
+    /// the running flag is cleared
+    Stopped,
+    /// the port is gone, likely the cable is unplugged or the board is reset
+    Disconnected(DynoErr),
+}
+
+/// how long a read waits before checking the running flag again
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// reads in a row that return EOF before the port is treated as disconnected, some CDC and
+/// USB-serial driver report EOF instead of an error after the device is unplugged
+const MAX_EOF_READS: u32 = 3;
+
+/// decode every complete frame in `buffer` and send it to `tx`, the rest that is already
+/// longer than a frame is discarded, it never end with a delimiter (noise or wrong baud rate)
+pub(crate) fn dispatch_frames(buffer: &mut Vec<u8>, tx: &Sender<AsyncMsg>) {
+    while let Some(mut frame) = take_frame(buffer) {
+        let msg = match postcard::from_bytes_cobs::<SerialResponse>(&mut frame) {
+            Ok(SerialResponse::Data(data)) => AsyncMsg::serial_data(data),
+            Ok(SerialResponse::Config(config)) => AsyncMsg::OnSerialConfig(config),
+            Ok(SerialResponse::Error(err)) => {
+                AsyncMsg::error(DynoErr::service_error(format!("device error: {err}")))
+            }
+            Ok(response) => {
+                dyno_core::log::debug!("ignoring serial response: {response}");
+                continue;
+            }
+            Err(err) => {
+                dyno_core::log::warn!("dropping invalid frame: {err}");
+                continue;
+            }
+        };
+        ignore_err!(tx.send(msg));
+    }
+    if buffer.len() >= MAX_FRAME_SIZE {
+        dyno_core::log::warn!("dropping {} bytes without frame delimiter", buffer.len());
+        buffer.clear();
+    }
+}
+
+/// decode the frames from `port` and send them to `tx` until stopped or disconnected,
+/// every chunk is also written to `capture` if there is one. the stop command is sent to the
+/// device when `device_stop` is set, without ending the stream.
+///
+/// while the device is silent, `info` is searched with [`ports::find_port`] once every
+/// [`SerialService::RECONNECT_INTERVAL`], so the port that is gone is disconnected even if the
+/// read never fail
+async fn read_frames(
+    port: &mut SerialStream,
+    info: &PortInfo,
+    tx: &Sender<AsyncMsg>,
+    flags: &ServiceFlags,
+    capture: &mut Option<CaptureWriter>,
+) -> StreamEnd {
+    let mut buffer: Vec<u8> = Vec::with_capacity(MAX_FRAME_SIZE * 2);
+    let mut chunk = [0u8; MAX_FRAME_SIZE];
+    let mut eof_reads = 0;
+    let mut last_seen = Instant::now();
+
+    while flags.running.load(Ordering::Relaxed) {
+        if flags.device_stop.swap(false, Ordering::Relaxed) {
+            if let Err(err) = send_command(port, &SerialCommand::Stop).await {
+                dyno_core::log::error!("failed sending stop to the device: {err}");
+            }
+        }
+        // EOF is counted the same whether it is reported as zero length or as an error
+        let read = timeout(READ_TIMEOUT, port.read(&mut chunk))
+            .await
+            .map(|read| match read {
+                Ok(0) => Err(IOEK::UnexpectedEof.into()),
+                read => read,
+            });
+        let len = match read {
+            Ok(Ok(len)) => len,
+            Err(_) if last_seen.elapsed() >= SerialService::RECONNECT_INTERVAL => {
+                match ports::find_port(info) {
+                    Ok(Some(_)) => last_seen = Instant::now(),
+                    Ok(None) => {
+                        return StreamEnd::Disconnected(DynoErr::input_output_error(format!(
+                            "port `{}` is gone",
+                            info.port_name
+                        )))
+                    }
+                    Err(err) => dyno_core::log::warn!("{err}"),
+                }
+                continue;
+            }
+            Err(_) => continue,
+            Ok(Err(err)) if err.kind() == IOEK::UnexpectedEof => {
+                eof_reads += 1;
+                if eof_reads >= MAX_EOF_READS {
+                    return StreamEnd::Disconnected(err.into());
+                }
+                sleep(READ_TIMEOUT).await;
+                continue;
+            }
+            Ok(Err(err)) if matches!(err.kind(), IOEK::TimedOut | IOEK::Interrupted) => continue,
+            Ok(Err(err)) => return StreamEnd::Disconnected(err.into()),
+        };
+        eof_reads = 0;
+        last_seen = Instant::now();
+        if let Some(writer) = capture {
+            if let Err(err) = writer.record(&chunk[..len]) {
+                dyno_core::log::error!("serial capture is stopped: {err}");
+                *capture = None;
+            }
+        }
+        buffer.extend_from_slice(&chunk[..len]);
+        dispatch_frames(&mut buffer, tx);
+    }
+    StreamEnd::Stopped
+}
+
+/// wait until the device in `info` is connected again and open it,
+/// return `None` if the service is stopped before that
+async fn reconnect(
+    info: &PortInfo,
+    settings: &SerialSettings,
+    running: &AtomicBool,
+) -> Option<(SerialStream, PortInfo)> {
+    while running.load(Ordering::Relaxed) {
+        sleep(SerialService::RECONNECT_INTERVAL).await;
+        let port = match ports::find_port(info) {
+            Ok(Some(port)) => port,
+            Ok(None) => continue,
+            Err(err) => {
+                dyno_core::log::warn!("{err}");
+                continue;
+            }
+        };
+        match open_async(port.port_name.clone(), settings) {
+            Ok(stream) => return Some((stream, port)),
+            Err(err) => dyno_core::log::debug!("port `{}` not ready: {err}", port.port_name),
+        }
+    }
+    None
+}
+
+/// where [`SerialService`] get the bytes from
+#[derive(Debug, Clone, PartialEq)]
+enum SerialSource {
+    /// real device, or pseudo-terminal fed by a simulator
+    Port,
+    /// replay `.dynocap` file, see [`capture::replay`]
+    Replay { path: PathBuf, speed: f32 },
+}
+
+/// shared between [`SerialService`] and its task
+#[derive(Debug, Default)]
+struct ServiceFlags {
+    running: AtomicBool,
+    /// send [`SerialCommand::Stop`] to the device on the next read, or when the task is ended
+    device_stop: AtomicBool,
+}
 
 #[derive(Clone)]
 pub struct SerialService {
     pub info: PortInfo,
-    running_flag: Arc<AtomicBool>,
+    source: SerialSource,
+    /// write the received bytes to this file while running
+    capture: Option<PathBuf>,
+    flags: Arc<ServiceFlags>,
 }
 
 impl SerialService {
     pub const MAX_BUFFER_SIZE: usize = 1024;
-    const BAUD_RATE: u32 = 512_000;
 
     pub fn new() -> Option<Self> {
         let info = match ports::get_dyno_port() {
             Ok(ok) => match ok {
                 Some(some) => some,
                 None => {
-                    toast_error!(
-                        "Failed to get port info, there is no port available in this machine"
-                    );
+                    toast_error!("{}", tr("toast-no-port"));
                     return None;
                 }
             },
             Err(err) => {
-                toast_error!("Failed to get port info, {err}");
+                toast_error!(
+                    "{}",
+                    dyno_core::tr!("toast-port-info-failed", error = err.to_string())
+                );
                 return None;
             }
         };
-        Some(Self {
+        Some(Self::with_port(info))
+    }
+
+    /// service for a specific port, used when there is more than one dyno connected
+    pub fn with_port(info: PortInfo) -> Self {
+        Self {
             info,
-            running_flag: Arc::default(),
-        })
+            source: SerialSource::Port,
+            capture: None,
+            flags: Arc::default(),
+        }
     }
 
-    pub fn start(&mut self, tx: Sender<AsyncMsg>) -> DynoResult<JoinHandle<()>> {
-        if self.running_flag.load(Ordering::Relaxed) {
-            return Err(DynoErr::service_error("Serial Service Already Running"));
+    /// virtual port that replay the capture file at `path`, `speed` is the multiplier of the
+    /// recorded timing and not positive means as fast as possible
+    pub fn replay(path: PathBuf, speed: f32) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self {
+            info: PortInfo::virtual_port(format!("replay: {name}")),
+            source: SerialSource::Replay { path, speed },
+            capture: None,
+            flags: Arc::default(),
         }
-        self.running_flag.store(true, Ordering::Relaxed);
+    }
 
-        let running = self.running_flag.clone();
-        let port_name = self.info.port_name.clone();
-        let serial_inner = open_async(port_name, Self::BAUD_RATE)?;
+    #[inline]
+    pub fn is_replay(&self) -> bool {
+        matches!(self.source, SerialSource::Replay { .. })
+    }
 
-        let serial_thread_spawn = async move {
-            let mut serial_port = BufReader::new(serial_inner);
-            // let mut codec = Cod;c::new(serial_port);
-            let mut last: usize = 0;
-            let mut buffer: Vec<u8> = Vec::with_capacity(SerialData::SIZE * 2);
+    /// capture the raw bytes to `path` on the next [`Self::start`], ignored while replaying
+    #[inline]
+    pub fn set_capture(&mut self, path: Option<PathBuf>) {
+        self.capture = path;
+    }
 
-            'loops: loop {
-                if !running.load(Ordering::Relaxed) {
-                    break 'loops;
-                }
-                // membaca data sampai dengan menemui Delimiter '\n',
-                // dan menyimpannya pada `buffer`
-                match serial_port.read_until(SerialData::DELIM, &mut buffer).await {
-                    // jika tidak ada yang terbaca ( byte yang terbaca 0 ), ulang kembali loop
-                    Ok(0) => continue,
-                    // jika terbaca dan diakhiri delimiter, proses data tersebut
-                    Ok(len) if buffer[last + len - 1] == SerialData::DELIM => {
-                        // memproses data dan menkonversi byte data tersebut ke tipe data 'SerialData'
-                        // dan mengirimnya melalui mpsc channel
-                        if let Some(data) = SerialData::from_bytes(&buffer[..len - 1]) {
-                            ignore_err!(tx.send(AsyncMsg::OnSerialData(data)))
-                        }
-                        // menghapus buffer, untuk menyiapkan data pada iterasi selanjutnya
-                        // yang akan diterima
-                        buffer.clear();
-                        last = 0;
-                    }
-                    // jika tidak diakhiri delimiter, tambah jumlah 'last' variable dengan jumlah
-                    // byte yang terbaca
-                    Ok(len) => last += len,
-                    // jika error
-                    Err(err) => {
-                        if matches!(
-                            err.kind(),
-                            IOEK::UnexpectedEof
-                                | IOEK::TimedOut
-                                | IOEK::BrokenPipe
-                                | IOEK::Interrupted
-                        ) {
-                            continue 'loops;
-                        }
-                        dyno_core::log::error!("{err}");
+    /// how often the port is searched again after the device is disconnected
+    pub(crate) const RECONNECT_INTERVAL: Duration = PortManager::POLL_INTERVAL;
+
+    /// request the device config, then start streaming with the period stored in the device,
+    /// the config is sent as [`AsyncMsg::OnSerialConfig`] before the first data arrive.
+    ///
+    /// when the device is disconnected while running, [`AsyncMsg::OnSerialDisconnected`] is sent
+    /// and the service keep waiting for the same device (by [`PortInfo::key`]) until stopped,
+    /// then [`AsyncMsg::OnSerialReconnected`] is sent with the duration of the gap
+    ///
+    /// when replaying, [`AsyncMsg::OnMessage`] is sent after the end of the capture
+    pub fn start(
+        &mut self,
+        settings: SerialSettings,
+        tx: Sender<AsyncMsg>,
+    ) -> DynoResult<JoinHandle<()>> {
+        if self.is_open() {
+            return Err(DynoErr::service_error("Serial Service Already Running"));
+        }
+
+        if let SerialSource::Replay { path, speed } = &self.source {
+            let capture = CaptureReader::open(path)?;
+            let (speed, flags) = (*speed, self.flags.clone());
+            flags.running.store(true, Ordering::Relaxed);
+            return Ok(dyno_core::tokio::spawn(async move {
+                match capture::replay(capture, speed, &tx, &flags.running).await {
+                    Ok(()) => {
+                        ignore_err!(tx.send(AsyncMsg::OnMessage(tr("toast-replay-finished"))))
                     }
+                    Err(err) => ignore_err!(tx.send(AsyncMsg::error(err))),
                 }
-            }
-            drop(serial_port);
+                flags.running.store(false, Ordering::Relaxed);
+            }));
+        }
+
+        let serial_port = open_async(self.info.port_name.clone(), &settings)?;
+        let capture = match &self.capture {
+            Some(path) => Some(CaptureWriter::create(path)?),
+            None => None,
         };
+        self.flags.device_stop.store(false, Ordering::Relaxed);
+        self.flags.running.store(true, Ordering::Relaxed);
+        Ok(dyno_core::tokio::spawn(Self::run(
+            serial_port,
+            self.info.clone(),
+            settings,
+            capture,
+            tx,
+            self.flags.clone(),
+        )))
+    }
 
-        Ok(dyno_core::tokio::spawn(serial_thread_spawn))
+    async fn run(
+        mut port: SerialStream,
+        mut info: PortInfo,
+        settings: SerialSettings,
+        mut capture: Option<CaptureWriter>,
+        tx: Sender<AsyncMsg>,
+        flags: Arc<ServiceFlags>,
+    ) {
+        loop {
+            if let Err(err) = start_streaming(&mut port).await {
+                ignore_err!(tx.send(AsyncMsg::error(err)));
+                break;
+            }
+            match read_frames(&mut port, &info, &tx, &flags, &mut capture).await {
+                StreamEnd::Stopped => {
+                    if flags.device_stop.swap(false, Ordering::Relaxed) {
+                        ignore_err!(send_command(&mut port, &SerialCommand::Stop).await);
+                    }
+                    break;
+                }
+                StreamEnd::Disconnected(err) => {
+                    dyno_core::log::warn!("serial port `{}` disconnected: {err}", info.port_name);
+                    ignore_err!(tx.send(AsyncMsg::OnSerialDisconnected(err)));
+                    let lost = Instant::now();
+                    let Some((new_port, new_info)) =
+                        reconnect(&info, &settings, &flags.running).await
+                    else {
+                        break;
+                    };
+                    dyno_core::log::info!("serial port reconnected to `{}`", new_info.port_name);
+                    ignore_err!(tx.send(AsyncMsg::OnSerialReconnected(
+                        new_info.clone(),
+                        lost.elapsed()
+                    )));
+                    (port, info) = (new_port, new_info);
+                }
+            }
+        }
+        if let Some(Err(err)) = capture.as_mut().map(CaptureWriter::flush) {
+            dyno_core::log::error!("failed writing serial capture: {err}");
+        }
+        flags.running.store(false, Ordering::Relaxed);
     }
 
+    /// stop the service and the streaming of the device
     pub fn stop(&self) {
-        self.running_flag.store(false, Ordering::Relaxed);
+        self.flags.device_stop.store(true, Ordering::Relaxed);
+        self.flags.running.store(false, Ordering::Relaxed);
+    }
+
+    /// stop the service, but the device is not commanded
+    pub fn stop_reading(&self) {
+        self.flags.running.store(false, Ordering::Relaxed);
+    }
+
+    /// send the stop command to the device, the service keep running and receive what the
+    /// device still send
+    pub fn stop_device(&self) {
+        if self.is_open() && !self.is_replay() {
+            self.flags.device_stop.store(true, Ordering::Relaxed);
+        }
     }
 
     pub fn is_open(&self) -> bool {
-        self.running_flag.load(Ordering::Relaxed)
+        self.flags.running.load(Ordering::Relaxed)
     }
 
     #[inline(always)]
     pub fn get_info(&self) -> &PortInfo {
         &self.info
     }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use dyno_core::{
+        crossbeam_channel::{unbounded, Receiver},
+        tokio,
+        types::{RawSerialData, RawTemp, RawTemps},
+    };
+
+    const DATA: RawSerialData = RawSerialData {
+        pulse_enc: 10,
+        pulse_rpm: 20,
+        raw_temps: RawTemps::from_array([RawTemp::max6675(420)]),
+    };
+
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build tokio runtime")
+            .block_on(fut)
+    }
+
+    /// the path of the pseudo-terminal that `device` is talking to
+    fn port_info(device: &SerialStream) -> PortInfo {
+        PortInfo::virtual_port(serialport::SerialPort::name(device.borrow()).unwrap())
+    }
+
+    /// wait until `rx` receive the message that `accept`, panic after 5 seconds
+    async fn wait_msg(rx: &Receiver<AsyncMsg>, accept: impl Fn(&AsyncMsg) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if rx.try_iter().any(|msg| accept(&msg)) {
+                return;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+        panic!("the expected message is never received");
+    }
+
+    #[test]
+    fn serial_read_frames_until_disconnected() {
+        block_on(async {
+            let (mut host, mut device) = SerialStream::pair().unwrap();
+            let info = port_info(&device);
+            let (tx, rx) = unbounded();
+            let flags = ServiceFlags {
+                running: AtomicBool::new(true),
+                ..Default::default()
+            };
+
+            let device_task = async move {
+                let frame = postcard::to_stdvec_cobs(&SerialResponse::Data(DATA)).unwrap();
+                device.write_all(&frame).await.unwrap();
+                // frame splitted in two read
+                device.write_all(&frame[..3]).await.unwrap();
+                sleep(Duration::from_millis(50)).await;
+                device.write_all(&frame[3..]).await.unwrap();
+                sleep(Duration::from_millis(50)).await;
+                // unplugged
+                drop(device);
+            };
+            let mut capture = None;
+            let (end, ()) = tokio::join!(
+                read_frames(&mut host, &info, &tx, &flags, &mut capture),
+                device_task
+            );
+
+            assert!(matches!(end, StreamEnd::Disconnected(_)), "{end:?}");
+            let received = rx
+                .try_iter()
+                .filter(|msg| matches!(msg, AsyncMsg::OnSerialData(data) if *data == DATA))
+                .count();
+            assert_eq!(received, 2);
+        });
+    }
+
+    #[test]
+    fn serial_stop_device_keep_reading() {
+        block_on(async {
+            let (mut host, mut device) = SerialStream::pair().unwrap();
+            let info = port_info(&device);
+            let (tx, rx) = unbounded();
+            let flags = ServiceFlags {
+                running: AtomicBool::new(true),
+                device_stop: AtomicBool::new(true),
+            };
+
+            let device_task = async move {
+                let mut buffer = [0u8; MAX_FRAME_SIZE];
+                let len = device.read(&mut buffer).await.unwrap();
+                let command: SerialCommand = postcard::from_bytes_cobs(&mut buffer[..len]).unwrap();
+                assert_eq!(command, SerialCommand::Stop);
+                // the last data sent before the device is stopped
+                let frame = postcard::to_stdvec_cobs(&SerialResponse::Data(DATA)).unwrap();
+                device.write_all(&frame).await.unwrap();
+                sleep(Duration::from_millis(50)).await;
+                drop(device);
+            };
+            let mut capture = None;
+            let (end, ()) = tokio::join!(
+                read_frames(&mut host, &info, &tx, &flags, &mut capture),
+                device_task
+            );
+
+            assert!(matches!(end, StreamEnd::Disconnected(_)), "{end:?}");
+            assert!(!flags.device_stop.load(Ordering::Relaxed));
+            assert_eq!(rx.try_iter().count(), 1);
+        });
+    }
 
-    pub const fn get_baudrate(&self) -> u32 {
-        Self::BAUD_RATE
+    #[test]
+    fn serial_reconnect_after_unplugged() {
+        let dir = std::env::temp_dir().join(format!("dynotest_serial_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let link = dir.join("ttyDYNO");
+        // the port is a link to a pseudo-terminal, replugged by pointing it to a new one
+        let plug = |link: &std::path::Path| {
+            let (device, port) = SerialStream::pair().unwrap();
+            let _ = std::fs::remove_file(link);
+            let name = serialport::SerialPort::name(port.borrow()).unwrap();
+            std::os::unix::fs::symlink(name, link).unwrap();
+            device
+        };
+        let frame = postcard::to_stdvec_cobs(&SerialResponse::Data(DATA)).unwrap();
+        let is_data = |msg: &AsyncMsg| matches!(msg, AsyncMsg::OnSerialData(data) if *data == DATA);
+        let is_disconnected = |msg: &AsyncMsg| matches!(msg, AsyncMsg::OnSerialDisconnected(_));
+        let is_reconnected = |msg: &AsyncMsg| matches!(msg, AsyncMsg::OnSerialReconnected(..));
+
+        block_on(async {
+            let mut device = plug(&link);
+            let mut service =
+                SerialService::with_port(PortInfo::virtual_port(link.to_string_lossy()));
+            let (tx, rx) = unbounded();
+            let task = service.start(SerialSettings::default(), tx).unwrap();
+            device.write_all(&frame).await.unwrap();
+            wait_msg(&rx, is_data).await;
+
+            // the port is gone but the read never fail, like some USB-serial bridge
+            std::fs::remove_file(&link).unwrap();
+            wait_msg(&rx, is_disconnected).await;
+            let mut device = plug(&link);
+            wait_msg(&rx, is_reconnected).await;
+            device.write_all(&frame).await.unwrap();
+            wait_msg(&rx, is_data).await;
+
+            // the device is unplugged
+            drop(device);
+            std::fs::remove_file(&link).unwrap();
+            wait_msg(&rx, is_disconnected).await;
+            let mut device = plug(&link);
+            wait_msg(&rx, is_reconnected).await;
+            device.write_all(&frame).await.unwrap();
+            wait_msg(&rx, is_data).await;
+
+            service.stop();
+            task.await.unwrap();
+            assert!(!service.is_open());
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn serial_handshake() {
+        block_on(async {
+            let (mut host, mut device) = SerialStream::pair().unwrap();
+            let version = FirmwareVersion::new(0, 2, 0);
+
+            let device_task = async move {
+                let mut buffer = [0u8; MAX_FRAME_SIZE];
+                let len = device.read(&mut buffer).await.unwrap();
+                let command: SerialCommand = postcard::from_bytes_cobs(&mut buffer[..len]).unwrap();
+                assert_eq!(command, SerialCommand::Dfu(DfuCommand::GetVersion));
+                // line noise before the answer, like a wrong baud rate
+                device.write_all(&[0x13, 0x37, 0x00]).await.unwrap();
+                let response = SerialResponse::Dfu(DfuResponse::Version {
+                    version,
+                    status: BootStatus::Confirmed,
+                });
+                let frame = postcard::to_stdvec_cobs(&response).unwrap();
+                device.write_all(&frame).await.unwrap();
+                device
+            };
+            let (answer, mut device) = tokio::join!(
+                handshake(&mut host, Duration::from_millis(500)),
+                device_task
+            );
+            assert_eq!(answer.unwrap(), Some((version, BootStatus::Confirmed)));
+
+            // the device is not answering
+            let answer = handshake(&mut host, Duration::from_millis(50)).await;
+            assert_eq!(answer.unwrap(), None);
+            let mut buffer = [0u8; MAX_FRAME_SIZE];
+            assert!(device.read(&mut buffer).await.unwrap() > 0);
+        });
+    }
+
+    #[test]
+    fn serial_read_frames_stopped() {
+        block_on(async {
+            let (mut host, device) = SerialStream::pair().unwrap();
+            let (tx, rx) = unbounded();
+            let flags = ServiceFlags::default();
+            let end = read_frames(&mut host, &port_info(&device), &tx, &flags, &mut None).await;
+            assert!(matches!(end, StreamEnd::Stopped));
+            assert!(rx.is_empty());
+        });
+    }
+
+    #[test]
+    fn serial_dispatch_frames_overflow() {
+        let (tx, rx) = unbounded();
+        let mut buffer = Vec::new();
+        // noise without any delimiter
+        for _ in 0..10 {
+            buffer.extend_from_slice(&[0x55; MAX_FRAME_SIZE / 2]);
+            dispatch_frames(&mut buffer, &tx);
+            assert!(buffer.len() < MAX_FRAME_SIZE, "{}", buffer.len());
+        }
+        // the link is in sync again after the next delimiter
+        buffer.push(0x00);
+        buffer.extend(postcard::to_stdvec_cobs(&SerialResponse::Data(DATA)).unwrap());
+        dispatch_frames(&mut buffer, &tx);
+        assert!(buffer.is_empty());
+        let received = rx
+            .try_iter()
+            .filter(|msg| matches!(msg, AsyncMsg::OnSerialData(data) if *data == DATA))
+            .count();
+        assert_eq!(received, 1);
     }
 }
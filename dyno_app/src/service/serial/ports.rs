@@ -1,19 +1,37 @@
 #![allow(unused)]
 
 use crate::{DynoErr, DynoResult};
+use dyno_core::types::{BRIDGE_PID, BRIDGE_VID, USB_PID, USB_PRODUCT, USB_VID};
 use serialport::{
     SerialPortInfo,
     SerialPortType::{PciPort, UsbPort},
     UsbPortInfo,
 };
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PortInfo {
     pub port_name: String,
     pub vid: u16,
     pub pid: u16,
+    /// USB serial number string, unique for every board with native USB
+    pub serial_number: Option<String>,
+    /// USB product string
+    pub product: Option<String>,
 }
 impl PortInfo {
+    /// port that is not listed as dynotests device, e.g. pseudo-terminal fed by a simulator
+    pub fn virtual_port(port_name: impl Into<String>) -> Self {
+        Self {
+            port_name: port_name.into(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn is_virtual(&self) -> bool {
+        (self.vid, self.pid) == (0, 0)
+    }
+
     /// construct from [SerialPortInfo] into [Self]
     #[inline]
     fn from_serialport(
@@ -23,32 +41,86 @@ impl PortInfo {
         }: SerialPortInfo,
     ) -> Option<Self> {
         match port_type {
-            UsbPort(UsbPortInfo { vid, pid, .. }) => Some(Self {
+            UsbPort(UsbPortInfo {
+                vid,
+                pid,
+                serial_number,
+                product,
+                ..
+            }) => Some(Self {
                 port_name,
                 vid,
                 pid,
+                serial_number,
+                product,
             }),
             PciPort if port_name.contains("/dev/tty") => Some(Self {
                 port_name,
-                vid: 3220,
-                pid: 1406,
+                vid: BRIDGE_VID,
+                pid: BRIDGE_PID,
+                ..Default::default()
             }),
             _ => None,
         }
     }
 
+    /// check if [Self] is board with native USB CDC-ACM, identified by the descriptor strings
+    fn is_native_usb(&self) -> bool {
+        let product_matches = match self.product.as_deref() {
+            Some(product) => product.contains(USB_PRODUCT),
+            // some platform (e.g. macos) not always report the product string
+            None => true,
+        };
+        (self.vid, self.pid) == (USB_VID, USB_PID) && product_matches
+    }
+
     /// check if [Self] is dynotests device port
-    const fn is_dyno_port(&self) -> bool {
-        matches!((self.vid, self.pid), (3220, 1406))
+    fn is_dyno_port(&self) -> bool {
+        self.is_native_usb() || matches!((self.vid, self.pid), (BRIDGE_VID, BRIDGE_PID))
+    }
+
+    /// key to remember the port across reconnection, serial number for native USB board
+    /// because the port name may change after replugged (e.g. `ttyACM0` to `ttyACM1`)
+    pub fn key(&self) -> &str {
+        match &self.serial_number {
+            Some(serial) if self.is_native_usb() => serial,
+            _ => &self.port_name,
+        }
+    }
+
+    /// name to show in the port selection, prefer the serial number for native USB board
+    pub fn display_name(&self) -> String {
+        match &self.serial_number {
+            Some(serial) if self.is_native_usb() => format!("{} ({serial})", self.port_name),
+            _ => self.port_name.clone(),
+        }
     }
 }
 
-pub fn get_dyno_port() -> DynoResult<Option<PortInfo>> {
-    serialport::available_ports()
+/// get all connected dynotests device port, native USB board is listed first
+pub fn get_dyno_ports() -> DynoResult<Vec<PortInfo>> {
+    let mut ports = serialport::available_ports()
         .map(|x| {
             x.into_iter()
                 .filter_map(PortInfo::from_serialport)
-                .find(PortInfo::is_dyno_port)
+                .filter(PortInfo::is_dyno_port)
+                .collect::<Vec<_>>()
         })
-        .map_err(|err| DynoErr::input_output_error(format!("Failed Getting Port: {err}")))
+        .map_err(|err| DynoErr::input_output_error(format!("Failed Getting Port: {err}")))?;
+    ports.sort_by_key(|port| !port.is_native_usb());
+    Ok(ports)
+}
+
+pub fn get_dyno_port() -> DynoResult<Option<PortInfo>> {
+    get_dyno_ports().map(|ports| ports.into_iter().next())
+}
+
+/// find the connected port with the same [`PortInfo::key`] as `info`
+pub fn find_port(info: &PortInfo) -> DynoResult<Option<PortInfo>> {
+    if info.is_virtual() {
+        return Ok(std::path::Path::new(&info.port_name)
+            .exists()
+            .then(|| info.clone()));
+    }
+    get_dyno_ports().map(|ports| ports.into_iter().find(|port| port.key() == info.key()))
 }
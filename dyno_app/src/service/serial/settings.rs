@@ -0,0 +1,396 @@
+//! parameters of the serial link, the default is what the firmware `Serial::config` use
+
+use std::{future::Future, time::Duration};
+
+use derive_more::Display;
+use dyno_core::{
+    i18n::tr,
+    serde, tr,
+    types::{BootStatus, FirmwareVersion},
+    DynoResult,
+};
+
+use crate::{row_label_value, widgets::DynoWidgets};
+
+#[derive(
+    Clone, Copy, Debug, Display, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+#[serde(crate = "serde")]
+pub enum Parity {
+    None,
+    Odd,
+    #[default]
+    Even,
+}
+
+impl Parity {
+    pub const ALL: [Self; 3] = [Self::None, Self::Odd, Self::Even];
+}
+
+impl From<Parity> for serialport::Parity {
+    fn from(value: Parity) -> Self {
+        match value {
+            Parity::None => Self::None,
+            Parity::Odd => Self::Odd,
+            Parity::Even => Self::Even,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Display, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+#[serde(crate = "serde")]
+pub enum StopBits {
+    #[default]
+    #[display("1")]
+    One,
+    #[display("2")]
+    Two,
+}
+
+impl StopBits {
+    pub const ALL: [Self; 2] = [Self::One, Self::Two];
+}
+
+impl From<StopBits> for serialport::StopBits {
+    fn from(value: StopBits) -> Self {
+        match value {
+            StopBits::One => Self::One,
+            StopBits::Two => Self::Two,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Display, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+#[serde(crate = "serde")]
+pub enum FlowControl {
+    None,
+    #[display("Software (XON/XOFF)")]
+    Software,
+    #[default]
+    #[display("Hardware (RTS/CTS)")]
+    Hardware,
+}
+
+impl FlowControl {
+    pub const ALL: [Self; 3] = [Self::None, Self::Software, Self::Hardware];
+}
+
+impl From<FlowControl> for serialport::FlowControl {
+    fn from(value: FlowControl) -> Self {
+        match value {
+            FlowControl::None => Self::None,
+            FlowControl::Software => Self::Software,
+            FlowControl::Hardware => Self::Hardware,
+        }
+    }
+}
+
+/// parameter of [`SerialSettings`] that is not match with the device
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkParam {
+    BaudRate,
+    Parity,
+    StopBits,
+    FlowControl,
+}
+
+impl LinkParam {
+    /// message key of the row in the settings
+    const fn key(self) -> &'static str {
+        match self {
+            Self::BaudRate => "serial-baud-rate",
+            Self::Parity => "serial-parity",
+            Self::StopBits => "serial-stop-bits",
+            Self::FlowControl => "serial-flow-control",
+        }
+    }
+}
+
+impl std::fmt::Display for LinkParam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&tr(self.key()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default, crate = "serde")]
+pub struct SerialSettings {
+    pub baud_rate: u32,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+    pub flow_control: FlowControl,
+    /// how long to wait for the device to answer a command
+    pub read_timeout_ms: u64,
+}
+
+impl Default for SerialSettings {
+    fn default() -> Self {
+        Self {
+            baud_rate: 512_000,
+            parity: Parity::Even,
+            stop_bits: StopBits::One,
+            flow_control: FlowControl::Hardware,
+            read_timeout_ms: 500,
+        }
+    }
+}
+
+impl SerialSettings {
+    /// baud rate that tried when the device is not answering
+    pub const BAUD_RATES: [u32; 10] = [
+        9_600, 19_200, 38_400, 57_600, 115_200, 230_400, 460_800, 512_000, 921_600, 1_000_000,
+    ];
+
+    #[inline]
+    pub fn read_timeout(&self) -> Duration {
+        Duration::from_millis(self.read_timeout_ms)
+    }
+
+    pub fn builder<'a>(
+        &self,
+        path: impl Into<std::borrow::Cow<'a, str>>,
+    ) -> serialport::SerialPortBuilder {
+        serialport::new(path, self.baud_rate)
+            .data_bits(serialport::DataBits::Eight)
+            .parity(self.parity.into())
+            .stop_bits(self.stop_bits.into())
+            .flow_control(self.flow_control.into())
+            .timeout(self.read_timeout())
+    }
+
+    /// current value of `param` as text
+    pub fn value_of(&self, param: LinkParam) -> String {
+        match param {
+            LinkParam::BaudRate => self.baud_rate.to_string(),
+            LinkParam::Parity => self.parity.to_string(),
+            LinkParam::StopBits => self.stop_bits.to_string(),
+            LinkParam::FlowControl => self.flow_control.to_string(),
+        }
+    }
+
+    /// every settings that only different from `self` in one parameter
+    fn neighbours(&self) -> Vec<(LinkParam, Self)> {
+        let baud_rates = Self::BAUD_RATES
+            .into_iter()
+            .filter(|&baud_rate| baud_rate != self.baud_rate)
+            .map(|baud_rate| Self {
+                baud_rate,
+                ..self.clone()
+            })
+            .map(|settings| (LinkParam::BaudRate, settings));
+        let parities = Parity::ALL
+            .into_iter()
+            .filter(|&parity| parity != self.parity)
+            .map(|parity| Self {
+                parity,
+                ..self.clone()
+            })
+            .map(|settings| (LinkParam::Parity, settings));
+        let stop_bits = StopBits::ALL
+            .into_iter()
+            .filter(|&stop_bits| stop_bits != self.stop_bits)
+            .map(|stop_bits| Self {
+                stop_bits,
+                ..self.clone()
+            })
+            .map(|settings| (LinkParam::StopBits, settings));
+        let flow_controls = FlowControl::ALL
+            .into_iter()
+            .filter(|&flow_control| flow_control != self.flow_control)
+            .map(|flow_control| Self {
+                flow_control,
+                ..self.clone()
+            })
+            .map(|settings| (LinkParam::FlowControl, settings));
+        baud_rates
+            .chain(parities)
+            .chain(stop_bits)
+            .chain(flow_controls)
+            .collect()
+    }
+
+    pub fn ui(&mut self, ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
+        use eframe::egui::{DragValue, Grid};
+
+        Grid::new("dyno_serial_settings_grid")
+            .num_columns(2)
+            .spacing([40.0, 4.0])
+            .show(ui, |ui| {
+                row_label_value!(
+                    ui => ui.combobox_from_iter(
+                        tr("serial-baud-rate"),
+                        &mut self.baud_rate,
+                        Self::BAUD_RATES.into_iter()
+                    ),
+                    "serial-baud-rate"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui => ui.combobox_from_iter(
+                        tr("serial-parity"),
+                        &mut self.parity,
+                        Parity::ALL.into_iter()
+                    ),
+                    "serial-parity"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui => ui.combobox_from_iter(
+                        tr("serial-stop-bits"),
+                        &mut self.stop_bits,
+                        StopBits::ALL.into_iter()
+                    ),
+                    "serial-stop-bits"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui => ui.combobox_from_iter(
+                        tr("serial-flow-control"),
+                        &mut self.flow_control,
+                        FlowControl::ALL.into_iter()
+                    ),
+                    "serial-flow-control"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    DragValue::new(&mut self.read_timeout_ms)
+                        .range(50..=5000)
+                        .suffix(" ms"),
+                    "serial-read-timeout"
+                );
+            })
+            .response
+    }
+}
+
+/// result of testing the serial link, see [`diagnose`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum LinkReport {
+    Connected {
+        version: FirmwareVersion,
+        status: BootStatus,
+    },
+    Mismatch {
+        param: LinkParam,
+        configured: String,
+        device: String,
+    },
+    NoResponse,
+}
+
+impl std::fmt::Display for LinkReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::Connected { version, status } => tr!(
+                "link-connected",
+                version = version.to_string(),
+                status = status.to_string(),
+            ),
+            Self::Mismatch {
+                param,
+                configured,
+                device,
+            } => tr!(
+                "link-mismatch",
+                param = param.to_string(),
+                configured = configured.as_str(),
+                device = device.as_str(),
+            ),
+            Self::NoResponse => tr("link-no-response"),
+        };
+        f.write_str(&text)
+    }
+}
+
+impl LinkReport {
+    #[inline]
+    pub const fn is_connected(&self) -> bool {
+        matches!(self, Self::Connected { .. })
+    }
+}
+
+/// handshake with `settings`, then with one parameter changed at a time to find which parameter
+/// is not match with the device, `attempt` return `None` if the device is not answering.
+/// only error from the configured `settings` is returned, e.g. the port is busy
+pub async fn diagnose<F, Fut>(settings: &SerialSettings, mut attempt: F) -> DynoResult<LinkReport>
+where
+    F: FnMut(SerialSettings) -> Fut,
+    Fut: Future<Output = DynoResult<Option<(FirmwareVersion, BootStatus)>>>,
+{
+    if let Some((version, status)) = attempt(settings.clone()).await? {
+        return Ok(LinkReport::Connected { version, status });
+    }
+    for (param, candidate) in settings.neighbours() {
+        match attempt(candidate.clone()).await {
+            Ok(Some(_)) => {
+                return Ok(LinkReport::Mismatch {
+                    param,
+                    configured: settings.value_of(param),
+                    device: candidate.value_of(param),
+                })
+            }
+            Ok(None) => {}
+            Err(err) => dyno_core::log::debug!("serial link with {param} changed: {err}"),
+        }
+    }
+    Ok(LinkReport::NoResponse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        dyno_core::tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("failed to build tokio runtime")
+            .block_on(fut)
+    }
+
+    /// device that only answer with the firmware default
+    async fn device(settings: SerialSettings) -> DynoResult<Option<(FirmwareVersion, BootStatus)>> {
+        let answer = SerialSettings {
+            read_timeout_ms: 500,
+            ..settings
+        } == SerialSettings::default();
+        Ok(answer.then_some((FirmwareVersion::new(0, 2, 0), BootStatus::Confirmed)))
+    }
+
+    #[test]
+    fn serial_settings_diagnose() {
+        let report = block_on(diagnose(&SerialSettings::default(), device)).unwrap();
+        assert!(report.is_connected(), "{report}");
+
+        let settings = SerialSettings {
+            baud_rate: 115_200,
+            ..Default::default()
+        };
+        let report = block_on(diagnose(&settings, device)).unwrap();
+        assert_eq!(
+            report,
+            LinkReport::Mismatch {
+                param: LinkParam::BaudRate,
+                configured: "115200".to_owned(),
+                device: "512000".to_owned(),
+            }
+        );
+
+        let settings = SerialSettings {
+            parity: Parity::None,
+            flow_control: FlowControl::None,
+            ..Default::default()
+        };
+        let report = block_on(diagnose(&settings, device)).unwrap();
+        assert_eq!(report, LinkReport::NoResponse);
+
+        let report = block_on(diagnose(&SerialSettings::default(), |_| async {
+            Err(dyno_core::DynoErr::service_error("port busy"))
+        }));
+        assert!(report.is_err());
+    }
+}
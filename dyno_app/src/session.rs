@@ -0,0 +1,327 @@
+//! session of one customer visit, an ordered list of runs (pulls) with the changes in between,
+//! saved and opened as one `.dynosession` file
+
+use std::path::Path;
+
+use dyno_core::{
+    chrono::{DateTime, NaiveDateTime, Utc},
+    serde,
+    types::DynotestData,
+    CompresedSaver, CsvSaver, DynoConfig, DynoResult, DynoSaver, ExcelSaver,
+};
+
+use crate::state::DynoFileType;
+
+pub const SESSION_EXTENSION: &str = "dynosession";
+
+/// customer and vehicle of the session
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, crate = "serde")]
+pub struct SessionInfo {
+    pub customer: String,
+    pub phone: String,
+    pub vehicle: String,
+    pub plate_number: String,
+    /// uuid of the linked vehicle in the registry
+    pub vehicle_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default, crate = "serde")]
+pub struct DynoRun {
+    pub name: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    /// recorded data, `data.config` is the snapshot of the config when the run is started
+    pub data: DynotestData,
+    pub start: Option<NaiveDateTime>,
+    pub stop: Option<NaiveDateTime>,
+    /// recording time in ms, without the time when the service is stopped
+    pub elapsed_ms: u64,
+}
+
+impl DynoRun {
+    pub fn new(name: impl Into<String>, config: DynoConfig) -> Self {
+        Self {
+            name: name.into(),
+            data: DynotestData::new(config),
+            ..Default::default()
+        }
+    }
+
+    /// run from the data opened from file
+    pub fn with_data(name: impl Into<String>, data: DynotestData) -> Self {
+        let elapsed_ms = data.total_time().num_milliseconds().unsigned_abs();
+        Self {
+            name: name.into(),
+            data,
+            elapsed_ms,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn config(&self) -> &DynoConfig {
+        &self.data.config
+    }
+
+    /// tags as comma separated text
+    pub fn tags_text(&self) -> String {
+        self.tags.join(", ")
+    }
+
+    pub fn set_tags_text(&mut self, text: &str) {
+        self.tags = text
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(ToOwned::to_owned)
+            .collect();
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct DynoSession {
+    pub info: SessionInfo,
+    pub notes: String,
+    pub created: DateTime<Utc>,
+    /// never empty
+    runs: Vec<DynoRun>,
+    active: usize,
+}
+
+impl Default for DynoSession {
+    fn default() -> Self {
+        Self::new(DynoConfig::default())
+    }
+}
+
+impl DynoSession {
+    /// new session with one empty run
+    pub fn new(config: DynoConfig) -> Self {
+        Self {
+            info: SessionInfo::default(),
+            notes: String::new(),
+            created: Utc::now(),
+            runs: vec![DynoRun::new("Run 1", config)],
+            active: 0,
+        }
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> DynoResult<Self> {
+        let mut session = Self::decompress_from_path(path)?;
+        if session.runs.is_empty() {
+            session
+                .runs
+                .push(DynoRun::new("Run 1", DynoConfig::default()));
+        }
+        session.active = session.active.min(session.runs.len() - 1);
+        Ok(session)
+    }
+
+    #[inline]
+    pub fn save(&self, path: impl AsRef<Path>) -> DynoResult<()> {
+        self.compress_to_path(path)
+    }
+
+    #[inline(always)]
+    pub fn runs(&self) -> &[DynoRun] {
+        &self.runs
+    }
+
+    #[inline(always)]
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    #[inline]
+    pub fn active(&self) -> &DynoRun {
+        &self.runs[self.active]
+    }
+
+    #[inline]
+    pub fn active_mut(&mut self) -> &mut DynoRun {
+        &mut self.runs[self.active]
+    }
+
+    #[inline]
+    pub fn run_mut(&mut self, idx: usize) -> Option<&mut DynoRun> {
+        self.runs.get_mut(idx)
+    }
+
+    /// data of the selected run
+    #[inline]
+    pub fn buffer(&self) -> &DynotestData {
+        &self.active().data
+    }
+
+    #[inline]
+    pub fn buffer_mut(&mut self) -> &mut DynotestData {
+        &mut self.active_mut().data
+    }
+
+    /// true if there is no recorded data in every run
+    pub fn is_empty(&self) -> bool {
+        self.runs.iter().all(|run| run.data.is_empty())
+    }
+
+    /// select the run to display and record, return false if `idx` is out of bound
+    pub fn select(&mut self, idx: usize) -> bool {
+        let valid = idx < self.runs.len();
+        if valid {
+            self.active = idx;
+        }
+        valid
+    }
+
+    /// add new empty run after the last run and select it
+    pub fn add_run(&mut self, config: DynoConfig) -> usize {
+        let name = self.next_run_name();
+        self.push_run(DynoRun::new(name, config))
+    }
+
+    /// add `run` after the last run and select it
+    pub fn push_run(&mut self, run: DynoRun) -> usize {
+        self.runs.push(run);
+        self.active = self.runs.len() - 1;
+        self.active
+    }
+
+    pub fn rename(&mut self, idx: usize, name: impl Into<String>) {
+        if let Some(run) = self.runs.get_mut(idx) {
+            run.name = name.into();
+        }
+    }
+
+    /// remove the run at `idx`, the last remaining run is replaced with an empty run
+    pub fn remove(&mut self, idx: usize) -> Option<DynoRun> {
+        if idx >= self.runs.len() {
+            return None;
+        }
+        let removed = self.runs.remove(idx);
+        if self.runs.is_empty() {
+            self.runs
+                .push(DynoRun::new("Run 1", removed.config().clone()));
+        }
+        if self.active > idx || self.active >= self.runs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        Some(removed)
+    }
+
+    /// `Run N` with the smallest `N` that is not used
+    pub fn next_run_name(&self) -> String {
+        (1..)
+            .map(|n| format!("Run {n}"))
+            .find(|name| self.runs.iter().all(|run| &run.name != name))
+            .unwrap_or_default()
+    }
+}
+
+/// runs in the file, every run of the session or the only run of the other format that named
+/// after the file
+pub fn open_runs(path: &Path) -> DynoResult<Vec<DynoRun>> {
+    let data = match DynoFileType::from_path(path)? {
+        DynoFileType::Session => return Ok(DynoSession::open(path)?.runs),
+        DynoFileType::Dyno => DynotestData::open_dyno_from_path(path)?,
+        DynoFileType::Csv => DynotestData::open_csv_from_path(path)?,
+        DynoFileType::Excel => DynotestData::open_excel_from_path(path)?,
+    };
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(vec![DynoRun::with_data(name, data)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::types::Data;
+
+    #[test]
+    fn session_runs() {
+        let mut session = DynoSession::new(DynoConfig::default());
+        assert!(session.is_empty());
+        assert_eq!(session.active().name, "Run 1");
+
+        session.buffer_mut().push(Data::default());
+        assert_eq!(session.add_run(DynoConfig::default()), 1);
+        assert_eq!(session.active().name, "Run 2");
+        session.rename(0, "Baseline");
+        assert_eq!(session.next_run_name(), "Run 1");
+        session.active_mut().set_tags_text(" stock, , intake ");
+        assert_eq!(session.active().tags, ["stock", "intake"]);
+
+        assert!(!session.select(2));
+        assert!(session.select(0));
+        assert_eq!(session.buffer().len(), 1);
+        assert!(!session.is_empty());
+
+        // removing before the selected run keep the same run selected
+        session.add_run(DynoConfig::default());
+        assert_eq!(
+            session.remove(0).map(|run| run.name).as_deref(),
+            Some("Baseline")
+        );
+        assert_eq!(session.active().name, "Run 1");
+        session.remove(1);
+        session.remove(0);
+        assert_eq!(session.runs().len(), 1);
+        assert_eq!(session.active_index(), 0);
+        assert!(session.is_empty());
+    }
+
+    #[test]
+    fn session_save_open() {
+        let mut session = DynoSession::new(DynoConfig::default());
+        session.info.customer = "customer".to_owned();
+        session.active_mut().notes = "stock".to_owned();
+        session.buffer_mut().push(Data::default());
+        session.add_run(DynoConfig::default());
+
+        let path = std::env::temp_dir().join(format!(
+            "dynotest_session_{}.{SESSION_EXTENSION}",
+            std::process::id()
+        ));
+        session.save(&path).unwrap();
+        let opened = DynoSession::open(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let opened = opened.unwrap();
+        assert_eq!(opened.info, session.info);
+        assert_eq!(opened.active_index(), 1);
+        assert_eq!(opened.runs().len(), 2);
+        assert_eq!(opened.runs()[0].notes, "stock");
+        assert_eq!(opened.runs()[0].data, session.runs()[0].data);
+    }
+
+    #[test]
+    fn session_open_runs() {
+        let dir = std::env::temp_dir().join(format!("dynotest_open_runs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut session = DynoSession::new(DynoConfig::default());
+        session.buffer_mut().push(Data::default());
+        session.add_run(DynoConfig::default());
+        session.active_mut().notes = "intake".to_owned();
+        let session_path = dir.join(format!("visit.{SESSION_EXTENSION}"));
+        session.save(&session_path).unwrap();
+        let dyno_path = dir.join("before.dyno");
+        session.runs()[0]
+            .data
+            .save_dyno_from_path(&dyno_path)
+            .unwrap();
+
+        let runs = open_runs(&session_path).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].notes, "intake");
+        let runs = open_runs(&dyno_path).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].name, "before");
+        assert_eq!(runs[0].data, session.runs()[0].data);
+        assert!(open_runs(&dir.join("before.txt")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,9 +1,14 @@
-use dyno_core::AsStr;
-use dyno_core::{paste::paste, serde};
+use std::path::Path;
+
+use crate::AsStr;
+use dyno_core::{serde, DynoErr, DynoResult};
+use paste::paste;
 
 #[derive(Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 #[serde(crate = "serde")]
 pub enum DynoFileType {
+    /// every run in the session, see [`crate::session::DynoSession`]
+    Session,
     Dyno,
     Csv,
     Excel,
@@ -19,6 +24,7 @@ impl AsStr<'static> for DynoFileType {
     #[inline]
     fn as_str(&self) -> &'static str {
         match self {
+            DynoFileType::Session => "Session",
             DynoFileType::Dyno => "Binaries",
             DynoFileType::Csv => "Csv",
             DynoFileType::Excel => "Excel",
@@ -35,12 +41,30 @@ impl DynoFileType {
     }
     pub fn from_extension(ext: &str) -> Option<Self> {
         match ext {
+            "dynosession" => Some(Self::Session),
             "dyno" | "dbin" => Some(Self::Dyno),
             "csv" | "dynocsv" => Some(Self::Csv),
             "xlsx" => Some(Self::Excel),
             _ => None,
         }
     }
+    /// the format keep the annotations of the run, csv and excel only keep the data
+    #[inline]
+    pub const fn keeps_annotations(self) -> bool {
+        matches!(self, Self::Session | Self::Dyno)
+    }
+    /// format of the file from its extension, for the file argument and the dropped file
+    pub fn from_path(path: &Path) -> DynoResult<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+            .ok_or_else(|| {
+                DynoErr::validation_error(format!(
+                    "Unknown format of `{}`, use .dyno, .csv, .xlsx or .dynosession",
+                    path.display()
+                ))
+            })
+    }
 }
 
 #[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
@@ -50,10 +74,12 @@ pub enum OperatorData {
     Noop,
     SaveFile(DynoFileType),
     OpenFile(DynoFileType),
+    /// open the dropped files or the file arguments, see [`crate::control::DynoControl::open_paths`]
+    OpenPaths,
 }
 impl OperatorData {
     pub fn save_default() -> Self {
-        Self::SaveFile(DynoFileType::Dyno)
+        Self::SaveFile(DynoFileType::Session)
     }
 
     #[inline]
@@ -1,112 +1,100 @@
 use crate::widgets::{DisplayStyle, DisplayStylePreset};
-use dyno_core::{derive_more::Display, paste::paste};
+use derive_more::Display;
 use eframe::egui::{
     vec2, Align2, FontFamily, FontId, Key, Rect, Response, Sense, Stroke, Ui, Widget,
 };
+use paste::paste;
 
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Display)]
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
 pub enum ButtonKind {
-    #[display(fmt = "\u{2714} Ok")]
     Ok,
-
-    #[display(fmt = "\u{1F6AB} Cancel")]
     Cancel,
-
-    #[display(fmt = "\u{2714} Apply")]
     Apply,
-
-    #[display(fmt = "\u{1F504} Reset")]
     Reset,
-
-    #[display(fmt = "\u{1F5C1} Open")]
     Open,
-
-    #[display(fmt = "\u{1F4BE} Save")]
     Save,
-
-    #[display(fmt = "\u{1F4BE} Save As...")]
     SaveAs,
-
-    #[display(fmt = "\u{1F5D9} Close")]
     Close,
-
-    #[display(fmt = "\u{1F5D1} Delete")]
     Delete,
-
-    #[display(fmt = "\u{25B6} Play")]
     Play,
-
-    #[display(fmt = "\u{23F8} Pause")]
     Pause,
-
-    #[display(fmt = "\u{23F9} Stop")]
     Stop,
-
-    #[display(fmt = "\u{23FA} Record")]
     Record,
-
-    #[display(fmt = "\u{23ED} Next")]
     Next,
-
-    #[display(fmt = "\u{23EE} Previous")]
     Previous,
-
-    #[display(fmt = "\u{26F6} Full Screen")]
     FullScreen,
-
-    #[display(fmt = "\u{1F3B2} Random")]
     Random,
-
-    #[display(fmt = "\u{270F} Edit")]
     Edit,
-
-    #[display(fmt = "\u{2605} Favorite")]
     Favorite,
-
-    #[display(fmt = "\u{2606} Unfavorite")]
     Unfavorite,
-
-    #[display(fmt = "\u{1F507} Mute")]
     Mute,
-
-    #[display(fmt = "\u{1F50A} Unmute")]
     Unmute,
-
-    #[display(fmt = "\u{1F512} Lock")]
     Lock,
-
-    #[display(fmt = "\u{1F513} Unlock")]
     Unlock,
-
-    #[display(fmt = "\u{1F503} Refresh")]
     Refresh,
-
-    #[display(fmt = "\u{1F5CB} New")]
     New,
-
-    #[display(fmt = "\u{1F5D0} Copy")]
     Copy,
-
-    #[display(fmt = "\u{1F4CB} Paste")]
     Paste,
-
-    #[display(fmt = "\u{2702} Cut")]
     Cut,
-
-    #[display(fmt = "\u{2718} No")]
     No,
-
-    #[display(fmt = "")]
+    #[default]
     Any,
 }
 
 impl ButtonKind {
+    /// icon and the message key of the label
+    const fn icon_key(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Ok => ("\u{2714}", "button-ok"),
+            Self::Cancel => ("\u{1F6AB}", "button-cancel"),
+            Self::Apply => ("\u{2714}", "button-apply"),
+            Self::Reset => ("\u{1F504}", "button-reset"),
+            Self::Open => ("\u{1F5C1}", "button-open"),
+            Self::Save => ("\u{1F4BE}", "button-save"),
+            Self::SaveAs => ("\u{1F4BE}", "button-save-as"),
+            Self::Close => ("\u{1F5D9}", "button-close"),
+            Self::Delete => ("\u{1F5D1}", "button-delete"),
+            Self::Play => ("\u{25B6}", "button-play"),
+            Self::Pause => ("\u{23F8}", "button-pause"),
+            Self::Stop => ("\u{23F9}", "button-stop"),
+            Self::Record => ("\u{23FA}", "button-record"),
+            Self::Next => ("\u{23ED}", "button-next"),
+            Self::Previous => ("\u{23EE}", "button-previous"),
+            Self::FullScreen => ("\u{26F6}", "button-full-screen"),
+            Self::Random => ("\u{1F3B2}", "button-random"),
+            Self::Edit => ("\u{270F}", "button-edit"),
+            Self::Favorite => ("\u{2605}", "button-favorite"),
+            Self::Unfavorite => ("\u{2606}", "button-unfavorite"),
+            Self::Mute => ("\u{1F507}", "button-mute"),
+            Self::Unmute => ("\u{1F50A}", "button-unmute"),
+            Self::Lock => ("\u{1F512}", "button-lock"),
+            Self::Unlock => ("\u{1F513}", "button-unlock"),
+            Self::Refresh => ("\u{1F503}", "button-refresh"),
+            Self::New => ("\u{1F5CB}", "button-new"),
+            Self::Copy => ("\u{1F5D0}", "button-copy"),
+            Self::Paste => ("\u{1F4CB}", "button-paste"),
+            Self::Cut => ("\u{2702}", "button-cut"),
+            Self::No => ("\u{2718}", "button-no"),
+            Self::Any => ("", ""),
+        }
+    }
+
     #[inline(always)]
     pub fn name_button_popup(self, desc: &'_ str) -> String {
         format!("clickk '{self}' to {desc}")
     }
 }
+
+impl std::fmt::Display for ButtonKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.icon_key() {
+            (_, "") => Ok(()),
+            (icon, key) => write!(f, "{icon} {}", dyno_core::i18n::tr(key)),
+        }
+    }
+}
+
 impl From<u8> for ButtonKind {
     fn from(b: u8) -> Self {
         match b {
@@ -145,12 +133,6 @@ impl From<u8> for ButtonKind {
     }
 }
 
-impl Default for ButtonKind {
-    fn default() -> Self {
-        Self::Any
-    }
-}
-
 macro_rules!  standart_button {
     ($traits:ident {$( $name: ident),*}) => {
         pub trait $traits {
@@ -238,10 +220,10 @@ fn set(get_set_value: &mut GetSetValue<'_>, value: bool) {
 #[non_exhaustive]
 #[derive(Clone, Copy, Display, Eq, PartialEq)]
 pub enum ButtonIndicatorBehavior {
-    #[display(fmt = "Toggle")]
+    #[display("Toggle")]
     Toggle,
 
-    #[display(fmt = "Hold")]
+    #[display("Hold")]
     Hold,
 }
 
@@ -361,7 +343,7 @@ impl<'a> Widget for ButtonIndicator<'a> {
                 }
             }
             ButtonIndicatorBehavior::Hold => {
-                if response.drag_started() || response.drag_released() {
+                if response.drag_started() || response.drag_stopped() {
                     set(&mut self.get_set_value, response.dragged());
                     response.mark_changed();
                 }
@@ -1,4 +1,4 @@
-use dyno_core::derive_more::Display;
+use derive_more::Display;
 
 use eframe::egui::{pos2, Color32, Pos2, Rgba, Stroke, Vec2};
 // ----------------------------------------------------------------------------
@@ -151,37 +151,37 @@ impl Default for DisplayStyle {
 #[serde(crate = "dyno_core::serde")]
 pub enum DisplayStylePreset {
     #[default]
-    #[display(fmt = "Default")]
+    #[display("Default")]
     Default,
 
-    #[display(fmt = "Calculator")]
+    #[display("Calculator")]
     Calculator,
 
-    #[display(fmt = "Nintendo Game Boy")]
+    #[display("Nintendo Game Boy")]
     NintendoGameBoy,
 
-    #[display(fmt = "Knight Rider")]
+    #[display("Knight Rider")]
     KnightRider,
 
-    #[display(fmt = "Blue Negative")]
+    #[display("Blue Negative")]
     BlueNegative,
 
-    #[display(fmt = "Amber")]
+    #[display("Amber")]
     Amber,
 
-    #[display(fmt = "Light Blue")]
+    #[display("Light Blue")]
     LightBlue,
 
-    #[display(fmt = "DeLorean Red")]
+    #[display("DeLorean Red")]
     DeLoreanRed,
 
-    #[display(fmt = "DeLorean Green")]
+    #[display("DeLorean Green")]
     DeLoreanGreen,
 
-    #[display(fmt = "DeLorean Amber")]
+    #[display("DeLorean Amber")]
     DeLoreanAmber,
 
-    #[display(fmt = "Yamaha MU2000")]
+    #[display("Yamaha MU2000")]
     YamahaMU2000,
 }
 impl DisplayStylePreset {
@@ -370,7 +370,7 @@ pub trait DynoWidgets: super::button::ButtonExt + Sized {
         values: &'s [V],
     ) -> eframe::egui::Response
     where
-        V: Clone + Copy + PartialEq + dyno_core::AsStr<'s>;
+        V: Clone + Copy + PartialEq + crate::AsStr<'s>;
 
     fn selectable_label_from_slice<W>(
         &mut self,
@@ -567,7 +567,7 @@ impl DynoWidgets for eframe::egui::Ui {
         values: &'s [V],
     ) -> eframe::egui::Response
     where
-        V: Clone + PartialEq + dyno_core::AsStr<'s>,
+        V: Clone + PartialEq + crate::AsStr<'s>,
     {
         let combobox = eframe::egui::ComboBox::from_label(label)
             .selected_text(current_value.as_str())
@@ -610,7 +610,12 @@ impl DynoWidgets for eframe::egui::Ui {
             response.mark_changed(); // report back that the value changed
         }
         response.widget_info(|| {
-            eframe::egui::WidgetInfo::selected(eframe::egui::WidgetType::Checkbox, *on, "")
+            eframe::egui::WidgetInfo::selected(
+                eframe::egui::WidgetType::Checkbox,
+                self.is_enabled(),
+                *on,
+                "",
+            )
         });
         if self.is_rect_visible(rect) {
             let how_on = self.ctx().animate_bool(response.id, *on);
@@ -636,7 +641,12 @@ impl DynoWidgets for eframe::egui::Ui {
             response.mark_changed();
         }
         response.widget_info(|| {
-            eframe::egui::WidgetInfo::selected(eframe::egui::WidgetType::Checkbox, *on, "")
+            eframe::egui::WidgetInfo::selected(
+                eframe::egui::WidgetType::Checkbox,
+                self.is_enabled(),
+                *on,
+                "",
+            )
         });
 
         if self.is_rect_visible(rect) {
@@ -687,16 +697,17 @@ impl PainterHelper for eframe::egui::Painter {
         wrap_width: Option<f32>,
     ) {
         let fontid = eframe::egui::FontId::monospace(text_size);
+        let color = color.into();
         let galley = if let Some(width) = wrap_width {
-            self.layout(text.to_string(), fontid, color.into(), width)
+            self.layout(text.to_string(), fontid, color, width)
         } else {
-            self.layout_no_wrap(text.to_string(), fontid, color.into())
+            self.layout_no_wrap(text.to_string(), fontid, color)
         };
         let rect = eframe::egui::Align2::CENTER_CENTER
             .anchor_rect(eframe::egui::Rect::from_min_size(pos.into(), galley.size()));
 
         if !galley.is_empty() {
-            self.add(eframe::egui::Shape::galley(rect.min, galley));
+            self.add(eframe::egui::Shape::galley(rect.min, galley, color));
         }
     }
     #[inline(always)]
@@ -709,16 +720,17 @@ impl PainterHelper for eframe::egui::Painter {
         wrap_width: Option<f32>,
     ) {
         let fontid = eframe::egui::FontId::proportional(text_size);
+        let color = color.into();
         let galley = if let Some(width) = wrap_width {
-            self.layout(text.to_string(), fontid, color.into(), width)
+            self.layout(text.to_string(), fontid, color, width)
         } else {
-            self.layout_no_wrap(text.to_string(), fontid, color.into())
+            self.layout_no_wrap(text.to_string(), fontid, color)
         };
         let rect = eframe::egui::Align2::CENTER_CENTER
             .anchor_rect(eframe::egui::Rect::from_min_size(pos.into(), galley.size()));
 
         if !galley.is_empty() {
-            self.add(eframe::egui::Shape::galley(rect.min, galley));
+            self.add(eframe::egui::Shape::galley(rect.min, galley, color));
         }
     }
 }
@@ -732,21 +744,21 @@ pub trait ImplResponseEgui {
 impl ImplResponseEgui for eframe::egui::Response {
     #[inline(always)]
     fn clicked_and(&self, callback: impl FnOnce()) {
-        if self.clicked[eframe::egui::PointerButton::Primary as usize] {
+        if self.clicked() {
             callback()
         }
     }
 
     #[inline(always)]
     fn clicked_set<T: Sized>(&self, set: &mut T, val: T) {
-        if self.clicked[eframe::egui::PointerButton::Primary as usize] {
+        if self.clicked() {
             *set = val
         }
     }
 
     #[inline(always)]
     fn clicked_swap<N: std::ops::Not<Output = N> + Copy>(&self, set: &mut N) {
-        if self.clicked[eframe::egui::PointerButton::Primary as usize] {
+        if self.clicked() {
             *set = std::ops::Not::not(*set);
         }
     }
@@ -0,0 +1,606 @@
+//! dashboard of the left and right panel, rows of tiles that show the last data of a channel as
+//! gauge, seven segment display, bar or big number. saved as named layouts in
+//! `ApplicationConfig::dashboard`
+
+use dyno_core::{i18n::tr, serde, types::Data, DynoConfig};
+use eframe::egui::{
+    Color32, ComboBox, DragValue, Frame, Grid, ProgressBar, RichText, Stroke, TextEdit, Ui,
+};
+
+use super::{
+    gauges::Gauge, plot_channels::PlotChannel, segment_display::SegmentedDisplay,
+    DisplayStylePreset,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum TileSource {
+    Channel(PlotChannel),
+    /// distance traveled of the run
+    Odo,
+    /// recording time of the run
+    Time,
+}
+
+impl TileSource {
+    /// every source that can be showed with `config`
+    pub fn all(config: &DynoConfig) -> impl Iterator<Item = Self> + '_ {
+        PlotChannel::all(config)
+            .map(Self::Channel)
+            .chain([Self::Odo, Self::Time])
+    }
+
+    pub fn name(self, config: &DynoConfig) -> String {
+        match self {
+            Self::Channel(ch) => ch.name(config),
+            Self::Odo => tr("dash-odo"),
+            Self::Time => tr("dash-time"),
+        }
+    }
+
+    pub const fn unit(self) -> &'static str {
+        match self {
+            Self::Channel(ch) => ch.unit(),
+            Self::Odo => "km",
+            Self::Time => "s",
+        }
+    }
+
+    pub fn value(self, values: &DashValues) -> f64 {
+        match self {
+            Self::Channel(ch) => ch.value(values.data),
+            Self::Odo => values.odo_km,
+            Self::Time => values.elapsed_ms as f64 / 1000.,
+        }
+    }
+
+    /// range of the gauge picture, or the usual range of the channel
+    const fn default_range(self) -> (f64, f64) {
+        match self {
+            Self::Channel(PlotChannel::Speed) => (0., 240.),
+            Self::Channel(PlotChannel::RpmEngine | PlotChannel::RpmRoda) => (0., 15_000.),
+            Self::Channel(PlotChannel::Torque | PlotChannel::Power) => (0., 100.),
+            Self::Channel(PlotChannel::Temp(_)) => (0., 150.),
+            Self::Odo => (0., 100.),
+            Self::Time => (0., 3_600.),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum TileWidget {
+    #[default]
+    Gauge,
+    Segment,
+    Bar,
+    Numeric,
+}
+
+impl TileWidget {
+    pub const ALL: [Self; 4] = [Self::Gauge, Self::Segment, Self::Bar, Self::Numeric];
+
+    pub fn label(self) -> String {
+        tr(match self {
+            Self::Gauge => "dash-gauge",
+            Self::Segment => "dash-segment",
+            Self::Bar => "dash-bar",
+            Self::Numeric => "dash-numeric",
+        })
+    }
+}
+
+/// state of the value against the thresholds of the tile
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TileLevel {
+    Normal,
+    Warning,
+    Redline,
+}
+
+impl TileLevel {
+    fn color(self, ui: &Ui) -> Color32 {
+        match self {
+            Self::Normal => ui.visuals().widgets.noninteractive.bg_stroke.color,
+            Self::Warning => Color32::from_rgb(255, 165, 0),
+            Self::Redline => Color32::RED,
+        }
+    }
+}
+
+/// last data of the selected run, showed in the dashboard
+pub struct DashValues<'a> {
+    pub data: &'a Data,
+    pub config: &'a DynoConfig,
+    pub odo_km: f64,
+    pub elapsed_ms: u64,
+    /// channels of the raised alarms, the tiles is flashing like the redline
+    pub alarms: &'a [PlotChannel],
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct DashTile {
+    pub source: TileSource,
+    pub widget: TileWidget,
+    /// range of the bar, and the gauge without picture scale (thermocouple, odo and time)
+    pub min: f64,
+    pub max: f64,
+    /// value is multiplied before showed as text, e.g. `0.001` for rpm x 1000
+    pub scale: f64,
+    pub decimals: usize,
+    pub warning: Option<f64>,
+    /// shift light, the tile is blinking above this value
+    pub redline: Option<f64>,
+}
+
+impl DashTile {
+    pub fn new(source: TileSource, widget: TileWidget) -> Self {
+        let (min, max) = source.default_range();
+        Self {
+            source,
+            widget,
+            min,
+            max,
+            scale: 1.,
+            decimals: match source {
+                TileSource::Time => 0,
+                _ => 2,
+            },
+            warning: None,
+            redline: None,
+        }
+    }
+
+    #[inline]
+    fn scale(self, scale: f64, decimals: usize) -> Self {
+        Self {
+            scale,
+            decimals,
+            ..self
+        }
+    }
+
+    #[inline]
+    fn thresholds(self, warning: f64, redline: f64) -> Self {
+        Self {
+            warning: Some(warning),
+            redline: Some(redline),
+            ..self
+        }
+    }
+
+    pub fn level(&self, value: f64) -> TileLevel {
+        match (self.warning, self.redline) {
+            (_, Some(redline)) if value >= redline => TileLevel::Redline,
+            (Some(warning), _) if value >= warning => TileLevel::Warning,
+            _ => TileLevel::Normal,
+        }
+    }
+
+    pub fn title(&self, config: &DynoConfig) -> String {
+        let name = self.source.name(config);
+        match self.source {
+            TileSource::Time => format!("{name} (HH:MM:SS)"),
+            _ if self.scale != 1. && self.scale > 0. => {
+                format!("{name} ({} x{})", self.source.unit(), 1. / self.scale)
+            }
+            _ => format!("{name} ({})", self.source.unit()),
+        }
+    }
+
+    pub fn text(&self, value: f64) -> String {
+        match self.source {
+            TileSource::Time => {
+                let seconds = value as u64;
+                format!(
+                    "{:02}:{:02}:{:02}",
+                    (seconds / 3600) % 24,
+                    (seconds / 60) % 60,
+                    seconds % 60
+                )
+            }
+            _ => format!("{:.*}", self.decimals, value * self.scale),
+        }
+    }
+
+    fn gauge(&self, value: f64) -> Gauge {
+        match self.source {
+            TileSource::Channel(PlotChannel::Speed) => Gauge::speed(value),
+            TileSource::Channel(PlotChannel::RpmEngine) => Gauge::rpm_engine(value),
+            TileSource::Channel(PlotChannel::RpmRoda) => Gauge::rpm_roda(value),
+            TileSource::Channel(PlotChannel::Torque) => Gauge::torque(value),
+            TileSource::Channel(PlotChannel::Power) => Gauge::horsepower(value),
+            _ => Gauge::temperature(value).range(self.min as f32, self.max as f32),
+        }
+    }
+
+    pub fn ui(&self, ui: &mut Ui, values: &DashValues, style: DisplayStylePreset) {
+        let value = self.source.value(values);
+        let level = match self.source {
+            TileSource::Channel(ch) if values.alarms.contains(&ch) => TileLevel::Redline,
+            _ => self.level(value),
+        };
+        let color = level.color(ui);
+        let fill = match level {
+            TileLevel::Normal => Color32::TRANSPARENT,
+            TileLevel::Warning => color.gamma_multiply(0.15),
+            TileLevel::Redline => {
+                ui.ctx().request_repaint();
+                match (ui.input(|i| i.time) * 4.) as i64 % 2 {
+                    0 => color.gamma_multiply(0.4),
+                    _ => Color32::TRANSPARENT,
+                }
+            }
+        };
+        Frame::group(ui.style())
+            .fill(fill)
+            .stroke(Stroke::new(1.5_f32, color))
+            .show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.strong(self.title(values.config));
+                    let width = ui.available_width();
+                    let text = self.text(value);
+                    match self.widget {
+                        TileWidget::Gauge => ui.add(self.gauge(value).diameter(width)),
+                        TileWidget::Segment => {
+                            // 7 chars fit the width, like the old fixed display
+                            let text = format!("{text:>7}");
+                            let digit_height = width / (text.len() as f32 * 0.75);
+                            ui.add(
+                                SegmentedDisplay::dyno_seven_segment(text)
+                                    .style_preset(style)
+                                    .digit_height(digit_height),
+                            )
+                        }
+                        TileWidget::Bar => {
+                            let fraction = ((value - self.min) / (self.max - self.min)) as f32;
+                            let bar = ProgressBar::new(fraction.clamp(0., 1.)).text(text);
+                            match level {
+                                TileLevel::Normal => ui.add(bar),
+                                _ => ui.add(bar.fill(color)),
+                            }
+                        }
+                        TileWidget::Numeric => {
+                            let text = RichText::new(text)
+                                .monospace()
+                                .size((width * 0.2).clamp(14., 96.));
+                            match level {
+                                TileLevel::Normal => ui.label(text.strong()),
+                                _ => ui.label(text.strong().color(color)),
+                            }
+                        }
+                    };
+                });
+            });
+    }
+
+    fn edit_ui(&mut self, ui: &mut Ui, config: &DynoConfig, id: (usize, usize, usize)) {
+        Grid::new(("dyno_dash_tile_grid", id))
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr("dash-source"));
+                let source = self.source;
+                ComboBox::from_id_source(("dyno_dash_tile_source", id))
+                    .selected_text(self.source.name(config))
+                    .show_ui(ui, |ui| {
+                        for source in TileSource::all(config) {
+                            ui.selectable_value(&mut self.source, source, source.name(config));
+                        }
+                    });
+                if self.source != source {
+                    *self = Self::new(self.source, self.widget);
+                }
+                ui.end_row();
+
+                ui.label(tr("dash-widget"));
+                ComboBox::from_id_source(("dyno_dash_tile_widget", id))
+                    .selected_text(self.widget.label())
+                    .show_ui(ui, |ui| {
+                        for widget in TileWidget::ALL {
+                            ui.selectable_value(&mut self.widget, widget, widget.label());
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(tr("dash-range"));
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.min).speed(1.));
+                    ui.label("-");
+                    ui.add(DragValue::new(&mut self.max).speed(1.));
+                });
+                ui.end_row();
+
+                ui.label(tr("dash-scale"));
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.scale).speed(0.001));
+                    ui.label(tr("dash-decimals"));
+                    ui.add(DragValue::new(&mut self.decimals).range(0..=4));
+                });
+                ui.end_row();
+
+                threshold_ui(ui, &tr("dash-warning"), &mut self.warning, self.max * 0.8);
+                ui.end_row();
+                threshold_ui(ui, &tr("dash-redline"), &mut self.redline, self.max * 0.9);
+                ui.end_row();
+            });
+    }
+}
+
+fn threshold_ui(ui: &mut Ui, label: &str, value: &mut Option<f64>, default: f64) {
+    let mut enabled = value.is_some();
+    if ui.checkbox(&mut enabled, label).changed() {
+        *value = enabled.then_some(default);
+    }
+    match value {
+        Some(value) => ui.add(DragValue::new(value).speed(1.)),
+        None => ui.label("-"),
+    };
+}
+
+/// rows of tiles, every row is divided equally to the tiles in it
+pub type TileRows = Vec<Vec<DashTile>>;
+
+/// show the `rows` with the width of `ui`
+pub fn tiles_ui(
+    ui: &mut Ui,
+    rows: &[Vec<DashTile>],
+    values: &DashValues,
+    style: DisplayStylePreset,
+) {
+    for row in rows.iter().filter(|row| !row.is_empty()) {
+        ui.columns(row.len(), |uis| {
+            for (tile, ui) in row.iter().zip(uis) {
+                tile.ui(ui, values, style);
+            }
+        });
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct DashLayout {
+    pub name: String,
+    pub left: TileRows,
+    pub right: TileRows,
+}
+
+impl DashLayout {
+    /// gauges on the left, speed, rpm, odo and time on the right
+    pub fn standard() -> Self {
+        use PlotChannel::*;
+        use TileSource::*;
+        use TileWidget::*;
+
+        Self {
+            name: "Standard".to_owned(),
+            left: vec![
+                vec![
+                    DashTile::new(Channel(Speed), Gauge),
+                    DashTile::new(Channel(RpmEngine), Gauge),
+                ],
+                vec![
+                    DashTile::new(Channel(Power), Gauge),
+                    DashTile::new(Channel(RpmRoda), Gauge),
+                    DashTile::new(Channel(Torque), Gauge),
+                ],
+            ],
+            right: vec![vec![
+                DashTile::new(Channel(Speed), Segment),
+                DashTile::new(Channel(RpmEngine), Segment).scale(0.001, 2),
+                DashTile::new(Odo, Segment),
+                DashTile::new(Time, Segment),
+            ]],
+        }
+    }
+
+    /// big rpm and power readout with shift light, visible from the bike seat
+    pub fn seat_view() -> Self {
+        use PlotChannel::*;
+        use TileSource::*;
+        use TileWidget::*;
+
+        Self {
+            name: "Seat View".to_owned(),
+            left: vec![
+                vec![DashTile::new(Channel(RpmEngine), Gauge)],
+                vec![
+                    DashTile::new(Channel(Torque), Numeric),
+                    DashTile::new(Channel(Speed), Numeric),
+                ],
+            ],
+            right: vec![
+                vec![
+                    DashTile::new(Channel(RpmEngine), Segment)
+                        .scale(1., 0)
+                        .thresholds(9_000., 10_500.),
+                    DashTile::new(Channel(Power), Segment).scale(1., 1),
+                ],
+                vec![DashTile::new(Channel(RpmEngine), Bar).thresholds(9_000., 10_500.)],
+            ],
+        }
+    }
+
+    /// editor of one side of the layout
+    fn rows_ui(rows: &mut TileRows, ui: &mut Ui, config: &DynoConfig, side: usize) {
+        let mut remove_row = None;
+        for (row_idx, row) in rows.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.strong(dyno_core::tr!("dash-row", number = row_idx + 1));
+                if ui.small_button(format!("➕ {}", tr("dash-tile"))).clicked() {
+                    let tile = row.last().cloned().unwrap_or_else(|| {
+                        DashTile::new(TileSource::Channel(PlotChannel::Speed), TileWidget::Gauge)
+                    });
+                    row.push(tile);
+                }
+                if ui
+                    .small_button(format!("🗑 {}", tr("dash-row-remove")))
+                    .clicked()
+                {
+                    remove_row = Some(row_idx);
+                }
+            });
+            let mut remove_tile = None;
+            ui.horizontal_wrapped(|ui| {
+                for (tile_idx, tile) in row.iter_mut().enumerate() {
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(tile.title(config));
+                                if ui.small_button("✖").clicked() {
+                                    remove_tile = Some(tile_idx);
+                                }
+                            });
+                            tile.edit_ui(ui, config, (side, row_idx, tile_idx));
+                        });
+                    });
+                }
+            });
+            if let Some(idx) = remove_tile {
+                row.remove(idx);
+            }
+            ui.separator();
+        }
+        if let Some(idx) = remove_row {
+            rows.remove(idx);
+        }
+        if ui.button(format!("➕ {}", tr("dash-add-row"))).clicked() {
+            rows.push(Vec::new());
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, crate = "serde")]
+pub struct DashboardSettings {
+    pub active: usize,
+    /// never empty
+    pub layouts: Vec<DashLayout>,
+}
+
+impl Default for DashboardSettings {
+    fn default() -> Self {
+        Self {
+            active: 0,
+            layouts: vec![DashLayout::standard(), DashLayout::seat_view()],
+        }
+    }
+}
+
+impl DashboardSettings {
+    /// selected layout, the standard layout if there is no layout
+    pub fn layout(&self) -> std::borrow::Cow<'_, DashLayout> {
+        match self.layouts.get(self.active) {
+            Some(layout) => std::borrow::Cow::Borrowed(layout),
+            None => std::borrow::Cow::Owned(DashLayout::standard()),
+        }
+    }
+
+    /// select the layout by name, return `false` if there is no layout named `name`
+    pub fn select(&mut self, name: &str) -> bool {
+        match self.layouts.iter().position(|layout| layout.name == name) {
+            Some(idx) => {
+                self.active = idx;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// name that is not used by the other layouts
+    fn new_name(&self, base: &str) -> String {
+        (1..)
+            .map(|n| match n {
+                1 => base.to_owned(),
+                n => format!("{base} {n}"),
+            })
+            .find(|name| self.layouts.iter().all(|layout| &layout.name != name))
+            .unwrap_or_default()
+    }
+
+    pub fn select_ui(&mut self, ui: &mut Ui) {
+        for (idx, layout) in self.layouts.iter().enumerate() {
+            if ui.radio(idx == self.active, &layout.name).clicked() {
+                self.active = idx;
+                ui.close_menu();
+            }
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, config: &DynoConfig) {
+        if self.layouts.is_empty() {
+            *self = Self::default();
+        }
+        self.active = self.active.min(self.layouts.len() - 1);
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source("dyno_dash_layout")
+                .selected_text(self.layouts[self.active].name.as_str())
+                .show_ui(ui, |ui| {
+                    for (idx, layout) in self.layouts.iter().enumerate() {
+                        ui.selectable_value(&mut self.active, idx, layout.name.as_str());
+                    }
+                });
+            ui.add(TextEdit::singleline(&mut self.layouts[self.active].name).desired_width(140.));
+            if ui.button(tr("action-duplicate")).clicked() {
+                let mut layout = self.layouts[self.active].clone();
+                layout.name = self.new_name(&layout.name);
+                self.layouts.push(layout);
+                self.active = self.layouts.len() - 1;
+            }
+            if ui
+                .add_enabled(
+                    self.layouts.len() > 1,
+                    eframe::egui::Button::new(tr("action-delete")),
+                )
+                .clicked()
+            {
+                self.layouts.remove(self.active);
+                self.active = self.active.saturating_sub(1);
+            }
+            if ui.button(tr("action-reset")).clicked() {
+                *self = Self::default();
+            }
+        });
+        ui.separator();
+        let layout = &mut self.layouts[self.active];
+        ui.collapsing(tr("dash-left-panel"), |ui| {
+            DashLayout::rows_ui(&mut layout.left, ui, config, 0)
+        });
+        ui.collapsing(tr("dash-right-panel"), |ui| {
+            DashLayout::rows_ui(&mut layout.right, ui, config, 1)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dashboard_tiles() {
+        let tile = DashTile::new(TileSource::Channel(PlotChannel::RpmEngine), TileWidget::Bar)
+            .thresholds(9_000., 10_500.);
+        assert_eq!(tile.level(8_999.), TileLevel::Normal);
+        assert_eq!(tile.level(9_000.), TileLevel::Warning);
+        assert_eq!(tile.level(11_000.), TileLevel::Redline);
+
+        let config = DynoConfig::default();
+        let tile = tile.scale(0.001, 2);
+        assert_eq!(tile.title(&config), "RPM (engine) (rpm x1000)");
+        assert_eq!(tile.text(7_250.), "7.25");
+        let time = DashTile::new(TileSource::Time, TileWidget::Segment);
+        assert_eq!(time.text(3_725.4), "01:02:05");
+
+        let mut settings = DashboardSettings::default();
+        assert!(settings.select("Seat View"));
+        assert!(!settings.select("Unknown"));
+        assert_eq!(settings.layout().name, "Seat View");
+        assert_eq!(settings.new_name("Standard"), "Standard 2");
+
+        let toml = toml::to_string(&settings).unwrap();
+        assert_eq!(
+            toml::from_str::<DashboardSettings>(&toml).unwrap(),
+            settings
+        );
+    }
+}
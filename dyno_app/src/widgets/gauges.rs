@@ -1,4 +1,3 @@
-use dyno_core::Numeric;
 use eframe::egui::*;
 use eframe::emath::Rot2;
 
@@ -6,6 +5,8 @@ use eframe::emath::Rot2;
 pub struct Gauge {
     value: f32,
     diameter: Option<f32>,
+    /// replace the min and max of the preset
+    range: Option<(f32, f32)>,
 
     types: GaugeTypes,
     animated: bool,
@@ -16,38 +17,43 @@ impl Gauge {
             value,
             types: preset,
             diameter: None,
+            range: None,
             animated: false,
         }
     }
 
-    pub fn speed(value: impl Numeric) -> Self {
-        Self::new(GaugeTypes::SpeedGauge, value.to_f32()).animated(true)
+    pub fn speed(value: f64) -> Self {
+        Self::new(GaugeTypes::SpeedGauge, value as f32).animated(true)
     }
 
-    pub fn rpm_roda(value: impl Numeric) -> Self {
+    pub fn rpm_roda(value: f64) -> Self {
         // map value from 1000 to 1 ( value *  1000 )
-        Self::new(GaugeTypes::RpmRodaGauge, value.to_f32() * 0.001).animated(true)
+        Self::new(GaugeTypes::RpmRodaGauge, value as f32 * 0.001).animated(true)
     }
-    pub fn rpm_engine(value: impl Numeric) -> Self {
-        Self::new(GaugeTypes::RpmEngineGauge, value.to_f32() * 0.001).animated(true)
+    pub fn rpm_engine(value: f64) -> Self {
+        Self::new(GaugeTypes::RpmEngineGauge, value as f32 * 0.001).animated(true)
     }
 
-    pub fn torque(value: impl Numeric) -> Self {
-        Self::new(GaugeTypes::TorqueGauge, value.to_f32()).animated(true)
+    pub fn torque(value: f64) -> Self {
+        Self::new(GaugeTypes::TorqueGauge, value as f32).animated(true)
     }
 
-    pub fn horsepower(value: impl Numeric) -> Self {
-        Self::new(GaugeTypes::HorsepowerGauge, value.to_f32()).animated(true)
+    pub fn horsepower(value: f64) -> Self {
+        Self::new(GaugeTypes::HorsepowerGauge, value as f32).animated(true)
     }
 
-    pub fn temperature(value: impl Numeric) -> Self {
-        Self::new(GaugeTypes::Temperature, value.to_f32()).animated(true)
+    pub fn temperature(value: f64) -> Self {
+        Self::new(GaugeTypes::Temperature, value as f32).animated(true)
     }
 
     pub fn diameter(mut self, diameter: f32) -> Self {
         self.diameter = Some(diameter);
         self
     }
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
     pub fn animated(mut self, animated: bool) -> Self {
         self.animated = animated;
         self
@@ -59,6 +65,7 @@ impl Widget for Gauge {
         let Self {
             value,
             diameter,
+            range,
             types,
             animated: _,
         } = self;
@@ -81,6 +88,7 @@ impl Widget for Gauge {
                 max_degree,
                 foreground_color: _,
             } = types.presets(ui.visuals());
+            let (min, max) = range.unwrap_or((min, max));
 
             let value_degree = ui.ctx().animate_value_with_time(
                 response.id,
@@ -107,10 +115,10 @@ struct GaugeNeedle {
 }
 impl GaugeNeedle {
     // IS MAGIC, dont touch it!
-    const ROT: Rot2 = Rot2 {
-        s: 0.125_333_23_f32,
-        c: 0.992_114_7_f32,
-    };
+    #[inline]
+    fn rot() -> Rot2 {
+        Rot2::from_angle(std::f32::consts::PI / 25.)
+    }
 
     fn new(value: f32, center: Pos2, radius: f32, color: Color32) -> Self {
         let radius = radius * 0.8;
@@ -135,10 +143,10 @@ impl GaugeNeedle {
         let tip = center + vec;
         let points = vec![
             self.center + self.vec,
-            tip - (length * 0.3) * (Self::ROT * dir),
-            self.center - length * (Self::ROT * dir),
-            self.center - length * (Self::ROT.inverse() * dir),
-            tip - (length * 0.3) * (Self::ROT.inverse() * dir),
+            tip - (length * 0.3) * (Self::rot() * dir),
+            self.center - length * (Self::rot() * dir),
+            self.center - length * (Self::rot().inverse() * dir),
+            tip - (length * 0.3) * (Self::rot().inverse() * dir),
         ];
 
         painter.add(Shape::convex_polygon(points, color, Stroke::NONE));
@@ -112,7 +112,7 @@ impl Img {
         ui: &mut eframe::egui::Ui,
         desired_size: eframe::egui::Vec2,
     ) -> eframe::egui::Response {
-        ui.image(self.texture_id(ui.ctx()), desired_size)
+        ui.image((self.texture_id(ui.ctx()), desired_size))
     }
 
     #[allow(unused)]
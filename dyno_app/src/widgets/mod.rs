@@ -8,41 +8,46 @@ pub mod toast;
 pub mod segment_display;
 
 mod common;
+mod dashboard;
 mod gauges;
 mod opener;
+mod plot_channels;
 mod realtime_plot;
 
+pub use dashboard::{
+    tiles_ui, DashLayout, DashTile, DashValues, DashboardSettings, TileLevel, TileRows, TileSource,
+    TileWidget,
+};
 pub use gauges::{Gauge, GaugePreset, GaugeTypes};
 pub use opener::{DynoFileManager, Filters};
+pub use plot_channels::{
+    ChannelSettings, LineKind, PlotChannel, PlotSettings, PlotSlot, PlotXAxis, YAxis,
+};
 pub use realtime_plot::RealtimePlot;
 
 pub use common::*;
 
+/// label of the grid row, `key` is the message in the locales, the `hint` attribute shown on hover
+pub fn row_label(ui: &mut eframe::egui::Ui, key: &str) -> eframe::egui::Response {
+    let label = dyno_core::i18n::tr(key);
+    ui.link(label.as_str()).on_hover_ui(|hover_ui| {
+        hover_ui.label(dyno_core::tr!("value-for", name = label.as_str()));
+        hover_ui.monospace(dyno_core::i18n::tr_attr(key, "hint"));
+    })
+}
+
 #[macro_export]
 macro_rules! row_label_value {
-    ($ui:ident, $v:ident, $name:literal, $desc:literal $(,)?) => {
-        $ui.link($name).on_hover_ui(|hover_ui| {
-            hover_ui.label(format!(
-                "Value for `{}` type of `{}`",
-                $name,
-                $v.name_type()
-            ));
-            hover_ui.monospace($desc);
-        });
-        $ui.label($v.to_string());
+    ($ui:ident, $v:ident, $key:literal $(,)?) => {
+        $crate::widgets::row_label($ui, $key);
+        $ui.label($v.as_str());
     };
-    ($ui:ident, $v:expr, $name:literal, $desc:literal $(,)?) => {
-        $ui.link($name).on_hover_ui(|hover_ui| {
-            hover_ui.label(concat!("Value for ", $name));
-            hover_ui.monospace($desc);
-        });
+    ($ui:ident, $v:expr, $key:literal $(,)?) => {
+        $crate::widgets::row_label($ui, $key);
         $ui.add($v)
     };
-    ($ui:ident => $v:expr, $name:literal, $desc:literal $(,)?) => {
-        $ui.link($name).on_hover_ui(|hover_ui| {
-            hover_ui.label(concat!("Value for ", $name));
-            hover_ui.monospace($desc);
-        });
+    ($ui:ident => $v:expr, $key:literal $(,)?) => {
+        $crate::widgets::row_label($ui, $key);
         $v
     };
 }
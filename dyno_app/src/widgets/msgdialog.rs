@@ -1,5 +1,5 @@
 use crate::DynoResult;
-use rfd::{MessageButtons, MessageDialog, MessageLevel};
+use rfd::{MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
 
 pub enum Level {
     Info,
@@ -63,12 +63,13 @@ where
     S: AsRef<str>,
     D: AsRef<str>,
 {
-    MessageDialog::new()
+    let result = MessageDialog::new()
         .set_title(title.as_ref())
         .set_description(desc.as_ref())
         .set_level(level.into())
         .set_buttons(buttons.into())
-        .show()
+        .show();
+    matches!(result, MessageDialogResult::Ok | MessageDialogResult::Yes)
 }
 
 #[macro_export]
@@ -1,6 +1,6 @@
 #![allow(unused)]
 
-use dyno_core::paste::paste;
+use paste::paste;
 use std::path::{Path, PathBuf};
 
 pub type Filters = &'static [(&'static str, &'static [&'static str])];
@@ -54,7 +54,7 @@ impl DynoFileManager {
     ) -> Option<Vec<PathBuf>> {
         let mut file = rfd::FileDialog::new().set_directory(dir).set_title(title);
         for (name, ext) in filters {
-            file = file.add_filter(name, ext);
+            file = file.add_filter(*name, ext);
         }
         file.pick_files()
     }
@@ -69,7 +69,7 @@ impl DynoFileManager {
             .set_directory(dir)
             .set_title(title);
         for (name, ext) in filters {
-            file = file.add_filter(name, ext);
+            file = file.add_filter(*name, ext);
         }
         file.pick_files()
     }
@@ -78,7 +78,7 @@ impl DynoFileManager {
     pub fn pick_file<P: AsRef<Path>>(title: &'_ str, dir: P, filters: Filters) -> Option<PathBuf> {
         let mut file = rfd::FileDialog::new().set_directory(dir).set_title(title);
         for (name, ext) in filters {
-            file = file.add_filter(name, ext);
+            file = file.add_filter(*name, ext);
         }
         file.pick_file()
     }
@@ -93,7 +93,7 @@ impl DynoFileManager {
             .set_directory(dir)
             .set_title(title);
         for (name, ext) in filters {
-            file = file.add_filter(name, ext);
+            file = file.add_filter(*name, ext);
         }
         file.pick_file()
     }
@@ -110,7 +110,7 @@ impl DynoFileManager {
             .set_directory(dir.as_ref())
             .set_file_name(file.as_ref());
         for (name, ext) in filters {
-            file = file.add_filter(name, ext);
+            file = file.add_filter(*name, ext);
         }
         file.save_file()
     }
@@ -127,7 +127,7 @@ impl DynoFileManager {
             .set_directory(dir.as_ref())
             .set_file_name(file.as_ref());
         for (name, ext) in filters {
-            file = file.add_filter(name, ext);
+            file = file.add_filter(*name, ext);
         }
         file.save_file()
     }
@@ -192,7 +192,10 @@ impl_file_picker!(
         ("Csv File",            &["csv", "dcsv"]),
         ("Excel File",          &["xlsx", "xls"]),
     ],
+    session     -> [("Dyno Session File", &["dynosession"])],
     binaries    -> [("Dyno Binaries File", &["dyno", "dbin"]), ("Binaries File", &["bin"])],
     csv         -> [("Csv File",      &["csv", "dcsv"])],
-    excel       -> [("Excel File",    &["xlsx", "xls"])]
+    excel       -> [("Excel File",    &["xlsx", "xls"])],
+    capture     -> [("Dyno Serial Capture", &["dynocap"])],
+    report      -> [("Dyno Sheet", &["svg"])]
 );
@@ -0,0 +1,405 @@
+//! which `Data` channel is drawn on which plot and Y axis of [`super::RealtimePlot`], with the
+//! colour and line style of every channel, persisted in `ApplicationConfig::plot`
+
+use std::collections::HashSet;
+
+use dyno_core::{
+    i18n::tr,
+    serde,
+    types::{Data, MAX_TEMP_CHANNELS},
+    uom::si::{
+        angular_velocity::revolution_per_minute, power::horsepower_metric,
+        temperature_interval::degree_celsius, torque::newton_meter, velocity::kilometer_per_hour,
+    },
+    DynoConfig,
+};
+use eframe::egui::{Color32, ComboBox, DragValue, Grid, Ui};
+use egui_plot as plot;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum PlotChannel {
+    Speed,
+    RpmEngine,
+    RpmRoda,
+    Torque,
+    Power,
+    /// thermocouple channel, named by `DynoConfig::temp_channels`
+    Temp(usize),
+}
+
+impl PlotChannel {
+    /// every channel that can be drawn with `config`
+    pub fn all(config: &DynoConfig) -> impl Iterator<Item = Self> + '_ {
+        [
+            Self::Speed,
+            Self::RpmEngine,
+            Self::RpmRoda,
+            Self::Torque,
+            Self::Power,
+        ]
+        .into_iter()
+        .chain((0..MAX_TEMP_CHANNELS).map(Self::Temp))
+        .filter(|ch| ch.is_available(config))
+    }
+
+    pub const fn unit(self) -> &'static str {
+        match self {
+            Self::Speed => "km/h",
+            Self::RpmEngine | Self::RpmRoda => "rpm",
+            Self::Torque => "Nm",
+            Self::Power => "HP",
+            Self::Temp(_) => "°C",
+        }
+    }
+
+    pub fn name(self, config: &DynoConfig) -> String {
+        match self {
+            Self::Speed => tr("channel-speed"),
+            Self::RpmEngine => tr("channel-rpm-engine"),
+            Self::RpmRoda => tr("channel-rpm-roda"),
+            Self::Torque => tr("channel-torque"),
+            Self::Power => tr("channel-power"),
+            Self::Temp(idx) => config.temp_channel_name(idx).into_owned(),
+        }
+    }
+
+    pub fn value(self, data: &Data) -> f64 {
+        match self {
+            Self::Speed => data.speed.get::<kilometer_per_hour>(),
+            Self::RpmEngine => data.rpm_engine.get::<revolution_per_minute>(),
+            Self::RpmRoda => data.rpm_roda.get::<revolution_per_minute>(),
+            Self::Torque => data.torque.get::<newton_meter>(),
+            Self::Power => data.horsepower.get::<horsepower_metric>(),
+            Self::Temp(idx) => data
+                .temps
+                .get(idx)
+                .map_or(0., |temp| temp.get::<degree_celsius>()),
+        }
+    }
+
+    /// the sensor of the channel report a fault in `data`, only the thermocouple can
+    #[inline]
+    pub const fn is_fault(self, data: &Data) -> bool {
+        match self {
+            Self::Temp(idx) => data.is_temp_fault(idx),
+            _ => false,
+        }
+    }
+
+    /// thermocouple channel that is not configured is not drawn, the first is always drawn
+    pub fn is_available(self, config: &DynoConfig) -> bool {
+        match self {
+            Self::Temp(idx) => idx < config.temp_channels.len().clamp(1, MAX_TEMP_CHANNELS),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum PlotSlot {
+    #[default]
+    First,
+    Second,
+}
+
+impl PlotSlot {
+    pub const ALL: [Self; 2] = [Self::First, Self::Second];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Self::First => "plot-panel-first",
+            Self::Second => "plot-panel-second",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum YAxis {
+    #[default]
+    Left,
+    Right,
+}
+
+impl YAxis {
+    pub const ALL: [Self; 2] = [Self::Left, Self::Right];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Self::Left => "plot-axis-left",
+            Self::Right => "plot-axis-right",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum PlotXAxis {
+    #[default]
+    Time,
+    RpmEngine,
+    Speed,
+}
+
+impl PlotXAxis {
+    pub const ALL: [Self; 3] = [Self::Time, Self::RpmEngine, Self::Speed];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Self::Time => "plot-x-time",
+            Self::RpmEngine => "plot-x-rpm-engine",
+            Self::Speed => "plot-x-speed",
+        })
+    }
+
+    /// x value of `data`, time is in ms since the `first_timestamp`
+    pub fn value(self, data: &Data, first_timestamp: i64) -> f64 {
+        match self {
+            Self::Time => (data.timestamp - first_timestamp) as f64,
+            Self::RpmEngine => PlotChannel::RpmEngine.value(data),
+            Self::Speed => PlotChannel::Speed.value(data),
+        }
+    }
+
+    /// title of the x axis with the unit
+    pub fn label(self) -> String {
+        match self {
+            Self::Time => self.name(),
+            Self::RpmEngine => format!("{} (rpm)", self.name()),
+            Self::Speed => format!("{} (km/h)", self.name()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub enum LineKind {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+impl LineKind {
+    pub const ALL: [Self; 3] = [Self::Solid, Self::Dashed, Self::Dotted];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Self::Solid => "plot-line-solid",
+            Self::Dashed => "plot-line-dashed",
+            Self::Dotted => "plot-line-dotted",
+        })
+    }
+}
+
+impl From<LineKind> for plot::LineStyle {
+    fn from(value: LineKind) -> Self {
+        match value {
+            LineKind::Solid => Self::Solid,
+            LineKind::Dashed => Self::dashed_dense(),
+            LineKind::Dotted => Self::dotted_dense(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+pub struct ChannelSettings {
+    pub channel: PlotChannel,
+    pub plot: PlotSlot,
+    pub axis: YAxis,
+    pub visible: bool,
+    pub color: Color32,
+    pub line: LineKind,
+    pub width: f32,
+}
+
+impl ChannelSettings {
+    fn new(channel: PlotChannel, plot: PlotSlot, axis: YAxis, color: Color32) -> Self {
+        Self {
+            channel,
+            plot,
+            axis,
+            visible: true,
+            color,
+            line: LineKind::Solid,
+            width: 3.0,
+        }
+    }
+
+    #[inline]
+    fn line(self, line: LineKind) -> Self {
+        Self { line, ..self }
+    }
+
+    /// name in the legend, also the key of the hidden items of the plot
+    pub fn legend_name(&self, config: &DynoConfig) -> String {
+        let axis = match self.axis {
+            YAxis::Left => "",
+            YAxis::Right => " ▸",
+        };
+        format!(
+            "{} ({}){axis}",
+            self.channel.name(config),
+            self.channel.unit()
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, crate = "serde")]
+pub struct PlotSettings {
+    pub x_axis: PlotXAxis,
+    pub channels: Vec<ChannelSettings>,
+}
+
+const TEMP_COLORS: [Color32; 4] = [
+    Color32::from_rgb(255, 140, 0),
+    Color32::from_rgb(255, 215, 0),
+    Color32::from_rgb(186, 85, 211),
+    Color32::from_rgb(0, 206, 209),
+];
+
+impl Default for PlotSettings {
+    fn default() -> Self {
+        use PlotChannel::*;
+        use PlotSlot::*;
+        use YAxis::*;
+
+        let mut channels = vec![
+            ChannelSettings::new(Speed, First, Left, Color32::from_rgb(0, 204, 255)),
+            ChannelSettings::new(RpmEngine, First, Right, Color32::LIGHT_GREEN)
+                .line(LineKind::Dashed),
+            ChannelSettings::new(RpmRoda, First, Right, Color32::from_rgb(46, 139, 87))
+                .line(LineKind::Dashed),
+            ChannelSettings::new(Torque, Second, Left, Color32::from_rgb(255, 99, 71)),
+            ChannelSettings::new(Power, Second, Right, Color32::from_rgb(65, 105, 225)),
+        ];
+        channels.extend((0..MAX_TEMP_CHANNELS).map(|idx| {
+            ChannelSettings::new(Temp(idx), First, Left, TEMP_COLORS[idx % TEMP_COLORS.len()])
+                .line(LineKind::Dotted)
+        }));
+        Self {
+            x_axis: PlotXAxis::default(),
+            channels,
+        }
+    }
+}
+
+impl PlotSettings {
+    /// channels of the `slot` that can be drawn with `config`, including the hidden one
+    pub fn channels_of<'a>(
+        &'a self,
+        slot: PlotSlot,
+        config: &'a DynoConfig,
+    ) -> impl Iterator<Item = &'a ChannelSettings> {
+        self.channels
+            .iter()
+            .filter(move |ch| ch.plot == slot && ch.channel.is_available(config))
+    }
+
+    /// legend names of the hidden channels of the `slot`
+    pub fn hidden_names(&self, slot: PlotSlot, config: &DynoConfig) -> Vec<String> {
+        self.channels_of(slot, config)
+            .filter(|ch| !ch.visible)
+            .map(|ch| ch.legend_name(config))
+            .collect()
+    }
+
+    /// sync the visibility from the legend of the `slot` plot
+    pub fn set_hidden(&mut self, slot: PlotSlot, config: &DynoConfig, hidden: &HashSet<String>) {
+        for ch in self
+            .channels
+            .iter_mut()
+            .filter(|ch| ch.plot == slot && ch.channel.is_available(config))
+        {
+            ch.visible = !hidden.contains(&ch.legend_name(config));
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, config: &DynoConfig) {
+        ui.horizontal(|ui| {
+            ui.label(tr("plot-x-axis"));
+            ComboBox::from_id_source("dyno_plot_x_axis")
+                .selected_text(self.x_axis.name())
+                .show_ui(ui, |ui| {
+                    for x_axis in PlotXAxis::ALL {
+                        ui.selectable_value(&mut self.x_axis, x_axis, x_axis.name());
+                    }
+                });
+            if ui.button(tr("action-reset")).clicked() {
+                *self = Self::default();
+            }
+        });
+        ui.separator();
+        Grid::new("dyno_plot_channels_grid")
+            .num_columns(6)
+            .striped(true)
+            .show(ui, |ui| {
+                for (idx, ch) in self
+                    .channels
+                    .iter_mut()
+                    .enumerate()
+                    .filter(|(_, ch)| ch.channel.is_available(config))
+                {
+                    ui.checkbox(
+                        &mut ch.visible,
+                        format!("{} ({})", ch.channel.name(config), ch.channel.unit()),
+                    );
+                    ComboBox::from_id_source(("dyno_plot_channel_slot", idx))
+                        .width(90.)
+                        .selected_text(ch.plot.name())
+                        .show_ui(ui, |ui| {
+                            for slot in PlotSlot::ALL {
+                                ui.selectable_value(&mut ch.plot, slot, slot.name());
+                            }
+                        });
+                    ComboBox::from_id_source(("dyno_plot_channel_axis", idx))
+                        .width(60.)
+                        .selected_text(ch.axis.name())
+                        .show_ui(ui, |ui| {
+                            for axis in YAxis::ALL {
+                                ui.selectable_value(&mut ch.axis, axis, axis.name());
+                            }
+                        });
+                    ui.color_edit_button_srgba(&mut ch.color);
+                    ComboBox::from_id_source(("dyno_plot_channel_line", idx))
+                        .width(60.)
+                        .selected_text(ch.line.name())
+                        .show_ui(ui, |ui| {
+                            for line in LineKind::ALL {
+                                ui.selectable_value(&mut ch.line, line, line.name());
+                            }
+                        });
+                    ui.add(DragValue::new(&mut ch.width).range(0.5..=8.0).speed(0.1));
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plot_settings_hidden() {
+        let config = DynoConfig::default();
+        let mut settings = PlotSettings::default();
+        // the three thermocouple of the firmware is drawn, the rest is not configured
+        assert_eq!(settings.channels_of(PlotSlot::First, &config).count(), 6);
+        assert_eq!(settings.channels_of(PlotSlot::Second, &config).count(), 2);
+        assert!(settings.hidden_names(PlotSlot::Second, &config).is_empty());
+
+        let power = settings.channels[4].legend_name(&config);
+        assert_eq!(power, "HorsePower (HP) ▸");
+        settings.set_hidden(PlotSlot::Second, &config, &HashSet::from([power.clone()]));
+        assert_eq!(settings.hidden_names(PlotSlot::Second, &config), [power]);
+        assert!(settings.channels[3].visible);
+
+        let toml = toml::to_string(&settings).unwrap();
+        assert_eq!(toml::from_str::<PlotSettings>(&toml).unwrap(), settings);
+    }
+}
@@ -6,7 +6,7 @@ use std::sync::{
 use super::button::ButtonKind;
 use crate::assets::{ICO_ERROR, ICO_INFO, ICO_WARN};
 use eframe::{
-    egui::{Color32, ColorImage, TextureHandle, Vec2},
+    egui::{Color32, ColorImage, TextureHandle, ViewportBuilder},
     AppCreator,
 };
 
@@ -77,11 +77,15 @@ impl<const BTN_SIZE: usize> PopupWindow<BTN_SIZE> {
         let title = self.title.clone();
 
         {
+            let mut viewport = ViewportBuilder::default()
+                .with_inner_size([300.0, 300.0])
+                .with_resizable(true)
+                .with_always_on_top();
+            if let Some((icon, _)) = option_icon.as_ref() {
+                viewport = viewport.with_icon(icon.clone());
+            }
             let native_options = NativeOptions {
-                initial_window_size: Some(Vec2 { x: 300.0, y: 300.0 }),
-                resizable: true,
-                always_on_top: true,
-                icon_data: option_icon.as_ref().map(|(i, _)| i.clone()),
+                viewport,
                 ..Default::default()
             };
             let img = option_icon.as_ref().map(|(ico, size)| {
@@ -92,7 +96,7 @@ impl<const BTN_SIZE: usize> PopupWindow<BTN_SIZE> {
                     ctx.egui_ctx
                         .load_texture("icon_popup", img, Default::default())
                 }));
-                Box::new(self)
+                Ok(Box::new(self))
             });
             eframe::run_native(&title, native_options, app).ok();
         }
@@ -109,10 +113,10 @@ impl<const BTN_SIZE: usize> PopupWindow<BTN_SIZE> {
 }
 
 impl<const BTN_SIZE: usize> eframe::App for PopupWindow<BTN_SIZE> {
-    fn update(&mut self, ctx: &eframe::egui::Context, frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
         use eframe::egui::*;
         if !self.opened {
-            frame.close()
+            ctx.send_viewport_cmd(ViewportCommand::Close)
         }
         let (header, desc) = match self.level {
             PopupLevel::Info => (
@@ -123,21 +127,21 @@ impl<const BTN_SIZE: usize> eframe::App for PopupWindow<BTN_SIZE> {
                 RichText::new("WARNING!").color(self.level),
                 RichText::new(format!(
                     "Something Went Wrong!\n{}",
-                    &self.description.clone()
+                    self.description.clone()
                 )),
             ),
             PopupLevel::Error => (
                 RichText::new("ERROR!").color(self.level),
                 RichText::new(format!(
                     "Something Went Wrong!\n{}",
-                    &self.description.clone()
+                    self.description.clone()
                 )),
             ),
         };
 
         let horz_top = |hui: &mut Ui| {
             if let Some(texture) = &*self.icon_texture {
-                hui.image(texture.id(), texture.size_vec2());
+                hui.image((texture.id(), texture.size_vec2()));
             }
             hui.heading(header.clone());
             hui.label(desc.clone());
@@ -170,7 +174,7 @@ impl<const BTN_SIZE: usize> eframe::App for PopupWindow<BTN_SIZE> {
                 ButtonKind::Any
             )
         {
-            frame.close()
+            ctx.send_viewport_cmd(ViewportCommand::Close)
         }
     }
 }
@@ -218,6 +222,7 @@ impl PopupWindow<3> {
 }
 
 #[test]
+#[ignore = "open a native window and wait for the user, run it with `--ignored`"]
 fn popup_window() {
     match PopupWindow::info("Hello", "Test Popup Hello") {
         ButtonKind::Ok => eprintln!("Button Ok Clicked"),
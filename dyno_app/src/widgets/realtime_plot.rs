@@ -1,6 +1,49 @@
-use dyno_core::{serde, AsStr, BufferData, PointShowed};
+use dyno_core::{
+    i18n::tr,
+    serde, tr,
+    types::{Data, DynotestData},
+};
 use eframe::egui::*;
-use std::hash::Hash;
+use egui_plot as plot;
+use itertools::Itertools;
+
+use super::plot_channels::{PlotChannel, PlotSettings, PlotSlot, PlotXAxis, YAxis};
+
+/// how many points of the buffer are showed in the plot, counted from the end of the buffer
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(crate = "serde")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PointShowed {
+    #[default]
+    All,
+    Half,
+    Quarter,
+    /// start from the index
+    Num(usize),
+}
+
+impl std::fmt::Display for PointShowed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => f.write_str(&tr("plot-all-points")),
+            Self::Half => f.write_str(&tr("plot-half-points")),
+            Self::Quarter => f.write_str(&tr("plot-quarter-points")),
+            Self::Num(idx) => f.write_str(&tr!("plot-from-point", index = *idx)),
+        }
+    }
+}
+
+impl PointShowed {
+    pub fn slice(self, data: &[Data]) -> &[Data] {
+        let start = match self {
+            Self::All => 0,
+            Self::Half => data.len() / 2,
+            Self::Quarter => data.len() - (data.len() / 4),
+            Self::Num(idx) => idx.min(data.len()),
+        };
+        &data[start..]
+    }
+}
 
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -12,13 +55,13 @@ pub enum PlotPanel {
     First,
     Second,
 }
-impl AsStr<'static> for PlotPanel {
-    fn as_str(&self) -> &'static str {
-        match self {
-            PlotPanel::All => "All Plot",
-            PlotPanel::First => "First Plot",
-            PlotPanel::Second => "Second Plot",
-        }
+impl PlotPanel {
+    fn label(self) -> String {
+        tr(match self {
+            PlotPanel::All => "plot-panel-all",
+            PlotPanel::First => "plot-panel-first",
+            PlotPanel::Second => "plot-panel-second",
+        })
     }
 }
 
@@ -40,6 +83,12 @@ pub struct RealtimePlot {
     allow_scroll: bool,
     allow_boxed_zoom: bool,
     animates: bool,
+
+    /// x value of the cursor A and B, `None` if the cursors is not showed
+    #[serde(skip)]
+    cursors: Option<[f64; 2]>,
+    #[serde(skip)]
+    note: String,
 }
 
 impl Default for RealtimePlot {
@@ -60,6 +109,9 @@ impl Default for RealtimePlot {
             allow_scroll: true,
             allow_boxed_zoom: true,
             animates: false,
+
+            cursors: None,
+            note: String::new(),
         }
     }
 }
@@ -71,134 +123,96 @@ impl RealtimePlot {
 }
 
 impl RealtimePlot {
-    const LEGENDS: plot::Legend = plot::Legend {
-        text_style: TextStyle::Monospace,
-        background_alpha: 0.75,
-        position: plot::Corner::RightTop,
-    };
+    fn legends() -> plot::Legend {
+        plot::Legend::default()
+            .text_style(TextStyle::Monospace)
+            .background_alpha(0.75)
+            .position(plot::Corner::RightTop)
+    }
 
-    pub fn ui(&mut self, ui: &mut Ui, data: &'_ BufferData) -> Response {
+    pub fn ui(
+        &mut self,
+        ui: &mut Ui,
+        data: &'_ mut DynotestData,
+        settings: &mut PlotSettings,
+    ) -> Response {
         if self.animates {
             ui.ctx().request_repaint();
         }
         ui.horizontal(|ui| {
-            ui.collapsing("Instructions", |ui| {
-                ui.label("Pan by dragging, or scroll (+ shift = horizontal).");
-                ui.label(
-                    "Box zooming: Middle (Scroll) click to zoom in and zoom out using a selection.",
-                );
-                ui.label("Zoom with ctrl + scroll.");
-                ui.label("Reset view with double-click.");
-                ui.label("Change behaviour with context menu right click in the plot");
+            ui.collapsing(tr("plot-instructions"), |ui| {
+                for key in [
+                    "plot-help-pan",
+                    "plot-help-box-zoom",
+                    "plot-help-zoom",
+                    "plot-help-reset",
+                    "plot-help-context-menu",
+                    "plot-help-cursor",
+                ] {
+                    ui.label(tr(key));
+                }
             });
             ui.with_layout(Layout::right_to_left(Align::Min), |left_ui| {
-                ComboBox::new("plot_panel_combobox", "Select Plot to Show")
-                    .selected_text(self.panel.as_str())
+                ComboBox::new("plot_panel_combobox", tr("plot-select-panel"))
+                    .selected_text(self.panel.label())
                     .show_ui(left_ui, |ui| {
-                        ui.selectable_value(
-                            &mut self.panel,
-                            PlotPanel::All,
-                            PlotPanel::All.as_str(),
-                        );
-                        ui.selectable_value(
-                            &mut self.panel,
-                            PlotPanel::First,
-                            PlotPanel::First.as_str(),
-                        );
-                        ui.selectable_value(
-                            &mut self.panel,
-                            PlotPanel::Second,
-                            PlotPanel::Second.as_str(),
-                        );
+                        for panel in [PlotPanel::All, PlotPanel::First, PlotPanel::Second] {
+                            ui.selectable_value(&mut self.panel, panel, panel.label());
+                        }
                     });
-                left_ui.menu_button("Plot Config", |cfg_ui| {
+                let mut cursors = self.cursors.is_some();
+                if left_ui
+                    .toggle_value(&mut cursors, tr("plot-cursors"))
+                    .changed()
+                {
+                    self.cursors = cursors
+                        .then(|| Self::initial_cursors(self.showed.slice(data), settings.x_axis));
+                }
+                left_ui.menu_button(tr("plot-channels"), |ch_ui| {
+                    settings.ui(ch_ui, &data.config)
+                });
+                left_ui.menu_button(tr("plot-config"), |cfg_ui| {
                     Grid::new("button_grid_plot").show(cfg_ui, |gridui| {
-                        gridui.checkbox(&mut self.allow_drag, "Drag");
-                        gridui.checkbox(&mut self.allow_zoom, "Zoom");
+                        gridui.checkbox(&mut self.allow_drag, tr("plot-drag"));
+                        gridui.checkbox(&mut self.allow_zoom, tr("plot-zoom"));
                         gridui.end_row();
-                        gridui.checkbox(&mut self.allow_scroll, "Scroll");
-                        gridui.checkbox(&mut self.allow_boxed_zoom, "Boxed Zoom");
+                        gridui.checkbox(&mut self.allow_scroll, tr("plot-scroll"));
+                        gridui.checkbox(&mut self.allow_boxed_zoom, tr("plot-boxed-zoom"));
                         gridui.end_row();
-                        gridui.checkbox(&mut self.animates, "Animate");
+                        gridui.checkbox(&mut self.animates, tr("plot-animate"));
                     });
                 })
             });
         });
-        let showed = self.showed;
 
-        self.first_x = data.time_stamp.first_value();
-        self.last_x = data.time_stamp.last_value();
+        self.first_x = data.first().timestamp;
+        self.last_x = data.last().timestamp;
         ui.horizontal(|ui| {
-            ComboBox::new("point_showed_combobox", "Points to Show")
-                .selected_text(format!("{}", self.showed))
+            ComboBox::new("point_showed_combobox", tr("plot-points-to-show"))
+                .selected_text(self.showed.to_string())
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.showed, PointShowed::All, "All Points");
-                    ui.selectable_value(&mut self.showed, PointShowed::Half, "Half Points");
-                    ui.selectable_value(&mut self.showed, PointShowed::Quarter, "Quarter Points");
+                    for showed in [PointShowed::All, PointShowed::Half, PointShowed::Quarter] {
+                        ui.selectable_value(&mut self.showed, showed, showed.to_string());
+                    }
                     let slider = ui.add(
                         Slider::new(&mut self.first_showed_x, 0..=(self.last_x - self.first_x))
                             .step_by(1000f64)
-                            .text("Show From")
+                            .text(tr("plot-show-from"))
                             .custom_formatter(|x, _| timestamp_diff_fmt(x as _)),
                     );
 
                     if slider.changed() {
                         self.showed = PointShowed::Num(
-                            data.time_stamp
-                                .iter()
-                                .position(|x| *x == self.first_showed_x)
+                            data.iter()
+                                .position(|x| x.timestamp - self.first_x >= self.first_showed_x)
                                 .unwrap_or_default(),
                         )
                     }
                 });
+            ui.separator();
+            self.annotation_ui(ui, data, settings.x_axis);
         });
-
-        let draw_plot_first = |pui: &mut plot::PlotUi| {
-            pui.line(
-                plot::Line::new(data.speed.into_points::<plot::PlotPoints>(showed))
-                    .width(3.0)
-                    .style(plot::LineStyle::Solid)
-                    .name("Speed (km/h)"),
-            );
-            pui.line(
-                plot::Line::new(
-                    data.rpm_engine
-                        .into_points_map::<plot::PlotPoints, _>(showed, |x| x * 0.001),
-                )
-                .width(3.0)
-                .style(plot::LineStyle::dashed_dense())
-                .name("RPM (engine) (rpm x 1000)"),
-            );
-            pui.line(
-                plot::Line::new(data.temp.into_points::<plot::PlotPoints>(showed))
-                    .width(3.0)
-                    .style(plot::LineStyle::dashed_loose())
-                    .name("Temp (°C)"),
-            );
-        };
-        let draw_plot_second = |pui: &mut plot::PlotUi| {
-            pui.line(
-                plot::Line::new(data.torque.into_points::<plot::PlotPoints>(showed))
-                    .width(3.0)
-                    .style(plot::LineStyle::Solid)
-                    .name("Torque (Nm)"),
-            );
-            pui.line(
-                plot::Line::new(data.horsepower.into_points::<plot::PlotPoints>(showed))
-                    .width(3.0)
-                    .style(plot::LineStyle::Solid)
-                    .name("HorsePower (HP)"),
-            );
-            pui.line(
-                plot::Line::new(
-                    data.rpm_roda
-                        .into_points_map::<plot::PlotPoints, _>(showed, |x| x * 0.001),
-                )
-                .width(3.0)
-                .style(plot::LineStyle::dashed_dense())
-                .name("RPM (roda) (rpm x 1000)"),
-            );
-        };
+        self.cursor_ui(ui, data, settings);
 
         ui.vertical_centered(|ui| {
             let width = ui.available_width() - (ui.spacing().item_spacing.x * 2.0);
@@ -206,51 +220,369 @@ impl RealtimePlot {
             match self.panel {
                 PlotPanel::All => {
                     let height = height * 0.5;
-                    self.draw_plot(ui, "dyno_plot_first", height, width, draw_plot_first);
+                    self.draw_plot(ui, PlotSlot::First, vec2(width, height), data, settings);
                     ui.separator();
-                    self.draw_plot(ui, "dyno_plot_second", height, width, draw_plot_second)
+                    self.draw_plot(ui, PlotSlot::Second, vec2(width, height), data, settings)
                 }
                 PlotPanel::First => {
-                    self.draw_plot(ui, "dyno_plot_first", height, width, draw_plot_first)
+                    self.draw_plot(ui, PlotSlot::First, vec2(width, height), data, settings)
                 }
                 PlotPanel::Second => {
-                    self.draw_plot(ui, "dyno_plot_second", height, width, draw_plot_second)
+                    self.draw_plot(ui, PlotSlot::Second, vec2(width, height), data, settings)
                 }
             }
         })
         .response
     }
 
-    #[inline]
-    fn draw_plot<F, R, S>(
+    /// cursor A and B at the third and two third of the showed x range
+    fn initial_cursors(data: &[Data], x_axis: PlotXAxis) -> [f64; 2] {
+        let first = data.first().map_or(0, |d| d.timestamp);
+        let (min, max) = data
+            .iter()
+            .map(|d| x_axis.value(d, first))
+            .fold((f64::MAX, f64::MIN), |(min, max), x| {
+                (min.min(x), max.max(x))
+            });
+        if min > max {
+            return [0., 0.];
+        }
+        let third = (max - min) / 3.;
+        [min + third, max - third]
+    }
+
+    /// value of every visible channel at the cursors, and the delta between them
+    fn cursor_ui(&self, ui: &mut Ui, data: &DynotestData, settings: &PlotSettings) {
+        let Some([a, b]) = self.cursors else {
+            return;
+        };
+        let showed = self.showed.slice(data);
+        let first = data.first().timestamp;
+        let (Some(a), Some(b)) = (
+            sample_index(showed, settings.x_axis, first, a),
+            sample_index(showed, settings.x_axis, first, b),
+        ) else {
+            return;
+        };
+        let config = &data.config;
+        CollapsingHeader::new(tr("plot-cursors"))
+            .default_open(true)
+            .show(ui, |ui| {
+                Grid::new("dyno_plot_cursor_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("plot-channel"));
+                        ui.strong("A");
+                        ui.strong("B");
+                        ui.strong("Δ (B - A)");
+                        ui.end_row();
+                        for ch in settings
+                            .channels
+                            .iter()
+                            .filter(|ch| ch.visible && ch.channel.is_available(config))
+                        {
+                            let value_a = ch.channel.value(&showed[a]);
+                            let value_b = ch.channel.value(&showed[b]);
+                            ui.label(format!(
+                                "{} ({})",
+                                ch.channel.name(config),
+                                ch.channel.unit()
+                            ));
+                            ui.monospace(format!("{value_a:.2}"));
+                            ui.monospace(format!("{value_b:.2}"));
+                            ui.monospace(format!("{:+.2}", value_b - value_a));
+                            ui.end_row();
+                        }
+                    });
+                let delta = CursorDelta::between(showed, a, b);
+                ui.horizontal(|ui| {
+                    ui.label(format!("Δt: {:.3} s", delta.time_ms as f64 / 1000.));
+                    ui.separator();
+                    ui.label(format!("ΔRPM: {:+.0} rpm", delta.rpm));
+                    ui.separator();
+                    ui.label(tr!(
+                        "plot-average-power",
+                        power = format!("{:.2}", delta.avg_power)
+                    ));
+                });
+            });
+    }
+
+    /// add a note at the cursor A, or at the last data when the cursors is not showed
+    fn annotation_ui(&mut self, ui: &mut Ui, data: &mut DynotestData, x_axis: PlotXAxis) {
+        let first = data.first().timestamp;
+        let response = ui.add(
+            TextEdit::singleline(&mut self.note)
+                .hint_text(tr("plot-note"))
+                .desired_width(160.),
+        );
+        let entered = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+        let label = tr(match self.cursors {
+            Some(_) => "plot-annotate-cursor",
+            None => "plot-annotate-last",
+        });
+        if (ui.button(label).clicked() || entered) && !self.note.trim().is_empty() {
+            let timestamp = match self.cursors {
+                Some([a, _]) => {
+                    let showed = self.showed.slice(data);
+                    sample_index(showed, x_axis, first, a).map(|idx| showed[idx].timestamp)
+                }
+                None => (!data.is_empty()).then(|| data.last().timestamp),
+            };
+            if let Some(timestamp) = timestamp {
+                data.annotate(timestamp, self.note.trim());
+                self.note.clear();
+            }
+        }
+
+        let mut removed = None;
+        let annotations = data.annotations();
+        ui.menu_button(tr!("plot-annotations", count = annotations.len()), |ui| {
+            if annotations.is_empty() {
+                ui.label(tr("plot-no-annotation"));
+            }
+            for (idx, annotation) in annotations.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.small_button("🗑").clicked() {
+                        removed = Some(idx);
+                    }
+                    ui.label(format!(
+                        "A{} {} - {}",
+                        idx + 1,
+                        timestamp_diff_fmt(annotation.timestamp - first),
+                        annotation.text
+                    ));
+                });
+            }
+        });
+        if let Some(idx) = removed {
+            data.annotations_mut().remove(idx);
+        }
+    }
+
+    /// draw the channels of `slot`, the channels on the right axis is scaled to the range of
+    /// the left axis, the right axis show the value before scaled
+    fn draw_plot(
         &mut self,
         ui: &mut Ui,
-        name: S,
-        height: f32,
-        width: f32,
-        draw_call: F,
-    ) -> Response
-    where
-        F: FnOnce(&mut plot::PlotUi) -> R,
-        S: Hash,
-    {
-        plot::Plot::new(name)
-            .legend(Self::LEGENDS)
-            .height(height)
-            .width(width)
-            .x_axis_formatter(|x, _| timestamp_diff_fmt(x as _))
+        slot: PlotSlot,
+        size: Vec2,
+        data: &'_ DynotestData,
+        settings: &mut PlotSettings,
+    ) -> Response {
+        let config = &data.config;
+        let showed = self.showed;
+        let x_axis = settings.x_axis;
+        let channels = settings
+            .channels_of(slot, config)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let axis_max = |axis: YAxis| {
+            channels
+                .iter()
+                .filter(|ch| ch.visible && ch.axis == axis)
+                .flat_map(|ch| showed.slice(data).iter().map(|d| ch.channel.value(d).abs()))
+                .fold(0f64, f64::max)
+        };
+        let axis_label = |axis: YAxis| {
+            channels
+                .iter()
+                .filter(|ch| ch.axis == axis)
+                .map(|ch| ch.channel.unit())
+                .unique()
+                .join(", ")
+        };
+        let (left_max, right_max) = (axis_max(YAxis::Left), axis_max(YAxis::Right));
+        let scale = match left_max > 0. && right_max > 0. {
+            true => left_max / right_max,
+            false => 1.,
+        };
+
+        let mut y_axes = vec![plot::AxisHints::new_y().label(axis_label(YAxis::Left))];
+        if channels.iter().any(|ch| ch.axis == YAxis::Right) {
+            y_axes.push(
+                plot::AxisHints::new_y()
+                    .label(axis_label(YAxis::Right))
+                    .placement(plot::HPlacement::Right)
+                    .formatter(move |mark, _, _| format!("{:.1}", mark.value / scale)),
+            );
+        }
+        let x_hints = match x_axis {
+            PlotXAxis::Time => {
+                plot::AxisHints::new_x().formatter(|mark, _, _| timestamp_diff_fmt(mark.value as _))
+            }
+            _ => plot::AxisHints::new_x(),
+        }
+        .label(x_axis.label());
+
+        let mut cursors = self.cursors;
+        let id = Id::new(("dyno_plot", slot as u8));
+        let legend = Self::legends().hidden_items(settings.hidden_names(slot, config));
+        let response = plot::Plot::new(id)
+            .id(id)
+            .legend(legend)
+            .height(size.y)
+            .width(size.x)
+            .custom_x_axes(vec![x_hints])
+            .custom_y_axes(y_axes)
             .coordinates_formatter(
                 plot::Corner::LeftBottom,
                 plot::CoordinatesFormatter::with_decimals(2),
             )
             .link_cursor(self.cursor_group, true, true)
             .link_axis(self.group, true, true)
-            .allow_drag(self.allow_drag)
+            .allow_drag(self.allow_drag && cursors.is_none())
             .allow_scroll(self.allow_scroll)
             .allow_zoom(self.allow_zoom)
             .allow_boxed_zoom(self.allow_boxed_zoom)
-            .show(ui, draw_call)
-            .response
+            .show(ui, |pui| {
+                let first = data.first().timestamp;
+                for ch in &channels {
+                    let factor = match ch.axis {
+                        YAxis::Left => 1.,
+                        YAxis::Right => scale,
+                    };
+                    let points = showed
+                        .slice(data)
+                        .iter()
+                        .map(|d| [x_axis.value(d, first), ch.channel.value(d) * factor])
+                        .collect::<plot::PlotPoints>();
+                    pui.line(
+                        plot::Line::new(points)
+                            .color(ch.color)
+                            .width(ch.width)
+                            .style(ch.line.into())
+                            .name(ch.legend_name(config)),
+                    );
+                }
+
+                // automatic marker at the peak of torque and power
+                for ch in channels.iter().filter(|ch| {
+                    ch.visible && matches!(ch.channel, PlotChannel::Torque | PlotChannel::Power)
+                }) {
+                    let factor = match ch.axis {
+                        YAxis::Left => 1.,
+                        YAxis::Right => scale,
+                    };
+                    let Some(peak) = showed
+                        .slice(data)
+                        .iter()
+                        .max_by(|l, r| ch.channel.value(l).total_cmp(&ch.channel.value(r)))
+                    else {
+                        continue;
+                    };
+                    let point = [x_axis.value(peak, first), ch.channel.value(peak) * factor];
+                    pui.points(
+                        plot::Points::new(point)
+                            .shape(plot::MarkerShape::Diamond)
+                            .radius(6_f32)
+                            .color(ch.color),
+                    );
+                    pui.text(
+                        plot::Text::new(
+                            point.into(),
+                            tr!(
+                                "plot-peak",
+                                value = format!("{:.2}", ch.channel.value(peak)),
+                                unit = ch.channel.unit(),
+                                rpm = format!("{:.0}", PlotChannel::RpmEngine.value(peak)),
+                            ),
+                        )
+                        .anchor(Align2::LEFT_BOTTOM)
+                        .color(ch.color),
+                    );
+                }
+
+                let top = pui.plot_bounds().max()[1];
+                for (idx, annotation) in data.annotations().iter().enumerate() {
+                    let Some(sample) = data.nearest_index(annotation.timestamp) else {
+                        continue;
+                    };
+                    let x = x_axis.value(&data[sample], first);
+                    pui.vline(
+                        plot::VLine::new(x)
+                            .color(Color32::GRAY)
+                            .style(plot::LineStyle::dashed_loose()),
+                    );
+                    pui.text(
+                        plot::Text::new(
+                            [x, top].into(),
+                            format!("A{} {}", idx + 1, annotation.text),
+                        )
+                        .anchor(Align2::LEFT_TOP)
+                        .color(Color32::GRAY),
+                    );
+                }
+
+                if let Some(cursors) = cursors.as_mut() {
+                    let response = pui.response();
+                    if response.dragged() || response.clicked() {
+                        if let Some(pointer) = pui.pointer_coordinate() {
+                            // the nearest cursor follow the pointer
+                            let nearest = usize::from(
+                                (cursors[1] - pointer.x).abs() < (cursors[0] - pointer.x).abs(),
+                            );
+                            cursors[nearest] = pointer.x;
+                        }
+                    }
+                    for (x, name, color) in [
+                        (cursors[0], "A", Color32::YELLOW),
+                        (cursors[1], "B", Color32::LIGHT_BLUE),
+                    ] {
+                        pui.vline(plot::VLine::new(x).color(color).width(1.5_f32));
+                        pui.text(
+                            plot::Text::new([x, top].into(), name)
+                                .anchor(Align2::RIGHT_TOP)
+                                .color(color),
+                        );
+                    }
+                }
+            })
+            .response;
+        self.cursors = cursors;
+
+        // channel is hidden and showed again by clicking the legend
+        if let Some(memory) = plot::PlotMemory::load(ui.ctx(), id) {
+            let hidden = memory.hidden_items.iter().cloned().collect();
+            settings.set_hidden(slot, config, &hidden);
+        }
+        response
+    }
+}
+
+/// index in `data` of the sample nearest to `x` of the plot
+fn sample_index(data: &[Data], x_axis: PlotXAxis, first_timestamp: i64, x: f64) -> Option<usize> {
+    data.iter()
+        .map(|d| (x_axis.value(d, first_timestamp) - x).abs())
+        .position_min_by(f64::total_cmp)
+}
+
+/// delta between the sample at cursor A and B
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CursorDelta {
+    pub time_ms: i64,
+    /// engine rpm
+    pub rpm: f64,
+    /// average horsepower of the samples between the cursors, including both
+    pub avg_power: f64,
+}
+
+impl CursorDelta {
+    pub fn between(data: &[Data], a: usize, b: usize) -> Self {
+        let (sample_a, sample_b) = (&data[a], &data[b]);
+        let samples = &data[a.min(b)..=a.max(b)];
+        let avg_power = samples
+            .iter()
+            .map(|d| PlotChannel::Power.value(d))
+            .sum::<f64>()
+            / samples.len() as f64;
+        Self {
+            time_ms: sample_b.timestamp - sample_a.timestamp,
+            rpm: PlotChannel::RpmEngine.value(sample_b) - PlotChannel::RpmEngine.value(sample_a),
+            avg_power,
+        }
     }
 }
 
@@ -265,3 +597,41 @@ fn timestamp_diff_fmt(timestamp: i64) -> String {
         ms = timestamp % 1000
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::uom::si::{
+        angular_velocity::revolution_per_minute, f64::*, power::horsepower_metric,
+    };
+
+    fn data(timestamp: i64, rpm: f64, hp: f64) -> Data {
+        Data {
+            timestamp,
+            rpm_engine: AngularVelocity::new::<revolution_per_minute>(rpm),
+            horsepower: Power::new::<horsepower_metric>(hp),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cursor_delta() {
+        let buffer = [
+            data(1_000, 3_000., 4.),
+            data(1_500, 4_000., 6.),
+            data(2_000, 5_000., 8.),
+            data(2_500, 6_000., 10.),
+        ];
+        assert_eq!(sample_index(&buffer, PlotXAxis::Time, 1_000, 900.), Some(2));
+        assert_eq!(
+            sample_index(&buffer, PlotXAxis::RpmEngine, 1_000, 3_900.),
+            Some(1)
+        );
+        assert_eq!(sample_index(&[], PlotXAxis::Time, 0, 0.), None);
+
+        let delta = CursorDelta::between(&buffer, 3, 1);
+        assert_eq!(delta.time_ms, -1_000);
+        assert_eq!(delta.rpm.round(), -2_000.);
+        assert_eq!(delta.avg_power.round(), 8.);
+    }
+}
@@ -1,4 +1,4 @@
-use dyno_core::derive_more::Display;
+use derive_more::Display;
 
 #[derive(Clone, Copy)]
 pub struct DisplayMetrics {
@@ -27,13 +27,13 @@ impl Default for DisplayMetrics {
 #[non_exhaustive]
 #[derive(Clone, Copy, Display, Eq, PartialEq)]
 pub enum DisplayMetricsPreset {
-    #[display(fmt = "Default")]
+    #[display("Default")]
     Default,
 
-    #[display(fmt = "Wide")]
+    #[display("Wide")]
     Wide,
 
-    #[display(fmt = "Calculator")]
+    #[display("Calculator")]
     Calculator,
 }
 
@@ -7,7 +7,7 @@ pub use metrics::{DisplayMetrics, DisplayMetricsPreset};
 pub use segments::{NineSegment, SevenSegment, SixteenSegment};
 pub use widget::SegmentedDisplay;
 
-use dyno_core::derive_more::Display;
+use derive_more::Display;
 
 // ----------------------------------------------------------------------------
 
@@ -26,13 +26,13 @@ pub struct DisplayDigit {
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
 pub enum DisplayKind {
-    #[display(fmt = "7-segment")]
+    #[display("7-segment")]
     SevenSegment,
 
-    #[display(fmt = "9-segment")]
+    #[display("9-segment")]
     NineSegment,
 
-    #[display(fmt = "16-segment")]
+    #[display("16-segment")]
     SixteenSegment,
 }
 
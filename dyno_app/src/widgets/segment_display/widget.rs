@@ -145,7 +145,7 @@ impl Widget for SegmentedDisplay {
 
         let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
 
-        let mut child_ui = ui.child_ui(rect, *ui.layout());
+        let mut child_ui = ui.child_ui(rect, *ui.layout(), None);
         child_ui.set_clip_rect(child_ui.clip_rect().intersect(rect));
 
         if child_ui.is_rect_visible(rect) {
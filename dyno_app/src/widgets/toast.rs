@@ -303,7 +303,7 @@ impl Toasts {
     /// Shortcut for adding a toast with info `success`.
     #[inline]
     pub fn success(&mut self, caption: impl ToString + std::fmt::Display) {
-        dyno_core::log::info!("{}", &caption);
+        dyno_core::log::info!("{}", caption);
         self.add(Toast::success(caption))
     }
 
@@ -311,7 +311,7 @@ impl Toasts {
     #[inline]
     pub fn info(&mut self, caption: impl ToString + std::fmt::Display) {
         let caption = caption.to_string();
-        dyno_core::log::info!("{}", &caption);
+        dyno_core::log::info!("{}", caption);
         self.add(Toast::info(caption))
     }
 
@@ -319,7 +319,7 @@ impl Toasts {
     #[inline]
     pub fn warning(&mut self, caption: impl ToString + std::fmt::Display) {
         let caption = caption.to_string();
-        dyno_core::log::warn!("{}", &caption);
+        dyno_core::log::warn!("{}", caption);
         self.add(Toast::warning(caption))
     }
 
@@ -327,7 +327,7 @@ impl Toasts {
     #[inline]
     pub fn error(&mut self, caption: impl ToString + std::fmt::Display) {
         let caption = caption.to_string();
-        dyno_core::log::error!("{}", &caption);
+        dyno_core::log::error!("{}", caption);
         self.add(Toast::error(caption))
     }
 
@@ -510,7 +510,7 @@ impl Toasts {
             {
                 let oy = toast.height / 2. - action_height / 2.;
                 let ox = padding.x + icon_x_padding.0;
-                painter.galley(rect.min + vec2(ox, oy), icon_galley);
+                painter.galley(rect.min + vec2(ox, oy), icon_galley, fg_color);
             }
 
             // Paint caption
@@ -526,7 +526,7 @@ impl Toasts {
                 cross_width + cross_x_padding.0
             };
             let ox = (toast.width / 2. - caption_width / 2.) + o_from_icon / 2. - o_from_cross / 2.;
-            painter.galley(rect.min + vec2(ox, oy), caption_galley);
+            painter.galley(rect.min + vec2(ox, oy), caption_galley, fg_color);
 
             // Paint cross
             if let Some(cross_galley) = cross_galley {
@@ -534,7 +534,7 @@ impl Toasts {
                 let oy = toast.height / 2. - cross_height / 2.;
                 let ox = toast.width - cross_width - cross_x_padding.1 - padding.x;
                 let cross_pos = rect.min + vec2(ox, oy);
-                painter.galley(cross_pos, cross_galley);
+                painter.galley(cross_pos, cross_galley, fg_color);
 
                 let screen_cross = Rect {
                     max: cross_pos + cross_rect.max.to_vec2(),
@@ -557,7 +557,7 @@ impl Toasts {
                             rect.min + vec2(0., toast.height),
                             rect.max - vec2((1. - (current / initial)) * toast.width, 0.),
                         ],
-                        Stroke::new(4., fg_color),
+                        Stroke::new(4.0_f32, fg_color),
                     );
                 }
             }
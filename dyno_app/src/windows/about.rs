@@ -2,7 +2,7 @@ use crate::{widgets::DynoWidgets, PACKAGE_INFO};
 use eframe::egui::Window;
 use eframe::epaint::Vec2;
 
-use dyno_core::serde;
+use dyno_core::{i18n::tr, serde, tr};
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 #[serde(crate = "serde")]
@@ -48,7 +48,7 @@ impl super::WindowState for AboutWindow {
                 ui.separator();
                 ui.horizontal(|ui| {
                     ui.spacing_mut().item_spacing = Vec2::splat(0.0);
-                    ui.label("License: ");
+                    ui.label(format!("{}: ", tr("about-license")));
 
                     license.split_whitespace().for_each(|s| match s {
                         operator @ ("OR" | "AND" | "WITH") => {
@@ -66,19 +66,18 @@ impl super::WindowState for AboutWindow {
 
             if let Some(license_file) = PACKAGE_INFO.license_file {
                 ui.separator();
-                ui.label(format!(
-                    "License: See the {license_file:} file for details."
-                ));
+                ui.label(tr!("about-license-file", file = license_file));
             };
         };
 
-        Window::new("About")
+        Window::new(tr("about-title"))
+            .id("dyno_about_window".into())
             .open(&mut self.open)
             .resizable(false)
             .collapsible(false)
             .show(ctx, |ui: &mut eframe::egui::Ui| {
                 ui.heading(PACKAGE_INFO.name);
-                ui.label(format!("Version {}", PACKAGE_INFO.version));
+                ui.label(tr!("about-version", version = PACKAGE_INFO.version));
 
                 ui.separator();
 
@@ -89,16 +88,16 @@ impl super::WindowState for AboutWindow {
 
                 ui.horizontal(|ui| {
                     if let Some(homepage) = PACKAGE_INFO.homepage {
-                        ui.hyperlink_with_icon_to("Home page", homepage);
+                        ui.hyperlink_with_icon_to(tr("about-homepage"), homepage);
                     }
                     if let Some(repository) = PACKAGE_INFO.repository {
-                        ui.hyperlink_with_icon_to("Repository", repository);
+                        ui.hyperlink_with_icon_to(tr("about-repository"), repository);
                     }
                 });
 
                 ui.separator();
 
-                ui.collapsing("Authors", uidraw_collapsing);
+                ui.collapsing(tr("about-authors"), uidraw_collapsing);
             });
     }
 
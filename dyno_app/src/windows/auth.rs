@@ -1,30 +1,28 @@
 use crate::{toast_warn, widgets::DynoWidgets};
 use dyno_core::{
-    role::Roles,
-    users::{UserLogin, UserRegistration},
-    validate_email, validate_nim, validate_password, PasswordStrength,
+    i18n::tr,
+    model::{
+        role::Roles,
+        users::{UserLogin, UserRegistration},
+        validate::{validate_email, validate_nim, validate_password, PasswordStrength},
+    },
 };
 use eframe::egui::*;
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 enum AuthSection {
+    #[default]
     Login,
     Register,
 }
 
-impl Default for AuthSection {
-    fn default() -> Self {
-        Self::Login
-    }
-}
-
 impl AuthSection {
-    fn as_str(&self) -> &'static str {
-        match self {
-            AuthSection::Login => "Login",
-            AuthSection::Register => "Register",
-        }
+    fn label(self) -> String {
+        tr(match self {
+            AuthSection::Login => "auth-login",
+            AuthSection::Register => "auth-register",
+        })
     }
     fn opposite(self) -> Self {
         match self {
@@ -51,9 +49,9 @@ impl LoginSection {
             password_strength,
         } = self;
 
-        text_edit_validate(ui, nim, status_nim, "nim: ", validate_nim);
+        text_edit_validate(ui, nim, status_nim, tr("auth-nim"), validate_nim);
         text_edit_validate_password(
-            "password: ",
+            tr("auth-password"),
             ui,
             password,
             status_password,
@@ -62,7 +60,7 @@ impl LoginSection {
             validate_password,
         );
         ui.horizontal(|ui| {
-            ui.checkbox(show_password, "show password");
+            ui.checkbox(show_password, tr("auth-show-password"));
             let (val, col) = password_strength.percent_color();
             ui.add(
                 ProgressBar::new(val)
@@ -101,10 +99,10 @@ impl RegistrationSection {
             password_strength,
             show_password,
         } = self;
-        text_edit_validate(ui, nim, status_nim, "nim: ", validate_nim);
-        text_edit_validate(ui, email, status_email, "email: ", validate_email);
+        text_edit_validate(ui, nim, status_nim, tr("auth-nim"), validate_nim);
+        text_edit_validate(ui, email, status_email, tr("auth-email"), validate_email);
         text_edit_validate_password(
-            "password: ",
+            tr("auth-password"),
             ui,
             password,
             status_password,
@@ -113,7 +111,7 @@ impl RegistrationSection {
             validate_password,
         );
         text_edit_validate_password(
-            "confirm password: ",
+            tr("auth-confirm-password"),
             ui,
             confirm_password,
             status_confirm_password,
@@ -121,19 +119,19 @@ impl RegistrationSection {
             !*show_password,
             |s| {
                 if s != password {
-                    Err(crate::DynoErr::validation_error(
-                        "Password not matching with first password",
-                    ))
+                    Err(crate::DynoErr::validation_error(tr(
+                        "auth-password-mismatch",
+                    )))
                 } else {
                     Ok(())
                 }
             },
         );
 
-        ui.combobox_from_slice("Role", role, &[Roles::User, Roles::Guest]);
+        ui.combobox_from_slice(tr("auth-role"), role, &[Roles::User, Roles::Guest]);
 
         ui.horizontal(|ui| {
-            ui.checkbox(show_password, "show password");
+            ui.checkbox(show_password, tr("auth-show-password"));
             let (val, col) = password_strength.percent_color();
             ui.add(
                 ProgressBar::new(val)
@@ -181,7 +179,7 @@ impl super::WindowState for AuthWindow {
             }
         });
 
-        Window::new(self.section.as_str())
+        Window::new(self.section.label())
             .id("dyno_auth_window".into())
             .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
             .open(&mut self.open)
@@ -195,7 +193,7 @@ impl super::WindowState for AuthWindow {
                     vertui.add_space(10.);
                     let section_opp = self.section.opposite();
                     let btn_section = vertui.add(
-                        Button::new(RichText::new(section_opp.as_str()).color(Color32::BLACK))
+                        Button::new(RichText::new(section_opp.label()).color(Color32::BLACK))
                             .rounding(Rounding::same(4.))
                             .fill(Color32::LIGHT_BLUE),
                     );
@@ -211,7 +209,7 @@ impl super::WindowState for AuthWindow {
 
                     vertui.add_space(30.);
                     let submit_btn = vertui.add(
-                        Button::new(RichText::new("Submit").color(Color32::BLACK))
+                        Button::new(RichText::new(tr("auth-submit")).color(Color32::BLACK))
                             .rounding(Rounding::same(4.))
                             .fill(Color32::LIGHT_BLUE)
                             .min_size(vec2(280., 30.)),
@@ -221,12 +219,16 @@ impl super::WindowState for AuthWindow {
                         control.set_loading();
                         match control.api() {
                             Some(api) => match self.section {
-                                AuthSection::Login => api.login(self.login.data.clone(), control.tx().clone()),
-                                AuthSection::Register => api.register(self.register.data.clone(), control.tx().clone()),
-                            }
+                                AuthSection::Login => {
+                                    api.login(self.login.data.clone(), control.tx().clone())
+                                }
+                                AuthSection::Register => {
+                                    api.register(self.register.data.clone(), control.tx().clone())
+                                }
+                            },
                             None => {
                                 control.unset_loading();
-                                toast_warn!("Aplication not connected Api Server - trying reconnecting.. and try again.");
+                                toast_warn!("{}", tr("toast-api-reconnecting"));
                                 control.reconnect_api();
                             }
                         }
@@ -252,7 +254,7 @@ fn text_edit_validate<'a>(
     ui: &mut Ui,
     input: &'a mut String,
     status: &mut Option<Color32>,
-    hint: &'static str,
+    hint: String,
     validation_fn: impl FnOnce(&'a str) -> crate::DynoResult<()>,
 ) -> Response {
     let resp = ui.add(
@@ -280,7 +282,7 @@ fn text_edit_validate<'a>(
 
 #[inline]
 fn text_edit_validate_password<'a>(
-    hint: &'static str,
+    hint: String,
     ui: &mut Ui,
     input: &'a mut String,
     status: &mut Option<Color32>,
@@ -307,7 +309,7 @@ fn text_edit_validate_password<'a>(
             true
         };
         if resp.changed() {
-            *strength = PasswordStrength::new(&input);
+            *strength = PasswordStrength::new(input);
             *status = dyno_core::ternary!((is_valid)?(None): (Some(Color32::RED)));
         }
     } else {
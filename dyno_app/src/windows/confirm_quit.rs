@@ -1,5 +1,5 @@
 use crate::widgets::button::ButtonExt;
-use dyno_core::serde;
+use dyno_core::{i18n::tr, serde};
 use eframe::egui::{
     Align2, Color32, Context, Id, InnerResponse, Key, LayerId, Order, Ui, Vec2, Window,
 };
@@ -35,13 +35,14 @@ impl super::WindowState for ConfirmQuitWindow {
         if let Some(InnerResponse {
             inner: Some(Some(b)),
             ..
-        }) = Window::new("Do you wanna close the Application?")
+        }) = Window::new(tr("quit-title"))
+            .id("dyno_confirm_quit_window".into())
             .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
             .open(&mut self.open)
             .collapsible(false)
             .resizable(false)
             .show(ctx, |ui: &mut Ui| {
-                ui.small("click 'Ok' to close the app or 'No' to rebort close event");
+                ui.small(tr("quit-hint"));
                 ui.horizontal(|horz_ui| {
                     if horz_ui.ok_button().clicked() || horz_ui.input(|i| i.key_down(Key::Enter)) {
                         Some(true)
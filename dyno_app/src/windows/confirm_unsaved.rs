@@ -1,5 +1,5 @@
 use crate::widgets::button::{ButtonExt, ButtonKind};
-use dyno_core::serde;
+use dyno_core::{i18n::tr, serde};
 use eframe::egui::{
     Align2, Color32, Context, Id, InnerResponse, Key, LayerId, Order, Vec2, Window,
 };
@@ -18,7 +18,7 @@ impl super::WindowState for ConfirmUnsavedWindow {
     fn show_window(
         &mut self,
         ctx: &Context,
-        _control: &mut crate::control::DynoControl,
+        control: &mut crate::control::DynoControl,
         state: &mut crate::state::DynoState,
     ) {
         let painter = ctx.layer_painter(LayerId::new(
@@ -31,14 +31,15 @@ impl super::WindowState for ConfirmUnsavedWindow {
             Color32::from_black_alpha(192),
         );
 
-        match Window::new("Buffer Data Records is unsaved. Do you want to save it?")
+        match Window::new(tr("unsaved-title"))
+            .id("dyno_confirm_unsaved_window".into())
             .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
             .open(&mut self.open)
             .collapsible(false)
             .resizable(false)
             .show(ctx, |ui| {
-                ui.label("there is data recorded in buffer, and its not saved");
-                ui.small("click 'Save' to save it or 'No' to rebort it, or 'Cancel' to cancel it");
+                ui.label(tr("unsaved-message"));
+                ui.small(tr("unsaved-hint"));
                 ui.horizontal(|horz_ui| {
                     if horz_ui.save_button().clicked() || horz_ui.input(|i| i.key_down(Key::Enter))
                     {
@@ -69,13 +70,20 @@ impl super::WindowState for ConfirmUnsavedWindow {
                 self.open = !self.open;
                 state.set_operator(crate::state::OperatorData::Noop);
                 if state.quitable() {
+                    control.discard_journal();
                     state.set_quit(true);
+                } else if control.has_pending_open() {
+                    // the unsaved session is discarded by the dropped session file
+                    control.on_open_paths();
                 }
             }
             Some(InnerResponse {
                 inner: Some(Some(ButtonKind::Cancel)),
                 ..
-            }) => self.open = !self.open,
+            }) => {
+                self.open = !self.open;
+                control.cancel_open();
+            }
             _ => {}
         }
     }
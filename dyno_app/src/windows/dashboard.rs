@@ -0,0 +1,47 @@
+use eframe::egui::{Align2, ScrollArea, Vec2, Window};
+
+use crate::control::DynoControl;
+use dyno_core::{i18n::tr, serde};
+
+/// editor of the dashboard layouts in `ApplicationConfig::dashboard`
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "serde")]
+pub struct DashboardWindow {
+    open: bool,
+}
+
+impl DashboardWindow {
+    pub fn new() -> Self {
+        Self { open: false }
+    }
+}
+
+impl super::WindowState for DashboardWindow {
+    fn show_window(
+        &mut self,
+        ctx: &eframe::egui::Context,
+        control: &mut DynoControl,
+        _state: &mut crate::state::DynoState,
+    ) {
+        Window::new(tr("dash-title"))
+            .id("dyno_dashboard_window".into())
+            .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
+            .open(&mut self.open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let config = control.session().active().config().clone();
+                ScrollArea::vertical()
+                    .max_height(480.)
+                    .show(ui, |ui| control.app_config.dashboard.ui(ui, &config));
+            });
+    }
+
+    #[inline]
+    fn set_open(&mut self, open: bool) {
+        self.open = open
+    }
+    #[inline]
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
@@ -1,5 +1,16 @@
 use crate::widgets::{button::ButtonExt, DynoWidgets};
-use dyno_core::{chrono::Utc, convertions, data_structure::ExponentialFilter, Data};
+use dyno_core::{
+    chrono::Utc,
+    types::Data,
+    uom::si::{
+        angular_velocity::revolution_per_minute,
+        f64::{AngularVelocity, Power, TemperatureInterval, Torque, Velocity},
+        power::horsepower_metric,
+        temperature_interval::degree_celsius,
+        torque::newton_meter,
+        velocity::kilometer_per_hour,
+    },
+};
 
 #[derive(Debug, Default)]
 pub struct DebugAction {
@@ -9,7 +20,6 @@ pub struct DebugAction {
     hp: f64,
     odo: f64,
     temp: f64,
-    speed_filter: ExponentialFilter<f64>,
     display_style: crate::widgets::DisplayStylePreset,
     start: bool,
 }
@@ -26,7 +36,6 @@ impl super::WindowState for DebugAction {
         control: &mut crate::control::DynoControl,
         _state: &mut crate::state::DynoState,
     ) {
-        use convertions::prelude::*;
         let ctx_time = ctx.input(|i| i.time);
         let Self {
             rpm,
@@ -37,7 +46,6 @@ impl super::WindowState for DebugAction {
             temp,
             display_style,
             start,
-            speed_filter,
         } = self;
         eframe::egui::Window::new("Debug Window")
             .id("window_debug_simulation".into())
@@ -61,7 +69,7 @@ impl super::WindowState for DebugAction {
 
                         if ui.reset_button().clicked() {
                             crate::log::debug!("Resetting Buffer in debug emulation");
-                            control.buffer_mut().clean();
+                            control.buffer_mut().clear();
                         }
 
                         ui.end_row();
@@ -90,24 +98,24 @@ impl super::WindowState for DebugAction {
                 )
             });
 
-        if *start && ((ctx_time as u64 * 1000) % 250) == 0 {
-            let rpm = control
-                .config
-                .filter_rpm_engine
-                .next(RotationPerMinute::new(*rpm));
+        if *start && (ctx_time as u64 * 1000).is_multiple_of(250) {
+            let rpm = AngularVelocity::new::<revolution_per_minute>(*rpm);
+            let temp = TemperatureInterval::new::<degree_celsius>(*temp);
             let data = Data {
-                speed: KilometresPerHour::new(speed_filter.next(*speed)),
+                speed: Velocity::new::<kilometer_per_hour>(*speed),
                 rpm_roda: rpm,
                 rpm_engine: rpm,
-                odo: KiloMetres::new(*odo),
-                horsepower: HorsePower::new(*hp),
-                torque: NewtonMeter::new(*torque),
-                temp: Celcius::new(0.0),
-                time_stamp: Utc::now().naive_utc(),
-                ..Default::default()
+                horsepower: Power::new::<horsepower_metric>(*hp),
+                torque: Torque::new::<newton_meter>(*torque),
+                temps: [temp; dyno_core::types::MAX_TEMP_CHANNELS],
+                temp_faults: 0,
+                timestamp: Utc::now().timestamp_millis(),
             };
-            control.buffer_mut().data = data;
-            control.buffer_mut().process_data();
+            let buffer = control.buffer_mut();
+            if buffer.is_empty() {
+                buffer.start();
+            }
+            buffer.push(data);
         }
     }
 }
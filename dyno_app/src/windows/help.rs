@@ -1,4 +1,4 @@
-use dyno_core::serde;
+use dyno_core::{i18n::tr, serde};
 use eframe::egui::Window;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -20,12 +20,13 @@ impl super::WindowState for HelpWindow {
         _control: &mut crate::control::DynoControl,
         _state: &mut crate::state::DynoState,
     ) {
-        Window::new("Help")
+        Window::new(tr("help-title"))
+            .id("dyno_help_window".into())
             .open(&mut self.open)
             .resizable(false)
             .collapsible(false)
             .show(ctx, |ui: &mut eframe::egui::Ui| {
-                ui.heading("Upcoming Update! Help Window")
+                ui.heading(tr("help-upcoming"))
             });
     }
 
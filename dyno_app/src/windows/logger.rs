@@ -7,7 +7,7 @@ use eframe::{
 use itertools::Itertools;
 
 use crate::widgets::{button::ButtonExt, DynoFileManager, DynoWidgets};
-use dyno_core::{log::Level, RECORDS_LOGGER};
+use dyno_core::{i18n::tr, log::Level, tr, RECORDS_LOGGER};
 
 const SIZE_LEVEL: usize = Level::Trace as usize;
 const LEVELS: [Level; SIZE_LEVEL] = [
@@ -65,20 +65,20 @@ impl LoggerWindow {
         }
 
         ui.horizontal(|ui| {
-            if ui.button("Clear").clicked() {
+            if ui.button(tr("log-clear")).clicked() {
                 logs.clear();
             }
-            ui.menu_button("Log Levels", |ui| {
+            ui.menu_button(tr("log-levels"), |ui| {
                 ui.selectable_label_from_slice(&mut self.loglevels, |idx| LEVELS[idx].as_str());
             });
         });
 
         ui.horizontal(|ui| {
-            ui.label("Search: ");
+            ui.label(format!("{}: ", tr("log-search")));
             let _response = ui.text_edit_singleline(&mut self.term);
             if ui
                 .selectable_label(self.case_sensitive, "Aa")
-                .on_hover_text("Case sensitive")
+                .on_hover_text(tr("log-case-sensitive"))
                 .clicked()
             {
                 self.case_sensitive = !self.case_sensitive;
@@ -86,12 +86,12 @@ impl LoggerWindow {
         });
 
         ui.horizontal(|ui| {
-            ui.label("Max Log output");
+            ui.label(tr("log-max-output"));
             ui.add(widgets::DragValue::new(&mut self.max_log_len).speed(1));
         });
 
         ui.horizontal(|ui| {
-            if ui.button("Sort").clicked() {
+            if ui.button(tr("log-sort")).clicked() {
                 logs.sort()
             }
         });
@@ -113,14 +113,14 @@ impl LoggerWindow {
             });
 
         ui.horizontal(|ui| {
-            ui.label(format!("Log size: {}", logs.len()));
-            ui.label(format!("Displayed: {}", logs_displayed));
+            ui.label(tr!("log-size", count = logs.len()));
+            ui.label(tr!("log-displayed", count = logs_displayed));
             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                 #[allow(deprecated)]
                 if ui.save_button().clicked() {
                     let homedir = env::home_dir().unwrap_or(PathBuf::from("/temp"));
                     if let Some(file) = DynoFileManager::save_file(
-                        "Saving Log File",
+                        &tr("log-save-dialog"),
                         "gui_log",
                         homedir,
                         &[("logfile", &["log", "log.log", "dlog"])],
@@ -133,7 +133,7 @@ impl LoggerWindow {
                         }
                     }
                 }
-                if ui.button("Copy").clicked() {
+                if ui.button(tr("log-copy")).clicked() {
                     ui.output_mut(|o| o.copied_text = logs.iter().map(|(_, s)| s).join("\n"));
                 }
             });
@@ -164,7 +164,7 @@ impl super::WindowState for LoggerWindow {
         _state: &mut crate::state::DynoState,
     ) {
         let mut open = self.open;
-        Window::new("Dyno Log Window")
+        Window::new(tr("log-title"))
             .open(&mut open)
             .resizable(true)
             .id("dyno_log_window".into())
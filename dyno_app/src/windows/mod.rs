@@ -2,11 +2,16 @@ pub mod about;
 pub mod auth;
 pub mod confirm_quit;
 pub mod confirm_unsaved;
+pub mod dashboard;
 pub mod help;
 pub mod logger;
 pub mod open_server;
+pub mod procedure;
+pub mod recovery;
 pub mod save_server;
+pub mod session;
 pub mod setting;
+pub mod vehicles;
 
 #[cfg(debug_assertions)]
 pub mod debug;
@@ -41,13 +46,18 @@ pub enum WSIdx {
     Auth,
     ConfirmQuit,
     ConfirmUnsaved,
+    Dashboard,
     #[cfg(debug_assertions)]
     Debug,
     Help,
     Logger,
     OpenServer,
+    Procedure,
+    Recovery,
     SaveServer,
+    Session,
     Setting,
+    Vehicles,
     WindowStateSize,
 }
 const WS_SIZE: usize = WSIdx::WindowStateSize as usize;
@@ -70,13 +80,18 @@ impl WindowStack {
                 Box::new(auth::AuthWindow::new()),
                 Box::new(confirm_quit::ConfirmQuitWindow::new()),
                 Box::new(confirm_unsaved::ConfirmUnsavedWindow::new()),
+                Box::new(dashboard::DashboardWindow::new()),
                 #[cfg(debug_assertions)]
                 Box::<debug::DebugAction>::default(),
                 Box::new(help::HelpWindow::new()),
                 Box::new(logger::LoggerWindow::new()),
                 Box::new(open_server::OpenServerWindow::new()),
+                Box::new(procedure::ProcedureWindow::new()),
+                Box::new(recovery::RecoveryWindow::new()),
                 Box::new(save_server::SaveServerWindow::new()),
+                Box::new(session::SessionWindow::new()),
                 Box::new(setting::SettingWindow::new()),
+                Box::new(vehicles::VehicleWindow::new()),
             ],
         }
     }
@@ -1,6 +1,6 @@
 use std::ops::Index;
 
-use dyno_core::dynotests::DynoTest;
+use dyno_core::{i18n::tr, model::dynotests::DynoTest};
 use eframe::egui::{Button, Id, LayerId, Layout, RichText, Ui, Window};
 use eframe::emath::{Align, Align2};
 use eframe::epaint::{vec2, Color32, Rounding, Vec2};
@@ -41,7 +41,7 @@ impl super::WindowState for OpenServerWindow {
         );
 
         let ui_window = |ui: &mut Ui| {
-            ui.heading("List File in Server");
+            ui.heading(tr("server-list-title"));
             ui.add_space(10.);
             TableBuilder::new(ui)
                 .striped(true)
@@ -50,28 +50,29 @@ impl super::WindowState for OpenServerWindow {
                 .resizable(true)
                 .header(20.0, |mut head_ui| {
                     head_ui.col(|col_ui| {
-                        col_ui.strong("id");
+                        col_ui.strong(tr("server-list-id"));
                     });
                     head_ui.col(|col_ui| {
-                        col_ui.strong("info id");
+                        col_ui.strong(tr("server-list-info-id"));
                     });
                     head_ui.col(|col_ui| {
-                        col_ui.strong("verified");
+                        col_ui.strong(tr("server-list-verified"));
                     });
                     head_ui.col(|col_ui| {
-                        col_ui.strong("updated");
+                        col_ui.strong(tr("server-list-updated"));
                     });
                     head_ui.col(|col_ui| {
-                        col_ui.strong("created");
+                        col_ui.strong(tr("server-list-created"));
                     });
                     head_ui.col(|col_ui| {
-                        col_ui.strong("open");
+                        col_ui.strong(tr("server-list-open"));
                     });
                 })
                 .body(|body_ui| {
                     let row_height = 18.0;
                     let num_rows = self.data.len();
-                    body_ui.rows(row_height, num_rows, |row_idx, mut row| {
+                    body_ui.rows(row_height, num_rows, |mut row| {
+                        let row_idx = row.index();
                         let DynoTest {
                             id,
                             info_id,
@@ -79,7 +80,7 @@ impl super::WindowState for OpenServerWindow {
                             updated_at,
                             created_at,
                             data_url,
-                        data_checksum,
+                            data_checksum,
                             ..
                         } = self.data.index(row_idx);
 
@@ -90,11 +91,11 @@ impl super::WindowState for OpenServerWindow {
                             ui.label((*info_id).map(|x| x.to_string()).unwrap_or_default());
                         });
                         row.col(|ui| {
-                            ui.label(if *verified {
-                                "verified"
+                            ui.label(tr(if *verified {
+                                "server-list-verified"
                             } else {
-                                "not verified"
-                            });
+                                "server-list-not-verified"
+                            }));
                         });
                         row.col(|ui| {
                             ui.label(updated_at.format("%d-%m-%Y %T").to_string());
@@ -106,12 +107,16 @@ impl super::WindowState for OpenServerWindow {
                             let open_btn = ui.small_open_button();
                             match (open_btn.clicked(), control.api()) {
                                 (true, None) => {
-                                    toast_error!("Something Wrong, Api is not Connected! trying to reconnecting..");
+                                    toast_error!("{}", tr("toast-api-reconnecting"));
                                     control.reconnect_api();
                                 }
                                 (true, Some(api)) => {
                                     control.set_loading();
-                                    api.load_dyno_file(data_url.clone(), data_checksum.clone(), control.tx().clone());
+                                    api.load_dyno_file(
+                                        data_url.clone(),
+                                        data_checksum.clone(),
+                                        control.tx().clone(),
+                                    );
                                 }
                                 _ => {}
                             }
@@ -121,7 +126,7 @@ impl super::WindowState for OpenServerWindow {
 
             ui.add_space(10.);
             let refresh_btn = ui.add(
-                Button::new(RichText::new("Refresh").color(Color32::BLACK))
+                Button::new(RichText::new(tr("server-list-refresh")).color(Color32::BLACK))
                     .rounding(Rounding::same(4.))
                     .fill(Color32::LIGHT_BLUE)
                     .min_size(vec2(280., 30.)),
@@ -131,14 +136,14 @@ impl super::WindowState for OpenServerWindow {
                 match control.api() {
                     Some(api) => api.get_dyno(control.tx().clone()),
                     None => {
-                        toast_warn!("Not connected to API, trying to reconnecting..");
+                        toast_warn!("{}", tr("toast-api-reconnecting"));
                         control.reconnect_api();
                     }
                 }
             }
         };
 
-        Window::new("Save DynoTests to Server")
+        Window::new(tr("server-list-window"))
             .id("dyno_save_server".into())
             .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
             .open(&mut self.open)
@@ -0,0 +1,153 @@
+use eframe::egui::{
+    Align2, Color32, ComboBox, ProgressBar, RichText, ScrollArea, Ui, Vec2, Window,
+};
+
+use crate::{control::DynoControl, procedure::StepCondition};
+use dyno_core::{
+    i18n::{tr, tr_attr},
+    serde,
+};
+
+/// walk the operator through the steps of the selected procedure
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "serde")]
+pub struct ProcedureWindow {
+    open: bool,
+    selected: usize,
+}
+
+impl ProcedureWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn select_ui(&mut self, ui: &mut Ui, control: &mut DynoControl) {
+        let count = control.procedures().len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+        ui.horizontal(|ui| {
+            let procedures = control.procedures();
+            ComboBox::from_id_source("dyno_procedure_select")
+                .width(220.)
+                .selected_text(
+                    procedures
+                        .get(self.selected)
+                        .map_or_else(|| tr("procedure-none"), |p| p.name.clone()),
+                )
+                .show_ui(ui, |ui| {
+                    for (idx, procedure) in procedures.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected, idx, procedure.name.as_str());
+                    }
+                });
+            if ui
+                .button(format!("🔃 {}", tr("procedure-reload")))
+                .on_hover_text(tr_attr("procedure-reload", "hint"))
+                .clicked()
+            {
+                control.reload_procedures();
+            }
+        });
+        let Some(procedure) = control.procedures().get(self.selected) else {
+            return;
+        };
+        if !procedure.description.is_empty() {
+            ui.label(procedure.description.as_str());
+        }
+        ui.separator();
+        for (idx, step) in procedure.steps.iter().enumerate() {
+            ui.label(format!("{}. {} ({})", idx + 1, step.name, step.condition));
+        }
+        ui.separator();
+        if ui.button(format!("▶ {}", tr("procedure-start"))).clicked() {
+            control.start_procedure(self.selected);
+        }
+    }
+
+    fn runner_ui(ui: &mut Ui, control: &mut DynoControl) {
+        let recording = control.is_recording();
+        let Some(runner) = control.procedure() else {
+            return;
+        };
+        ui.heading(runner.procedure().name.as_str());
+        ui.separator();
+        for (idx, step) in runner.procedure().steps.iter().enumerate() {
+            let text = format!("{}. {}", idx + 1, step.name);
+            match idx.cmp(&runner.step_index()) {
+                std::cmp::Ordering::Less => ui.label(RichText::new(format!("✔ {text}")).weak()),
+                std::cmp::Ordering::Equal => ui.label(
+                    RichText::new(format!("▶ {text}"))
+                        .strong()
+                        .color(crate::COLOR_BLUE_DYNO),
+                ),
+                std::cmp::Ordering::Greater => ui.label(format!("○ {text}")),
+            };
+        }
+        ui.separator();
+
+        let finished = runner.is_finished();
+        let mut next = false;
+        match runner.current() {
+            Some(step) => {
+                ui.label(RichText::new(step.prompt.as_str()).size(22.).strong());
+                ui.label(step.condition.to_string());
+                if let Some(progress) = runner.step_progress() {
+                    ui.add(ProgressBar::new(progress).show_percentage());
+                }
+                if !recording {
+                    ui.colored_label(Color32::YELLOW, tr("procedure-not-recording"));
+                }
+                let label = match step.condition {
+                    StepCondition::Manual => format!("✔ {}", tr("procedure-confirm")),
+                    _ => format!("⏭ {}", tr("procedure-skip")),
+                };
+                next = ui.button(label).clicked();
+            }
+            None => {
+                ui.label(RichText::new(tr("procedure-done")).size(22.).strong());
+            }
+        }
+        let stop_label = match finished {
+            true => tr("procedure-close"),
+            false => format!("⏹ {}", tr("procedure-abort")),
+        };
+        if ui.button(stop_label).clicked() {
+            control.stop_procedure();
+        }
+        if next {
+            control.next_step();
+        }
+    }
+}
+
+impl super::WindowState for ProcedureWindow {
+    fn show_window(
+        &mut self,
+        ctx: &eframe::egui::Context,
+        control: &mut DynoControl,
+        _state: &mut crate::state::DynoState,
+    ) {
+        let mut open = self.open;
+        Window::new(tr("procedure-title"))
+            .id("dyno_procedure_window".into())
+            .anchor(Align2::RIGHT_TOP, Vec2::new(-10.0, 40.0))
+            .open(&mut open)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                ScrollArea::vertical()
+                    .max_height(480.)
+                    .show(ui, |ui| match control.procedure() {
+                        Some(_) => Self::runner_ui(ui, control),
+                        None => self.select_ui(ui, control),
+                    });
+            });
+        self.open = open;
+    }
+
+    #[inline]
+    fn set_open(&mut self, open: bool) {
+        self.open = open
+    }
+    #[inline]
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
@@ -0,0 +1,94 @@
+use crate::widgets::button::{ButtonExt, ButtonKind};
+use dyno_core::{i18n::tr, serde, tr};
+use eframe::egui::{Align2, Color32, Context, Id, LayerId, Order, Vec2, Window};
+
+/// offer to restore the session from the journal of the previous app that crashed
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "serde")]
+pub struct RecoveryWindow {
+    open: bool,
+}
+
+impl RecoveryWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl super::WindowState for RecoveryWindow {
+    fn show_window(
+        &mut self,
+        ctx: &Context,
+        control: &mut crate::control::DynoControl,
+        _state: &mut crate::state::DynoState,
+    ) {
+        let Some(session) = control.recovered() else {
+            self.open = false;
+            return;
+        };
+        let runs = session
+            .runs()
+            .iter()
+            .filter(|run| !run.data.is_empty())
+            .map(|run| {
+                format!(
+                    "{} ({})",
+                    run.name,
+                    tr!("session-run-data", count = run.data.len())
+                )
+            })
+            .collect::<Vec<_>>();
+        let customer = session.info.customer.clone();
+
+        ctx.layer_painter(LayerId::new(
+            Order::Background,
+            Id::new("confirmation_popup_recovery"),
+        ))
+        .rect_filled(
+            ctx.input(|inp| inp.screen_rect()),
+            0.0,
+            Color32::from_black_alpha(192),
+        );
+
+        let response = Window::new(tr("recovery-title"))
+            .id("dyno_recovery_window".into())
+            .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr("recovery-message"));
+                if !customer.is_empty() {
+                    ui.label(tr!("recovery-customer", customer = customer.as_str()));
+                }
+                for run in &runs {
+                    ui.label(format!("\u{2022} {run}"));
+                }
+                ui.small(tr("recovery-hint"));
+                ui.horizontal(|horz_ui| {
+                    if horz_ui.ok_button().clicked() {
+                        Some(ButtonKind::Ok)
+                    } else if horz_ui.no_button().clicked() {
+                        Some(ButtonKind::No)
+                    } else {
+                        None
+                    }
+                })
+                .inner
+            });
+        match response.and_then(|r| r.inner).flatten() {
+            Some(ButtonKind::Ok) => control.restore_recovered(),
+            Some(ButtonKind::No) => control.discard_recovered(),
+            _ => {}
+        }
+    }
+
+    #[inline]
+    fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    #[inline]
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
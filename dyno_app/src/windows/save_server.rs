@@ -1,9 +1,8 @@
+use dyno_core::i18n::tr;
 use eframe::egui::{Button, Id, LayerId, RichText, Ui, Window};
 use eframe::emath::Align2;
 use eframe::epaint::{vec2, Color32, Rounding, Vec2};
 
-use crate::toast_warn;
-
 #[derive(Debug, Clone, Default)]
 pub struct SaveServerWindow {
     open: bool,
@@ -32,34 +31,23 @@ impl super::WindowState for SaveServerWindow {
         );
 
         let ui_window = |ui: &mut Ui| {
-            ui.heading("Info Dynotests: ");
+            ui.heading(tr("server-save-info"));
             ui.add_space(10.);
             super::setting::SettingWindow::setting_info(ui, &mut control.config);
             ui.add_space(10.);
             let submit_btn = ui.add(
-                Button::new(RichText::new("Save").color(Color32::BLACK))
+                Button::new(RichText::new(tr("server-save")).color(Color32::BLACK))
                     .rounding(Rounding::same(4.))
                     .fill(Color32::LIGHT_BLUE)
                     .min_size(vec2(280., 30.)),
             );
 
             if submit_btn.clicked() {
-                match control.api() {
-                    Some(api) => {
-                        let buffer = control.buffer().clone();
-                        let config = control.config.clone();
-                        let start = control.start.unwrap_or_default();
-                        let stop = control.stop.unwrap_or_default();
-                        api.save_dyno(buffer, config, start, stop, control.tx().clone());
-                    }
-                    None => {
-                        toast_warn!("Not connected to API, try reconnecting or check the internet connection.")
-                    }
-                }
+                control.queue_upload();
             }
         };
 
-        Window::new("Save DynoTests to Server")
+        Window::new(tr("server-save-title"))
             .id("dyno_save_server".into())
             .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
             .open(&mut self.open)
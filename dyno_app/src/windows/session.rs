@@ -0,0 +1,189 @@
+use eframe::egui::{Align2, Grid, ScrollArea, TextEdit, Ui, Vec2, Window};
+
+use crate::{control::DynoControl, row_label_value};
+use dyno_core::{
+    i18n::{tr, tr_attr},
+    serde,
+};
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "serde")]
+pub struct SessionWindow {
+    open: bool,
+}
+
+impl SessionWindow {
+    pub fn new() -> Self {
+        Self { open: false }
+    }
+
+    fn info_ui(ui: &mut Ui, control: &mut DynoControl) {
+        let linked = control
+            .session()
+            .info
+            .vehicle_id
+            .as_deref()
+            .map(|uuid| control.registry().get(uuid).map(|v| v.title()));
+        let session = control.session_mut();
+        if let Some(title) = linked {
+            ui.horizontal(|ui| {
+                let title = title.unwrap_or_else(|| tr("session-deleted-vehicle"));
+                ui.label(format!(
+                    "🔗 {}",
+                    dyno_core::tr!("session-linked", vehicle = title)
+                ));
+                if ui.small_button(tr("session-unlink")).clicked() {
+                    session.info.vehicle_id = None;
+                }
+            });
+        }
+        Grid::new("dyno_session_info_grid")
+            .num_columns(2)
+            .spacing([40.0, 4.0])
+            .show(ui, |ui| {
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut session.info.customer),
+                    "session-customer"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut session.info.phone),
+                    "session-phone"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut session.info.vehicle),
+                    "session-vehicle"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut session.info.plate_number),
+                    "session-plate-number"
+                );
+                ui.end_row();
+            });
+        ui.label(tr("session-notes"));
+        ui.add(TextEdit::multiline(&mut session.notes).desired_rows(2));
+    }
+
+    fn runs_ui(ui: &mut Ui, control: &mut DynoControl) {
+        let recording = control.is_recording();
+        let mut remove = None;
+        let session = control.session_mut();
+        let active = session.active_index();
+        for idx in 0..session.runs().len() {
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!recording, |ui| {
+                    if ui.radio(idx == active, "").clicked() {
+                        session.select(idx);
+                    }
+                });
+                let mut name = session.runs()[idx].name.clone();
+                if ui
+                    .add(TextEdit::singleline(&mut name).desired_width(160.))
+                    .changed()
+                {
+                    session.rename(idx, name);
+                }
+                ui.label(dyno_core::tr!(
+                    "session-run-data",
+                    count = session.runs()[idx].data.len()
+                ));
+                if ui
+                    .add_enabled(
+                        !(recording && idx == active),
+                        eframe::egui::Button::new("🗑"),
+                    )
+                    .on_hover_text(tr("session-delete-run"))
+                    .clicked()
+                {
+                    remove = Some(idx);
+                }
+            });
+            if idx == active {
+                let run = session.active_mut();
+                let mut tags = run.tags_text();
+                ui.horizontal(|ui| {
+                    ui.label(tr("session-tags"));
+                    if ui
+                        .add(
+                            TextEdit::singleline(&mut tags)
+                                .hint_text(tr_attr("session-tags", "hint"))
+                                .desired_width(240.),
+                        )
+                        .changed()
+                    {
+                        run.set_tags_text(&tags);
+                    }
+                });
+                ui.add(
+                    TextEdit::multiline(&mut run.notes)
+                        .hint_text(tr("session-run-notes"))
+                        .desired_rows(2),
+                );
+            }
+            ui.separator();
+        }
+        if let Some(idx) = remove {
+            control.remove_run(idx);
+        }
+    }
+}
+
+impl super::WindowState for SessionWindow {
+    fn show_window(
+        &mut self,
+        ctx: &eframe::egui::Context,
+        control: &mut DynoControl,
+        _state: &mut crate::state::DynoState,
+    ) {
+        Window::new(tr("session-title"))
+            .id("dyno_session_window".into())
+            .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
+            .open(&mut self.open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.heading(tr("session-customer"));
+                Self::info_ui(ui, control);
+                ui.separator();
+                ui.heading(tr("session-runs"));
+                ScrollArea::vertical()
+                    .max_height(320.)
+                    .show(ui, |ui| Self::runs_ui(ui, control));
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !control.is_recording(),
+                            eframe::egui::Button::new(format!("➕ {}", tr("status-new-run"))),
+                        )
+                        .clicked()
+                    {
+                        control.new_run();
+                    }
+                    if ui
+                        .add_enabled(
+                            control.is_buffer_saved(),
+                            eframe::egui::Button::new(format!("🗋 {}", tr("session-new"))),
+                        )
+                        .on_disabled_hover_text(tr_attr("session-new", "hint"))
+                        .clicked()
+                    {
+                        control.new_session();
+                    }
+                });
+            });
+    }
+
+    #[inline]
+    fn set_open(&mut self, open: bool) {
+        self.open = open
+    }
+    #[inline]
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
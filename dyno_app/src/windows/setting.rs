@@ -1,7 +1,9 @@
 use crate::{config::ApplicationConfig, paths::DynoPaths, row_label_value, widgets::DynoWidgets};
 use dyno_core::{
-    serde, Cylinder, DynoConfig, ElectricMotor, InfoMotor, MotorType, Stroke as InfoMotorStroke,
-    Transmition,
+    i18n::{tr, tr_attr},
+    serde,
+    types::{Cylinder, MotorInfo, MotorKind, Stroke, Transmition},
+    DynoConfig,
 };
 use eframe::egui::*;
 
@@ -37,120 +39,190 @@ impl SettingWindow {
         paths: &mut DynoPaths,
     ) {
         let path_ui = |ui: &mut Ui| {};
-        CollapsingHeader::new("✒ Paths")
+        CollapsingHeader::new(format!("✒ {}", tr("setting-paths")))
+            .id_source("dyno_paths_setting_id")
             .default_open(true)
             .show(ui, |path_ui| paths.draw(path_ui, &mut self.edit_path));
         ui.separator();
 
-        CollapsingHeader::new(" Configurations")
+        CollapsingHeader::new(format!(" {}", tr("setting-configurations")))
+            .id_source("dyno_configurations_setting_id")
             .default_open(true)
             .show(ui, |config_ui| {
                 app_config.draw(config_ui);
             });
     }
 
-    #[allow(unused)]
-    pub fn setting_info(ui: &mut Ui, conf: &mut DynoConfig) {
-        let info_motor_ui = |im_ui: &mut Ui| match &mut conf.motor_type {
-            MotorType::Electric(ElectricMotor { name }) => {
-                im_ui.add(TextEdit::singleline(name).hint_text("isi nama motor"));
-            }
-            MotorType::Engine(InfoMotor {
-                name,
-                cc,
+    fn setting_serial(ui: &mut Ui, control: &mut crate::control::DynoControl) {
+        CollapsingHeader::new(format!("🔌 {}", tr("setting-serial")))
+            .id_source("dyno_serial_link_setting_id")
+            .default_open(true)
+            .show(ui, |ui| {
+                control.app_config.serial.ui(ui);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr("setting-test-serial"))
+                        .on_hover_text(tr_attr("setting-test-serial", "hint"))
+                        .clicked()
+                    {
+                        control.test_serial_link();
+                    }
+                    if ui.button(tr("setting-serial-default")).clicked() {
+                        control.app_config.serial = Default::default();
+                    }
+                });
+                if let Some(report) = control.link_report() {
+                    let color = match report.is_connected() {
+                        true => Color32::GREEN,
+                        false => Color32::RED,
+                    };
+                    ui.label(RichText::new(report.to_string()).color(color));
+                }
+            });
+    }
+
+    fn setting_server(ui: &mut Ui, control: &mut crate::control::DynoControl) {
+        CollapsingHeader::new(format!("🌐 {}", tr("setting-server")))
+            .id_source("dyno_server_setting_id")
+            .show(ui, |ui| {
+                control.app_config.server.ui(ui);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr("setting-test-server"))
+                        .on_hover_text(tr_attr("setting-test-server", "hint"))
+                        .clicked()
+                    {
+                        control.test_server();
+                    }
+                    if ui
+                        .button(tr("setting-connect"))
+                        .on_hover_text(tr_attr("setting-connect", "hint"))
+                        .clicked()
+                    {
+                        control.reconnect_api();
+                    }
+                });
+                if let Some(api) = control.api() {
+                    ui.small(dyno_core::tr!(
+                        "setting-connected-to",
+                        url = api.url.to_string()
+                    ));
+                }
+            });
+    }
+
+    /// rows of the motor info grid, also used by the vehicle registry
+    pub fn motor_info_ui(im_ui: &mut Ui, info: &mut MotorInfo) {
+        let MotorInfo {
+            cc,
+            kind,
+            cylinder,
+            stroke,
+            transmition,
+        } = info;
+        row_label_value!(
+            im_ui => im_ui.combobox_from_iter(
+                tr("motor-kind"),
+                kind,
+                [MotorKind::Engine, MotorKind::Electric].into_iter()
+            ),
+            "motor-kind"
+        );
+        im_ui.end_row();
+        if kind.is_electric() {
+            return;
+        }
+        row_label_value!(
+            im_ui,
+            Slider::new(cc, 20u16..=2000u16).suffix(" cc"),
+            "motor-cc"
+        );
+        im_ui.end_row();
+        row_label_value!(
+            im_ui => im_ui.combobox_from_iter(
+                tr("motor-cylinder"),
                 cylinder,
+                Cylinder::into_iter()
+            ),
+            "motor-cylinder"
+        );
+        im_ui.end_row();
+        row_label_value!(
+            im_ui => im_ui.combobox_from_iter(
+                tr("motor-stroke"),
                 stroke,
+                Stroke::into_iter()
+            ),
+            "motor-stroke"
+        );
+        im_ui.end_row();
+        row_label_value!(
+            im_ui => im_ui.combobox_from_iter(
+                tr("motor-transmition"),
                 transmition,
-            }) => {
-                row_label_value!(
-                    im_ui,
-                    TextEdit::singleline(name).hint_text("isi nama motor"),
-                    "Motor Name",
-                    "nama motor (hanya untuk informasi data)"
-                );
-                im_ui.end_row();
-                row_label_value!(
-                    im_ui,
-                    Slider::new(cc, 20u32..=2000u32).suffix(" cc"),
-                    "CC",
-                    "nama motor (hanya untuk informasi data)"
-                );
-                im_ui.end_row();
-                row_label_value!(
-                    im_ui => im_ui.combobox_from_iter(
-                        "Cylinder of Engine",
-                        cylinder,
-                        Cylinder::into_iter()
-                    ),
-                    "Cylinder",
-                    "silinder mesin (hanya untuk informasi data dan perhitungan rpm engine)"
-                );
-                im_ui.end_row();
-                row_label_value!(
-                    im_ui => im_ui.combobox_from_iter(
-                        "Stroke of Engine",
-                        stroke,
-                        InfoMotorStroke::into_iter()
-                    ),
-                    "Stroke",
-                    "stroke mesin (hanya untuk informasi data dan perhitungan rpm engine)"
-                );
-                im_ui.end_row();
-                row_label_value!(
-                    im_ui => im_ui.combobox_from_iter(
-                        "Transmitions of Engine",
-                        transmition,
-                        Transmition::into_iter()
-                    ),
-                    "Transmitions",
-                    "transmisi mesin (hanya untuk informasi data)"
-                );
-            }
-        };
+                Transmition::into_iter()
+            ),
+            "motor-transmition"
+        );
+    }
+
+    #[allow(unused)]
+    pub fn setting_info(ui: &mut Ui, conf: &mut DynoConfig) {
+        let info_motor_ui = |im_ui: &mut Ui| Self::motor_info_ui(im_ui, &mut conf.motor_info);
         let other_motor_info_config_ui = |ui: &mut Ui| {
-            // diameter_roller: length::Metres,
             row_label_value!(
-                ui, DragValue::new(conf.diameter_roller.value_mut()).suffix(" m"),
-                "Diameter Roller",
-                "Diameter dari roller dynotest chasis (digunakan untuk menghitung informasi data sensor)"
+                ui,
+                DragValue::new(&mut conf.diameter_roller_cm).suffix(" cm"),
+                "config-diameter-roller"
+            );
+            ui.end_row();
+            row_label_value!(
+                ui,
+                DragValue::new(&mut conf.diameter_roller_beban_cm).suffix(" cm"),
+                "config-diameter-roller-beban"
             );
             ui.end_row();
-            // diameter_roller_beban: length::Metres,
             row_label_value!(
-                ui, DragValue::new(conf.diameter_roller_beban.value_mut()).suffix(" m"),
-                "Diameter Roller Beban",
-                "Beban dari roller dynotest chasis (digunakan untuk menghitung informasi data sensor)"
+                ui,
+                DragValue::new(&mut conf.diameter_gear_encoder_cm).suffix(" cm"),
+                "config-diameter-gear-encoder"
             );
             ui.end_row();
-            // diameter_gear_encoder: length::Metres,
             row_label_value!(
-                ui, DragValue::new(conf.diameter_gear_encoder.value_mut()).suffix(" m"),
-                "Diameter Gear Encoder",
-                "Diameter dari gear yang terdapat pada Encoder dynotest chasis (digunakan untuk menghitung informasi data sensor)"
+                ui,
+                DragValue::new(&mut conf.diameter_gear_beban_cm).suffix(" cm"),
+                "config-diameter-gear-beban"
             );
             ui.end_row();
-            // diameter_gear_beban: length::Metres,
             row_label_value!(
-                ui, DragValue::new(conf.diameter_gear_beban.value_mut()).suffix(" m"),
-                "Diameter Gear Beban",
-                "Diameter dari gear yang terdapat pada roller Beban dynotest chasis (digunakan untuk menghitung informasi data sensor)"
+                ui,
+                DragValue::new(&mut conf.berat_beban_kg).suffix(" kg"),
+                "config-berat-beban"
             );
             ui.end_row();
-            // jarak_gear: length::Metres,
             row_label_value!(
-                ui, DragValue::new(conf.jarak_gear.value_mut()).suffix(" m"),
-                "Jarak Antar Gear",
-                "jarak diantara gear pada roller Beban dan sensor Encoder dynotest chasis (digunakan untuk menghitung informasi data sensor)"
+                ui,
+                DragValue::new(&mut conf.max_encoder_pulse).suffix(" ppr"),
+                "config-pulse-encoder"
             );
             ui.end_row();
-            // berat_beban: weight::KiloGram,
             row_label_value!(
-                ui, DragValue::new(conf.berat_beban.value_mut()).suffix(" kg"),
-                "Berat Roller Beban",
-                "berat roller Beban pada dynotest chasis (digunakan untuk menghitung informasi data sensor)"
+                ui,
+                DragValue::new(&mut conf.rpm_pickup_ppr)
+                    .range(1..=16)
+                    .suffix(" ppr"),
+                "config-pulse-rpm"
+            );
+            ui.end_row();
+            row_label_value!(
+                ui,
+                DragValue::new(&mut conf.delta_ms)
+                    .range(250..=2000)
+                    .suffix(" ms"),
+                "config-periode"
             );
         };
-        CollapsingHeader::new("Info Motor Config")
+        CollapsingHeader::new(tr("setting-motor-config"))
             .id_source("dyno_info_motor_config_id")
             .default_open(true)
             .show(ui, |ui| {
@@ -159,7 +231,7 @@ impl SettingWindow {
                     .spacing([40.0, 4.0])
                     .show(ui, info_motor_ui)
             });
-        CollapsingHeader::new("Data Configuration")
+        CollapsingHeader::new(tr("setting-data-config"))
             .id_source("dyno_configuration_id")
             .default_open(true)
             .show(ui, |ui| {
@@ -179,7 +251,7 @@ impl super::WindowState for SettingWindow {
         _state: &mut crate::state::DynoState,
     ) {
         let mut open = self.open;
-        Window::new("Dyno Control Settings")
+        Window::new(tr("setting-title"))
             .id(Id::new("id_control_setting"))
             .open(&mut open)
             .collapsible(false)
@@ -188,9 +260,9 @@ impl super::WindowState for SettingWindow {
                 ui.vertical_centered_justified(|ui| {
                     ui.horizontal(|ui| {
                         use PanelSetting::*;
-                        ui.selectable_value(&mut self.panel, Generic, stringify!(Generic));
-                        ui.selectable_value(&mut self.panel, Info, stringify!(InfoMotor));
-                        ui.selectable_value(&mut self.panel, Style, stringify!(Style));
+                        ui.selectable_value(&mut self.panel, Generic, tr("setting-generic"));
+                        ui.selectable_value(&mut self.panel, Info, tr("setting-info"));
+                        ui.selectable_value(&mut self.panel, Style, tr("setting-style"));
                     });
                 });
                 ui.separator();
@@ -198,20 +270,32 @@ impl super::WindowState for SettingWindow {
                 ScrollArea::vertical()
                     .id_source("dyno_settings")
                     .show(ui, |scr_ui| {
-                        let crate::control::DynoControl {
-                            paths,
-                            app_config,
-                            config,
-                            ..
-                        } = control;
                         match self.panel {
                             PanelSetting::Generic => {
-                                self.setting_generic(scr_ui, app_config, paths)
+                                self.setting_generic(
+                                    scr_ui,
+                                    &mut control.app_config,
+                                    &mut control.paths,
+                                );
+                                scr_ui.separator();
+                                Self::setting_serial(scr_ui, control);
+                                scr_ui.separator();
+                                Self::setting_server(scr_ui, control);
+                                scr_ui.separator();
+                                CollapsingHeader::new(format!("🖨 {}", tr("setting-report")))
+                                    .id_source("dyno_report_setting_id")
+                                    .show(scr_ui, |ui| control.app_config.report.ui(ui));
+                                scr_ui.separator();
+                                CollapsingHeader::new(format!("⚠ {}", tr("setting-alarms")))
+                                    .id_source("dyno_alarm_setting_id")
+                                    .show(scr_ui, |ui| {
+                                        control.app_config.alarms.ui(ui, &control.config)
+                                    });
                             }
                             PanelSetting::Info => {
-                                scr_ui.heading("Info Setting");
+                                scr_ui.heading(tr("setting-info-heading"));
                                 scr_ui.separator();
-                                Self::setting_info(scr_ui, config)
+                                Self::setting_info(scr_ui, &mut control.config)
                             }
                             PanelSetting::Style => {
                                 ctx.settings_ui(scr_ui);
@@ -0,0 +1,243 @@
+use eframe::egui::{
+    Align2, Button, CollapsingHeader, Grid, ScrollArea, TextEdit, Ui, Vec2, Window,
+};
+
+use crate::{control::DynoControl, row_label_value, windows::setting::SettingWindow};
+use dyno_core::{
+    i18n::{tr, tr_attr},
+    model::vehicle::Vehicle,
+    serde,
+};
+
+/// search, edit and link the vehicles of the registry to the session
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "serde")]
+pub struct VehicleWindow {
+    open: bool,
+    query: String,
+    /// vehicle in the form, not saved to the registry until `Save` is clicked
+    #[serde(skip)]
+    edit: Option<Vehicle>,
+}
+
+impl VehicleWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn search_ui(&mut self, ui: &mut Ui, control: &mut DynoControl) {
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.query)
+                    .hint_text(format!("🔍 {}", tr("vehicle-search")))
+                    .desired_width(200.),
+            );
+            if ui.button(format!("➕ {}", tr("vehicle-new"))).clicked() {
+                self.edit = Some(Vehicle {
+                    uuid: uuid::Uuid::new_v4().to_string(),
+                    ..Default::default()
+                });
+            }
+            if ui
+                .button(format!("➕ {}", tr("vehicle-from-session")))
+                .on_hover_text(tr_attr("vehicle-from-session", "hint"))
+                .clicked()
+            {
+                self.edit = Some(control.vehicle_from_session());
+            }
+        });
+        ui.horizontal(|ui| {
+            let logined = control.api().is_some_and(|api| api.is_logined());
+            if ui
+                .add_enabled(logined, Button::new(format!("🔃 {}", tr("vehicle-sync"))))
+                .on_disabled_hover_text(tr_attr("vehicle-sync", "hint"))
+                .clicked()
+            {
+                control.sync_registry();
+            }
+            let registry = control.registry();
+            let last_sync = registry.last_sync().map_or_else(
+                || tr("vehicle-never-synced"),
+                |time| time.format("%Y-%m-%d %H:%M").to_string(),
+            );
+            ui.small(dyno_core::tr!(
+                "vehicle-sync-status",
+                count = registry.pending(),
+                last = last_sync,
+            ));
+        });
+        ui.separator();
+
+        let linked = control.session().info.vehicle_id.clone();
+        ScrollArea::vertical()
+            .id_source("dyno_vehicle_list")
+            .max_height(160.)
+            .show(ui, |ui| {
+                for vehicle in control.registry().search(&self.query) {
+                    let selected = self.edit.as_ref().is_some_and(|v| v.uuid == vehicle.uuid);
+                    let mut text = format!(
+                        "{} - {} ({})",
+                        vehicle.plate_number,
+                        vehicle.title(),
+                        vehicle.customer
+                    );
+                    if linked.as_ref() == Some(&vehicle.uuid) {
+                        text.push_str(" 🔗");
+                    }
+                    if ui.selectable_label(selected, text).clicked() {
+                        self.edit = Some(vehicle.clone());
+                    }
+                }
+            });
+    }
+
+    fn edit_ui(&mut self, ui: &mut Ui, control: &mut DynoControl) {
+        let Some(vehicle) = &mut self.edit else {
+            return;
+        };
+        Grid::new("dyno_vehicle_grid")
+            .num_columns(2)
+            .spacing([40.0, 4.0])
+            .show(ui, |ui| {
+                row_label_value!(ui, TextEdit::singleline(&mut vehicle.make), "vehicle-make");
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut vehicle.model),
+                    "vehicle-model"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut vehicle.plate_number),
+                    "session-plate-number"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut vehicle.customer),
+                    "vehicle-customer"
+                );
+                ui.end_row();
+                row_label_value!(
+                    ui,
+                    TextEdit::singleline(&mut vehicle.phone),
+                    "vehicle-phone"
+                );
+                ui.end_row();
+                SettingWindow::motor_info_ui(ui, &mut vehicle.motor_info);
+                ui.end_row();
+            });
+        ui.label(tr("session-notes"));
+        ui.add(TextEdit::multiline(&mut vehicle.notes).desired_rows(2));
+
+        let stored = control.registry().get(&vehicle.uuid);
+        let changed = stored.is_none_or(|stored| {
+            Vehicle {
+                updated_at: stored.updated_at,
+                ..vehicle.clone()
+            } != *stored
+        });
+        let exists = stored.is_some();
+        let mut removed = false;
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(changed, Button::new(format!("💾 {}", tr("vehicle-save"))))
+                .clicked()
+            {
+                control.save_vehicle(vehicle.clone());
+            }
+            if ui
+                .button(format!("🔗 {}", tr("vehicle-use")))
+                .on_hover_text(tr_attr("vehicle-use", "hint"))
+                .clicked()
+            {
+                if changed {
+                    control.save_vehicle(vehicle.clone());
+                }
+                control.use_vehicle(&vehicle.uuid);
+            }
+            if ui
+                .add_enabled(exists, Button::new(format!("🗑 {}", tr("vehicle-delete"))))
+                .clicked()
+            {
+                control.remove_vehicle(&vehicle.uuid);
+                removed = true;
+            }
+        });
+        if removed {
+            self.edit = None;
+        }
+    }
+
+    fn history_ui(&self, ui: &mut Ui, control: &DynoControl) {
+        let Some(vehicle) = &self.edit else {
+            return;
+        };
+        let history = control.registry().history(&vehicle.uuid);
+        CollapsingHeader::new(dyno_core::tr!("vehicle-history", count = history.len()))
+            .id_source("dyno_vehicle_history")
+            .show(ui, |ui| {
+                Grid::new("dyno_vehicle_history_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in [
+                            "vehicle-history-date",
+                            "vehicle-history-run",
+                            "vehicle-history-tags",
+                            "vehicle-history-time",
+                            "vehicle-history-power",
+                            "vehicle-history-torque",
+                        ] {
+                            ui.strong(tr(header));
+                        }
+                        ui.end_row();
+                        for run in history.iter().rev() {
+                            let date = run.start.unwrap_or(run.session.naive_utc());
+                            ui.label(date.format("%Y-%m-%d %H:%M").to_string());
+                            ui.label(run.run.as_str());
+                            ui.label(run.tags.join(", "));
+                            ui.label(format!("{:.1}s", run.elapsed_ms as f64 / 1000.));
+                            ui.label(format!("{:.2} HP", run.max_power));
+                            ui.label(format!("{:.2} Nm", run.max_torque));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+impl super::WindowState for VehicleWindow {
+    fn show_window(
+        &mut self,
+        ctx: &eframe::egui::Context,
+        control: &mut DynoControl,
+        _state: &mut crate::state::DynoState,
+    ) {
+        let mut open = self.open;
+        Window::new(tr("vehicle-title"))
+            .id("dyno_vehicle_window".into())
+            .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                self.search_ui(ui, control);
+                if self.edit.is_some() {
+                    ui.separator();
+                    self.edit_ui(ui, control);
+                    self.history_ui(ui, control);
+                }
+            });
+        self.open = open;
+    }
+
+    #[inline]
+    fn set_open(&mut self, open: bool) {
+        self.open = open
+    }
+    #[inline]
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
@@ -7,4 +7,11 @@ then
     echo "\$DOCKER_ID_RUN is empty"
 else
     docker cp "${DOCKER_ID_RUN}:/dynotests/target/debian/" bin/
+    # `.dyno` and `.dynosession` is opened by double click only with the mime type installed,
+    # dpkg trigger of shared-mime-info and desktop-file-utils register it on install
+    for deb in bin/debian/*.deb
+    do
+        dpkg-deb --contents "$deb" | grep -q "usr/share/mime/packages/dynotest_app.xml"
+        dpkg-deb --contents "$deb" | grep -q "usr/share/applications/DynotestApp.desktop"
+    done
 fi
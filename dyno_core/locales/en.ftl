@@ -0,0 +1,562 @@
+# english messages of the desktop app and the web frontend,
+# every key in here must also exist in `id.ftl` (checked by the test in `dyno_core::i18n`)
+
+value-for = Value for { $name }
+language = Language
+    .hint = language of the application, applied immediately
+
+## menu bar of the desktop app
+
+menu-file = File
+menu-open-as = Open As..
+menu-save-as = Save As..
+file-session = Session File
+file-csv = Csv File
+file-excel = Excel File
+file-binaries = Binaries File
+menu-export-sheet = Export Dyno Sheet..
+    .hint = printable sheet of every recorded run in the session
+menu-serial-capture = Serial Capture
+menu-quit = Quit
+menu-view = View
+view-bottom-panel = Bottom Panel
+view-left-panel = Left Panel
+view-logger-window = Logger Window
+menu-session = Session
+menu-vehicles = Vehicles
+menu-procedure = Procedure
+menu-server = Server
+menu-edit-servers = Edit Servers..
+menu-dashboard = Dashboard
+menu-edit-layouts = Edit Layouts..
+menu-config = Config
+menu-help = Help
+menu-about = About
+menu-login = Login
+    .hint = login first to access server, like saving data to server.
+menu-logout = Logout
+menu-open-server = Open from Server
+menu-save-server = Save to Server
+drop-files = Drop the .dyno, .csv, .xlsx or .dynosession files to open them
+
+## realtime data
+
+data-speed = Speed
+    .hint = calculated from rotational speed distance of the roller in dynotests chasis
+data-odo = ODO (Distance)
+    .hint = distance traveled, calculated from rotational rounds of the roller in dynotests chasis
+data-rpm-engine = Rpm Engine
+    .hint = calculated from rotational engine from rpm driver sensor in dynotests chasis
+data-rpm-roda = Rpm Tire
+    .hint = calculated from rotational of the roller in dynotests
+data-torque = Torque
+    .hint = calculated from rotational speed of the roller in dynotests chasis
+data-horsepower = HorsePower
+    .hint = calculated from rotational speed of the roller in dynotests chasis
+data-engine-temp = Engine Temperature
+    .hint = calculated from thermocouple sensor driver in dynotests chasis
+data-temp-channels = Temperature Channels
+    .hint = every thermocouple channel, named in the configuration
+
+## settings window
+
+setting-title = Dyno Control Settings
+setting-generic = Generic
+setting-info = Info Motor
+setting-style = Style
+setting-info-heading = Info Setting
+setting-paths = Paths
+setting-configurations = Configurations
+setting-serial = Serial Link
+setting-server = Server
+setting-report = Dyno Sheet
+setting-alarms = Alarms
+setting-test-serial = Test Connection
+    .hint = ping the device and check which parameter is not match
+setting-test-server = Test Connection
+    .hint = check the health of the server, without connecting to it
+setting-serial-default = Reset to Firmware Default
+setting-connect = Connect
+    .hint = connect to this server and restore its login
+setting-connected-to = connected to { $url }
+setting-motor-config = Info Motor Config
+setting-data-config = Data Configuration
+
+option-heading = Application Options Setting
+option-show-startup = Show Startup Window
+option-segment-style = Style for SevenSegment
+option-icon-path = Icon Path
+    .hint = icon of the application, default to the icon embedded into the app
+option-always-on-top = Always On Top
+    .hint = keep the window on top of the other windows
+option-maximize = Maximize
+    .hint = open the window maximized
+option-decorated = Decorated
+    .hint = show the title bar and the border of the window
+option-fullscreen = Fullscreen
+    .hint = open the window fullscreen
+option-drag-and-drop = Drag and Drop
+    .hint = allow dropping files into the window
+option-resizable = Resizeable
+    .hint = allow resizing the window
+option-follow-theme = Follow System Theme
+    .hint = dark or light theme follow the os
+
+serial-baud-rate = Baud Rate
+    .hint = speed of the serial link, must match the firmware (default 512000)
+serial-parity = Parity
+    .hint = parity bit of the serial link, must match the firmware (default Even)
+serial-stop-bits = Stop Bits
+    .hint = stop bits of the serial link, must match the firmware (default 1)
+serial-flow-control = Flow Control
+    .hint = flow control of the serial link, must match the firmware (default RTS/CTS)
+serial-read-timeout = Read Timeout
+    .hint = time to wait for the answer of the device
+
+link-connected = Connected to firmware { $version } ({ $status })
+link-mismatch = The { $param } is not match: configured `{ $configured }`, but the device answer with `{ $device }`
+link-no-response = No answer from the device, check the cable, the port or more than one parameter
+
+server-url = Url
+    .hint = address of the server, e.g https://dyno.example.com
+server-verify-tls = Verify TLS
+    .hint = check the https certificate of the server, only turn off for testing
+server-ca-file = CA File
+    .hint = CA certificate (PEM) for server with self signed certificate
+server-ca-pick = Certificate Authority
+server-timeout = Timeout
+    .hint = time to wait for every request to the server
+server-live-stream = Live Stream
+    .hint = send the recorded data to the live page of the server while logged in
+
+report-workshop = Workshop
+    .hint = name of the workshop in the header of the dyno sheet
+report-address = Address
+    .hint = address of the workshop in the header of the dyno sheet
+report-operator = Operator
+    .hint = name of the dynotest operator
+
+## motor and dynotest configuration
+
+motor-kind = Motor Kind
+    .hint = kind of the motor, engine or electric (only for data information)
+motor-cc = CC
+    .hint = engine capacity (only for data information)
+motor-cylinder = Cylinder
+    .hint = cylinder of the engine (for data information and rpm engine calculation)
+motor-stroke = Stroke
+    .hint = stroke of the engine (for data information and rpm engine calculation)
+motor-transmition = Transmition
+    .hint = transmition of the engine (only for data information)
+
+config-diameter-roller = Roller Diameter
+    .hint = diameter of the dynotest chasis roller (used to calculate the sensor data)
+config-diameter-roller-beban = Load Roller Diameter
+    .hint = diameter of the dynotest chasis load roller (used to calculate the sensor data)
+config-diameter-gear-encoder = Encoder Gear Diameter
+    .hint = diameter of the gear on the dynotest chasis encoder (used to calculate the sensor data)
+config-diameter-gear-beban = Load Gear Diameter
+    .hint = diameter of the gear on the dynotest chasis load roller (used to calculate the sensor data)
+config-berat-beban = Load Roller Weight
+    .hint = weight of the dynotest chasis load roller (used to calculate the sensor data)
+config-pulse-encoder = Pulse Encoder
+    .hint = pulses of the encoder in one round of the roller
+config-pulse-rpm = Pulse RPM Pickup
+    .hint = pulses of the rpm sensor in one round of the engine
+config-periode = Data Period
+    .hint = period of taking the data from the dynotest chasis
+
+## session and vehicles
+
+session-customer = Customer
+    .hint = name of the customer
+session-phone = Phone
+    .hint = phone number of the customer
+session-vehicle = Vehicle
+    .hint = make and model of the vehicle
+session-plate-number = Plate Number
+    .hint = plate number of the vehicle
+
+vehicle-make = Make
+    .hint = make of the vehicle
+vehicle-model = Model
+    .hint = model of the vehicle
+vehicle-customer = Owner
+    .hint = name of the owner of the vehicle
+vehicle-phone = Phone
+    .hint = phone number of the owner
+save-drops-annotations = { $format } file can not keep the { $count } annotations of the run, save it as session or .dyno to keep them
+
+session-title = Session
+session-new = New Session
+    .hint = Save the current session first
+session-runs = Runs
+session-linked = Linked to { $vehicle }
+session-deleted-vehicle = deleted vehicle
+session-unlink = Unlink
+session-notes = Notes:
+session-run-data = { $count } data
+session-delete-run = Delete this run
+session-tags = Tags:
+    .hint = stock, new intake, ..
+session-run-notes = what is changed before this run
+
+vehicle-title = Vehicles
+vehicle-search = plate, make, customer..
+vehicle-new = New
+vehicle-from-session = From Session
+    .hint = new vehicle from the customer of the session and the motor info
+vehicle-sync = Sync
+    .hint = Login first, the changes stay queued until then
+vehicle-never-synced = never
+vehicle-sync-status = { $count } queued, last sync { $last }
+vehicle-save = Save
+vehicle-use = Use for Session
+    .hint = fill the customer of the session and the motor info from this
+vehicle-delete = Delete
+vehicle-history = History ({ $count })
+vehicle-history-date = Date
+vehicle-history-run = Run
+vehicle-history-tags = Tags
+vehicle-history-time = Time
+vehicle-history-power = Power
+vehicle-history-torque = Torque
+
+## common actions and buttons
+
+action-reset = Reset
+action-duplicate = Duplicate
+action-delete = Delete
+
+button-ok = Ok
+button-cancel = Cancel
+button-apply = Apply
+button-reset = Reset
+button-open = Open
+button-save = Save
+button-save-as = Save As...
+button-close = Close
+button-delete = Delete
+button-play = Play
+button-pause = Pause
+button-stop = Stop
+button-record = Record
+button-next = Next
+button-previous = Previous
+button-full-screen = Full Screen
+button-random = Random
+button-edit = Edit
+button-favorite = Favorite
+button-unfavorite = Unfavorite
+button-mute = Mute
+button-unmute = Unmute
+button-lock = Lock
+button-unlock = Unlock
+button-refresh = Refresh
+button-new = New
+button-copy = Copy
+button-paste = Paste
+button-cut = Cut
+button-no = No
+
+## status bar and toasts of the desktop app
+
+status-run-selector = Run of the session that is displayed and recorded
+status-new-run = New Run
+status-replaying = STATUS: Replaying
+status-reconnecting = STATUS: Reconnecting
+status-running = STATUS: Running
+status-connected = STATUS: Connected
+status-not-connected = STATUS: Not Initialize / Connected
+    .hint = PORT INFO: [NO PORT DETECTED] (XX:XX), click to try Initialize the port
+status-reconnect = Try Reconnect
+status-port-info = PORT INFO: [{ $port }] ({ $vid }:{ $pid })
+status-start = Click to Start the Service
+status-stop = Click to Stop/Pause the Service
+status-reset = Click to Stop and Reset recorded data of the selected run
+status-motor-info = Active Info: { $kind } { $cc }cc, { $stroke } Stroke, { $cylinder } Cylinder, { $transmition }
+status-no-port = No Port
+status-unknown-device = Unknown Device
+status-port-locked = stop the service first to change the port
+
+live-status = live
+live-streaming = the recording is streamed to the live page of the server
+live-connecting = connecting to the server, the samples is kept until then
+
+outbox-queued-logout = { $count } queued, login to upload
+outbox-queued = { $count } queued
+outbox-sending = sending { $sending }/{ $count }
+outbox-next-retry = next try in { $seconds }s, click to retry now
+outbox-item-sending = sending
+outbox-item-failed = { $attempts } failed - { $error }
+outbox-item-waiting = waiting
+
+capture-raw = Capture Raw Traffic
+    .hint = write every received byte to `.dynocap` file in the cache folder
+capture-replay-speed = Replay Speed
+    .hint = multiplier of the recorded timing, 0 is as fast as possible
+capture-replay = Replay Capture..
+capture-virtual-port = Connect
+    .hint = pseudo-terminal or port that fed by a simulator
+
+toast-stop-before-restore = Stop the running Serial Service before restoring the session
+toast-stop-before-new-session = Stop the running Serial Service before starting new session
+toast-stop-before-new-run = Stop the running Serial Service before adding new run
+toast-stop-before-open = Stop the running Serial Service before opening files
+toast-stop-before-remove-run = Stop the running Serial Service before removing the recorded run
+toast-stop-before-test-link = Stop the running Serial Service before testing the connection
+toast-stop-before-replay = Stop the running Serial Service before replaying a capture
+toast-session-restored = Restored unsaved session with { $count } runs
+toast-unknown-format = Unknown format of `{ $path }`, use .dyno, .csv, .xlsx or .dynosession
+toast-api-connected = SUCCES! connected to Api Endpoint: { $url }
+toast-api-healthy = API Check Health is Success
+toast-api-reconnecting = Not connected to API, trying to reconnecting..
+toast-no-port = Failed to get port info, there is no port available in this machine
+toast-api-client-failed = Failed to create Api Client - { $error }
+toast-api-active = Success Connecting to API Server!
+toast-api-non-active = Success Disconnecting to API Server!
+toast-logout = Logout is Success!
+toast-port-info-failed = Failed to get port info, { $error }
+toast-replay-finished = Serial capture replay is finished
+toast-serial-connected = SUCCES! connected to Serial: [{ $port }]:[{ $vid }-{ $pid }]
+toast-serial-start-failed = Serial Service Failed to start - { $error }
+toast-serial-disconnected = Serial device disconnected, waiting to reconnect - { $error }
+toast-serial-reconnected = Serial device reconnected on `{ $port }` after { $gap }s
+toast-port-detached = Serial port `{ $port }` is detached
+toast-capture-loaded = Serial capture is loaded as `{ $port }`
+toast-device-config = Loaded config from device `{ $device }`
+toast-opened-runs = Opened { $count } runs
+toast-opened-session = Opened session with { $count } runs
+toast-open-failed = Failed to open `{ $name }` - { $error }
+toast-upload-saved = Run is saved to the server with id { $id }
+toast-upload-failed = Failed to save the run to the server, it is retried later - { $error }
+toast-upload-queued = Run `{ $name }` is queued to be saved to the server
+toast-upload-queue-failed = Failed to queue the run - { $error }
+toast-login-restored = Logged in with the saved session
+toast-procedure-step = Step { $number }: { $name } - { $prompt }
+toast-procedure-done = Procedure `{ $name }` is finished
+toast-alarm-stopped = Recording is stopped by the alarm
+toast-alarm-device-stopped = Device is stopped by the alarm
+toast-registry-save-failed = Failed to save the vehicle registry - { $error }
+toast-vehicle-linked = Session is linked to `{ $vehicle }`
+toast-report-empty = There is no recorded data to print
+toast-report-saved = Dyno sheet saved to `{ $path }`
+
+annotation-procedure-step = STEP { $number }/{ $total }: { $name }
+annotation-procedure-done = PROCEDURE DONE: { $name }
+
+## plot
+
+plot-instructions = Instructions
+plot-help-pan = Pan by dragging, or scroll (+ shift = horizontal).
+plot-help-box-zoom = Box zooming: Middle (Scroll) click to zoom in and zoom out using a selection.
+plot-help-zoom = Zoom with ctrl + scroll.
+plot-help-reset = Reset view with double-click.
+plot-help-context-menu = Change behaviour with context menu right click in the plot
+plot-help-cursor = Move the nearest cursor by dragging, when the cursors is showed.
+plot-select-panel = Select Plot to Show
+plot-panel-all = All Plot
+plot-panel-first = First Plot
+plot-panel-second = Second Plot
+plot-cursors = Cursors
+plot-channels = Channels
+plot-config = Plot Config
+plot-drag = Drag
+plot-zoom = Zoom
+plot-scroll = Scroll
+plot-boxed-zoom = Boxed Zoom
+plot-animate = Animate
+plot-points-to-show = Points to Show
+plot-all-points = All Points
+plot-half-points = Half Points
+plot-quarter-points = Quarter Points
+plot-from-point = From Point { $index }
+plot-show-from = Show From
+plot-channel = Channel
+plot-average-power = Average Power: { $power } HP
+plot-note = Note
+plot-annotate-cursor = Add at Cursor A
+plot-annotate-last = Add at Last Data
+plot-annotations = Annotations ({ $count })
+plot-no-annotation = There is no annotation
+plot-peak = peak { $value } { $unit } @ { $rpm } rpm
+plot-x-axis = X Axis
+plot-x-time = Time
+plot-x-rpm-engine = Engine RPM
+plot-x-speed = Roller Speed
+plot-axis-left = Left
+plot-axis-right = Right
+plot-line-solid = Solid
+plot-line-dashed = Dashed
+plot-line-dotted = Dotted
+
+channel-speed = Speed
+channel-rpm-engine = RPM (engine)
+channel-rpm-roda = RPM (roda)
+channel-torque = Torque
+channel-power = HorsePower
+
+## dashboard
+
+dash-title = Dashboard Layout
+dash-left-panel = Left Panel
+dash-right-panel = Right Panel
+dash-add-row = Add Row
+dash-row = Row { $number }
+dash-row-remove = Row
+dash-tile = Tile
+dash-source = Source
+dash-widget = Widget
+dash-range = Range
+dash-scale = Scale
+dash-decimals = decimals
+dash-warning = Warning
+dash-redline = Redline
+dash-gauge = Gauge
+dash-segment = Seven Segment
+dash-bar = Bar
+dash-numeric = Numeric
+dash-odo = ODO
+dash-time = Time
+
+## alarms
+
+alarm-enable = Enable Alarms
+alarm-add-rule = Add Rule
+alarm-new-rule = New alarm
+alarm-rule = Rule
+alarm-channel = Channel
+alarm-condition = Condition
+alarm-severity = Severity
+alarm-actions = Actions
+alarm-toast = Toast
+alarm-sound = Sound
+    .hint = beep on the speaker, three times for critical
+alarm-bell = Blink Window
+    .hint = blink the border of the window and ask the attention of the os
+alarm-flash = Flash
+alarm-stop-recording = Stop Recording
+    .hint = end the recording of the run, the device is stopped too when Stop Device is checked
+alarm-stop-device = Stop Device
+    .hint = send the stop command to the device, the recording keep going
+alarm-above = Above
+alarm-below = Below
+alarm-rate-above = Rate Above
+alarm-sensor-fault = Sensor Fault
+alarm-engine-temp-high = Engine temperature high
+alarm-engine-overheat = Engine overheat
+alarm-thermocouple-fault = Thermocouple fault
+alarm-roller-overspeed = Roller overspeed
+alarm-message = ALARM { $severity }: { $name } ({ $value } { $unit })
+severity-info = Info
+severity-warning = Warning
+severity-critical = Critical
+
+## test procedure
+
+procedure-title = Test Procedure
+procedure-none = No Procedure
+procedure-reload = Reload
+    .hint = load the procedures again from the config folder
+procedure-start = Start Procedure
+procedure-not-recording = Start the recording, the step is detected from the live data
+procedure-confirm = Confirm
+procedure-skip = Skip Step
+procedure-done = Every step is done
+procedure-close = Close Procedure
+procedure-abort = Abort Procedure
+step-manual = confirm to continue
+step-above = until above { $value } { $unit }
+step-below = until below { $value } { $unit }
+step-hold = hold { $value } ± { $tolerance } { $unit } for { $seconds } s
+step-duration = wait { $seconds } s
+
+## paths
+
+path-app-dir = app dir name
+path-edit = Edit Paths Config
+path-edit-hint = Click on the path in the right to edit the paths
+path-click-edit = Click to Edit
+path-click-change = Left Click to Change
+path-change = Change Path
+path-project = PROJECT PATH
+path-cache = CACHE PATH
+path-config = CONFIG PATH
+path-data = DATA PATH
+path-data-local = DATA LOCAL PATH
+path-preference = PREFERENCE PATH
+
+## other windows of the desktop app
+
+quit-title = Do you wanna close the Application?
+quit-hint = click 'Ok' to close the app or 'No' to rebort close event
+unsaved-title = Buffer Data Records is unsaved. Do you want to save it?
+unsaved-message = there is data recorded in buffer, and its not saved
+unsaved-hint = click 'Save' to save it or 'No' to rebort it, or 'Cancel' to cancel it
+recovery-title = Restore Unsaved Session?
+recovery-message = the app was not closed properly, and there is unsaved recorded data
+recovery-customer = Customer: { $customer }
+recovery-hint = click 'Ok' to restore it, or 'No' to discard it
+help-title = Help
+help-upcoming = Upcoming Update! Help Window
+about-title = About
+about-version = Version { $version }
+about-license = License
+about-license-file = License: See the { $file } file for details.
+about-homepage = Home page
+about-repository = Repository
+about-authors = Authors
+
+log-title = Dyno Log Window
+log-clear = Clear
+log-levels = Log Levels
+log-search = Search
+log-case-sensitive = Case sensitive
+log-max-output = Max Log output
+log-sort = Sort
+log-size = Log size: { $count }
+log-displayed = Displayed: { $count }
+log-save-dialog = Saving Log File
+log-copy = Copy
+
+auth-login = Login
+auth-register = Register
+auth-nim = nim
+auth-email = email
+auth-password = password
+auth-confirm-password = confirm password
+auth-password-mismatch = Password not matching with first password
+auth-role = Role
+auth-show-password = show password
+auth-submit = Submit
+
+server-list-window = Open DynoTests from Server
+server-list-title = List File in Server
+server-list-id = id
+server-list-info-id = info id
+server-list-verified = verified
+server-list-not-verified = not verified
+server-list-updated = updated
+server-list-created = created
+server-list-open = open
+server-list-refresh = Refresh
+server-save-title = Save DynoTests to Server
+server-save-info = Info Dynotests:
+server-save = Save
+
+## web frontend
+
+nav-dashboard = Dashboard
+nav-activities = Activities
+nav-profile = Profile
+nav-administration = Administration
+nav-users = Users
+nav-dynos = Dynos
+nav-infos = Infos
+nav-history = History
+nav-sop = SOP
+header-profile-settings = Profile Settings
+header-logout = Logout
+live-title = Dynotest Live Data
+live-start = Start Stream
+live-stop = Stop Stream
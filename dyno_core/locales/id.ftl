@@ -0,0 +1,563 @@
+# pesan bahasa indonesia dari desktop app dan web frontend,
+# setiap key di sini harus ada juga di `en.ftl` (dicek oleh test di `dyno_core::i18n`)
+
+value-for = Nilai untuk { $name }
+language = Bahasa
+    .hint = bahasa aplikasi, langsung diterapkan
+
+## menu bar desktop app
+
+menu-file = Berkas
+menu-open-as = Buka Sebagai..
+menu-save-as = Simpan Sebagai..
+file-session = Berkas Sesi
+file-csv = Berkas Csv
+file-excel = Berkas Excel
+file-binaries = Berkas Biner
+menu-export-sheet = Ekspor Dyno Sheet..
+    .hint = lembar cetak dari setiap run yang direkam pada sesi
+menu-serial-capture = Rekaman Serial
+menu-quit = Keluar
+menu-view = Tampilan
+view-bottom-panel = Panel Bawah
+view-left-panel = Panel Kiri
+view-logger-window = Jendela Log
+menu-session = Sesi
+menu-vehicles = Kendaraan
+menu-procedure = Prosedur
+menu-server = Server
+menu-edit-servers = Ubah Server..
+menu-dashboard = Dashboard
+menu-edit-layouts = Ubah Tata Letak..
+menu-config = Konfigurasi
+menu-help = Bantuan
+menu-about = Tentang
+menu-login = Masuk
+    .hint = masuk terlebih dahulu untuk mengakses server, seperti menyimpan data ke server.
+menu-logout = Keluar Akun
+menu-open-server = Buka dari Server
+menu-save-server = Simpan ke Server
+drop-files = Lepaskan berkas .dyno, .csv, .xlsx atau .dynosession untuk membukanya
+
+## data realtime
+
+data-speed = Kecepatan
+    .hint = dihitung dari jarak putaran roller pada dynotest chasis
+data-odo = ODO (Jarak Tempuh)
+    .hint = jarak tempuh, dihitung dari jumlah putaran roller pada dynotest chasis
+data-rpm-engine = Rpm Mesin
+    .hint = dihitung dari putaran mesin oleh sensor rpm pada dynotest chasis
+data-rpm-roda = Rpm Roda
+    .hint = dihitung dari putaran roller pada dynotest
+data-torque = Torsi
+    .hint = dihitung dari kecepatan putaran roller pada dynotest chasis
+data-horsepower = Tenaga Kuda
+    .hint = dihitung dari kecepatan putaran roller pada dynotest chasis
+data-engine-temp = Suhu Mesin
+    .hint = dihitung dari sensor thermocouple pada dynotest chasis
+data-temp-channels = Kanal Suhu
+    .hint = setiap kanal thermocouple, namanya diatur di konfigurasi
+
+## jendela pengaturan
+
+setting-title = Pengaturan Dyno Control
+setting-generic = Umum
+setting-info = Info Motor
+setting-style = Gaya
+setting-info-heading = Pengaturan Info
+setting-paths = Direktori
+setting-configurations = Konfigurasi
+setting-serial = Serial Link
+setting-server = Server
+setting-report = Dyno Sheet
+setting-alarms = Alarm
+setting-test-serial = Tes Koneksi
+    .hint = ping perangkat dan cek parameter mana yang tidak cocok
+setting-test-server = Tes Koneksi
+    .hint = cek kesehatan server, tanpa terhubung ke server
+setting-serial-default = Kembalikan ke Default Firmware
+setting-connect = Hubungkan
+    .hint = hubungkan ke server ini dan pulihkan loginnya
+setting-connected-to = terhubung ke { $url }
+setting-motor-config = Konfigurasi Info Motor
+setting-data-config = Konfigurasi Data
+
+option-heading = Pengaturan Opsi Aplikasi
+option-show-startup = Tampilkan Jendela Awal
+option-segment-style = Gaya SevenSegment
+option-icon-path = Path Icon
+    .hint = icon aplikasi, default icon yang tertanam di aplikasi
+option-always-on-top = Selalu di Atas
+    .hint = jendela selalu di atas jendela lain
+option-maximize = Maksimal
+    .hint = buka jendela dalam ukuran maksimal
+option-decorated = Dekorasi
+    .hint = tampilkan title bar dan border jendela
+option-fullscreen = Layar Penuh
+    .hint = buka jendela dalam layar penuh
+option-drag-and-drop = Drag and Drop
+    .hint = izinkan menjatuhkan berkas ke jendela
+option-resizable = Ubah Ukuran
+    .hint = izinkan mengubah ukuran jendela
+option-follow-theme = Ikuti Tema Sistem
+    .hint = tema gelap atau terang mengikuti os
+
+serial-baud-rate = Baud Rate
+    .hint = kecepatan serial link, harus sama dengan firmware (default 512000)
+serial-parity = Parity
+    .hint = parity bit serial link, harus sama dengan firmware (default Even)
+serial-stop-bits = Stop Bits
+    .hint = jumlah stop bit serial link, harus sama dengan firmware (default 1)
+serial-flow-control = Flow Control
+    .hint = flow control serial link, harus sama dengan firmware (default RTS/CTS)
+serial-read-timeout = Batas Waktu Baca
+    .hint = waktu tunggu jawaban dari device
+
+link-connected = Terhubung ke firmware { $version } ({ $status })
+link-mismatch = { $param } tidak cocok: dikonfigurasi `{ $configured }`, tetapi perangkat menjawab `{ $device }`
+link-no-response = Tidak ada jawaban dari perangkat, cek kabel, port atau lebih dari satu parameter
+
+server-url = Url
+    .hint = alamat server, contoh https://dyno.example.com
+server-verify-tls = Verifikasi TLS
+    .hint = periksa sertifikat server https, matikan hanya untuk testing
+server-ca-file = Berkas CA
+    .hint = sertifikat CA (PEM) untuk server dengan sertifikat self signed
+server-ca-pick = Certificate Authority
+server-timeout = Batas Waktu
+    .hint = waktu tunggu setiap request ke server
+server-live-stream = Live Stream
+    .hint = kirim data rekaman ke halaman live server saat login
+
+report-workshop = Bengkel
+    .hint = nama bengkel di header dyno sheet
+report-address = Alamat
+    .hint = alamat bengkel di header dyno sheet
+report-operator = Operator
+    .hint = nama operator dynotest
+
+## konfigurasi motor dan dynotest
+
+motor-kind = Jenis Motor
+    .hint = jenis motor, mesin atau listrik (hanya untuk informasi data)
+motor-cc = CC
+    .hint = kapasitas mesin (hanya untuk informasi data)
+motor-cylinder = Silinder
+    .hint = silinder mesin (hanya untuk informasi data dan perhitungan rpm engine)
+motor-stroke = Langkah
+    .hint = stroke mesin (hanya untuk informasi data dan perhitungan rpm engine)
+motor-transmition = Transmisi
+    .hint = transmisi mesin (hanya untuk informasi data)
+
+config-diameter-roller = Diameter Roller
+    .hint = diameter dari roller dynotest chasis (digunakan untuk menghitung informasi data sensor)
+config-diameter-roller-beban = Diameter Roller Beban
+    .hint = diameter dari roller beban dynotest chasis (digunakan untuk menghitung informasi data sensor)
+config-diameter-gear-encoder = Diameter Gear Encoder
+    .hint = diameter dari gear yang terdapat pada encoder dynotest chasis (digunakan untuk menghitung informasi data sensor)
+config-diameter-gear-beban = Diameter Gear Beban
+    .hint = diameter dari gear yang terdapat pada roller beban dynotest chasis (digunakan untuk menghitung informasi data sensor)
+config-berat-beban = Berat Roller Beban
+    .hint = berat roller beban pada dynotest chasis (digunakan untuk menghitung informasi data sensor)
+config-pulse-encoder = Pulse Encoder
+    .hint = jumlah pulse encoder dalam satu putaran roller
+config-pulse-rpm = Pulse RPM Pickup
+    .hint = jumlah pulse sensor rpm dalam satu putaran mesin
+config-periode = Periode Data
+    .hint = periode pengambilan data dari dynotest chasis
+
+## sesi dan kendaraan
+
+session-customer = Pelanggan
+    .hint = nama pelanggan
+session-phone = Telepon
+    .hint = nomor telepon pelanggan
+session-vehicle = Kendaraan
+    .hint = merk dan tipe kendaraan
+session-plate-number = Nomor Polisi
+    .hint = nomor polisi kendaraan
+
+vehicle-make = Merk
+    .hint = merk kendaraan
+vehicle-model = Tipe
+    .hint = tipe kendaraan
+vehicle-customer = Pemilik
+    .hint = nama pemilik kendaraan
+vehicle-phone = Telepon
+    .hint = nomor telepon pemilik
+
+save-drops-annotations = Berkas { $format } tidak dapat menyimpan { $count } anotasi dari run ini, simpan sebagai sesi atau .dyno untuk menyimpannya
+
+session-title = Sesi
+session-new = Sesi Baru
+    .hint = Simpan sesi saat ini terlebih dahulu
+session-runs = Run
+session-linked = Terhubung ke { $vehicle }
+session-deleted-vehicle = kendaraan terhapus
+session-unlink = Lepas
+session-notes = Catatan:
+session-run-data = { $count } data
+session-delete-run = Hapus run ini
+session-tags = Tag:
+    .hint = standar, intake baru, ..
+session-run-notes = apa yang diubah sebelum run ini
+
+vehicle-title = Kendaraan
+vehicle-search = plat, merek, pelanggan..
+vehicle-new = Baru
+vehicle-from-session = Dari Sesi
+    .hint = kendaraan baru dari pelanggan sesi dan info motor
+vehicle-sync = Sinkron
+    .hint = Login terlebih dahulu, perubahan tetap diantrekan sampai saat itu
+vehicle-never-synced = belum pernah
+vehicle-sync-status = { $count } antre, sinkron terakhir { $last }
+vehicle-save = Simpan
+vehicle-use = Gunakan untuk Sesi
+    .hint = isi pelanggan sesi dan info motor dari kendaraan ini
+vehicle-delete = Hapus
+vehicle-history = Riwayat ({ $count })
+vehicle-history-date = Tanggal
+vehicle-history-run = Run
+vehicle-history-tags = Tag
+vehicle-history-time = Waktu
+vehicle-history-power = Tenaga
+vehicle-history-torque = Torsi
+
+## aksi dan tombol umum
+
+action-reset = Reset
+action-duplicate = Duplikat
+action-delete = Hapus
+
+button-ok = Ok
+button-cancel = Batal
+button-apply = Terapkan
+button-reset = Reset
+button-open = Buka
+button-save = Simpan
+button-save-as = Simpan Sebagai...
+button-close = Tutup
+button-delete = Hapus
+button-play = Putar
+button-pause = Jeda
+button-stop = Berhenti
+button-record = Rekam
+button-next = Berikutnya
+button-previous = Sebelumnya
+button-full-screen = Layar Penuh
+button-random = Acak
+button-edit = Ubah
+button-favorite = Favorit
+button-unfavorite = Hapus Favorit
+button-mute = Bisukan
+button-unmute = Bunyikan
+button-lock = Kunci
+button-unlock = Buka Kunci
+button-refresh = Muat Ulang
+button-new = Baru
+button-copy = Salin
+button-paste = Tempel
+button-cut = Potong
+button-no = Tidak
+
+## status bar dan toast aplikasi desktop
+
+status-run-selector = Run dari sesi yang ditampilkan dan direkam
+status-new-run = Run Baru
+status-replaying = STATUS: Memutar Ulang
+status-reconnecting = STATUS: Menghubungkan Ulang
+status-running = STATUS: Berjalan
+status-connected = STATUS: Terhubung
+status-not-connected = STATUS: Belum Diinisialisasi / Terhubung
+    .hint = INFO PORT: [TIDAK ADA PORT] (XX:XX), klik untuk mencoba inisialisasi port
+status-reconnect = Coba Hubungkan Ulang
+status-port-info = INFO PORT: [{ $port }] ({ $vid }:{ $pid })
+status-start = Klik untuk Memulai Layanan
+status-stop = Klik untuk Menghentikan/Menjeda Layanan
+status-reset = Klik untuk Menghentikan dan Mereset data rekaman dari run yang dipilih
+status-motor-info = Info Aktif: { $kind } { $cc }cc, { $stroke } Tak, { $cylinder } Silinder, { $transmition }
+status-no-port = Tidak Ada Port
+status-unknown-device = Perangkat Tidak Dikenal
+status-port-locked = hentikan layanan terlebih dahulu untuk mengganti port
+
+live-status = live
+live-streaming = rekaman dikirim ke halaman live server
+live-connecting = menghubungkan ke server, sampel disimpan sampai saat itu
+
+outbox-queued-logout = { $count } antre, login untuk mengunggah
+outbox-queued = { $count } antre
+outbox-sending = mengirim { $sending }/{ $count }
+outbox-next-retry = coba lagi dalam { $seconds } detik, klik untuk coba sekarang
+outbox-item-sending = mengirim
+outbox-item-failed = { $attempts } gagal - { $error }
+outbox-item-waiting = menunggu
+
+capture-raw = Rekam Lalu Lintas Mentah
+    .hint = tulis setiap byte yang diterima ke berkas `.dynocap` di folder cache
+capture-replay-speed = Kecepatan Putar Ulang
+    .hint = pengali waktu rekaman, 0 berarti secepat mungkin
+capture-replay = Putar Ulang Rekaman..
+capture-virtual-port = Hubungkan
+    .hint = pseudo-terminal atau port yang diisi oleh simulator
+
+toast-stop-before-restore = Hentikan Layanan Serial yang berjalan sebelum memulihkan sesi
+toast-stop-before-new-session = Hentikan Layanan Serial yang berjalan sebelum memulai sesi baru
+toast-stop-before-new-run = Hentikan Layanan Serial yang berjalan sebelum menambah run baru
+toast-stop-before-open = Hentikan Layanan Serial yang berjalan sebelum membuka berkas
+toast-stop-before-remove-run = Hentikan Layanan Serial yang berjalan sebelum menghapus run yang direkam
+toast-stop-before-test-link = Hentikan Layanan Serial yang berjalan sebelum menguji koneksi
+toast-stop-before-replay = Hentikan Layanan Serial yang berjalan sebelum memutar ulang rekaman
+toast-session-restored = Sesi yang belum disimpan dipulihkan dengan { $count } run
+toast-unknown-format = Format `{ $path }` tidak dikenal, gunakan .dyno, .csv, .xlsx atau .dynosession
+toast-api-connected = BERHASIL! terhubung ke Api Endpoint: { $url }
+toast-api-healthy = Cek Kesehatan API Berhasil
+toast-api-reconnecting = Tidak terhubung ke API, mencoba menghubungkan ulang..
+toast-no-port = Gagal mendapatkan info port, tidak ada port yang tersedia di mesin ini
+toast-api-client-failed = Gagal membuat Api Client - { $error }
+toast-api-active = Berhasil Terhubung ke API Server!
+toast-api-non-active = Berhasil Memutus API Server!
+toast-logout = Berhasil Keluar!
+toast-port-info-failed = Gagal mendapatkan info port, { $error }
+toast-replay-finished = Putar ulang rekaman serial selesai
+toast-serial-connected = BERHASIL! terhubung ke Serial: [{ $port }]:[{ $vid }-{ $pid }]
+toast-serial-start-failed = Layanan Serial Gagal dimulai - { $error }
+toast-serial-disconnected = Perangkat serial terputus, menunggu terhubung ulang - { $error }
+toast-serial-reconnected = Perangkat serial terhubung ulang di `{ $port }` setelah { $gap } detik
+toast-port-detached = Port serial `{ $port }` terlepas
+toast-capture-loaded = Rekaman serial dimuat sebagai `{ $port }`
+toast-device-config = Konfigurasi dimuat dari perangkat `{ $device }`
+toast-opened-runs = { $count } run dibuka
+toast-opened-session = Sesi dengan { $count } run dibuka
+toast-open-failed = Gagal membuka `{ $name }` - { $error }
+toast-upload-saved = Run disimpan ke server dengan id { $id }
+toast-upload-failed = Gagal menyimpan run ke server, akan dicoba lagi nanti - { $error }
+toast-upload-queued = Run `{ $name }` diantrekan untuk disimpan ke server
+toast-upload-queue-failed = Gagal mengantrekan run - { $error }
+toast-login-restored = Masuk dengan sesi login yang tersimpan
+toast-procedure-step = Langkah { $number }: { $name } - { $prompt }
+toast-procedure-done = Prosedur `{ $name }` selesai
+toast-alarm-stopped = Rekaman dihentikan oleh alarm
+toast-alarm-device-stopped = Perangkat dihentikan oleh alarm
+toast-registry-save-failed = Gagal menyimpan daftar kendaraan - { $error }
+toast-vehicle-linked = Sesi terhubung ke `{ $vehicle }`
+toast-report-empty = Tidak ada data rekaman untuk dicetak
+toast-report-saved = Dyno sheet disimpan ke `{ $path }`
+
+annotation-procedure-step = LANGKAH { $number }/{ $total }: { $name }
+annotation-procedure-done = PROSEDUR SELESAI: { $name }
+
+## grafik
+
+plot-instructions = Petunjuk
+plot-help-pan = Geser dengan menyeret, atau scroll (+ shift = horizontal).
+plot-help-box-zoom = Zoom kotak: klik tengah (Scroll) untuk memperbesar dan memperkecil dengan seleksi.
+plot-help-zoom = Zoom dengan ctrl + scroll.
+plot-help-reset = Reset tampilan dengan klik ganda.
+plot-help-context-menu = Ubah perilaku dengan menu konteks klik kanan di grafik
+plot-help-cursor = Pindahkan kursor terdekat dengan menyeret, saat kursor ditampilkan.
+plot-select-panel = Pilih Grafik yang Ditampilkan
+plot-panel-all = Semua Grafik
+plot-panel-first = Grafik Pertama
+plot-panel-second = Grafik Kedua
+plot-cursors = Kursor
+plot-channels = Kanal
+plot-config = Konfigurasi Grafik
+plot-drag = Seret
+plot-zoom = Zoom
+plot-scroll = Scroll
+plot-boxed-zoom = Zoom Kotak
+plot-animate = Animasi
+plot-points-to-show = Titik yang Ditampilkan
+plot-all-points = Semua Titik
+plot-half-points = Setengah Titik
+plot-quarter-points = Seperempat Titik
+plot-from-point = Dari Titik { $index }
+plot-show-from = Tampilkan Dari
+plot-channel = Kanal
+plot-average-power = Tenaga Rata-rata: { $power } HP
+plot-note = Catatan
+plot-annotate-cursor = Tambah di Kursor A
+plot-annotate-last = Tambah di Data Terakhir
+plot-annotations = Anotasi ({ $count })
+plot-no-annotation = Tidak ada anotasi
+plot-peak = puncak { $value } { $unit } @ { $rpm } rpm
+plot-x-axis = Sumbu X
+plot-x-time = Waktu
+plot-x-rpm-engine = RPM Mesin
+plot-x-speed = Kecepatan Roller
+plot-axis-left = Kiri
+plot-axis-right = Kanan
+plot-line-solid = Solid
+plot-line-dashed = Putus-putus
+plot-line-dotted = Titik-titik
+
+channel-speed = Kecepatan
+channel-rpm-engine = RPM (mesin)
+channel-rpm-roda = RPM (roda)
+channel-torque = Torsi
+channel-power = Tenaga
+
+## dashboard
+
+dash-title = Tata Letak Dashboard
+dash-left-panel = Panel Kiri
+dash-right-panel = Panel Kanan
+dash-add-row = Tambah Baris
+dash-row = Baris { $number }
+dash-row-remove = Baris
+dash-tile = Tile
+dash-source = Sumber
+dash-widget = Widget
+dash-range = Rentang
+dash-scale = Skala
+dash-decimals = desimal
+dash-warning = Peringatan
+dash-redline = Redline
+dash-gauge = Gauge
+dash-segment = Seven Segment
+dash-bar = Bar
+dash-numeric = Angka
+dash-odo = ODO
+dash-time = Waktu
+
+## alarm
+
+alarm-enable = Aktifkan Alarm
+alarm-add-rule = Tambah Aturan
+alarm-new-rule = Alarm baru
+alarm-rule = Aturan
+alarm-channel = Kanal
+alarm-condition = Kondisi
+alarm-severity = Tingkat
+alarm-actions = Aksi
+alarm-toast = Toast
+alarm-sound = Suara
+    .hint = bunyikan beep di speaker, tiga kali untuk kritis
+alarm-bell = Kedipkan Jendela
+    .hint = kedipkan tepi jendela dan minta perhatian os
+alarm-flash = Kedip
+alarm-stop-recording = Hentikan Rekaman
+    .hint = akhiri rekaman run, perangkat juga dihentikan jika Hentikan Perangkat dicentang
+alarm-stop-device = Hentikan Perangkat
+    .hint = kirim perintah stop ke perangkat, rekaman tetap berjalan
+alarm-above = Di Atas
+alarm-below = Di Bawah
+alarm-rate-above = Laju Di Atas
+alarm-sensor-fault = Sensor Rusak
+alarm-engine-temp-high = Suhu mesin tinggi
+alarm-engine-overheat = Mesin overheat
+alarm-thermocouple-fault = Thermocouple rusak
+alarm-roller-overspeed = Roller terlalu cepat
+alarm-message = ALARM { $severity }: { $name } ({ $value } { $unit })
+severity-info = Info
+severity-warning = Peringatan
+severity-critical = Kritis
+
+## prosedur tes
+
+procedure-title = Prosedur Tes
+procedure-none = Tidak Ada Prosedur
+procedure-reload = Muat Ulang
+    .hint = muat ulang prosedur dari folder konfigurasi
+procedure-start = Mulai Prosedur
+procedure-not-recording = Mulai rekaman, langkah dideteksi dari data live
+procedure-confirm = Konfirmasi
+procedure-skip = Lewati Langkah
+procedure-done = Semua langkah selesai
+procedure-close = Tutup Prosedur
+procedure-abort = Batalkan Prosedur
+step-manual = konfirmasi untuk lanjut
+step-above = sampai di atas { $value } { $unit }
+step-below = sampai di bawah { $value } { $unit }
+step-hold = tahan { $value } ± { $tolerance } { $unit } selama { $seconds } detik
+step-duration = tunggu { $seconds } detik
+
+## direktori
+
+path-app-dir = nama direktori aplikasi
+path-edit = Ubah Konfigurasi Direktori
+path-edit-hint = Klik direktori di sebelah kanan untuk mengubahnya
+path-click-edit = Klik untuk Mengubah
+path-click-change = Klik Kiri untuk Mengganti
+path-change = Ganti Direktori
+path-project = DIREKTORI PROYEK
+path-cache = DIREKTORI CACHE
+path-config = DIREKTORI KONFIGURASI
+path-data = DIREKTORI DATA
+path-data-local = DIREKTORI DATA LOKAL
+path-preference = DIREKTORI PREFERENSI
+
+## jendela lain aplikasi desktop
+
+quit-title = Apakah Anda ingin menutup Aplikasi?
+quit-hint = klik 'Ok' untuk menutup aplikasi atau 'Tidak' untuk membatalkan
+unsaved-title = Data rekaman di buffer belum disimpan. Apakah Anda ingin menyimpannya?
+unsaved-message = ada data yang direkam di buffer, dan belum disimpan
+unsaved-hint = klik 'Simpan' untuk menyimpan, 'Tidak' untuk membuangnya, atau 'Batal' untuk membatalkan
+recovery-title = Pulihkan Sesi yang Belum Disimpan?
+recovery-message = aplikasi tidak ditutup dengan benar, dan ada data rekaman yang belum disimpan
+recovery-customer = Pelanggan: { $customer }
+recovery-hint = klik 'Ok' untuk memulihkan, atau 'Tidak' untuk membuangnya
+help-title = Bantuan
+help-upcoming = Segera Hadir! Jendela Bantuan
+about-title = Tentang
+about-version = Versi { $version }
+about-license = Lisensi
+about-license-file = Lisensi: Lihat berkas { $file } untuk detailnya.
+about-homepage = Halaman utama
+about-repository = Repositori
+about-authors = Penulis
+
+log-title = Jendela Log Dyno
+log-clear = Bersihkan
+log-levels = Level Log
+log-search = Cari
+log-case-sensitive = Peka huruf besar kecil
+log-max-output = Maks keluaran Log
+log-sort = Urutkan
+log-size = Ukuran log: { $count }
+log-displayed = Ditampilkan: { $count }
+log-save-dialog = Menyimpan Berkas Log
+log-copy = Salin
+
+auth-login = Masuk
+auth-register = Daftar
+auth-nim = nim
+auth-email = email
+auth-password = kata sandi
+auth-confirm-password = konfirmasi kata sandi
+auth-password-mismatch = Kata sandi tidak sama dengan kata sandi pertama
+auth-role = Peran
+auth-show-password = tampilkan kata sandi
+auth-submit = Kirim
+
+server-list-window = Buka DynoTests dari Server
+server-list-title = Daftar Berkas di Server
+server-list-id = id
+server-list-info-id = id info
+server-list-verified = terverifikasi
+server-list-not-verified = belum terverifikasi
+server-list-updated = diperbarui
+server-list-created = dibuat
+server-list-open = buka
+server-list-refresh = Muat Ulang
+server-save-title = Simpan DynoTests ke Server
+server-save-info = Info Dynotests:
+server-save = Simpan
+
+## web frontend
+
+nav-dashboard = Dashboard
+nav-activities = Aktivitas
+nav-profile = Profil
+nav-administration = Administrasi
+nav-users = Pengguna
+nav-dynos = Dyno
+nav-infos = Info
+nav-history = Riwayat
+nav-sop = SOP
+header-profile-settings = Pengaturan Profil
+header-logout = Keluar
+live-title = Data Live Dynotest
+live-start = Mulai Stream
+live-stop = Hentikan Stream
@@ -12,13 +12,16 @@ use uom::si::{
     time::millisecond,
 };
 
-use crate::types::{MotorInfo, MotorKind, Stroke, GRAVITY_SPEED, PI};
+use crate::types::{DeviceConfig, MotorInfo, MotorKind, Stroke, GRAVITY_SPEED, PI};
+
+/// thermocouple channels wired by the firmware, in the order of `with_chip` in `dyno_stm32`
+pub const DEFAULT_TEMP_CHANNELS: [&str; 3] = ["Cylinder Head", "Exhaust Gas", "Oil"];
 
 #[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
 #[cfg_attr(feature = "derive_serde", serde(default))]
 #[cfg_attr(feature = "std", derive(Display))]
 #[cfg_attr(feature = "std", display("{self:#?}"))]
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct DynoConfig {
     pub diameter_roller_cm: f64,
     pub diameter_roller_beban_cm: f64,
@@ -28,7 +31,15 @@ pub struct DynoConfig {
 
     pub motor_info: MotorInfo,
     pub max_encoder_pulse: u16,
+    /// pulses per engine revolution of the RPM pickup
+    pub rpm_pickup_ppr: u16,
     pub delta_ms: u16,
+
+    /// id of the device this config is loaded from, `None` if loaded from local `config.toml`
+    pub device_id: Option<u32>,
+
+    /// name for every thermocouple channel, ordered the same as `RawSerialData::raw_temps`
+    pub temp_channels: Vec<String>,
 }
 
 impl Default for DynoConfig {
@@ -40,8 +51,11 @@ impl Default for DynoConfig {
             diameter_gear_encoder_cm: 10.,
             diameter_gear_beban_cm: 5.4,
             max_encoder_pulse: 300,
+            rpm_pickup_ppr: 1,
             delta_ms: 250,
+            device_id: None,
             motor_info: MotorInfo::default(),
+            temp_channels: DEFAULT_TEMP_CHANNELS.map(str::to_owned).to_vec(),
         }
     }
 }
@@ -96,15 +110,30 @@ impl DynoConfig {
 
     #[inline(always)]
     pub fn rpm_factor(&self) -> f64 {
+        let pickup = 1. / (self.rpm_pickup_ppr.max(1) as f64);
         if matches!(self.motor_info.kind, MotorKind::Electric) {
-            return 1.;
+            return pickup;
         }
         match self.motor_info.stroke {
-            Stroke::Four => 2. / ((self.motor_info.cylinder as u8) as f64),
-            Stroke::Two => 1.,
+            Stroke::Four => pickup * 2. / ((self.motor_info.cylinder as u8) as f64),
+            Stroke::Two => pickup,
         }
     }
 
+    /// override the calibration with the config that stored in the device flash
+    pub fn apply_device_config(&mut self, device: &DeviceConfig) {
+        let cal = &device.calibration;
+        self.diameter_roller_cm = cal.diameter_roller_cm;
+        self.diameter_roller_beban_cm = cal.diameter_roller_beban_cm;
+        self.diameter_gear_encoder_cm = cal.diameter_gear_encoder_cm;
+        self.diameter_gear_beban_cm = cal.diameter_gear_beban_cm;
+        self.berat_beban_kg = cal.berat_beban_kg;
+        self.max_encoder_pulse = device.encoder_ppr;
+        self.rpm_pickup_ppr = device.rpm_ppr;
+        self.delta_ms = device.period_ms;
+        self.device_id = Some(device.id);
+    }
+
     #[inline(always)]
     pub fn inertia_roller_beban(&self) -> f64 {
         let r = (self.diameter_roller_beban_cm * 0.5) / 100.;
@@ -127,4 +156,19 @@ impl DynoConfig {
     pub fn delta_time(&self) -> Time {
         Time::new::<millisecond>(self.delta_ms as f64)
     }
+
+    /// name of thermocouple channel `idx`, fallback to `TEMP {idx}` if not named
+    pub fn temp_channel_name(&self, idx: usize) -> std::borrow::Cow<'_, str> {
+        match self.temp_channels.get(idx) {
+            Some(name) => std::borrow::Cow::Borrowed(name.as_str()),
+            None => std::borrow::Cow::Owned(format!("TEMP {idx}")),
+        }
+    }
+
+    #[inline]
+    pub fn temp_channel_index(&self, name: &str) -> Option<usize> {
+        self.temp_channels
+            .iter()
+            .position(|x| x.eq_ignore_ascii_case(name))
+    }
 }
@@ -0,0 +1,43 @@
+//! checksum of the uploaded dynotests data, the server store it as lowercase hex sha256
+
+use sha2::{Digest, Sha256};
+
+/// sha256 of `bytes` as lowercase hex string
+pub fn checksum_from_bytes(bytes: impl AsRef<[u8]>) -> String {
+    Sha256::digest(bytes.as_ref())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// compare two checksums in constant time, case of the hex string is ignored
+pub fn compare_checksums(left: impl AsRef<[u8]>, right: impl AsRef<[u8]>) -> bool {
+    let (left, right) = (left.as_ref(), right.as_ref());
+    if left.len() != right.len() {
+        return false;
+    }
+    left.iter().zip(right).fold(0u8, |acc, (l, r)| {
+        acc | (l.to_ascii_lowercase() ^ r.to_ascii_lowercase())
+    }) == 0
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_sha256_hex() {
+        let checksum = checksum_from_bytes(b"dynotests");
+        assert_eq!(checksum.len(), 64);
+        assert!(compare_checksums(&checksum, checksum.to_uppercase()));
+        assert!(!compare_checksums(
+            &checksum,
+            checksum_from_bytes(b"dynotest")
+        ));
+        assert!(!compare_checksums(&checksum, &checksum[1..]));
+        assert!(compare_checksums(
+            checksum_from_bytes(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        ));
+    }
+}
@@ -0,0 +1,487 @@
+//! host side of the firmware update, see `DfuCommand` for the device side protocol
+
+use derive_more::{Display, Error};
+
+#[cfg(feature = "dfu_sign")]
+use crate::types::FIRMWARE_SIGN_CONTEXT;
+use crate::types::{
+    BootStatus, DfuCommand, DfuError, DfuResponse, FirmwareHeader, FirmwareSignature,
+    FirmwareVersion, SerialCommand, SerialResponse, DFU_CHUNK_SIZE, FIRMWARE_CRC,
+};
+
+/// magic of `.dynofw` file, followed by u32 LE header length, postcard header and the image
+pub const FIRMWARE_FILE_MAGIC: &[u8; 4] = b"DYFW";
+
+#[derive(Debug, Display, Error, Clone, PartialEq)]
+pub enum DfuUpdateError {
+    #[display("link error: {_0}")]
+    Link(#[error(not(source))] String),
+    #[display("device is not responding")]
+    Timeout,
+    #[display("unexpected response from device: {_0}")]
+    Unexpected(#[error(not(source))] String),
+    #[display("device error: {_0}")]
+    Device(#[error(not(source))] DfuError),
+    #[display("firmware {image} is not newer than the device firmware {device}")]
+    NotNewer {
+        image: FirmwareVersion,
+        device: FirmwareVersion,
+    },
+    #[display("invalid firmware image: {_0}")]
+    InvalidImage(#[error(not(source))] &'static str),
+    #[display("the new firmware failed to boot, the device is rolled back to {_0}")]
+    RolledBack(#[error(not(source))] FirmwareVersion),
+}
+
+pub type DfuResult<T> = Result<T, DfuUpdateError>;
+
+/// signed firmware image, readed from `.dynofw` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FirmwareImage {
+    pub header: FirmwareHeader,
+    pub payload: Vec<u8>,
+}
+
+impl FirmwareImage {
+    /// unsigned image from raw firmware binary
+    pub fn new(version: FirmwareVersion, payload: Vec<u8>) -> Self {
+        let header = FirmwareHeader {
+            version,
+            size: payload.len() as u32,
+            crc32: FIRMWARE_CRC.checksum(&payload),
+            signature: FirmwareSignature::default(),
+        };
+        Self { header, payload }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> DfuResult<Self> {
+        let rest = bytes
+            .strip_prefix(FIRMWARE_FILE_MAGIC)
+            .ok_or(DfuUpdateError::InvalidImage(
+                "not a dynotests firmware file",
+            ))?;
+        let (len, rest) = rest
+            .split_first_chunk::<4>()
+            .ok_or(DfuUpdateError::InvalidImage("truncated header"))?;
+        let len = u32::from_le_bytes(*len) as usize;
+        if rest.len() < len {
+            return Err(DfuUpdateError::InvalidImage("truncated header"));
+        }
+        let (header, payload) = rest.split_at(len);
+        let header: FirmwareHeader = postcard::from_bytes(header)
+            .map_err(|_| DfuUpdateError::InvalidImage("malformed header"))?;
+        if header.size as usize != payload.len() {
+            return Err(DfuUpdateError::InvalidImage("size is not match"));
+        }
+        if header.crc32 != FIRMWARE_CRC.checksum(payload) {
+            return Err(DfuUpdateError::InvalidImage("checksum is not match"));
+        }
+        Ok(Self {
+            header,
+            payload: payload.to_vec(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let header = postcard::to_stdvec(&self.header).expect("header is always serializable");
+        let mut bytes = Vec::with_capacity(8 + header.len() + self.payload.len());
+        bytes.extend_from_slice(FIRMWARE_FILE_MAGIC);
+        bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&header);
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    /// SHA-512 prehash of the signed message, [`FirmwareHeader::signed_prefix`] and the image
+    #[cfg(feature = "dfu_sign")]
+    pub fn prehash(&self) -> sha2::Sha512 {
+        use sha2::Digest;
+        let mut digest = sha2::Sha512::new();
+        digest.update(self.header.signed_prefix());
+        digest.update(&self.payload);
+        digest
+    }
+
+    /// sign with Ed25519ph, used by the release tooling
+    #[cfg(feature = "dfu_sign")]
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        let signature = key
+            .sign_prehashed(self.prehash(), Some(FIRMWARE_SIGN_CONTEXT))
+            .expect("context is shorter than 256 bytes");
+        self.header.signature = FirmwareSignature(signature.to_bytes());
+    }
+
+    #[cfg(feature = "dfu_sign")]
+    pub fn verify(&self, key: &ed25519_dalek::VerifyingKey) -> bool {
+        let signature = ed25519_dalek::Signature::from_bytes(&self.header.signature.0);
+        key.verify_prehashed(self.prehash(), Some(FIRMWARE_SIGN_CONTEXT), &signature)
+            .is_ok()
+    }
+}
+
+/// framed link to the device
+pub trait DfuLink {
+    fn send(&mut self, command: &SerialCommand) -> DfuResult<()>;
+
+    /// wait for the next response, `Ok(None)` if timed out
+    fn recv(&mut self) -> DfuResult<Option<SerialResponse>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfuProgress {
+    Erasing,
+    Writing { written: u32, total: u32 },
+    Verifying,
+    Rebooting,
+}
+
+/// drive the firmware update over [`DfuLink`]
+pub struct DfuUpdater<'a, L> {
+    link: &'a mut L,
+    retries: usize,
+}
+
+impl<'a, L: DfuLink> DfuUpdater<'a, L> {
+    pub const DEFAULT_RETRIES: usize = 3;
+
+    pub fn new(link: &'a mut L) -> Self {
+        Self {
+            link,
+            retries: Self::DEFAULT_RETRIES,
+        }
+    }
+
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries.max(1);
+        self
+    }
+
+    pub fn device_version(&mut self) -> DfuResult<(FirmwareVersion, BootStatus)> {
+        match self.request(&DfuCommand::GetVersion)? {
+            DfuResponse::Version { version, status } => Ok((version, status)),
+            other => Err(Self::unexpected(other)),
+        }
+    }
+
+    /// upload `image` and reboot the device into the bootloader,
+    /// call [`Self::confirm`] after the device is reconnected
+    pub fn update(
+        &mut self,
+        image: &FirmwareImage,
+        allow_downgrade: bool,
+        mut progress: impl FnMut(DfuProgress),
+    ) -> DfuResult<()> {
+        // stop streaming, so the responses is not mixed with data
+        self.link.send(&SerialCommand::Stop)?;
+
+        let (device, _) = self.device_version()?;
+        if !allow_downgrade && image.header.version <= device {
+            return Err(DfuUpdateError::NotNewer {
+                image: image.header.version,
+                device,
+            });
+        }
+
+        progress(DfuProgress::Erasing);
+        match self.request(&DfuCommand::Begin(image.header))? {
+            DfuResponse::Ready => {}
+            other => return Err(Self::unexpected(other)),
+        }
+
+        let total = image.header.size;
+        let mut offset = 0u32;
+        for chunk in image.payload.chunks(DFU_CHUNK_SIZE) {
+            let next = offset + chunk.len() as u32;
+            let command = DfuCommand::Write {
+                offset,
+                data: heapless::Vec::from_slice(chunk).expect("chunk is DFU_CHUNK_SIZE"),
+            };
+            match self.request(&command) {
+                Ok(DfuResponse::Written { next: n }) if n == next => {}
+                Ok(other) => return self.abort(Self::unexpected(other)),
+                Err(err) => return self.abort(err),
+            }
+            offset = next;
+            progress(DfuProgress::Writing {
+                written: offset,
+                total,
+            });
+        }
+
+        progress(DfuProgress::Verifying);
+        match self.request(&DfuCommand::Finish) {
+            Ok(DfuResponse::Verified) => {}
+            Ok(other) => return self.abort(Self::unexpected(other)),
+            Err(err) => return self.abort(err),
+        }
+        progress(DfuProgress::Rebooting);
+        Ok(())
+    }
+
+    /// check the device is running `image` after it reconnected, the first command from the
+    /// host confirm the new firmware so the bootloader not roll it back
+    pub fn confirm(&mut self, image: &FirmwareImage) -> DfuResult<()> {
+        match self.device_version()? {
+            (version, BootStatus::RolledBack) => Err(DfuUpdateError::RolledBack(version)),
+            (version, _) if version == image.header.version => Ok(()),
+            (version, _) => Err(DfuUpdateError::Unexpected(format!(
+                "device is running {version}, expected {}",
+                image.header.version
+            ))),
+        }
+    }
+
+    /// send `command` and wait the dfu response, resend on timeout
+    fn request(&mut self, command: &DfuCommand) -> DfuResult<DfuResponse> {
+        let command = SerialCommand::Dfu(command.clone());
+        for _ in 0..self.retries {
+            self.link.send(&command)?;
+            loop {
+                match self.link.recv()? {
+                    None => break,
+                    // data that still in flight after `Stop`
+                    Some(SerialResponse::Data(_)) => continue,
+                    Some(SerialResponse::Dfu(DfuResponse::Error(err))) => {
+                        return Err(DfuUpdateError::Device(err))
+                    }
+                    Some(SerialResponse::Dfu(response)) => return Ok(response),
+                    Some(other) => return Err(Self::unexpected(other)),
+                }
+            }
+        }
+        Err(DfuUpdateError::Timeout)
+    }
+
+    fn abort<T>(&mut self, err: DfuUpdateError) -> DfuResult<T> {
+        let _ = self.link.send(&SerialCommand::Dfu(DfuCommand::Abort));
+        Err(err)
+    }
+
+    fn unexpected(response: impl core::fmt::Debug) -> DfuUpdateError {
+        DfuUpdateError::Unexpected(format!("{response:?}"))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// simulate the device side of `DfuCommand`, including the bootloader swap
+    struct MockDevice {
+        version: FirmwareVersion,
+        status: BootStatus,
+        header: Option<FirmwareHeader>,
+        flash: Vec<u8>,
+        pending: Option<FirmwareHeader>,
+        responses: VecDeque<SerialResponse>,
+        /// drop the response of the n-th write command
+        drop_write_ack: Option<usize>,
+        writes: usize,
+        /// flip a bit of the n-th written chunk
+        corrupt_write: Option<usize>,
+        /// the new firmware crash on boot
+        broken_firmware: bool,
+    }
+
+    impl MockDevice {
+        fn new(version: FirmwareVersion) -> Self {
+            Self {
+                version,
+                status: BootStatus::Confirmed,
+                header: None,
+                flash: Vec::new(),
+                pending: None,
+                responses: VecDeque::new(),
+                drop_write_ack: None,
+                writes: 0,
+                corrupt_write: None,
+                broken_firmware: false,
+            }
+        }
+
+        /// reset into the bootloader then the application
+        fn reboot(&mut self) {
+            if let Some(header) = self.pending.take() {
+                if self.broken_firmware {
+                    // never confirmed, bootloader restore the backup
+                    self.status = BootStatus::RolledBack;
+                } else {
+                    self.version = header.version;
+                    self.status = BootStatus::Testing;
+                }
+            }
+        }
+
+        fn handle(&mut self, command: DfuCommand) -> Option<DfuResponse> {
+            if self.status == BootStatus::Testing {
+                self.status = BootStatus::Confirmed;
+            }
+            let response = match command {
+                DfuCommand::GetVersion => DfuResponse::Version {
+                    version: self.version,
+                    status: self.status,
+                },
+                DfuCommand::Begin(header) => {
+                    self.header = Some(header);
+                    self.flash.clear();
+                    DfuResponse::Ready
+                }
+                DfuCommand::Write { offset, data } => {
+                    let Some(_) = self.header else {
+                        return Some(DfuResponse::Error(DfuError::NotStarted));
+                    };
+                    let written = self.flash.len() as u32;
+                    if offset + data.len() as u32 == written {
+                        // retransmit of the last chunk, the ack is lost
+                    } else if offset != written {
+                        return Some(DfuResponse::Error(DfuError::UnexpectedOffset));
+                    } else {
+                        let mut data = data.to_vec();
+                        if self.corrupt_write == Some(self.writes) {
+                            data[0] ^= 0x1;
+                        }
+                        self.flash.extend_from_slice(&data);
+                    }
+                    self.writes += 1;
+                    if self.drop_write_ack == Some(self.writes - 1) {
+                        return None;
+                    }
+                    DfuResponse::Written {
+                        next: self.flash.len() as u32,
+                    }
+                }
+                DfuCommand::Finish => {
+                    let Some(header) = self.header.take() else {
+                        return Some(DfuResponse::Error(DfuError::NotStarted));
+                    };
+                    if self.flash.len() as u32 != header.size
+                        || FIRMWARE_CRC.checksum(&self.flash) != header.crc32
+                    {
+                        return Some(DfuResponse::Error(DfuError::Corrupted));
+                    }
+                    self.pending = Some(header);
+                    DfuResponse::Verified
+                }
+                DfuCommand::Abort => {
+                    self.header = None;
+                    DfuResponse::Aborted
+                }
+            };
+            Some(response)
+        }
+    }
+
+    impl DfuLink for MockDevice {
+        fn send(&mut self, command: &SerialCommand) -> DfuResult<()> {
+            match command.clone() {
+                SerialCommand::Dfu(command) => {
+                    if let Some(response) = self.handle(command) {
+                        // streaming data that still in flight
+                        self.responses
+                            .push_back(SerialResponse::Data(Default::default()));
+                        self.responses.push_back(SerialResponse::Dfu(response));
+                    }
+                }
+                SerialCommand::Stop => {}
+                other => panic!("unexpected command {other:?}"),
+            }
+            Ok(())
+        }
+
+        fn recv(&mut self) -> DfuResult<Option<SerialResponse>> {
+            Ok(self.responses.pop_front())
+        }
+    }
+
+    fn image(version: FirmwareVersion) -> FirmwareImage {
+        let payload = (0..1000u32).map(|x| (x * 7) as u8).collect();
+        FirmwareImage::new(version, payload)
+    }
+
+    #[test]
+    fn test_dfu_update_and_confirm() {
+        let image = image(FirmwareVersion::new(0, 2, 0));
+        let mut device = MockDevice::new(FirmwareVersion::new(0, 1, 0));
+        device.drop_write_ack = Some(3);
+
+        let mut last = None;
+        DfuUpdater::new(&mut device)
+            .update(&image, false, |p| last = Some(p))
+            .unwrap();
+        assert_eq!(last, Some(DfuProgress::Rebooting));
+        assert_eq!(device.flash, image.payload);
+
+        device.reboot();
+        assert_eq!(device.status, BootStatus::Testing);
+        DfuUpdater::new(&mut device).confirm(&image).unwrap();
+        assert_eq!(device.status, BootStatus::Confirmed);
+    }
+
+    #[test]
+    fn test_dfu_reject_old_version() {
+        let image = image(FirmwareVersion::new(0, 1, 0));
+        let mut device = MockDevice::new(FirmwareVersion::new(0, 1, 0));
+        let err = DfuUpdater::new(&mut device)
+            .update(&image, false, |_| {})
+            .unwrap_err();
+        assert!(matches!(err, DfuUpdateError::NotNewer { .. }));
+        assert!(device.header.is_none());
+
+        DfuUpdater::new(&mut device)
+            .update(&image, true, |_| {})
+            .unwrap();
+    }
+
+    #[test]
+    fn test_dfu_corrupted_transfer() {
+        let image = image(FirmwareVersion::new(0, 2, 0));
+        let mut device = MockDevice::new(FirmwareVersion::new(0, 1, 0));
+        device.corrupt_write = Some(2);
+        let err = DfuUpdater::new(&mut device)
+            .update(&image, false, |_| {})
+            .unwrap_err();
+        assert_eq!(err, DfuUpdateError::Device(DfuError::Corrupted));
+        assert!(device.pending.is_none());
+    }
+
+    #[test]
+    fn test_dfu_rollback_reported() {
+        let image = image(FirmwareVersion::new(0, 2, 0));
+        let mut device = MockDevice::new(FirmwareVersion::new(0, 1, 0));
+        device.broken_firmware = true;
+        DfuUpdater::new(&mut device)
+            .update(&image, false, |_| {})
+            .unwrap();
+        device.reboot();
+        let err = DfuUpdater::new(&mut device).confirm(&image).unwrap_err();
+        assert_eq!(
+            err,
+            DfuUpdateError::RolledBack(FirmwareVersion::new(0, 1, 0))
+        );
+    }
+
+    #[test]
+    fn test_firmware_image_file() {
+        let image = image(FirmwareVersion::parse("1.2.3-rc.1"));
+        assert_eq!(image.header.version, FirmwareVersion::new(1, 2, 3));
+        let bytes = image.to_bytes();
+        assert_eq!(FirmwareImage::from_bytes(&bytes).unwrap(), image);
+
+        let mut broken = bytes.clone();
+        *broken.last_mut().unwrap() ^= 0xFF;
+        assert!(FirmwareImage::from_bytes(&broken).is_err());
+        assert!(FirmwareImage::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[cfg(feature = "dfu_sign")]
+    #[test]
+    fn test_firmware_image_sign() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let mut image = image(FirmwareVersion::new(1, 0, 0));
+        image.sign(&key);
+        assert!(image.verify(&key.verifying_key()));
+        image.header.version.patch += 1;
+        assert!(!image.verify(&key.verifying_key()));
+    }
+}
@@ -0,0 +1,149 @@
+//! error type shared by the desktop app and the web services
+
+use derive_more::Display;
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrKind {
+    #[display("Input/Output Error")]
+    InputOutput,
+    #[display("Filesystem Error")]
+    Filesystem,
+    #[display("Service Error")]
+    Service,
+    #[display("Api Error")]
+    Api,
+    #[display("Validation Error")]
+    Validation,
+    #[display("Serialize Error")]
+    Serialize,
+    #[display("Encoding Error")]
+    Encoding,
+    #[display("Database Error")]
+    Database,
+    #[display("Bad Request")]
+    BadRequest,
+    #[display("Unauthorized")]
+    Unauthorized,
+    #[display("Forbidden")]
+    Forbidden,
+    #[display("Not Found")]
+    NotFound,
+    #[display("Expectation Failed")]
+    ExpectationFailed,
+    #[display("Internal Server Error")]
+    InternalServer,
+    #[display("Error")]
+    Any,
+}
+
+impl ErrKind {
+    /// http status code of the error, used by the backend responses
+    pub const fn status_code(self) -> u16 {
+        match self {
+            Self::Validation | Self::BadRequest | Self::Serialize | Self::Encoding => 400,
+            Self::Unauthorized => 401,
+            Self::Forbidden => 403,
+            Self::NotFound => 404,
+            Self::ExpectationFailed => 417,
+            _ => 500,
+        }
+    }
+}
+
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+#[display("[{kind}] {desc}")]
+pub struct DynoErr {
+    pub kind: ErrKind,
+    pub desc: String,
+}
+
+impl std::error::Error for DynoErr {}
+
+pub type DynoResult<T> = Result<T, DynoErr>;
+
+macro_rules! impl_constructors {
+    ($($fn_name:ident => $kind:ident),* $(,)?) => {$(
+        #[inline]
+        pub fn $fn_name(desc: impl ToString) -> Self {
+            Self::new(ErrKind::$kind, desc)
+        }
+    )*};
+}
+
+impl DynoErr {
+    pub fn new(kind: ErrKind, desc: impl ToString) -> Self {
+        Self {
+            kind,
+            desc: desc.to_string(),
+        }
+    }
+
+    impl_constructors! {
+        input_output_error => InputOutput,
+        filesystem_error => Filesystem,
+        service_error => Service,
+        api_error => Api,
+        validation_error => Validation,
+        serialize_error => Serialize,
+        encoding_error => Encoding,
+        database_error => Database,
+        bad_request_error => BadRequest,
+        unauthorized_error => Unauthorized,
+        forbidden_error => Forbidden,
+        not_found_error => NotFound,
+        expectation_failed_error => ExpectationFailed,
+        internal_server_error => InternalServer,
+        any_error => Any,
+    }
+
+    #[inline]
+    pub const fn kind(&self) -> ErrKind {
+        self.kind
+    }
+}
+
+impl From<std::io::Error> for DynoErr {
+    fn from(value: std::io::Error) -> Self {
+        Self::input_output_error(value)
+    }
+}
+
+impl From<postcard::Error> for DynoErr {
+    fn from(value: postcard::Error) -> Self {
+        Self::encoding_error(value)
+    }
+}
+
+impl From<crate::dfu::DfuUpdateError> for DynoErr {
+    fn from(value: crate::dfu::DfuUpdateError) -> Self {
+        Self::service_error(value)
+    }
+}
+
+impl From<&str> for DynoErr {
+    fn from(value: &str) -> Self {
+        Self::any_error(value)
+    }
+}
+
+impl From<String> for DynoErr {
+    fn from(value: String) -> Self {
+        Self::any_error(value)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_and_kind() {
+        let err = DynoErr::validation_error("nim is empty");
+        assert_eq!(err.kind(), ErrKind::Validation);
+        assert_eq!(err.kind().status_code(), 400);
+        assert_eq!(err.to_string(), "[Validation Error] nim is empty");
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "no file");
+        assert_eq!(DynoErr::from(io).kind, ErrKind::InputOutput);
+    }
+}
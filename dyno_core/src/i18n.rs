@@ -0,0 +1,214 @@
+//! message catalogue of the desktop app and the web frontend.
+//!
+//! the messages are fluent files in `dyno_core/locales/<code>.ftl` that embedded into the binary,
+//! a message can have `hint` attribute that shown on hover of the label.
+//! the message that missing in the selected language fallback to english, then to the key itself.
+
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    OnceLock,
+};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentResource};
+pub use fluent_bundle::{FluentArgs, FluentValue};
+#[cfg(feature = "derive_serde")]
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+#[repr(u8)]
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English = 0,
+    Indonesian = 1,
+}
+
+impl Language {
+    pub const ALL: [Self; 2] = [Self::English, Self::Indonesian];
+
+    /// BCP 47 code of the language
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Indonesian => "id",
+        }
+    }
+
+    /// name of the language in the language itself
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Indonesian => "Bahasa Indonesia",
+        }
+    }
+
+    /// language from BCP 47 code like `id-ID` (the locale of the browser or the os)
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code.split(['-', '_']).next().unwrap_or_default();
+        Self::ALL
+            .into_iter()
+            .find(|lang| lang.code().eq_ignore_ascii_case(primary))
+    }
+
+    const fn source(self) -> &'static str {
+        match self {
+            Self::English => include_str!("../locales/en.ftl"),
+            Self::Indonesian => include_str!("../locales/id.ftl"),
+        }
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+/// change the language of every next [`tr`], the ui pick it up on the next frame
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Language::Indonesian,
+        _ => Language::English,
+    }
+}
+
+/// translated message of `key`
+pub fn tr(key: &str) -> String {
+    format(key, None, None).unwrap_or_else(|| key.to_owned())
+}
+
+/// translated message of `key` with the variables, see also the [`crate::tr`] macro
+pub fn tr_args(key: &str, args: &FluentArgs) -> String {
+    format(key, None, Some(args)).unwrap_or_else(|| key.to_owned())
+}
+
+/// translated attribute of message `key`, empty if the message dont have the attribute
+pub fn tr_attr(key: &str, attr: &str) -> String {
+    format(key, Some(attr), None).unwrap_or_default()
+}
+
+fn bundles() -> &'static [FluentBundle<FluentResource>; 2] {
+    static BUNDLES: OnceLock<[FluentBundle<FluentResource>; 2]> = OnceLock::new();
+    BUNDLES.get_or_init(|| Language::ALL.map(bundle))
+}
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let langid = language
+        .code()
+        .parse::<LanguageIdentifier>()
+        .unwrap_or_default();
+    let resource = FluentResource::try_new(language.source().to_owned()).unwrap_or_else(
+        |(resource, errors)| {
+            log::error!("Failed to parse locale `{}` - {errors:?}", language.code());
+            resource
+        },
+    );
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // no unicode isolation marks around the variables, egui draw them as boxes
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        log::error!(
+            "Duplicate messages in locale `{}` - {errors:?}",
+            language.code()
+        );
+    }
+    bundle
+}
+
+fn format(key: &str, attr: Option<&str>, args: Option<&FluentArgs>) -> Option<String> {
+    let bundles = bundles();
+    [language(), Language::English]
+        .into_iter()
+        .find_map(|language| {
+            let bundle = &bundles[language as usize];
+            let message = bundle.get_message(key)?;
+            let pattern = match attr {
+                Some(attr) => message.get_attribute(attr)?.value(),
+                None => message.value()?,
+            };
+            let mut errors = vec![];
+            let value = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                log::warn!("Failed to format message `{key}` - {errors:?}");
+            }
+            Some(value.into_owned())
+        })
+}
+
+/// translate message with optional variables, `tr!("value-for", name = "Speed")`
+#[macro_export]
+macro_rules! tr {
+    ($key:expr $(,)?) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::tr_args($key, &args)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent_syntax::ast::Entry;
+    use std::collections::BTreeSet;
+
+    /// `key` of every message and `key.attr` of every attribute
+    fn keys(language: Language) -> BTreeSet<String> {
+        let resource = FluentResource::try_new(language.source().to_owned())
+            .unwrap_or_else(|(_, errors)| panic!("{language} - {errors:?}"));
+        resource
+            .entries()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) => Some(message),
+                _ => None,
+            })
+            .flat_map(|message| {
+                let key = message.id.name;
+                std::iter::once(key.to_owned()).chain(
+                    message
+                        .attributes
+                        .iter()
+                        .map(move |attr| format!("{key}.{}", attr.id.name)),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn i18n_every_key_in_every_locale() {
+        let english = keys(Language::English);
+        assert!(!english.is_empty());
+        for language in Language::ALL {
+            let other = keys(language);
+            let missing = english.difference(&other).collect::<Vec<_>>();
+            let extra = other.difference(&english).collect::<Vec<_>>();
+            assert!(missing.is_empty(), "missing in {language}: {missing:?}");
+            assert!(extra.is_empty(), "only in {language}: {extra:?}");
+        }
+    }
+
+    #[test]
+    fn i18n_translate() {
+        assert_eq!(Language::from_code("id-ID"), Some(Language::Indonesian));
+        assert_eq!(Language::from_code("en_US"), Some(Language::English));
+        assert_eq!(Language::from_code("jv"), None);
+
+        set_language(Language::Indonesian);
+        assert_eq!(tr("menu-file"), "Berkas");
+        assert_eq!(tr!("value-for", name = "Torsi"), "Nilai untuk Torsi");
+        assert!(!tr_attr("data-speed", "hint").is_empty());
+        assert_eq!(tr_attr("menu-file", "hint"), "");
+        assert_eq!(tr("no-such-message"), "no-such-message");
+        set_language(Language::English);
+        assert_eq!(tr("menu-file"), "File");
+    }
+}
@@ -3,6 +3,20 @@
 
 #[cfg(feature = "std")]
 pub mod config;
+#[cfg(feature = "checksum")]
+pub mod crypto;
+#[cfg(feature = "std")]
+pub mod dfu;
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+#[cfg(feature = "use_log")]
+mod logger;
+#[cfg(feature = "report")]
+pub mod report;
+#[cfg(feature = "saver")]
+mod saver;
 pub mod types;
 
 #[cfg(feature = "use_model")]
@@ -19,11 +33,50 @@ macro_rules! ternary {
     };
 }
 
+/// ignore the result of expression, for the channel sends that only fail when the receiver is gone
+#[macro_export]
+macro_rules! ignore_err {
+    ($expr:expr) => {{
+        let _ = $expr;
+    }};
+}
+
+/// run blocking closure that returning `DynoResult` on tokio blocking thread pool
+#[cfg(feature = "use_async")]
+#[macro_export]
+macro_rules! asyncify {
+    ($fn:expr) => {
+        match $crate::tokio::task::spawn_blocking($fn).await {
+            Ok(res) => res,
+            Err(err) => Err($crate::DynoErr::service_error(format!(
+                "background task failed - {err}"
+            ))),
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+pub use config::DynoConfig;
+#[cfg(feature = "std")]
+pub use error::{DynoErr, DynoResult, ErrKind};
+#[cfg(feature = "use_log")]
+pub use logger::{FileAction, LoggerBuilder, RECORDS_LOGGER};
+#[cfg(feature = "use_excel")]
+pub use saver::ExcelSaver;
+#[cfg(feature = "saver")]
+pub use saver::{CompresedSaver, CsvSaver, DynoSaver};
+
+#[cfg(feature = "std")]
+pub use chrono;
+#[cfg(feature = "use_async")]
+pub use crossbeam_channel;
 #[cfg(feature = "std")]
 pub use derive_more;
 #[cfg(feature = "std")]
 pub use log;
 #[cfg(feature = "derive_serde")]
 pub use serde;
+#[cfg(feature = "use_async")]
+pub use tokio;
 #[cfg(feature = "std")]
 pub use uom;
@@ -0,0 +1,162 @@
+//! simple logger, every records is also keeped in `RECORDS_LOGGER` for the log window in app
+
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::{DynoErr, DynoResult};
+
+/// records of the logger, drained by the log window in app
+pub static RECORDS_LOGGER: Mutex<Vec<(Level, String)>> = Mutex::new(Vec::new());
+
+/// what to do with log file when its size reach `max_size`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FileAction {
+    /// keep appending to the file
+    #[default]
+    Append,
+    /// rename the file to `<file>.old` and start a new one
+    Roll,
+    /// truncate the file and start over
+    Truncate,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoggerBuilder {
+    file: Option<PathBuf>,
+    max_size: u64,
+    max_level: LevelFilter,
+    roll_action: FileAction,
+}
+
+impl Default for LoggerBuilder {
+    fn default() -> Self {
+        Self {
+            file: None,
+            max_size: 10,
+            max_level: LevelFilter::Info,
+            roll_action: FileAction::Append,
+        }
+    }
+}
+
+impl LoggerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// max size of the log file in MiB
+    pub fn set_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn set_max_level(mut self, max_level: LevelFilter) -> Self {
+        self.max_level = max_level;
+        self
+    }
+
+    pub fn set_roll_action(mut self, roll_action: FileAction) -> Self {
+        self.roll_action = roll_action;
+        self
+    }
+
+    /// log to stderr
+    pub fn build_console_logger(self) -> DynoResult<()> {
+        self.install(Output::Console)
+    }
+
+    /// log to the file from `set_file`
+    pub fn build_file_logger(self) -> DynoResult<()> {
+        let path = self
+            .file
+            .clone()
+            .ok_or_else(|| DynoErr::filesystem_error("logger file is not set"))?;
+        let file = open_log_file(&path, self.max_size * 1024 * 1024, self.roll_action)?;
+        self.install(Output::File(Mutex::new(file)))
+    }
+
+    fn install(self, output: Output) -> DynoResult<()> {
+        let logger = DynoLogger {
+            level: self.max_level,
+            output,
+        };
+        log::set_boxed_logger(Box::new(logger))
+            .map(|()| log::set_max_level(self.max_level))
+            .map_err(|err| DynoErr::service_error(format!("failed to set logger - {err}")))
+    }
+}
+
+fn open_log_file(path: &PathBuf, max_size: u64, action: FileAction) -> DynoResult<File> {
+    let full = std::fs::metadata(path).is_ok_and(|meta| meta.len() >= max_size);
+    match action {
+        FileAction::Roll if full => {
+            let mut old = path.clone().into_os_string();
+            old.push(".old");
+            std::fs::rename(path, old)?;
+        }
+        FileAction::Truncate if full => {
+            return Ok(File::create(path)?);
+        }
+        _ => {}
+    }
+    Ok(OpenOptions::new().create(true).append(true).open(path)?)
+}
+
+enum Output {
+    Console,
+    File(Mutex<File>),
+}
+
+struct DynoLogger {
+    level: LevelFilter,
+    output: Output,
+}
+
+impl Log for DynoLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let msg = format!(
+            "[{}] {:<5} {} - {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        match &self.output {
+            Output::Console => eprintln!("{msg}"),
+            Output::File(file) => {
+                if let Ok(mut file) = file.lock() {
+                    let _ = writeln!(file, "{msg}");
+                }
+            }
+        }
+        if let Ok(mut records) = RECORDS_LOGGER.lock() {
+            records.push((record.level(), msg));
+        }
+    }
+
+    fn flush(&self) {
+        if let Output::File(file) = &self.output {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.flush();
+            }
+        }
+    }
+}
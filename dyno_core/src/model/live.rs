@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// marker of the run streamed to the `/ws` viewers, the samples between the start and the stop
+/// is sent as JSON `Vec<Data>`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum LiveEvent {
+    /// also sent again after reconnecting, before the samples that was kept while disconnected
+    Start {
+        run: String,
+        user_id: i64,
+        start: DateTime<Utc>,
+    },
+    Stop {
+        run: String,
+        user_id: i64,
+        stop: DateTime<Utc>,
+    },
+}
@@ -3,8 +3,11 @@ use chrono::{DateTime, Utc};
 use crate::config::DynoConfig;
 
 pub mod dynotests;
+pub mod live;
 pub mod role;
 pub mod users;
+pub mod validate;
+pub mod vehicle;
 
 pub const COOKIE_NAME: &str = "dyno_session";
 pub const USER_HEADER_NAME: &str = "x-user-id";
@@ -52,6 +55,25 @@ impl TokenClaims {
     }
 }
 
+/// token of the logged in user, returned by the login and the refresh endpoint
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct TokenDetails {
+    pub token: Option<String>,
+    pub token_uuid: String,
+    pub user_id: i64,
+    /// expiry of `token`, timestamp in ms
+    pub expires_in: Option<i64>,
+    /// exchanged on `/api/auth/refresh` for new tokens, valid only once
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+}
+
+/// body of `POST /api/auth/refresh`
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct ActiveResponse<'s> {
     pub user: Option<users::UserResponse<'s>>,
@@ -0,0 +1,133 @@
+//! validation of the user input on the register and login form
+
+use crate::{DynoErr, DynoResult};
+
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// nim of Polije student, one letter of the major followed by 8 digits (E32211406)
+pub fn validate_nim(nim: &str) -> DynoResult<()> {
+    let mut chars = nim.chars();
+    match chars.next() {
+        None => Err(DynoErr::validation_error("nim is empty")),
+        Some(c) if !c.is_ascii_alphabetic() => Err(DynoErr::validation_error(
+            "nim should start with the major letter",
+        )),
+        _ if nim.len() != 9 => Err(DynoErr::validation_error("nim should be 9 characters")),
+        _ if !chars.all(|c| c.is_ascii_digit()) => Err(DynoErr::validation_error(
+            "nim should be digits after the major letter",
+        )),
+        _ => Ok(()),
+    }
+}
+
+pub fn validate_email(email: &str) -> DynoResult<()> {
+    let Some((user, domain)) = email.split_once('@') else {
+        return Err(DynoErr::validation_error("email should contain '@'"));
+    };
+    let valid_part = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '+'))
+    };
+    if !valid_part(user) || !valid_part(domain) {
+        return Err(DynoErr::validation_error(
+            "email contains invalid character",
+        ));
+    }
+    match domain.rsplit_once('.') {
+        Some((name, tld)) if !name.is_empty() && tld.len() >= 2 => Ok(()),
+        _ => Err(DynoErr::validation_error("email domain is not valid")),
+    }
+}
+
+pub fn validate_password(password: &str) -> DynoResult<()> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(DynoErr::validation_error(format!(
+            "password should be at least {MIN_PASSWORD_LEN} characters"
+        )));
+    }
+    if password.chars().any(char::is_whitespace) {
+        return Err(DynoErr::validation_error(
+            "password should not contain space",
+        ));
+    }
+    Ok(())
+}
+
+/// strength of the password, from 0 (empty) to 4 (strong)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PasswordStrength(u8);
+
+impl PasswordStrength {
+    pub fn new(password: &str) -> Self {
+        if password.is_empty() {
+            return Self(0);
+        }
+        let classes = [
+            password.chars().any(|c| c.is_ascii_lowercase()),
+            password.chars().any(|c| c.is_ascii_uppercase()),
+            password.chars().any(|c| c.is_ascii_digit()),
+            password.chars().any(|c| !c.is_ascii_alphanumeric()),
+        ]
+        .into_iter()
+        .filter(|x| *x)
+        .count() as u8;
+        let long = password.chars().count() >= MIN_PASSWORD_LEN;
+        Self(crate::ternary!((long)?(classes.clamp(1, 4)):(1)))
+    }
+
+    #[inline]
+    pub const fn score(self) -> u8 {
+        self.0
+    }
+
+    /// percent of strength and the rgb color for the progress bar
+    pub const fn percent_color(self) -> (f32, [u8; 3]) {
+        let color = match self.0 {
+            0 | 1 => [0xe7, 0x4c, 0x3c],
+            2 => [0xe6, 0x7e, 0x22],
+            3 => [0xf1, 0xc4, 0x0f],
+            _ => [0x2e, 0xcc, 0x71],
+        };
+        (self.0 as f32 / 4., color)
+    }
+
+    pub const fn desc(self) -> &'static str {
+        match self.0 {
+            0 => "empty",
+            1 => "weak",
+            2 => "fair",
+            3 => "good",
+            _ => "strong",
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_user_input() {
+        assert!(validate_nim("E32211406").is_ok());
+        assert!(validate_nim("").is_err());
+        assert!(validate_nim("332211406").is_err());
+        assert!(validate_nim("E3221140A").is_err());
+        assert!(validate_email("rizal.ahmadp@gmail.com").is_ok());
+        assert!(validate_email("rizal@gmail").is_err());
+        assert!(validate_email("@gmail.com").is_err());
+        assert!(validate_password("secret12").is_ok());
+        assert!(validate_password("secret").is_err());
+    }
+
+    #[test]
+    fn password_strength() {
+        assert_eq!(PasswordStrength::new("").desc(), "empty");
+        assert_eq!(PasswordStrength::new("Ab1!").desc(), "weak");
+        assert_eq!(PasswordStrength::new("password").desc(), "weak");
+        assert_eq!(PasswordStrength::new("password12").desc(), "fair");
+        assert_eq!(PasswordStrength::new("Password12!").desc(), "strong");
+        assert_eq!(PasswordStrength::new("Password12!").percent_color().0, 1.);
+    }
+}
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::MotorInfo;
+
+/// vehicle and the owner, registered in the desktop app and synced with the server.
+/// the record with the newer `updated_at` is kept when both side is changed
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Vehicle {
+    pub uuid: String,
+    pub make: String,
+    pub model: String,
+    pub plate_number: String,
+    pub customer: String,
+    pub phone: String,
+    pub notes: String,
+    pub motor_info: MotorInfo,
+    /// deleted record is kept, so the deletion is synced too
+    pub deleted: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Vehicle {
+    /// `make model`, or the plate number if both is empty
+    pub fn title(&self) -> String {
+        let name = format!("{} {}", self.make, self.model);
+        match name.trim() {
+            "" => self.plate_number.clone(),
+            name => name.to_owned(),
+        }
+    }
+
+    /// case insensitive match of the plate number, make, model, customer or phone
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || [
+                &self.plate_number,
+                &self.make,
+                &self.model,
+                &self.customer,
+                &self.phone,
+            ]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+/// request of `POST /api/vehicle/sync`, the response is every vehicle of the server
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct VehicleSync {
+    pub changed: Vec<Vehicle>,
+}
@@ -0,0 +1,737 @@
+//! printable dyno sheet of one or more runs, rendered as one A4 page SVG without any native
+//! dependency, so the app and the backend can render it headless.
+//!
+//! the sheet have these sections (every section is a `<g id="...">` group):
+//! `header`, `details`, `chart` (power and torque vs rpm, peaks annotated), `ambient` and `summary`.
+//! more than one run is drawn as overlay (e.g. before/after), the first run is the baseline.
+//! annotations of the runs are numbered on the chart and listed below the summary.
+
+use std::{fmt::Write, path::Path};
+
+use chrono::{DateTime, Utc};
+use uom::si::{
+    angular_velocity::revolution_per_minute, power::horsepower_metric,
+    temperature_interval::degree_celsius, torque::newton_meter, velocity::kilometer_per_hour,
+};
+
+use crate::{
+    types::{Annotation, DynotestData, MotorInfo, MotorKind},
+    DynoResult,
+};
+
+/// A4 in px at 96 dpi
+const PAGE_WIDTH: f64 = 794.;
+const PAGE_HEIGHT: f64 = 1123.;
+const MARGIN: f64 = 40.;
+
+/// colors of the overlay runs, the run after the last color use the first color again
+const RUN_COLORS: [&str; 4] = ["#d62728", "#1f77b4", "#2ca02c", "#9467bd"];
+
+/// workshop that print the sheet
+#[derive(Debug, Clone, Default)]
+pub struct ReportHeader {
+    pub workshop: String,
+    pub address: String,
+    /// png bytes, embedded as data uri
+    pub logo_png: Option<Vec<u8>>,
+}
+
+/// customer and vehicle of the sheet
+#[derive(Debug, Clone, Default)]
+pub struct ReportInfo {
+    pub customer: String,
+    pub vehicle: String,
+    pub plate_number: String,
+    pub operator: String,
+    pub notes: String,
+}
+
+/// peaks and totals of one run
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunSummary {
+    /// unit [HP]
+    pub max_power: f64,
+    /// unit [REVpM]
+    pub max_power_rpm: f64,
+    /// unit [NM]
+    pub max_torque: f64,
+    /// unit [REVpM]
+    pub max_torque_rpm: f64,
+    /// unit [KMpH]
+    pub max_speed: f64,
+    pub duration_ms: i64,
+    pub odo_km: f64,
+}
+
+impl RunSummary {
+    pub fn from_data(data: &DynotestData) -> Self {
+        let use_engine = use_engine_rpm(data);
+        data.iter().fold(
+            Self {
+                duration_ms: data.total_time().num_milliseconds(),
+                odo_km: data.odo_km(),
+                ..Default::default()
+            },
+            |mut summary, d| {
+                let rpm = rpm_of(d, use_engine);
+                let power = d.horsepower.get::<horsepower_metric>();
+                let torque = d.torque.get::<newton_meter>();
+                if power > summary.max_power {
+                    summary.max_power = power;
+                    summary.max_power_rpm = rpm;
+                }
+                if torque > summary.max_torque {
+                    summary.max_torque = torque;
+                    summary.max_torque_rpm = rpm;
+                }
+                summary.max_speed = summary.max_speed.max(d.speed.get::<kilometer_per_hour>());
+                summary
+            },
+        )
+    }
+}
+
+/// the x axis is the engine rpm, or the roller rpm if the engine rpm is not measured
+/// (electric motor or no pickup)
+fn use_engine_rpm(data: &DynotestData) -> bool {
+    data.config.motor_info.kind != MotorKind::Electric
+        && data
+            .iter()
+            .any(|d| d.rpm_engine.get::<revolution_per_minute>() > 0.)
+}
+
+#[inline]
+fn rpm_of(data: &crate::types::Data, use_engine: bool) -> f64 {
+    match use_engine {
+        true => data.rpm_engine.get::<revolution_per_minute>(),
+        false => data.rpm_roda.get::<revolution_per_minute>(),
+    }
+}
+
+/// smallest 1, 2 or 5 times power of ten that is not less than `value / ticks`, times `ticks`
+fn nice_max(value: f64, ticks: usize) -> f64 {
+    if value <= 0. || !value.is_finite() {
+        return ticks as f64;
+    }
+    let raw = value / ticks as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1., 2., 5., 10.]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw)
+        .unwrap_or(10. * magnitude);
+    step * ticks as f64
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (u32::from(*b) << (16 - 8 * i)));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out.push(TABLE[((n >> (18 - 6 * i)) & 0x3F) as usize] as char),
+                false => out.push('='),
+            }
+        }
+    }
+    out
+}
+
+struct Run<'a> {
+    label: &'a str,
+    data: &'a DynotestData,
+    summary: RunSummary,
+}
+
+/// dyno sheet of the runs, render with [`DynoReport::render_svg`]
+pub struct DynoReport<'a> {
+    header: ReportHeader,
+    info: ReportInfo,
+    date: DateTime<Utc>,
+    runs: Vec<Run<'a>>,
+}
+
+impl<'a> DynoReport<'a> {
+    pub fn new(header: ReportHeader, info: ReportInfo) -> Self {
+        Self {
+            header,
+            info,
+            date: Utc::now(),
+            runs: Vec::new(),
+        }
+    }
+
+    /// date printed on the sheet, default to now
+    pub fn set_date(&mut self, date: DateTime<Utc>) -> &mut Self {
+        self.date = date;
+        self
+    }
+
+    /// add the run to the overlay with the notes of the operator, the empty run is ignored
+    pub fn add_run(&mut self, label: &'a str, data: &'a DynotestData) -> &mut Self {
+        if !data.is_empty() {
+            self.runs.push(Run {
+                label,
+                data,
+                summary: RunSummary::from_data(data),
+            });
+        }
+        self
+    }
+
+    /// every annotation with the index of the run, numbered from 1 in this order
+    fn annotations(&self) -> impl Iterator<Item = (usize, &Annotation)> {
+        self.runs
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, run)| run.data.annotations().iter().map(move |note| (idx, note)))
+    }
+
+    #[inline]
+    pub fn summaries(&self) -> impl Iterator<Item = (&str, &RunSummary)> {
+        self.runs.iter().map(|run| (run.label, &run.summary))
+    }
+
+    pub fn save_svg(&self, path: impl AsRef<Path>) -> DynoResult<()> {
+        std::fs::write(path, self.render_svg()).map_err(From::from)
+    }
+
+    pub fn render_svg(&self) -> String {
+        let mut svg = String::with_capacity(64 * 1024);
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="210mm" height="297mm" viewBox="0 0 {PAGE_WIDTH} {PAGE_HEIGHT}" font-family="Helvetica, Arial, sans-serif" font-size="12">"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{PAGE_WIDTH}" height="{PAGE_HEIGHT}" fill="white"/>"#
+        );
+        let mut y = MARGIN;
+        y = self.render_header(&mut svg, y);
+        y = self.render_details(&mut svg, y);
+        y = self.render_chart(&mut svg, y);
+        y = self.render_ambient(&mut svg, y);
+        self.render_summary(&mut svg, y);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn render_header(&self, svg: &mut String, y: f64) -> f64 {
+        let ReportHeader {
+            workshop,
+            address,
+            logo_png,
+        } = &self.header;
+        let mut x = MARGIN;
+        svg.push_str("<g id=\"header\">\n");
+        if let Some(logo) = logo_png {
+            let _ = writeln!(
+                svg,
+                r#"<image x="{x}" y="{y}" width="64" height="64" href="data:image/png;base64,{}"/>"#,
+                base64(logo)
+            );
+            x += 76.;
+        }
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" font-size="22" font-weight="bold">{}</text>"#,
+            y + 26.,
+            escape(workshop)
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x}" y="{}">{}</text>"#,
+            y + 46.,
+            escape(address)
+        );
+        let right = PAGE_WIDTH - MARGIN;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{right}" y="{}" text-anchor="end" font-size="18" font-weight="bold">DYNO SHEET</text>"#,
+            y + 26.
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{right}" y="{}" text-anchor="end">{}</text>"#,
+            y + 46.,
+            self.date.format("%Y-%m-%d %H:%M UTC")
+        );
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN}" y1="{0}" x2="{right}" y2="{0}" stroke="#333" stroke-width="2"/>"##,
+            y + 74.
+        );
+        svg.push_str("</g>\n");
+        y + 90.
+    }
+
+    fn render_details(&self, svg: &mut String, y: f64) -> f64 {
+        let ReportInfo {
+            customer,
+            vehicle,
+            plate_number,
+            operator,
+            notes,
+        } = &self.info;
+        let MotorInfo {
+            cc,
+            kind,
+            cylinder,
+            stroke,
+            transmition,
+        } = self
+            .runs
+            .first()
+            .map(|run| run.data.config.motor_info)
+            .unwrap_or_default();
+        let left = [
+            ("Customer", customer.clone()),
+            ("Vehicle", vehicle.clone()),
+            ("Plate Number", plate_number.clone()),
+            ("Operator", operator.clone()),
+        ];
+        let right = [
+            ("Motor", kind.to_string()),
+            ("Capacity", format!("{cc} cc")),
+            ("Cylinder", format!("{cylinder} / {stroke} Stroke")),
+            ("Transmition", transmition.to_string()),
+        ];
+        svg.push_str("<g id=\"details\">\n");
+        for (column, rows) in [(MARGIN, left), (PAGE_WIDTH / 2., right)] {
+            for (idx, (label, value)) in rows.iter().enumerate() {
+                let row_y = y + 16. * idx as f64;
+                let _ = writeln!(
+                    svg,
+                    r##"<text x="{column}" y="{row_y}" fill="#555">{label}</text><text x="{}" y="{row_y}">{}</text>"##,
+                    column + 100.,
+                    escape(value)
+                );
+            }
+        }
+        let mut y = y + 16. * 4.;
+        if !notes.is_empty() {
+            let _ = writeln!(
+                svg,
+                r##"<text x="{MARGIN}" y="{y}" fill="#555">Notes</text><text x="{}" y="{y}">{}</text>"##,
+                MARGIN + 100.,
+                escape(notes)
+            );
+            y += 16.;
+        }
+        svg.push_str("</g>\n");
+        y + 8.
+    }
+
+    fn render_chart(&self, svg: &mut String, y: f64) -> f64 {
+        const HEIGHT: f64 = 440.;
+        const TICKS: usize = 5;
+        let (x0, x1) = (MARGIN + 50., PAGE_WIDTH - MARGIN - 50.);
+        let (y0, y1) = (y + 30., y + 30. + HEIGHT);
+
+        let use_engine = self.runs.iter().all(|run| use_engine_rpm(run.data));
+        let max_rpm = self
+            .runs
+            .iter()
+            .flat_map(|run| run.data.iter().map(|d| rpm_of(d, use_engine)))
+            .fold(0., f64::max);
+        let max_power = self
+            .runs
+            .iter()
+            .map(|run| run.summary.max_power)
+            .fold(0., f64::max);
+        let max_torque = self
+            .runs
+            .iter()
+            .map(|run| run.summary.max_torque)
+            .fold(0., f64::max);
+        let (max_rpm, max_power, max_torque) = (
+            nice_max(max_rpm, TICKS),
+            nice_max(max_power, TICKS),
+            nice_max(max_torque, TICKS),
+        );
+        let sx = |rpm: f64| x0 + (x1 - x0) * rpm / max_rpm;
+        let sy = |value: f64, max: f64| y1 - (y1 - y0) * value / max;
+
+        svg.push_str("<g id=\"chart\">\n");
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle" font-size="14" font-weight="bold">Power &amp; Torque vs {} RPM</text>"#,
+            PAGE_WIDTH / 2.,
+            y + 14.,
+            if use_engine { "Engine" } else { "Roller" }
+        );
+        for tick in 0..=TICKS {
+            let frac = tick as f64 / TICKS as f64;
+            let (gx, gy) = (x0 + (x1 - x0) * frac, y1 - (y1 - y0) * frac);
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{gx}" y1="{y0}" x2="{gx}" y2="{y1}" stroke="#ddd"/><line x1="{x0}" y1="{gy}" x2="{x1}" y2="{gy}" stroke="#ddd"/>"##
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{gx}" y="{}" text-anchor="middle">{:.0}</text><text x="{}" y="{}" text-anchor="end">{:.0}</text><text x="{}" y="{}">{:.0}</text>"#,
+                y1 + 16.,
+                max_rpm * frac,
+                x0 - 6.,
+                gy + 4.,
+                max_power * frac,
+                x1 + 6.,
+                gy + 4.,
+                max_torque * frac,
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{x0}" y="{y0}" width="{}" height="{}" fill="none" stroke="#333"/>"##,
+            x1 - x0,
+            y1 - y0
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">RPM</text><text x="{}" y="{}" text-anchor="middle" transform="rotate(-90 {2} {3})">Power (HP)</text><text x="{}" y="{}" text-anchor="middle" transform="rotate(90 {4} {5})">Torque (Nm)</text>"#,
+            (x0 + x1) / 2.,
+            y1 + 34.,
+            x0 - 38.,
+            (y0 + y1) / 2.,
+            x1 + 38.,
+            (y0 + y1) / 2.,
+        );
+
+        for (idx, run) in self.runs.iter().enumerate() {
+            let color = RUN_COLORS[idx % RUN_COLORS.len()];
+            let points = run
+                .data
+                .iter()
+                .map(|d| {
+                    (
+                        rpm_of(d, use_engine),
+                        d.horsepower.get::<horsepower_metric>(),
+                        d.torque.get::<newton_meter>(),
+                    )
+                })
+                .filter(|(rpm, ..)| *rpm > 0.)
+                .collect::<Vec<_>>();
+            let path = |value: fn(&(f64, f64, f64)) -> f64, max: f64| {
+                points
+                    .iter()
+                    .enumerate()
+                    .fold(String::new(), |mut d, (i, point)| {
+                        let cmd = if i == 0 { 'M' } else { 'L' };
+                        let _ = write!(d, "{cmd}{:.1},{:.1} ", sx(point.0), sy(value(point), max));
+                        d
+                    })
+            };
+            let _ = writeln!(
+                svg,
+                r#"<path class="power" data-run="{idx}" d="{}" fill="none" stroke="{color}" stroke-width="2"/>"#,
+                path(|p| p.1, max_power).trim_end()
+            );
+            let _ = writeln!(
+                svg,
+                r#"<path class="torque" data-run="{idx}" d="{}" fill="none" stroke="{color}" stroke-width="2" stroke-dasharray="6 4"/>"#,
+                path(|p| p.2, max_torque).trim_end()
+            );
+
+            let RunSummary {
+                max_power: peak_power,
+                max_power_rpm,
+                max_torque: peak_torque,
+                max_torque_rpm,
+                ..
+            } = run.summary;
+            for (class, px, py, text) in [
+                (
+                    "peak-power",
+                    sx(max_power_rpm),
+                    sy(peak_power, max_power),
+                    format!("{peak_power:.2} HP @ {max_power_rpm:.0}"),
+                ),
+                (
+                    "peak-torque",
+                    sx(max_torque_rpm),
+                    sy(peak_torque, max_torque),
+                    format!("{peak_torque:.2} Nm @ {max_torque_rpm:.0}"),
+                ),
+            ] {
+                let _ = writeln!(
+                    svg,
+                    r#"<g class="{class}" data-run="{idx}"><circle cx="{px:.1}" cy="{py:.1}" r="4" fill="{color}"/><text x="{:.1}" y="{:.1}" fill="{color}">{text}</text></g>"#,
+                    px + 6.,
+                    py - 6. - 14. * idx as f64,
+                );
+            }
+
+            let ly = y0 + 14. + 16. * idx as f64;
+            let _ = writeln!(
+                svg,
+                r#"<g class="legend"><line x1="{0}" y1="{1}" x2="{2}" y2="{1}" stroke="{color}" stroke-width="2"/><line x1="{3}" y1="{1}" x2="{4}" y2="{1}" stroke="{color}" stroke-width="2" stroke-dasharray="6 4"/><text x="{5}" y="{6}">{7}</text></g>"#,
+                x0 + 10.,
+                ly,
+                x0 + 30.,
+                x0 + 34.,
+                x0 + 54.,
+                x0 + 60.,
+                ly + 4.,
+                escape(run.label),
+            );
+        }
+
+        for (number, (idx, note)) in self.annotations().enumerate() {
+            let run = &self.runs[idx];
+            let Some(data) = run.data.nearest_index(note.timestamp).map(|i| &run.data[i]) else {
+                continue;
+            };
+            let color = RUN_COLORS[idx % RUN_COLORS.len()];
+            let ax = sx(rpm_of(data, use_engine));
+            let _ = writeln!(
+                svg,
+                r#"<g class="annotation" data-run="{idx}"><line x1="{ax:.1}" y1="{y0}" x2="{ax:.1}" y2="{y1}" stroke="{color}" stroke-dasharray="2 3"/><text x="{:.1}" y="{:.1}" fill="{color}" font-weight="bold">A{}</text></g>"#,
+                ax + 3.,
+                y1 - 6. - 14. * (number % 3) as f64,
+                number + 1,
+            );
+        }
+        svg.push_str("</g>\n");
+        y1 + 50.
+    }
+
+    fn render_ambient(&self, svg: &mut String, y: f64) -> f64 {
+        svg.push_str("<g id=\"ambient\">\n");
+        let _ = writeln!(
+            svg,
+            r#"<text x="{MARGIN}" y="{y}" font-size="14" font-weight="bold">Ambient &amp; Correction</text>"#
+        );
+        let mut row_y = y + 18.;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{MARGIN}" y="{row_y}">Correction: none, the values are observed (uncorrected)</text>"#
+        );
+        if let Some(run) = self.runs.first() {
+            let config = &run.data.config;
+            let len = run.data.len().max(1) as f64;
+            let temps = (0..config
+                .temp_channels
+                .len()
+                .min(crate::types::MAX_TEMP_CHANNELS))
+                .map(|idx| {
+                    let mean = run
+                        .data
+                        .iter()
+                        .map(|d| d.temps[idx].get::<degree_celsius>())
+                        .sum::<f64>()
+                        / len;
+                    format!("{}: {mean:.1} °C", config.temp_channel_name(idx))
+                })
+                .collect::<Vec<_>>();
+            if !temps.is_empty() {
+                row_y += 16.;
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{MARGIN}" y="{row_y}">Average temperature ({}): {}</text>"#,
+                    escape(run.label),
+                    escape(&temps.join(", "))
+                );
+            }
+        }
+        svg.push_str("</g>\n");
+        row_y + 30.
+    }
+
+    fn render_summary(&self, svg: &mut String, y: f64) -> f64 {
+        const COLUMNS: [(&str, f64); 6] = [
+            ("Run", 0.),
+            ("Max Power", 150.),
+            ("Max Torque", 290.),
+            ("Max Speed", 430.),
+            ("Duration", 530.),
+            ("Distance", 630.),
+        ];
+        svg.push_str("<g id=\"summary\">\n");
+        for (name, x) in COLUMNS {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{y}" font-weight="bold">{name}</text>"#,
+                MARGIN + x
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN}" y1="{0}" x2="{1}" y2="{0}" stroke="#333"/>"##,
+            y + 6.,
+            PAGE_WIDTH - MARGIN
+        );
+        let mut row_y = y;
+        for (idx, run) in self.runs.iter().enumerate() {
+            row_y += 18.;
+            let s = &run.summary;
+            let cells = [
+                escape(run.label),
+                format!("{:.2} HP @ {:.0}", s.max_power, s.max_power_rpm),
+                format!("{:.2} Nm @ {:.0}", s.max_torque, s.max_torque_rpm),
+                format!("{:.1} km/h", s.max_speed),
+                format!("{:.1} s", s.duration_ms as f64 / 1000.),
+                format!("{:.3} km", s.odo_km),
+            ];
+            let _ = write!(svg, r#"<g class="summary-row" data-run="{idx}">"#);
+            for ((_, x), cell) in COLUMNS.iter().zip(cells) {
+                let _ = write!(svg, r#"<text x="{}" y="{row_y}">{cell}</text>"#, MARGIN + x);
+            }
+            svg.push_str("</g>\n");
+        }
+        // difference of the last run from the baseline, e.g. after the tuning
+        if let [first, .., last] = self.runs.as_slice() {
+            row_y += 18.;
+            let _ = writeln!(
+                svg,
+                r#"<text class="summary-diff" x="{MARGIN}" y="{row_y}">{} vs {}: power {:+.2} HP, torque {:+.2} Nm</text>"#,
+                escape(last.label),
+                escape(first.label),
+                last.summary.max_power - first.summary.max_power,
+                last.summary.max_torque - first.summary.max_torque,
+            );
+        }
+        for (number, (idx, note)) in self.annotations().enumerate() {
+            let run = &self.runs[idx];
+            row_y += 16.;
+            let elapsed = (note.timestamp - run.data.first().timestamp) as f64 / 1000.;
+            let _ = writeln!(
+                svg,
+                r#"<text class="annotation-note" x="{MARGIN}" y="{row_y}">A{} ({}, {elapsed:.1} s): {}</text>"#,
+                number + 1,
+                escape(run.label),
+                escape(&note.text),
+            );
+        }
+        svg.push_str("</g>\n");
+        row_y + 18.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Data;
+    use uom::si::f64::{AngularVelocity, Power, Torque};
+
+    fn run(scale: f64) -> DynotestData {
+        let mut data = DynotestData::default();
+        for step in 1..=40 {
+            let rpm = step as f64 * 250.;
+            // peak around 6000 rpm
+            let torque = scale * (20. - ((rpm - 6000.) / 1000.).powi(2)).max(1.);
+            data.push(Data {
+                rpm_engine: AngularVelocity::new::<revolution_per_minute>(rpm),
+                torque: Torque::new::<newton_meter>(torque),
+                horsepower: Power::new::<horsepower_metric>(torque * rpm / 7023.5),
+                timestamp: 1_000 + step * 250,
+                ..Default::default()
+            });
+        }
+        data
+    }
+
+    #[test]
+    fn report_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(nice_max(87., 5), 100.);
+        assert_eq!(nice_max(10_000., 5), 10_000.);
+        assert_eq!(nice_max(0., 5), 5.);
+    }
+
+    #[test]
+    fn report_svg_structure() {
+        let before = run(1.0);
+        let mut after = run(1.1);
+        after.annotate(3_000, "changed jet");
+        let empty = DynotestData::default();
+        let mut report = DynoReport::new(
+            ReportHeader {
+                workshop: "Dyno <Polije>".to_owned(),
+                address: "Jember".to_owned(),
+                logo_png: Some(vec![0x89, b'P', b'N', b'G']),
+            },
+            ReportInfo {
+                customer: "Budi & Co".to_owned(),
+                ..Default::default()
+            },
+        );
+        report
+            .add_run("Stock", &before)
+            .add_run("Empty", &empty)
+            .add_run("Tuned", &after);
+
+        let summary = report.summaries().map(|(_, s)| *s).collect::<Vec<_>>();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].max_torque_rpm.round(), 6000.);
+        assert!(summary[1].max_power > summary[0].max_power);
+
+        let svg = report.render_svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        for section in ["header", "details", "chart", "ambient", "summary"] {
+            assert_eq!(svg.matches(&format!("<g id=\"{section}\">")).count(), 1);
+        }
+        assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+        assert_eq!(svg.matches("class=\"power\"").count(), 2);
+        assert_eq!(svg.matches("class=\"torque\"").count(), 2);
+        assert_eq!(svg.matches("class=\"peak-torque\"").count(), 2);
+        assert_eq!(svg.matches("class=\"summary-row\"").count(), 2);
+        assert!(svg.contains("Nm @ 6000"));
+        assert!(svg.contains("Tuned vs Stock: power +"));
+        assert_eq!(
+            svg.matches("class=\"annotation\" data-run=\"1\"").count(),
+            1
+        );
+        assert!(svg.contains("A1 (Tuned, 1.8 s): changed jet"));
+        assert!(svg.contains("data:image/png;base64,iVBORw=="));
+        assert!(svg.contains("Dyno &lt;Polije&gt;") && svg.contains("Budi &amp; Co"));
+    }
+
+    /// attribute `name` of the first element with `class` in the svg
+    fn attr<'a>(svg: &'a str, class: &str, name: &str) -> &'a str {
+        let start = svg.find(&format!("class=\"{class}\" data-run=\"0\""));
+        let element = &svg[start.unwrap()..];
+        let value = &element[element.find(&format!(" {name}=\"")).unwrap() + name.len() + 3..];
+        &value[..value.find('"').unwrap()]
+    }
+
+    #[test]
+    fn report_peak_marker_on_curve() {
+        let data = run(1.0);
+        let mut report = DynoReport::new(ReportHeader::default(), ReportInfo::default());
+        report.add_run("Stock", &data);
+        let svg = report.render_svg();
+
+        for (class, curve) in [("peak-power", "power"), ("peak-torque", "torque")] {
+            let marker = (attr(&svg, class, "cx"), attr(&svg, class, "cy"));
+            // the highest point of the curve is the smallest y
+            let top = attr(&svg, curve, "d")
+                .split_whitespace()
+                .filter_map(|point| point.trim_start_matches(['M', 'L']).split_once(','))
+                .min_by(|a, b| a.1.parse::<f64>().unwrap().total_cmp(&b.1.parse().unwrap()))
+                .unwrap();
+            assert_eq!(marker, top, "{class}");
+        }
+    }
+}
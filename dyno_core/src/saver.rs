@@ -0,0 +1,466 @@
+//! saving and opening `DynotestData` (and anything serializable) in the app file formats
+//!
+//! - `.dyno`: postcard encoded and deflate compressed, keep the config of the dynotests.
+//!   the postcard payload is prefixed by [`DYNO_MAGIC`] and the format version, the file
+//!   without it is the first layout (single temperature), opened by the legacy decoder
+//! - `.csv` and `.xlsx`: one row per data, only the data (config is set to default on open)
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use serde::{de::DeserializeOwned, Serialize};
+use uom::si::{
+    angular_velocity::revolution_per_minute,
+    f64::{AngularVelocity, Power, TemperatureInterval, Torque, Velocity},
+    power::horsepower_metric,
+    temperature_interval::degree_celsius,
+    torque::newton_meter,
+    velocity::kilometer_per_hour,
+};
+
+use crate::{
+    types::{Data, DynotestData, MAX_TEMP_CHANNELS},
+    DynoErr, DynoResult,
+};
+
+pub trait CompresedSaver: Serialize {
+    fn compress(&self) -> DynoResult<Vec<u8>> {
+        let bytes = postcard::to_stdvec(self)?;
+        let mut encoder =
+            DeflateEncoder::new(Vec::with_capacity(bytes.len()), Compression::default());
+        encoder.write_all(&bytes)?;
+        encoder.finish().map_err(From::from)
+    }
+
+    fn decompress(bytes: impl AsRef<[u8]>) -> DynoResult<Self>
+    where
+        Self: DeserializeOwned,
+    {
+        let mut decoded = Vec::new();
+        DeflateDecoder::new(bytes.as_ref()).read_to_end(&mut decoded)?;
+        postcard::from_bytes(&decoded).map_err(From::from)
+    }
+
+    fn compress_to_path(&self, path: impl AsRef<Path>) -> DynoResult<()> {
+        let bytes = self.compress()?;
+        std::fs::write(path, bytes).map_err(DynoErr::filesystem_error)
+    }
+
+    fn decompress_from_path(path: impl AsRef<Path>) -> DynoResult<Self>
+    where
+        Self: DeserializeOwned,
+    {
+        let bytes = std::fs::read(path).map_err(DynoErr::filesystem_error)?;
+        Self::decompress(bytes)
+    }
+}
+
+impl<T: Serialize> CompresedSaver for T {}
+
+/// first bytes of the decompressed `.dyno`, the file without it is version 0
+pub const DYNO_MAGIC: [u8; 4] = *b"DYNO";
+/// version of the `.dyno` layout, increase it on every change of `DynotestData` encoding
+/// and keep the decoder of the previous one
+pub const DYNO_FORMAT_VERSION: u8 = 1;
+
+/// `.dyno` file of the run, versioned unlike [`CompresedSaver`]
+pub trait DynoSaver: Sized {
+    fn save_dyno_into_bytes(&self) -> DynoResult<Vec<u8>>;
+    fn open_dyno_from_bytes(bytes: impl AsRef<[u8]>) -> DynoResult<Self>;
+
+    fn save_dyno_from_path(&self, path: impl AsRef<Path>) -> DynoResult<()> {
+        let bytes = self.save_dyno_into_bytes()?;
+        std::fs::write(path, bytes).map_err(DynoErr::filesystem_error)
+    }
+
+    fn open_dyno_from_path(path: impl AsRef<Path>) -> DynoResult<Self> {
+        let bytes = std::fs::read(path).map_err(DynoErr::filesystem_error)?;
+        Self::open_dyno_from_bytes(bytes)
+    }
+}
+
+impl DynoSaver for DynotestData {
+    fn save_dyno_into_bytes(&self) -> DynoResult<Vec<u8>> {
+        let mut bytes = DYNO_MAGIC.to_vec();
+        bytes.push(DYNO_FORMAT_VERSION);
+        let bytes = postcard::to_extend(self, bytes)?;
+        let mut encoder =
+            DeflateEncoder::new(Vec::with_capacity(bytes.len()), Compression::default());
+        encoder.write_all(&bytes)?;
+        encoder.finish().map_err(From::from)
+    }
+
+    fn open_dyno_from_bytes(bytes: impl AsRef<[u8]>) -> DynoResult<Self> {
+        let mut decoded = Vec::new();
+        DeflateDecoder::new(bytes.as_ref()).read_to_end(&mut decoded)?;
+        let Some(payload) = decoded.strip_prefix(&DYNO_MAGIC) else {
+            return legacy::decode(&decoded);
+        };
+        match payload.split_first() {
+            Some((&DYNO_FORMAT_VERSION, payload)) => {
+                postcard::from_bytes(payload).map_err(From::from)
+            }
+            Some((version, _)) => Err(DynoErr::encoding_error(format!(
+                "`.dyno` version {version} is not supported, the file is made by a newer app"
+            ))),
+            None => Err(DynoErr::encoding_error("`.dyno` file has no version")),
+        }
+    }
+}
+
+/// the first `.dyno` layout, before the thermocouple channels
+mod legacy {
+    use chrono::{DateTime, Utc};
+    use serde::Deserialize;
+    use uom::si::f64::{AngularVelocity, Power, TemperatureInterval, Torque, Velocity};
+
+    use crate::{
+        config::DEFAULT_TEMP_CHANNELS,
+        types::{Data, DynotestData, MotorInfo},
+        DynoConfig, DynoResult,
+    };
+
+    #[derive(Deserialize)]
+    #[cfg_attr(test, derive(serde::Serialize))]
+    pub(super) struct LegacyConfig {
+        pub diameter_roller_cm: f64,
+        pub diameter_roller_beban_cm: f64,
+        pub diameter_gear_encoder_cm: f64,
+        pub diameter_gear_beban_cm: f64,
+        pub berat_beban_kg: f64,
+        pub motor_info: MotorInfo,
+        pub max_encoder_pulse: u16,
+        pub delta_ms: u16,
+    }
+
+    #[derive(Deserialize)]
+    #[cfg_attr(test, derive(serde::Serialize))]
+    pub(super) struct LegacyData {
+        pub speed: Velocity,
+        pub torque: Torque,
+        pub horsepower: Power,
+        pub rpm_roda: AngularVelocity,
+        pub rpm_engine: AngularVelocity,
+        pub temp: TemperatureInterval,
+        pub timestamp: i64,
+    }
+
+    #[derive(Deserialize)]
+    #[cfg_attr(test, derive(serde::Serialize))]
+    pub(super) struct LegacyDynotestData {
+        pub config: LegacyConfig,
+        pub data: Vec<LegacyData>,
+        pub start_time: DateTime<Utc>,
+        pub odo_km: f64,
+    }
+
+    pub(super) fn decode(bytes: &[u8]) -> DynoResult<DynotestData> {
+        let legacy: LegacyDynotestData = postcard::from_bytes(bytes)?;
+        let config = legacy.config;
+        let mut buffer = DynotestData::new(DynoConfig {
+            diameter_roller_cm: config.diameter_roller_cm,
+            diameter_roller_beban_cm: config.diameter_roller_beban_cm,
+            diameter_gear_encoder_cm: config.diameter_gear_encoder_cm,
+            diameter_gear_beban_cm: config.diameter_gear_beban_cm,
+            berat_beban_kg: config.berat_beban_kg,
+            motor_info: config.motor_info,
+            max_encoder_pulse: config.max_encoder_pulse,
+            delta_ms: config.delta_ms,
+            // the only thermocouple of the old board is on the cylinder head
+            temp_channels: vec![DEFAULT_TEMP_CHANNELS[0].to_owned()],
+            ..Default::default()
+        });
+        buffer.start_time = legacy.start_time;
+        buffer.odo_km = legacy.odo_km;
+        buffer.data = legacy
+            .data
+            .into_iter()
+            .map(|data| {
+                let mut temps = Data::ZERO.temps;
+                temps[0] = data.temp;
+                Data {
+                    speed: data.speed,
+                    torque: data.torque,
+                    horsepower: data.horsepower,
+                    rpm_roda: data.rpm_roda,
+                    rpm_engine: data.rpm_engine,
+                    temps,
+                    temp_faults: 0,
+                    timestamp: data.timestamp,
+                }
+            })
+            .collect();
+        Ok(buffer)
+    }
+}
+
+const COLUMNS: usize = 6 + MAX_TEMP_CHANNELS;
+
+fn header(config_names: impl Fn(usize) -> String) -> [String; COLUMNS] {
+    core::array::from_fn(|idx| match idx {
+        0 => "SPEED (km/h)".to_owned(),
+        1 => "RPM Roda (rpm)".to_owned(),
+        2 => "RPM Engine (rpm)".to_owned(),
+        3 => "TORQUE (Nm)".to_owned(),
+        4 => "HORSEPOWER (HP)".to_owned(),
+        x if x == COLUMNS - 1 => "TIMESTAMP (ms)".to_owned(),
+        x => format!("{} (C)", config_names(x - 5)),
+    })
+}
+
+fn data_to_row(data: &Data) -> [f64; COLUMNS] {
+    core::array::from_fn(|idx| match idx {
+        0 => data.speed.get::<kilometer_per_hour>(),
+        1 => data.rpm_roda.get::<revolution_per_minute>(),
+        2 => data.rpm_engine.get::<revolution_per_minute>(),
+        3 => data.torque.get::<newton_meter>(),
+        4 => data.horsepower.get::<horsepower_metric>(),
+        x if x == COLUMNS - 1 => data.timestamp as f64,
+        x => data.temps[x - 5].get::<degree_celsius>(),
+    })
+}
+
+fn data_from_row(row: &[f64]) -> DynoResult<Data> {
+    if row.len() != COLUMNS {
+        return Err(DynoErr::encoding_error(format!(
+            "expected {COLUMNS} columns, found {} columns",
+            row.len()
+        )));
+    }
+    let temps: [TemperatureInterval; MAX_TEMP_CHANNELS] =
+        core::array::from_fn(|idx| TemperatureInterval::new::<degree_celsius>(row[5 + idx]));
+    Ok(Data {
+        speed: Velocity::new::<kilometer_per_hour>(row[0]),
+        rpm_roda: AngularVelocity::new::<revolution_per_minute>(row[1]),
+        rpm_engine: AngularVelocity::new::<revolution_per_minute>(row[2]),
+        torque: Torque::new::<newton_meter>(row[3]),
+        horsepower: Power::new::<horsepower_metric>(row[4]),
+        temps,
+        temp_faults: 0,
+        timestamp: row[COLUMNS - 1] as i64,
+    })
+}
+
+pub trait CsvSaver: Sized {
+    fn save_csv_from_path(&self, path: impl AsRef<Path>) -> DynoResult<()>;
+    fn open_csv_from_path(path: impl AsRef<Path>) -> DynoResult<Self>;
+}
+
+impl CsvSaver for DynotestData {
+    fn save_csv_from_path(&self, path: impl AsRef<Path>) -> DynoResult<()> {
+        let file = File::create(path).map_err(DynoErr::filesystem_error)?;
+        let mut writer = BufWriter::new(file);
+        let header = header(|idx| self.config.temp_channel_name(idx).into_owned());
+        writeln!(writer, "{}", header.join(","))?;
+        for data in self.iter() {
+            let row = data_to_row(data).map(|x| x.to_string());
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        writer.flush().map_err(From::from)
+    }
+
+    fn open_csv_from_path(path: impl AsRef<Path>) -> DynoResult<Self> {
+        let file = File::open(path).map_err(DynoErr::filesystem_error)?;
+        BufReader::new(file)
+            .lines()
+            .skip(1)
+            .enumerate()
+            .filter(|(_, line)| line.as_ref().map_or(true, |l| !l.trim().is_empty()))
+            .map(|(idx, line)| {
+                let row = line?
+                    .split(',')
+                    .map(|cell| cell.trim().parse::<f64>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| DynoErr::encoding_error(format!("line {}: {err}", idx + 2)))?;
+                data_from_row(&row)
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "use_excel")]
+pub trait ExcelSaver: Sized {
+    fn save_excel_from_path(&self, path: impl AsRef<Path>) -> DynoResult<()>;
+    fn open_excel_from_path(path: impl AsRef<Path>) -> DynoResult<Self>;
+}
+
+#[cfg(feature = "use_excel")]
+impl ExcelSaver for DynotestData {
+    fn save_excel_from_path(&self, path: impl AsRef<Path>) -> DynoResult<()> {
+        let excel_err = |err: rust_xlsxwriter::XlsxError| DynoErr::encoding_error(err);
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet();
+        let header = header(|idx| self.config.temp_channel_name(idx).into_owned());
+        for (col, name) in header.iter().enumerate() {
+            sheet.write_string(0, col as _, name).map_err(excel_err)?;
+        }
+        for (row, data) in self.iter().enumerate() {
+            for (col, value) in data_to_row(data).into_iter().enumerate() {
+                sheet
+                    .write_number(row as u32 + 1, col as _, value)
+                    .map_err(excel_err)?;
+            }
+        }
+        workbook.save(path.as_ref()).map_err(excel_err)
+    }
+
+    fn open_excel_from_path(path: impl AsRef<Path>) -> DynoResult<Self> {
+        use calamine::{DataType, Reader};
+
+        let mut workbook = calamine::open_workbook_auto(path).map_err(DynoErr::filesystem_error)?;
+        let range = workbook
+            .worksheet_range_at(0)
+            .ok_or_else(|| DynoErr::encoding_error("excel file has no worksheet"))?
+            .map_err(DynoErr::encoding_error)?;
+        range
+            .rows()
+            .skip(1)
+            .filter(|row| !row.iter().all(|cell| cell.is_empty()))
+            .enumerate()
+            .map(|(idx, row)| {
+                let row = row
+                    .iter()
+                    .map(|cell| cell.as_f64())
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| {
+                        DynoErr::encoding_error(format!("row {}: cell is not a number", idx + 2))
+                    })?;
+                data_from_row(&row)
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        types::{RawSerialData, RawTemp, RawTemps},
+        DynoConfig,
+    };
+
+    fn buffer() -> DynotestData {
+        let mut buffer = DynotestData::default();
+        buffer.config.temp_channels = vec!["Head".to_owned()];
+        buffer.start();
+        for pulse in 1..10 {
+            buffer.push_from_raw_serial_data(RawSerialData {
+                pulse_enc: pulse,
+                pulse_rpm: pulse * 4,
+                raw_temps: RawTemps::from_array([RawTemp::max6675((300 + pulse) << 3)]),
+            });
+        }
+        buffer
+    }
+
+    fn tmp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("dyno_core_saver_{}_{name}", std::process::id()))
+    }
+
+    #[test]
+    fn compress_roundtrip() {
+        let mut buffer = buffer();
+        buffer.annotate(buffer.last_timestamp(), "changed jet");
+        let compressed = buffer.compress().unwrap();
+        assert_eq!(DynotestData::decompress(&compressed).unwrap(), buffer);
+
+        let path = tmp_path("data.dyno");
+        buffer.compress_to_path(&path).unwrap();
+        let opened = DynotestData::decompress_from_path(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(opened, buffer);
+    }
+
+    #[test]
+    fn dyno_roundtrip() {
+        let buffer = buffer();
+        let path = tmp_path("run.dyno");
+        buffer.save_dyno_from_path(&path).unwrap();
+        let opened = DynotestData::open_dyno_from_path(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(opened, buffer);
+
+        // made by a newer app
+        let mut bytes = DYNO_MAGIC.to_vec();
+        bytes.push(DYNO_FORMAT_VERSION + 1);
+        let bytes = bytes.compress().unwrap();
+        let err = DynotestData::open_dyno_from_bytes(bytes).unwrap_err();
+        assert_eq!(err.kind, crate::ErrKind::Encoding);
+    }
+
+    #[test]
+    fn dyno_legacy() {
+        use legacy::{LegacyConfig, LegacyData, LegacyDynotestData};
+
+        let config = DynoConfig::default();
+        let legacy = LegacyDynotestData {
+            config: LegacyConfig {
+                diameter_roller_cm: 15.,
+                diameter_roller_beban_cm: config.diameter_roller_beban_cm,
+                diameter_gear_encoder_cm: config.diameter_gear_encoder_cm,
+                diameter_gear_beban_cm: config.diameter_gear_beban_cm,
+                berat_beban_kg: config.berat_beban_kg,
+                motor_info: config.motor_info,
+                max_encoder_pulse: config.max_encoder_pulse,
+                delta_ms: config.delta_ms,
+            },
+            data: vec![LegacyData {
+                speed: Velocity::new::<kilometer_per_hour>(42.),
+                torque: Torque::new::<newton_meter>(12.),
+                horsepower: Power::new::<horsepower_metric>(8.),
+                rpm_roda: AngularVelocity::new::<revolution_per_minute>(600.),
+                rpm_engine: AngularVelocity::new::<revolution_per_minute>(7000.),
+                temp: TemperatureInterval::new::<degree_celsius>(95.),
+                timestamp: 1_700_000_000_000,
+            }],
+            start_time: chrono::DateTime::from_timestamp_millis(1_700_000_000_000).unwrap(),
+            odo_km: 1.5,
+        };
+        // the old app save the struct as is, without the version
+        let bytes = postcard::to_stdvec(&legacy).unwrap();
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bytes).unwrap();
+        let opened = DynotestData::open_dyno_from_bytes(encoder.finish().unwrap()).unwrap();
+
+        assert_eq!(opened.config.diameter_roller_cm, 15.);
+        assert_eq!(opened.config.temp_channels.len(), 1);
+        assert_eq!(opened.start_time, legacy.start_time);
+        assert_eq!(opened.odo_km(), 1.5);
+        assert_eq!(opened.len(), 1);
+        let data = opened.last();
+        assert_eq!(data.speed, legacy.data[0].speed);
+        assert_eq!(data.rpm_engine, legacy.data[0].rpm_engine);
+        assert_eq!(data.temps[0].get::<degree_celsius>(), 95.);
+        assert_eq!(data.timestamp, 1_700_000_000_000);
+    }
+
+    #[test]
+    fn csv_roundtrip() {
+        let buffer = buffer();
+        let path = tmp_path("data.csv");
+        buffer.save_csv_from_path(&path).unwrap();
+        let header = std::fs::read_to_string(&path).unwrap();
+        assert!(header.starts_with("SPEED (km/h),"));
+        assert!(header.contains(",Head (C),"));
+
+        let opened = DynotestData::open_csv_from_path(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(opened.len(), buffer.len());
+        for (opened, data) in opened.iter().zip(buffer.iter()) {
+            assert_eq!(data_to_row(opened), data_to_row(data));
+        }
+    }
+
+    #[test]
+    fn csv_wrong_columns() {
+        let path = tmp_path("wrong.csv");
+        std::fs::write(&path, "SPEED\n1,2,3\n").unwrap();
+        let err = DynotestData::open_csv_from_path(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind, crate::ErrKind::Encoding);
+    }
+}
@@ -1,10 +1,11 @@
-use super::{DynotestData, RawSerialData};
+use super::{DynotestData, RawSerialData, RawTemp, MAX_TEMP_CHANNELS};
+use crate::config::DynoConfig;
 
 use derive_more::Display;
 #[cfg(feature = "derive_serde")]
 use serde::{Deserialize, Serialize};
 
-use chrono::{DateTime, TimeDelta, Utc};
+use chrono::{DateTime, Utc};
 use si_type::*;
 use uom::ConstZero;
 
@@ -26,8 +27,11 @@ pub struct Data {
     pub rpm_roda: AngularVelocity,
     /// unit [REVpM]
     pub rpm_engine: AngularVelocity,
-    /// unit [Celcius]
-    pub temp: TemperatureInterval,
+    /// unit [Celcius], every thermocouple channel, named by `DynoConfig::temp_channels`
+    pub temps: [TemperatureInterval; MAX_TEMP_CHANNELS],
+    /// bit `1 << idx` is set when the thermocouple channel `idx` report a fault (e.g. unplugged),
+    /// the value in `temps` is the last good reading of the channel
+    pub temp_faults: u8,
 
     pub timestamp: i64,
 }
@@ -38,7 +42,8 @@ impl Data {
         horsepower: ConstZero::ZERO,
         rpm_roda: ConstZero::ZERO,
         rpm_engine: ConstZero::ZERO,
-        temp: ConstZero::ZERO,
+        temps: [ConstZero::ZERO; MAX_TEMP_CHANNELS],
+        temp_faults: 0,
         timestamp: DateTime::UNIX_EPOCH.timestamp_millis(),
     };
 
@@ -66,6 +71,20 @@ impl Data {
     pub fn timestamp_date_time(&self) -> DateTime<Utc> {
         DateTime::from_timestamp_millis(self.timestamp).unwrap_or_default()
     }
+
+    /// temperature of the channel named `name` in `config`
+    #[inline]
+    pub fn temp_named(&self, config: &DynoConfig, name: &str) -> Option<TemperatureInterval> {
+        config
+            .temp_channel_index(name)
+            .and_then(|idx| self.temps.get(idx).copied())
+    }
+
+    /// the thermocouple channel `idx` report a fault in this data
+    #[inline]
+    pub const fn is_temp_fault(&self, idx: usize) -> bool {
+        idx < MAX_TEMP_CHANNELS && self.temp_faults & (1 << idx) != 0
+    }
 }
 
 pub(crate) fn calculate_odo_from_data(last_time: &DateTime<Utc>, data: &Data) -> Length {
@@ -91,17 +110,11 @@ impl DynotestData {
             ..
         }: RawSerialData,
     ) {
-        let last_data = self.last();
+        let last_data = *self.last();
 
         let delta_time = self.config.delta_time();
 
-        let timestamp = {
-            let delta_ms = TimeDelta::milliseconds(self.config.delta_ms as _);
-            self.start_time
-                .checked_add_signed(delta_ms)
-                .map(|x| x.timestamp_millis())
-                .unwrap_or(last_data.timestamp)
-        };
+        let timestamp = self.last_timestamp() + i64::from(self.config.delta_ms);
 
         // change from phase A to phase Z (pulse per revolution)
         // let roller_revolution = pulse_enc / f64::from(self.config.max_encoder_pulse);
@@ -130,9 +143,16 @@ impl DynotestData {
 
         let odo = self.config.circumference_roller::<kilometer>() * roller_revolution;
         let speed = Velocity::new::<kilometer_per_hour>(odo / delta_time.get::<hour>());
-        let temp = TemperatureInterval::new::<degree_celsius>(
-            raw.temp_celcius().unwrap_or_else(|| self.last().temp.value),
-        );
+        let mut temps = last_data.temps;
+        let mut temp_faults = 0;
+        for (idx, temp) in temps.iter_mut().enumerate() {
+            if raw.raw_temps.get(idx).is_some_and(RawTemp::is_fault) {
+                temp_faults |= 1 << idx;
+            }
+            if let Some(celcius) = raw.temp_celcius(idx) {
+                *temp = TemperatureInterval::new::<degree_celsius>(celcius);
+            }
+        }
 
         self.odo_km += odo;
         self.data.push(Data {
@@ -142,7 +162,8 @@ impl DynotestData {
             rpm_engine,
             timestamp,
             speed,
-            temp,
+            temps,
+            temp_faults,
         });
     }
 }
@@ -28,7 +28,37 @@ pub struct DynotestData {
 
     pub(crate) start_time: DateTime<Utc>,
     pub(crate) odo_km: f64,
+    pub(crate) gaps: Vec<DataGap>,
+    /// notes placed on the run, sorted by the timestamp
+    pub(crate) annotations: Vec<Annotation>,
 }
+
+/// interval in the run where there is no data from the device (e.g. cable unplugged),
+/// in timestamp millis of the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[display("{start}..{end}")]
+pub struct DataGap {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl DataGap {
+    #[inline(always)]
+    pub const fn duration_ms(&self) -> i64 {
+        self.end - self.start
+    }
+}
+
+/// note placed by the operator on a run (e.g. "changed jet here"), at timestamp millis of the data
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Display)]
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[display("{timestamp}: {text}")]
+pub struct Annotation {
+    pub timestamp: i64,
+    pub text: String,
+}
+
 const BUFFER_DATA_INIT_CAP: usize = 1800;
 
 impl DynotestData {
@@ -38,6 +68,8 @@ impl DynotestData {
             config,
             data: Vec::with_capacity(BUFFER_DATA_INIT_CAP),
             odo_km: 0.,
+            gaps: Vec::new(),
+            annotations: Vec::new(),
         }
     }
 
@@ -51,6 +83,8 @@ impl DynotestData {
         self.data.clear();
         self.data.push(Data::default());
         self.odo_km = 0.;
+        self.gaps.clear();
+        self.annotations.clear();
     }
 
     #[inline(always)]
@@ -66,6 +100,78 @@ impl DynotestData {
         self.data.push(data);
     }
 
+    /// distance traveled of the roller in km
+    #[inline(always)]
+    pub const fn odo_km(&self) -> f64 {
+        self.odo_km
+    }
+
+    #[inline(always)]
+    pub const fn start_time(&self) -> DateTime<Utc> {
+        self.start_time
+    }
+
+    /// gaps recorded in the run, ordered by time
+    #[inline(always)]
+    pub fn gaps(&self) -> &[DataGap] {
+        &self.gaps
+    }
+
+    /// notes placed on the run, sorted by the timestamp
+    #[inline(always)]
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// the caller must keep the notes sorted by the timestamp, see [`Self::annotate`]
+    #[inline(always)]
+    pub fn annotations_mut(&mut self) -> &mut Vec<Annotation> {
+        &mut self.annotations
+    }
+
+    /// place the note after the other notes at the same timestamp
+    pub fn annotate(&mut self, timestamp: i64, text: impl Into<String>) {
+        let idx = self
+            .annotations
+            .partition_point(|note| note.timestamp <= timestamp);
+        self.annotations.insert(
+            idx,
+            Annotation {
+                timestamp,
+                text: text.into(),
+            },
+        );
+    }
+
+    /// record `duration` without data after the last data, the next data pushed from the
+    /// serial continue after the gap
+    pub fn record_gap(&mut self, duration: Duration) {
+        let start = self.last_timestamp();
+        self.gaps.push(DataGap {
+            start,
+            end: start + duration.num_milliseconds().max(0),
+        });
+    }
+
+    /// index of the data nearest to `timestamp`, `None` if there is no data
+    pub fn nearest_index(&self, timestamp: i64) -> Option<usize> {
+        let after = self.data.partition_point(|d| d.timestamp < timestamp);
+        [after.checked_sub(1), Some(after)]
+            .into_iter()
+            .flatten()
+            .filter(|&idx| idx < self.data.len())
+            .min_by_key(|&idx| (self.data[idx].timestamp - timestamp).abs())
+    }
+
+    /// timestamp of the last data or the end of the last gap, start time if there is no data
+    pub(crate) fn last_timestamp(&self) -> i64 {
+        let last = match self.last().timestamp {
+            0 => self.start_time.timestamp_millis(),
+            timestamp => timestamp,
+        };
+        self.gaps.last().map_or(last, |gap| gap.end.max(last))
+    }
+
     #[inline]
     pub fn add_odo_km(&mut self, odo: f64) {
         self.odo_km += odo;
@@ -84,8 +190,8 @@ impl DynotestData {
     }
     #[inline]
     pub fn total_time(&self) -> Duration {
-        let first = self.last().timestamp_date_time();
-        let last = self.first().timestamp_date_time();
+        let first = self.first().timestamp_date_time();
+        let last = self.last().timestamp_date_time();
         last - first
     }
 
@@ -163,6 +269,8 @@ impl FromIterator<Data> for DynotestData {
             odo_km,
             start_time,
             config: Default::default(),
+            gaps: Vec::new(),
+            annotations: Vec::new(),
         }
     }
 }
@@ -189,14 +297,14 @@ mod tests {
         temperature_interval::degree_celsius, torque::newton_meter, velocity::kilometer_per_hour,
     };
 
-    use crate::types::{Data, DynotestData, RawSerialData};
+    use crate::types::{Data, DynotestData, RawSerialData, RawTemp, RawTemps, MAX_TEMP_CHANNELS};
 
     const SIZE_TESTED: usize = 5;
 
     const SER_DATA: RawSerialData = RawSerialData {
         pulse_enc: 10,
         pulse_rpm: 20,
-        raw_temp: 420,
+        raw_temps: RawTemps::from_array([RawTemp::max6675(420)]),
     };
 
     fn data_buffer() -> &'static DynotestData {
@@ -253,11 +361,74 @@ mod tests {
             horsepower: Power::new::<horsepower_metric>(horsepower),
             rpm_roda: AngularVelocity::new::<revolution_per_minute>(rpm_roda),
             rpm_engine: AngularVelocity::new::<revolution_per_minute>(rpm_engine),
-            temp: TemperatureInterval::new::<degree_celsius>(420.),
+            temps: [TemperatureInterval::new::<degree_celsius>(420.); MAX_TEMP_CHANNELS],
+            temp_faults: 0,
             timestamp: Default::default(),
         }
     }
 
+    #[test]
+    fn test_data_named_temps() {
+        let mut buffer = DynotestData::default();
+        buffer.config.temp_channels = vec!["Head".to_owned(), "EGT".to_owned()];
+        buffer.push_from_raw_serial_data(RawSerialData {
+            raw_temps: RawTemps::from_array([
+                RawTemp::max6675(360 << 3),
+                RawTemp::max31855(2600 << 18),
+            ]),
+            ..SER_DATA
+        });
+        // faulted channel keep the last readed value
+        buffer.push_from_raw_serial_data(RawSerialData {
+            raw_temps: RawTemps::from_array([RawTemp::max6675(0x4), RawTemp::max31855(2800 << 18)]),
+            ..SER_DATA
+        });
+        let last = buffer.last();
+        let celcius = |name| {
+            last.temp_named(&buffer.config, name)
+                .map(|t| t.get::<degree_celsius>())
+        };
+        assert_eq!(celcius("head"), Some(90.));
+        assert_eq!(celcius("EGT"), Some(700.));
+        assert_eq!(celcius("Oil"), None);
+    }
+
+    #[test]
+    fn test_data_gap() {
+        let mut buffer = DynotestData::default();
+        buffer.start();
+        let start = buffer.start_time().timestamp_millis();
+        let delta = buffer.config.delta_ms as i64;
+        buffer.push_from_raw_serial_data(SER_DATA);
+        buffer.push_from_raw_serial_data(SER_DATA);
+        assert_eq!(buffer.last().timestamp, start + 2 * delta);
+
+        buffer.record_gap(chrono::Duration::seconds(3));
+        buffer.push_from_raw_serial_data(SER_DATA);
+        assert_eq!(buffer.gaps().len(), 1);
+        assert_eq!(buffer.gaps()[0].duration_ms(), 3000);
+        assert_eq!(buffer.last().timestamp, start + 3 * delta + 3000);
+
+        buffer.clear();
+        assert!(buffer.gaps().is_empty());
+    }
+
+    #[test]
+    fn test_data_nearest_index() {
+        let buffer = [100, 350, 600]
+            .into_iter()
+            .map(|timestamp| Data {
+                timestamp,
+                ..Default::default()
+            })
+            .collect::<DynotestData>();
+        assert_eq!(buffer.nearest_index(0), Some(0));
+        assert_eq!(buffer.nearest_index(400), Some(1));
+        assert_eq!(buffer.nearest_index(500), Some(2));
+        assert_eq!(buffer.nearest_index(10_000), Some(2));
+        assert_eq!(DynotestData::default().nearest_index(0), None);
+    }
+
     #[test]
     fn test_data_buffer() {
         let buffer = data_buffer();
@@ -0,0 +1,167 @@
+#[cfg(feature = "std")]
+use derive_more::Display;
+#[cfg(feature = "derive_serde")]
+use serde::{Deserialize, Serialize};
+
+/// maximum length of [`DeviceConfig::name`] in bytes
+pub const DEVICE_NAME_LEN: usize = 32;
+
+/// roller and load calibration constants, same meaning as the fields in `DynoConfig`
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display), display("{self:#?}"))]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct DeviceCalibration {
+    pub diameter_roller_cm: f64,
+    pub diameter_roller_beban_cm: f64,
+    pub diameter_gear_encoder_cm: f64,
+    pub diameter_gear_beban_cm: f64,
+    pub berat_beban_kg: f64,
+}
+
+impl DeviceCalibration {
+    pub const fn new() -> Self {
+        Self {
+            diameter_roller_cm: 14.22,
+            diameter_roller_beban_cm: 19.33,
+            diameter_gear_encoder_cm: 10.,
+            diameter_gear_beban_cm: 5.4,
+            berat_beban_kg: 18.5,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        [
+            self.diameter_roller_cm,
+            self.diameter_roller_beban_cm,
+            self.diameter_gear_encoder_cm,
+            self.diameter_gear_beban_cm,
+            self.berat_beban_kg,
+        ]
+        .iter()
+        .all(|x| x.is_finite() && *x > 0.)
+    }
+}
+
+impl Default for DeviceCalibration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// configuration that stored in the board flash, identify which physical dyno is connected
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display), display("{self:#?}"))]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct DeviceConfig {
+    pub id: u32,
+    pub name: heapless::String<DEVICE_NAME_LEN>,
+    /// default sample period, used when the host start without period
+    pub period_ms: u16,
+    /// pulses per revolution of the roller encoder
+    pub encoder_ppr: u16,
+    /// pulses per engine revolution of the RPM pickup
+    pub rpm_ppr: u16,
+    pub calibration: DeviceCalibration,
+}
+
+impl DeviceConfig {
+    /// maximum postcard encoded size
+    pub const BYTE_SIZE: usize = 5 + (1 + DEVICE_NAME_LEN) + 3 + 3 + 3 + 5 * 8;
+
+    pub const fn new() -> Self {
+        Self {
+            id: 0,
+            name: heapless::String::new(),
+            period_ms: 250,
+            encoder_ppr: 300,
+            rpm_ppr: 1,
+            calibration: DeviceCalibration::new(),
+        }
+    }
+
+    /// check the config before it written to the flash
+    pub fn is_valid(&self) -> bool {
+        (250..=2000).contains(&self.period_ms)
+            && self.encoder_ppr > 0
+            && self.rpm_ppr > 0
+            && self.calibration.is_valid()
+    }
+}
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl DeviceConfig {
+    /// create from desktop config, name longer than [`DEVICE_NAME_LEN`] is truncated
+    pub fn from_dyno_config(id: u32, name: &str, config: &crate::config::DynoConfig) -> Self {
+        let mut device_name = heapless::String::new();
+        for c in name.chars() {
+            if device_name.push(c).is_err() {
+                break;
+            }
+        }
+        Self {
+            id,
+            name: device_name,
+            period_ms: config.delta_ms,
+            encoder_ppr: config.max_encoder_pulse,
+            rpm_ppr: config.rpm_pickup_ppr,
+            calibration: DeviceCalibration {
+                diameter_roller_cm: config.diameter_roller_cm,
+                diameter_roller_beban_cm: config.diameter_roller_beban_cm,
+                diameter_gear_encoder_cm: config.diameter_gear_encoder_cm,
+                diameter_gear_beban_cm: config.diameter_gear_beban_cm,
+                berat_beban_kg: config.berat_beban_kg,
+            },
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::config::DynoConfig;
+
+    #[test]
+    fn test_device_config_roundtrip() {
+        let mut config = DynoConfig {
+            delta_ms: 500,
+            max_encoder_pulse: 360,
+            rpm_pickup_ppr: 2,
+            berat_beban_kg: 20.,
+            ..Default::default()
+        };
+        let device =
+            DeviceConfig::from_dyno_config(7, "Dyno Lab A with a very long name indeed", &config);
+        assert!(device.is_valid());
+        assert_eq!(device.name.len(), DEVICE_NAME_LEN);
+
+        let mut buffer = [0u8; DeviceConfig::BYTE_SIZE];
+        let encoded = postcard::to_slice(&device, &mut buffer).unwrap();
+        let decoded: DeviceConfig = postcard::from_bytes(encoded).unwrap();
+        assert_eq!(decoded, device);
+
+        config = DynoConfig::default();
+        config.apply_device_config(&decoded);
+        assert_eq!(config.delta_ms, 500);
+        assert_eq!(config.max_encoder_pulse, 360);
+        assert_eq!(config.rpm_pickup_ppr, 2);
+        assert_eq!(config.berat_beban_kg, 20.);
+        assert_eq!(config.device_id, Some(7));
+    }
+
+    #[test]
+    fn test_device_config_invalid() {
+        let mut device = DeviceConfig::new();
+        assert!(device.is_valid());
+        device.encoder_ppr = 0;
+        assert!(!device.is_valid());
+        device.encoder_ppr = 1;
+        device.calibration.berat_beban_kg = f64::NAN;
+        assert!(!device.is_valid());
+    }
+}
@@ -0,0 +1,218 @@
+#[cfg(feature = "std")]
+use derive_more::Display;
+#[cfg(feature = "derive_serde")]
+use serde::{Deserialize, Serialize};
+
+/// maximum bytes of firmware in one [`DfuCommand::Write`]
+pub const DFU_CHUNK_SIZE: usize = 128;
+
+/// CRC-32 (ISO-HDLC) of the firmware image, [`FirmwareHeader::crc32`]
+pub const FIRMWARE_CRC: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+
+/// context of the Ed25519ph signature of the firmware image
+pub const FIRMWARE_SIGN_CONTEXT: &[u8] = b"dynotests-firmware";
+
+/// semver of the firmware image
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display), display("{major}.{minor}.{patch}"))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FirmwareVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+impl FirmwareVersion {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// parse `major.minor.patch` in const context, e.g. `env!("CARGO_PKG_VERSION")`,
+    /// pre-release and build metadata is ignored
+    pub const fn parse(version: &str) -> Self {
+        let bytes = version.as_bytes();
+        let mut parts = [0u16; 3];
+        let mut part = 0;
+        let mut idx = 0;
+        while idx < bytes.len() && part < 3 {
+            match bytes[idx] {
+                b'0'..=b'9' => parts[part] = parts[part] * 10 + (bytes[idx] - b'0') as u16,
+                b'.' => part += 1,
+                _ => break,
+            }
+            idx += 1;
+        }
+        Self::new(parts[0], parts[1], parts[2])
+    }
+}
+
+/// ed25519 signature of the firmware image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FirmwareSignature(pub [u8; 64]);
+
+impl Default for FirmwareSignature {
+    fn default() -> Self {
+        Self([0; 64])
+    }
+}
+
+#[cfg(feature = "derive_serde")]
+impl Serialize for FirmwareSignature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeTuple;
+        let mut tuple = serializer.serialize_tuple(self.0.len())?;
+        for byte in &self.0 {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "derive_serde")]
+impl<'de> Deserialize<'de> for FirmwareSignature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SignatureVisitor;
+        impl<'de> serde::de::Visitor<'de> for SignatureVisitor {
+            type Value = FirmwareSignature;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("64 bytes ed25519 signature")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut signature = [0u8; 64];
+                for (idx, byte) in signature.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(idx, &self))?;
+                }
+                Ok(FirmwareSignature(signature))
+            }
+        }
+        deserializer.deserialize_tuple(64, SignatureVisitor)
+    }
+}
+
+/// header of signed firmware image, the signature is over the image bytes
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FirmwareHeader {
+    pub version: FirmwareVersion,
+    /// length of the image in bytes
+    pub size: u32,
+    /// CRC-32 (ISO-HDLC) of the image
+    pub crc32: u32,
+    pub signature: FirmwareSignature,
+}
+
+impl FirmwareHeader {
+    /// maximum postcard encoded size
+    pub const BYTE_SIZE: usize = 3 * 3 + 5 + 5 + 64;
+
+    /// bytes that signed before the image: version and size (little endian),
+    /// so the signature is not valid for other version
+    pub fn signed_prefix(&self) -> [u8; 10] {
+        let mut prefix = [0u8; 10];
+        prefix[0..2].copy_from_slice(&self.version.major.to_le_bytes());
+        prefix[2..4].copy_from_slice(&self.version.minor.to_le_bytes());
+        prefix[4..6].copy_from_slice(&self.version.patch.to_le_bytes());
+        prefix[6..10].copy_from_slice(&self.size.to_le_bytes());
+        prefix
+    }
+}
+
+/// state of the running firmware, reported by the bootloader
+#[repr(u8)]
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BootStatus {
+    /// the firmware is confirmed to work
+    #[default]
+    Confirmed,
+    /// new firmware on the first boot, confirmed on the first command from the host
+    Testing,
+    /// the new firmware never confirmed, the previous firmware is restored
+    RolledBack,
+}
+
+/// firmware update command, wrapped in `SerialCommand::Dfu`
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DfuCommand {
+    /// answered with [`DfuResponse::Version`]
+    GetVersion,
+    /// erase the update partition, answered with [`DfuResponse::Ready`]
+    Begin(FirmwareHeader),
+    /// write the chunk at `offset`, must be in order, answered with [`DfuResponse::Written`]
+    Write {
+        offset: u32,
+        data: heapless::Vec<u8, DFU_CHUNK_SIZE>,
+    },
+    /// check size and CRC, answered with [`DfuResponse::Verified`] then the device is reset
+    /// into the bootloader to verify the signature and swap the image
+    Finish,
+    Abort,
+}
+
+impl DfuCommand {
+    /// maximum postcard encoded size
+    pub const BYTE_SIZE: usize = 1 + if FirmwareHeader::BYTE_SIZE > 5 + 2 + DFU_CHUNK_SIZE {
+        FirmwareHeader::BYTE_SIZE
+    } else {
+        5 + 2 + DFU_CHUNK_SIZE
+    };
+}
+
+/// error of firmware update on the device side
+#[repr(u8)]
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DfuError {
+    /// the image is bigger than the update partition
+    TooLarge,
+    /// [`DfuCommand::Write`] or [`DfuCommand::Finish`] without [`DfuCommand::Begin`]
+    NotStarted,
+    /// chunk offset is not the next expected offset
+    UnexpectedOffset,
+    /// size or CRC of written image is not match with the header
+    Corrupted,
+    Flash,
+}
+
+/// firmware update response, wrapped in `SerialResponse::Dfu`
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfuResponse {
+    Version {
+        version: FirmwareVersion,
+        status: BootStatus,
+    },
+    Ready,
+    /// chunk is written, `next` is the next expected offset
+    Written {
+        next: u32,
+    },
+    Verified,
+    Aborted,
+    Error(DfuError),
+}
+
+impl DfuResponse {
+    /// maximum postcard encoded size
+    pub const BYTE_SIZE: usize = 1 + 3 * 3 + 1;
+}
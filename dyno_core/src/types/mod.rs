@@ -2,6 +2,8 @@
 mod data;
 #[cfg(feature = "std")]
 mod data_buffer;
+mod device;
+mod dfu;
 #[cfg(feature = "std")]
 mod infomotor;
 mod serial;
@@ -17,6 +19,8 @@ pub const GRAVITY_SPEED: Float = 9.806_65;
 pub use data::*;
 #[cfg(feature = "std")]
 pub use data_buffer::*;
+pub use device::*;
+pub use dfu::*;
 #[cfg(feature = "std")]
 pub use infomotor::*;
 pub use serial::*;
@@ -3,51 +3,373 @@ use derive_more::Display;
 #[cfg(feature = "derive_serde")]
 use serde::{Deserialize, Serialize};
 
+use super::{DeviceConfig, DfuCommand, DfuResponse};
+
+/// maximum number of thermocouple channels carried in one [`RawSerialData`] frame
+pub const MAX_TEMP_CHANNELS: usize = 4;
+
+/// USB vendor id of the board with native USB CDC-ACM (`usb` feature in `dyno_stm32`)
+pub const USB_VID: u16 = 0x1209;
+/// USB product id of the board with native USB CDC-ACM (`usb` feature in `dyno_stm32`)
+pub const USB_PID: u16 = 0xD770;
+/// USB manufacturer string descriptor of the board
+pub const USB_MANUFACTURER: &str = "Dynotests Polije";
+/// USB product string descriptor of the board, used by the desktop to find the dyno ports
+pub const USB_PRODUCT: &str = "Dynotests Dynamometer";
+
+/// VID/PID of the old USB-serial bridge connected to USART1
+pub const BRIDGE_VID: u16 = 3220;
+pub const BRIDGE_PID: u16 = 1406;
+
 #[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
-#[cfg_attr(
-    feature = "std",
-    derive(Debug, Clone, Copy, PartialEq, PartialOrd, Display)
-)]
+#[cfg_attr(feature = "std", derive(Display))]
 #[cfg_attr(feature = "std", display("{self:?}"))]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct SerialDataInit {
     period_ms: u16,
 }
 
+/// thermocouple converter chip that produced a [`RawTemp`] word
+#[repr(u8)]
+#[cfg_attr(feature = "std", derive(Display))]
 #[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
-#[cfg_attr(
-    feature = "std",
-    derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Display)
-)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ThermocoupleKind {
+    /// type-K, 0 °C to 1024 °C, 16 bit word
+    #[default]
+    Max6675,
+    /// type-K, -270 °C to 1800 °C (higher range for EGT), 32 bit word
+    Max31855,
+}
+
+/// raw word readed from one thermocouple chip
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display))]
+#[cfg_attr(feature = "std", display("{self:?}"))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawTemp {
+    pub kind: ThermocoupleKind,
+    pub raw: u32,
+}
+
+impl RawTemp {
+    /// maximum postcard encoded size, 1 byte kind + 5 bytes varint u32
+    pub const BYTE_SIZE: usize = 6;
+
+    const MAX6675_OPEN: u32 = 0x4;
+    const MAX31855_FAULT: u32 = 0x1_0000;
+
+    #[inline]
+    pub const fn max6675(raw: u16) -> Self {
+        Self {
+            kind: ThermocoupleKind::Max6675,
+            raw: raw as u32,
+        }
+    }
+
+    #[inline]
+    pub const fn max31855(raw: u32) -> Self {
+        Self {
+            kind: ThermocoupleKind::Max31855,
+            raw,
+        }
+    }
+
+    /// word with the fault bit set, used until the chip is readed for the first time
+    #[inline]
+    pub const fn fault(kind: ThermocoupleKind) -> Self {
+        match kind {
+            ThermocoupleKind::Max6675 => Self::max6675(Self::MAX6675_OPEN as u16),
+            ThermocoupleKind::Max31855 => Self::max31855(Self::MAX31855_FAULT),
+        }
+    }
+
+    #[inline]
+    pub const fn is_fault(&self) -> bool {
+        match self.kind {
+            ThermocoupleKind::Max6675 => (self.raw & Self::MAX6675_OPEN) != 0,
+            ThermocoupleKind::Max31855 => (self.raw & Self::MAX31855_FAULT) != 0,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn celcius(&self) -> Option<f64> {
+        if self.is_fault() {
+            return None;
+        }
+        match self.kind {
+            // D14..D3 is 12 bit temperature with 0.25 °C resolution
+            ThermocoupleKind::Max6675 => Some((((self.raw >> 3) & 0x0FFF) as f64) * 0.25),
+            // D31..D18 is 14 bit signed temperature with 0.25 °C resolution
+            ThermocoupleKind::Max31855 => Some((((self.raw as i32) >> 18) as f64) * 0.25),
+        }
+    }
+}
+
+/// fixed capacity list of [`RawTemp`], serialized as a sequence of only the used channels
+#[cfg_attr(feature = "std", derive(Display))]
 #[cfg_attr(feature = "std", display("{self:?}"))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawTemps {
+    len: u8,
+    temps: [RawTemp; MAX_TEMP_CHANNELS],
+}
+
+impl RawTemps {
+    /// maximum postcard encoded size, 1 byte length + every channel
+    pub const BYTE_SIZE: usize = 1 + MAX_TEMP_CHANNELS * RawTemp::BYTE_SIZE;
+
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            temps: [RawTemp::fault(ThermocoupleKind::Max6675); MAX_TEMP_CHANNELS],
+        }
+    }
+
+    /// create from array, channels above [`MAX_TEMP_CHANNELS`] is ignored
+    pub const fn from_array<const N: usize>(array: [RawTemp; N]) -> Self {
+        let mut this = Self::new();
+        while (this.len as usize) < N && (this.len as usize) < MAX_TEMP_CHANNELS {
+            this.temps[this.len as usize] = array[this.len as usize];
+            this.len += 1;
+        }
+        this
+    }
+
+    /// push new channel, return the temp back if the list is full
+    #[inline]
+    pub fn push(&mut self, temp: RawTemp) -> Result<(), RawTemp> {
+        if self.is_full() {
+            return Err(temp);
+        }
+        self.temps[self.len as usize] = temp;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// replace channel `idx`, ignored if the channel is not exists
+    #[inline]
+    pub fn set(&mut self, idx: usize, temp: RawTemp) {
+        if let Some(t) = self.as_mut_slice().get_mut(idx) {
+            *t = temp;
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&RawTemp> {
+        self.as_slice().get(idx)
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len as usize >= MAX_TEMP_CHANNELS
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[RawTemp] {
+        &self.temps[..self.len as usize]
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [RawTemp] {
+        &mut self.temps[..self.len as usize]
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, RawTemp> {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a RawTemps {
+    type Item = &'a RawTemp;
+    type IntoIter = core::slice::Iter<'a, RawTemp>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "derive_serde")]
+impl Serialize for RawTemps {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for temp in self {
+            seq.serialize_element(temp)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "derive_serde")]
+impl<'de> Deserialize<'de> for RawTemps {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct RawTempsVisitor;
+        impl<'de> serde::de::Visitor<'de> for RawTempsVisitor {
+            type Value = RawTemps;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(
+                    f,
+                    "a sequence of at most {MAX_TEMP_CHANNELS} raw temperatures"
+                )
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut temps = RawTemps::new();
+                while let Some(temp) = seq.next_element::<RawTemp>()? {
+                    if temps.push(temp).is_err() {
+                        return Err(serde::de::Error::invalid_length(
+                            MAX_TEMP_CHANNELS + 1,
+                            &self,
+                        ));
+                    }
+                }
+                Ok(temps)
+            }
+        }
+        deserializer.deserialize_seq(RawTempsVisitor)
+    }
+}
+
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display))]
+#[cfg_attr(feature = "std", display("{self:?}"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RawSerialData {
     pub pulse_rpm: u16,
     pub pulse_enc: u16,
-    pub raw_temp: u16,
+    pub raw_temps: RawTemps,
 }
 impl RawSerialData {
-    pub const BYTE_SIZE: usize = 8;
+    /// maximum postcard encoded size (without COBS framing)
+    pub const BYTE_SIZE: usize = 3 + 3 + RawTemps::BYTE_SIZE;
     pub const fn new() -> Self {
         Self {
             pulse_rpm: 0,
             pulse_enc: 0,
-            raw_temp: 0,
+            raw_temps: RawTemps::new(),
         }
     }
 
+    /// temperature of channel `idx` in celcius, `None` if the channel is not exists or faulted
     #[cfg(feature = "std")]
-    pub fn temp_celcius(&self) -> Option<f64> {
-        if (self.raw_temp & 0x4) != 0 {
-            return None;
-        }
-        Some(((self.raw_temp >> 4) as f64) * 0.25_f64)
+    pub fn temp_celcius(&self, idx: usize) -> Option<f64> {
+        self.raw_temps.get(idx).and_then(RawTemp::celcius)
     }
 }
 
 impl SerialDataInit {
+    /// start with the default period stored in the device [`DeviceConfig`]
+    pub const DEVICE_DEFAULT: Self = Self { period_ms: 0 };
+
+    #[inline]
+    pub const fn new(period_ms: u16) -> Self {
+        Self { period_ms }
+    }
+
     #[inline]
     pub fn period_ms(self) -> u16 {
         core::cmp::Ord::clamp(self.period_ms, 250, 2000)
     }
+
+    /// same as [`Self::period_ms`], but use `default` for [`Self::DEVICE_DEFAULT`]
+    #[inline]
+    pub fn period_ms_or(self, default: u16) -> u16 {
+        match self.period_ms {
+            0 => Self::new(default).period_ms(),
+            _ => self.period_ms(),
+        }
+    }
+}
+
+/// command from the desktop app to the device
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display), display("{self:?}"))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerialCommand {
+    /// start streaming [`SerialResponse::Data`]
+    Start(SerialDataInit),
+    Stop,
+    /// request the stored config, answered with [`SerialResponse::Config`]
+    GetConfig,
+    /// store new config in the device flash, answered with [`SerialResponse::ConfigSaved`]
+    SetConfig(DeviceConfig),
+    /// firmware update, answered with [`SerialResponse::Dfu`]
+    #[cfg_attr(feature = "std", display("{_0:?}"))]
+    Dfu(DfuCommand),
+}
+
+impl SerialCommand {
+    /// maximum postcard encoded size (without COBS framing)
+    pub const BYTE_SIZE: usize = 1 + max(DeviceConfig::BYTE_SIZE, DfuCommand::BYTE_SIZE);
+}
+
+/// error that reported by the device
+#[repr(u8)]
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DeviceError {
+    /// the config is rejected by [`DeviceConfig::is_valid`]
+    InvalidConfig,
+    /// failed erasing or writing the flash
+    Flash,
+}
+
+/// response from the device to the desktop app
+#[cfg_attr(feature = "derive_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "std", derive(Display), display("{self:?}"))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerialResponse {
+    Data(RawSerialData),
+    Config(DeviceConfig),
+    ConfigSaved,
+    Error(DeviceError),
+    #[cfg_attr(feature = "std", display("{_0:?}"))]
+    Dfu(DfuResponse),
+}
+
+impl SerialResponse {
+    /// maximum postcard encoded size (without COBS framing)
+    pub const BYTE_SIZE: usize = 1 + max(
+        max(RawSerialData::BYTE_SIZE, DeviceConfig::BYTE_SIZE),
+        DfuResponse::BYTE_SIZE,
+    );
+}
+
+/// maximum size of one COBS frame in either direction, including the `0x00` delimiter
+pub const MAX_FRAME_SIZE: usize = {
+    let size = max(SerialCommand::BYTE_SIZE, SerialResponse::BYTE_SIZE);
+    size + size / 254 + 2
+};
+
+const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
 }
 
 impl core::default::Default for RawSerialData {
@@ -60,3 +382,88 @@ impl core::default::Default for SerialDataInit {
         Self { period_ms: 250 }
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::types::DFU_CHUNK_SIZE;
+
+    #[test]
+    fn test_raw_temp_decode() {
+        // 100.25 °C => 401 << 3
+        assert_eq!(RawTemp::max6675(401 << 3).celcius(), Some(100.25));
+        assert_eq!(RawTemp::max6675(0x4).celcius(), None);
+        // 850 °C => 3400 << 18
+        assert_eq!(RawTemp::max31855(3400 << 18).celcius(), Some(850.));
+        // -10 °C => -40 << 18
+        assert_eq!(
+            RawTemp::max31855(((-40i32) << 18) as u32).celcius(),
+            Some(-10.)
+        );
+        assert!(RawTemp::fault(ThermocoupleKind::Max31855)
+            .celcius()
+            .is_none());
+    }
+
+    #[test]
+    fn test_raw_temps_capacity() {
+        let mut temps = RawTemps::new();
+        for _ in 0..MAX_TEMP_CHANNELS {
+            assert!(temps.push(RawTemp::max6675(0)).is_ok());
+        }
+        assert!(temps.push(RawTemp::max6675(0)).is_err());
+        assert_eq!(temps.len(), MAX_TEMP_CHANNELS);
+    }
+
+    #[test]
+    fn test_raw_serial_data_variable_channels() {
+        let mut buffer = [0u8; RawSerialData::BYTE_SIZE + 2];
+        for channels in 0..=MAX_TEMP_CHANNELS {
+            let mut data = RawSerialData {
+                pulse_rpm: u16::MAX,
+                pulse_enc: u16::MAX,
+                raw_temps: RawTemps::new(),
+            };
+            for _ in 0..channels {
+                data.raw_temps.push(RawTemp::max31855(u32::MAX)).unwrap();
+            }
+            let encoded = postcard::to_slice_cobs(&data, &mut buffer).unwrap();
+            assert!(encoded.len() <= RawSerialData::BYTE_SIZE + 2);
+            let decoded: RawSerialData = postcard::from_bytes_cobs(encoded).unwrap();
+            assert_eq!(decoded, data);
+            assert_eq!(decoded.raw_temps.len(), channels);
+        }
+    }
+
+    #[test]
+    fn test_serial_protocol_max_frame() {
+        let mut buffer = [0u8; MAX_FRAME_SIZE];
+        let mut config = DeviceConfig::new();
+        config.id = u32::MAX;
+        config.period_ms = u16::MAX;
+        config.encoder_ppr = u16::MAX;
+        config.rpm_ppr = u16::MAX;
+        while config.name.push('#').is_ok() {}
+
+        let command = SerialCommand::SetConfig(config.clone());
+        let encoded = postcard::to_slice_cobs(&command, &mut buffer).unwrap();
+        let decoded: SerialCommand = postcard::from_bytes_cobs(encoded).unwrap();
+        assert_eq!(decoded, command);
+
+        let response = SerialResponse::Config(config);
+        let encoded = postcard::to_slice_cobs(&response, &mut buffer).unwrap();
+        let decoded: SerialResponse = postcard::from_bytes_cobs(encoded).unwrap();
+        assert_eq!(decoded, response);
+
+        let command = SerialCommand::Dfu(DfuCommand::Write {
+            offset: u32::MAX,
+            data: heapless::Vec::from_slice(&[0u8; DFU_CHUNK_SIZE]).unwrap(),
+        });
+        let encoded = postcard::to_slice_cobs(&command, &mut buffer).unwrap();
+        let decoded: SerialCommand = postcard::from_bytes_cobs(encoded).unwrap();
+        assert_eq!(decoded, command);
+
+        assert_eq!(SerialDataInit::DEVICE_DEFAULT.period_ms_or(1000), 1000);
+        assert_eq!(SerialDataInit::new(10).period_ms_or(1000), 250);
+    }
+}
@@ -0,0 +1,40 @@
+use std::{env, fs, path::PathBuf};
+
+fn main() {
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+
+    let bootloader = env::var_os("CARGO_FEATURE_BOOTLOADER").is_some();
+
+    // put `memory.x` in the linker search path only for the bootloader binary, link search path
+    // is propagated to the firmware that depend on this library
+    if bootloader {
+        fs::write(out.join("memory.x"), include_bytes!("memory.x")).unwrap();
+        println!("cargo:rustc-link-search={}", out.display());
+    }
+    println!("cargo:rerun-if-changed=memory.x");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // raw 32 bytes ed25519 public key of the release signing key
+    println!("cargo:rerun-if-env-changed=DYNO_DFU_PUBLIC_KEY");
+    let key = match env::var_os("DYNO_DFU_PUBLIC_KEY") {
+        Some(path) => {
+            println!("cargo:rerun-if-changed={}", path.to_string_lossy());
+            let key = fs::read(&path).expect("failed reading DYNO_DFU_PUBLIC_KEY");
+            assert_eq!(
+                key.len(),
+                32,
+                "DYNO_DFU_PUBLIC_KEY must be raw 32 bytes key"
+            );
+            key
+        }
+        None => {
+            if bootloader {
+                println!(
+                    "cargo:warning=DYNO_DFU_PUBLIC_KEY is not set, every firmware update is rejected"
+                );
+            }
+            vec![0u8; 32]
+        }
+    };
+    fs::write(out.join("dfu_public.key"), key).unwrap();
+}
@@ -0,0 +1,7 @@
+/* bootloader of STM32F411CE, see `src/layout.rs` for the whole flash layout */
+MEMORY
+{
+  /* sector 0..1 */
+  FLASH : ORIGIN = 0x08000000, LENGTH = 32K
+  RAM   : ORIGIN = 0x20000000, LENGTH = 128K
+}
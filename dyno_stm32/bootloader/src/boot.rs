@@ -0,0 +1,127 @@
+use dyno_core::types::{FirmwareHeader, FIRMWARE_CRC};
+use embedded_storage::nor_flash::NorFlash;
+
+use crate::{
+    layout::{self, Partition},
+    slot_log::LogError,
+    state::{boot_state_log, BootState},
+};
+
+/// times the new firmware is booted without confirmed before it is rolled back
+pub const MAX_BOOT_ATTEMPTS: u8 = 3;
+
+const COPY_CHUNK: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum BootError {
+    Flash,
+    State(LogError),
+}
+
+impl From<LogError> for BootError {
+    fn from(value: LogError) -> Self {
+        Self::State(value)
+    }
+}
+
+/// continue the firmware update from the saved [`BootState`], return the state when the
+/// active firmware is ready to boot.
+/// `verify` check the image in [`layout::DFU`] (CRC and signature) before it is swapped.
+pub fn process<F, V>(flash: &mut F, mut verify: V) -> Result<BootState, BootError>
+where
+    F: NorFlash,
+    V: FnMut(&mut F, &FirmwareHeader) -> bool,
+{
+    let mut log = boot_state_log();
+    let mut state = log.load(flash).unwrap_or_default();
+    loop {
+        state = match state {
+            BootState::Boot | BootState::RolledBack => return Ok(state),
+            BootState::Pending(header) => {
+                if header.size <= layout::DFU.size && verify(flash, &header) {
+                    BootState::Backup(header)
+                } else {
+                    defmt::warn!("rejecting invalid firmware image");
+                    BootState::Boot
+                }
+            }
+            BootState::Backup(header) => {
+                copy(flash, layout::ACTIVE, layout::BACKUP)?;
+                BootState::Swap(header)
+            }
+            BootState::Swap(header) => {
+                copy(flash, layout::DFU, layout::ACTIVE)?;
+                BootState::Testing {
+                    version: header.version,
+                    attempts: 0,
+                }
+            }
+            BootState::Testing { version, attempts } if attempts < MAX_BOOT_ATTEMPTS => {
+                let state = BootState::Testing {
+                    version,
+                    attempts: attempts + 1,
+                };
+                log.save(flash, &state)?;
+                return Ok(state);
+            }
+            BootState::Testing { .. } => BootState::Rollback,
+            BootState::Rollback => {
+                copy(flash, layout::BACKUP, layout::ACTIVE)?;
+                BootState::RolledBack
+            }
+        };
+        log.save(flash, &state)?;
+    }
+}
+
+/// CRC of the first `size` bytes of `partition`
+pub fn partition_crc<F: NorFlash>(
+    flash: &mut F,
+    partition: Partition,
+    size: u32,
+) -> Result<u32, BootError> {
+    let mut digest = FIRMWARE_CRC.digest();
+    for_each_chunk(flash, partition, size, |chunk| digest.update(chunk))?;
+    Ok(digest.finalize())
+}
+
+/// read the first `size` bytes of `partition` in chunks
+pub fn for_each_chunk<F: NorFlash>(
+    flash: &mut F,
+    partition: Partition,
+    size: u32,
+    mut f: impl FnMut(&[u8]),
+) -> Result<(), BootError> {
+    let mut buf = [0u8; COPY_CHUNK];
+    let mut offset = 0;
+    while offset < size {
+        let len = (size - offset).min(COPY_CHUNK as u32) as usize;
+        flash
+            .read(partition.offset + offset, &mut buf[..len])
+            .map_err(|_| BootError::Flash)?;
+        f(&buf[..len]);
+        offset += len as u32;
+    }
+    Ok(())
+}
+
+fn copy<F: NorFlash>(flash: &mut F, from: Partition, to: Partition) -> Result<(), BootError> {
+    flash
+        .erase(to.offset, to.end())
+        .map_err(|_| BootError::Flash)?;
+    let mut buf = [0u8; COPY_CHUNK];
+    let mut offset = 0;
+    while offset < to.size.min(from.size) {
+        flash
+            .read(from.offset + offset, &mut buf)
+            .map_err(|_| BootError::Flash)?;
+        // skip erased chunk, mostly the unused tail of the partition
+        if buf.iter().any(|&b| b != 0xFF) {
+            flash
+                .write(to.offset + offset, &buf)
+                .map_err(|_| BootError::Flash)?;
+        }
+        offset += COPY_CHUNK as u32;
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! flash layout of STM32F411CE (512K), sector 0..3 is 16K, sector 4 is 64K, sector 5..7 is 128K
+
+/// start address of the flash in the memory map, every [`Partition::offset`] is relative to it
+pub const FLASH_BASE: u32 = 0x0800_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub struct Partition {
+    pub offset: u32,
+    pub size: u32,
+}
+
+impl Partition {
+    #[inline]
+    pub const fn end(&self) -> u32 {
+        self.offset + self.size
+    }
+
+    #[inline]
+    pub const fn address(&self) -> u32 {
+        FLASH_BASE + self.offset
+    }
+}
+
+/// sector 0..1, the bootloader binary
+pub const BOOTLOADER: Partition = Partition {
+    offset: 0x0000_0000,
+    size: 0x8000,
+};
+/// sector 2, log of [`crate::BootState`]
+pub const BOOT_STATE: Partition = Partition {
+    offset: 0x0000_8000,
+    size: 0x4000,
+};
+/// sector 3, log of the device config
+pub const CONFIG: Partition = Partition {
+    offset: 0x0000_C000,
+    size: 0x4000,
+};
+/// sector 4 (64K), the other half of [`BOOT_STATE`], the log is alternating between the two so
+/// the state is never erased before the next one is written
+pub const BOOT_STATE_SPARE: Partition = Partition {
+    offset: 0x0001_0000,
+    size: 0x1_0000,
+};
+
+/// sector 5, the running firmware
+pub const ACTIVE: Partition = Partition {
+    offset: 0x0002_0000,
+    size: 0x2_0000,
+};
+/// sector 6, the new firmware is uploaded here before it is swapped into [`ACTIVE`]
+pub const DFU: Partition = Partition {
+    offset: 0x0004_0000,
+    size: 0x2_0000,
+};
+/// sector 7, copy of the previous firmware, restored if the new firmware never confirmed
+pub const BACKUP: Partition = Partition {
+    offset: 0x0006_0000,
+    size: 0x2_0000,
+};
@@ -0,0 +1,10 @@
+#![cfg_attr(not(test), no_std)]
+
+mod boot;
+pub mod layout;
+mod slot_log;
+mod state;
+
+pub use boot::*;
+pub use slot_log::*;
+pub use state::*;
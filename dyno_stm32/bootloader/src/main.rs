@@ -0,0 +1,69 @@
+#![no_main]
+#![no_std]
+
+use defmt_rtt as _;
+use panic_probe as _;
+
+use dyno_boot::{layout, BootState};
+use dyno_core::types::{FirmwareHeader, FIRMWARE_SIGN_CONTEXT};
+use ed25519_dalek::{Signature, VerifyingKey};
+use embassy_stm32::{
+    flash::{Blocking, Flash},
+    wdg::IndependentWatchdog,
+};
+use sha2::{Digest, Sha512};
+
+/// public key of the release signing key, see `build.rs`
+const PUBLIC_KEY: &[u8; 32] = include_bytes!(concat!(env!("OUT_DIR"), "/dfu_public.key"));
+
+/// the new firmware must start its own watchdog task before this timeout
+const TESTING_WATCHDOG_US: u32 = 8_000_000;
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    let ph = embassy_stm32::init(Default::default());
+    let mut flash = Flash::new_blocking(ph.FLASH);
+
+    let state = match dyno_boot::process(&mut flash, verify) {
+        Ok(state) => state,
+        Err(err) => {
+            // every step is saved before it is started, retry it after reset
+            defmt::error!("firmware update failed: {}, retrying", err);
+            cortex_m::peripheral::SCB::sys_reset()
+        }
+    };
+    defmt::info!("booting firmware, state: {}", defmt::Debug2Format(&state));
+
+    if let BootState::Testing { .. } = state {
+        // the new firmware is not confirmed yet, reset into the bootloader if it hang
+        let mut wdg = IndependentWatchdog::new(ph.IWDG, TESTING_WATCHDOG_US);
+        wdg.unleash();
+    }
+    drop(flash);
+    unsafe { jump(layout::ACTIVE.address()) }
+}
+
+fn verify(flash: &mut Flash<'static, Blocking>, header: &FirmwareHeader) -> bool {
+    match dyno_boot::partition_crc(flash, layout::DFU, header.size) {
+        Ok(crc) if crc == header.crc32 => {}
+        _ => return false,
+    }
+    let Ok(key) = VerifyingKey::from_bytes(PUBLIC_KEY) else {
+        return false;
+    };
+    let mut digest = Sha512::new();
+    digest.update(header.signed_prefix());
+    if dyno_boot::for_each_chunk(flash, layout::DFU, header.size, |c| digest.update(c)).is_err() {
+        return false;
+    }
+    let signature = Signature::from_bytes(&header.signature.0);
+    key.verify_prehashed_strict(digest, Some(FIRMWARE_SIGN_CONTEXT), &signature)
+        .is_ok()
+}
+
+/// jump to the firmware vector table at `address`
+unsafe fn jump(address: u32) -> ! {
+    let scb = &*cortex_m::peripheral::SCB::PTR;
+    scb.vtor.write(address);
+    cortex_m::asm::bootload(address as *const u32)
+}
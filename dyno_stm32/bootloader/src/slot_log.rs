@@ -0,0 +1,317 @@
+use embedded_storage::nor_flash::NorFlash;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::layout::Partition;
+
+const HEADER_SIZE: usize = 16;
+const MAGIC: u32 = 0x4459_4346; // "DYCF"
+const ERASED: u32 = 0xFFFF_FFFF;
+
+const CRC: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum LogError {
+    Flash,
+    Encode,
+}
+
+/// append only log of fixed size slots in one erase sector, every save write the next erased
+/// slot and the sector is erased only when every slot is used.
+///
+/// with a spare sector ([`SlotLog::with_spare`]) the log is alternating between the two sectors,
+/// the next value is written into the other sector before the full one is ever erased, so power
+/// loss on any point is leaving at least one valid slot. without it the log is empty between
+/// the erase and the write.
+///
+/// slot layout (little endian):
+/// | magic u32 | sequence u32 | payload len u16 | reserved u16 | crc32 u32 | postcard payload |
+/// crc32 is computed from sequence, len and the payload
+pub struct SlotLog<const SLOT_SIZE: usize> {
+    sectors: [Partition; 2],
+    sector_count: usize,
+    active: usize,
+    sequence: u32,
+    next_slot: u32,
+}
+
+impl<const SLOT_SIZE: usize> SlotLog<SLOT_SIZE> {
+    pub const fn new(partition: Partition) -> Self {
+        Self {
+            sectors: [partition, partition],
+            sector_count: 1,
+            active: 0,
+            sequence: 0,
+            next_slot: 0,
+        }
+    }
+
+    /// log alternating between `partition` and `spare`, each must be a whole erase sector
+    pub const fn with_spare(partition: Partition, spare: Partition) -> Self {
+        Self {
+            sectors: [partition, spare],
+            sector_count: 2,
+            active: 0,
+            sequence: 0,
+            next_slot: 0,
+        }
+    }
+
+    #[inline]
+    const fn slot_count(&self, sector: usize) -> u32 {
+        self.sectors[sector].size / SLOT_SIZE as u32
+    }
+
+    /// scan every slot of every sector and return the newest valid value, `None` if never
+    /// saved or every slot is corrupt
+    pub fn load<T, F>(&mut self, flash: &mut F) -> Option<T>
+    where
+        T: DeserializeOwned,
+        F: NorFlash,
+    {
+        let mut newest: Option<(u32, T)> = None;
+        self.active = 0;
+        self.next_slot = self.scan(flash, 0, &mut newest);
+        for sector in 1..self.sector_count {
+            let sequence = newest.as_ref().map(|(seq, _)| *seq);
+            let next_slot = self.scan(flash, sector, &mut newest);
+            if newest.as_ref().map(|(seq, _)| *seq) != sequence {
+                self.active = sector;
+                self.next_slot = next_slot;
+            }
+        }
+        let (sequence, value) = newest?;
+        self.sequence = sequence;
+        Some(value)
+    }
+
+    /// write `value` to the next slot, when the sector is full the other sector (or the same
+    /// one without a spare) is erased first
+    pub fn save<T, F>(&mut self, flash: &mut F, value: &T) -> Result<(), LogError>
+    where
+        T: Serialize,
+        F: NorFlash,
+    {
+        let mut slot = [0xFFu8; SLOT_SIZE];
+        let payload_len = postcard::to_slice(value, &mut slot[HEADER_SIZE..])
+            .map_err(|_| LogError::Encode)?
+            .len();
+        let sequence = self.sequence.wrapping_add(1);
+        let crc = Self::checksum(sequence, &slot[HEADER_SIZE..HEADER_SIZE + payload_len]);
+
+        slot[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+        slot[4..8].copy_from_slice(&sequence.to_le_bytes());
+        slot[8..10].copy_from_slice(&(payload_len as u16).to_le_bytes());
+        slot[10..12].copy_from_slice(&[0, 0]);
+        slot[12..16].copy_from_slice(&crc.to_le_bytes());
+
+        if self.next_slot >= self.slot_count(self.active) {
+            // the full sector is kept until the other one is full too, it is still holding the
+            // newest value if the erase or the write below is interrupted
+            let next = (self.active + 1) % self.sector_count;
+            let sector = self.sectors[next];
+            flash
+                .erase(sector.offset, sector.end())
+                .map_err(|_| LogError::Flash)?;
+            self.active = next;
+            self.next_slot = 0;
+        }
+        let offset = self.slot_offset(self.active, self.next_slot);
+        // slot is used even if the write failed, it is not erased anymore
+        self.next_slot += 1;
+        flash.write(offset, &slot).map_err(|_| LogError::Flash)?;
+        self.sequence = sequence;
+        Ok(())
+    }
+
+    /// scan the slots of `sector`, keep the newest value in `newest` and return the index of
+    /// the first erased slot
+    fn scan<T, F>(&self, flash: &mut F, sector: usize, newest: &mut Option<(u32, T)>) -> u32
+    where
+        T: DeserializeOwned,
+        F: NorFlash,
+    {
+        let mut slot = [0u8; SLOT_SIZE];
+        for idx in 0..self.slot_count(sector) {
+            if flash
+                .read(self.slot_offset(sector, idx), &mut slot)
+                .is_err()
+            {
+                continue;
+            }
+            let magic = u32::from_le_bytes([slot[0], slot[1], slot[2], slot[3]]);
+            if magic == ERASED {
+                // slots is written in order, so the rest is erased too
+                return idx;
+            }
+            let Some((sequence, value)) = Self::decode_slot(&slot) else {
+                defmt::warn!(
+                    "slot {} at {:x} is corrupt",
+                    idx,
+                    self.sectors[sector].offset
+                );
+                continue;
+            };
+            if newest.as_ref().is_none_or(|(seq, _)| sequence > *seq) {
+                *newest = Some((sequence, value));
+            }
+        }
+        self.slot_count(sector)
+    }
+
+    fn decode_slot<T: DeserializeOwned>(slot: &[u8; SLOT_SIZE]) -> Option<(u32, T)> {
+        let magic = u32::from_le_bytes([slot[0], slot[1], slot[2], slot[3]]);
+        let sequence = u32::from_le_bytes([slot[4], slot[5], slot[6], slot[7]]);
+        let payload_len = u16::from_le_bytes([slot[8], slot[9]]) as usize;
+        let crc = u32::from_le_bytes([slot[12], slot[13], slot[14], slot[15]]);
+        if magic != MAGIC || payload_len > SLOT_SIZE - HEADER_SIZE {
+            return None;
+        }
+        let payload = &slot[HEADER_SIZE..HEADER_SIZE + payload_len];
+        if Self::checksum(sequence, payload) != crc {
+            return None;
+        }
+        let value = postcard::from_bytes(payload).ok()?;
+        Some((sequence, value))
+    }
+
+    fn checksum(sequence: u32, payload: &[u8]) -> u32 {
+        let mut digest = CRC.digest();
+        digest.update(&sequence.to_le_bytes());
+        digest.update(&(payload.len() as u16).to_le_bytes());
+        digest.update(payload);
+        digest.finalize()
+    }
+
+    #[inline]
+    const fn slot_offset(&self, sector: usize, idx: u32) -> u32 {
+        self.sectors[sector].offset + idx * SLOT_SIZE as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_storage::nor_flash::{ErrorType, NorFlashError, NorFlashErrorKind, ReadNorFlash};
+
+    use super::*;
+
+    const SECTOR: Partition = Partition {
+        offset: 0,
+        size: 512,
+    };
+    const SPARE: Partition = Partition {
+        offset: 512,
+        size: 1024,
+    };
+
+    #[derive(Debug)]
+    struct PowerLoss;
+
+    impl NorFlashError for PowerLoss {
+        fn kind(&self) -> NorFlashErrorKind {
+            NorFlashErrorKind::Other
+        }
+    }
+
+    /// flash in RAM, the power is cut after `budget` erase or write operations
+    struct RamFlash {
+        data: Vec<u8>,
+        budget: usize,
+    }
+
+    impl RamFlash {
+        fn new() -> Self {
+            Self {
+                data: vec![0xFF; (SPARE.end()) as usize],
+                budget: usize::MAX,
+            }
+        }
+
+        fn spend(&mut self) -> Result<(), PowerLoss> {
+            self.budget = self.budget.checked_sub(1).ok_or(PowerLoss)?;
+            Ok(())
+        }
+    }
+
+    impl ErrorType for RamFlash {
+        type Error = PowerLoss;
+    }
+
+    impl ReadNorFlash for RamFlash {
+        const READ_SIZE: usize = 1;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            let offset = offset as usize;
+            bytes.copy_from_slice(&self.data[offset..offset + bytes.len()]);
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.data.len()
+        }
+    }
+
+    impl NorFlash for RamFlash {
+        const WRITE_SIZE: usize = 4;
+        const ERASE_SIZE: usize = 512;
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+            self.spend()?;
+            self.data[from as usize..to as usize].fill(0xFF);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            self.spend()?;
+            let offset = offset as usize;
+            for (dst, src) in self.data[offset..].iter_mut().zip(bytes) {
+                *dst &= *src;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_slot_log_alternate() {
+        let mut flash = RamFlash::new();
+        let mut log = SlotLog::<128>::with_spare(SECTOR, SPARE);
+        assert_eq!(log.load::<u32, _>(&mut flash), None);
+        // 4 slots in the sector, 8 in the spare, wrap around both twice
+        for value in 0..30u32 {
+            log.save(&mut flash, &value).unwrap();
+            let mut reloaded = SlotLog::<128>::with_spare(SECTOR, SPARE);
+            assert_eq!(reloaded.load(&mut flash), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_slot_log_power_loss() {
+        // cut the power on every erase and write from the first to the second wrap around
+        for budget in 0..16 {
+            let mut flash = RamFlash::new();
+            let mut log = SlotLog::<128>::with_spare(SECTOR, SPARE);
+            for value in 0..4u32 {
+                log.save(&mut flash, &value).unwrap();
+            }
+            flash.budget = budget;
+            let mut saved = 3;
+            for value in 4..16u32 {
+                if log.save(&mut flash, &value).is_err() {
+                    break;
+                }
+                saved = value;
+            }
+            flash.budget = usize::MAX;
+
+            let mut log = SlotLog::<128>::with_spare(SECTOR, SPARE);
+            assert_eq!(
+                log.load(&mut flash),
+                Some(saved),
+                "power loss after {budget}"
+            );
+            // and the log is still usable after the reboot
+            log.save(&mut flash, &100u32).unwrap();
+            let mut log = SlotLog::<128>::with_spare(SECTOR, SPARE);
+            assert_eq!(log.load(&mut flash), Some(100u32));
+        }
+    }
+}
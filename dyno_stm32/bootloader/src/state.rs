@@ -0,0 +1,46 @@
+use dyno_core::types::{BootStatus, FirmwareHeader, FirmwareVersion};
+use serde::{Deserialize, Serialize};
+
+use crate::{layout, slot_log::SlotLog};
+
+/// state of the firmware update, every step is saved before and after it is done so the
+/// bootloader can continue after power loss
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BootState {
+    /// the active firmware is confirmed
+    #[default]
+    Boot,
+    /// new image is written to [`layout::DFU`] by the firmware, waiting for the bootloader
+    Pending(FirmwareHeader),
+    /// bootloader is copying [`layout::ACTIVE`] to [`layout::BACKUP`]
+    Backup(FirmwareHeader),
+    /// bootloader is copying [`layout::DFU`] to [`layout::ACTIVE`]
+    Swap(FirmwareHeader),
+    /// new firmware is booted `attempts` times without confirmed by the firmware
+    Testing {
+        version: FirmwareVersion,
+        attempts: u8,
+    },
+    /// bootloader is copying [`layout::BACKUP`] to [`layout::ACTIVE`]
+    Rollback,
+    /// the new firmware never confirmed and the previous firmware is restored
+    RolledBack,
+}
+
+impl BootState {
+    /// status of the running firmware that reported to the host
+    pub const fn status(&self) -> BootStatus {
+        match self {
+            Self::Testing { .. } => BootStatus::Testing,
+            Self::RolledBack => BootStatus::RolledBack,
+            _ => BootStatus::Confirmed,
+        }
+    }
+}
+
+/// slot is big enough for [`BootState::Pending`] with the signature
+pub type BootStateLog = SlotLog<128>;
+
+pub const fn boot_state_log() -> BootStateLog {
+    SlotLog::with_spare(layout::BOOT_STATE, layout::BOOT_STATE_SPARE)
+}
@@ -0,0 +1,9 @@
+/* firmware of STM32F411CE, booted by `dyno_boot`,
+ * see `bootloader/src/layout.rs` for the whole flash layout
+ */
+MEMORY
+{
+  /* sector 5, `layout::ACTIVE` */
+  FLASH : ORIGIN = 0x08020000, LENGTH = 128K
+  RAM   : ORIGIN = 0x20000000, LENGTH = 128K
+}
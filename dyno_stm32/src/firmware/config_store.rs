@@ -0,0 +1,32 @@
+use dyno_boot::{layout, LogError, SlotLog};
+use dyno_core::types::DeviceConfig;
+use embedded_storage::nor_flash::NorFlash;
+
+/// device config log in [`layout::CONFIG`]
+pub struct ConfigStore {
+    log: SlotLog<256>,
+}
+
+impl ConfigStore {
+    pub const fn new() -> Self {
+        Self {
+            log: SlotLog::new(layout::CONFIG),
+        }
+    }
+
+    /// newest valid config, `None` if never saved or every slot is corrupt
+    pub fn load<F: NorFlash>(&mut self, flash: &mut F) -> Option<DeviceConfig> {
+        self.log
+            .load::<DeviceConfig, _>(flash)
+            .filter(DeviceConfig::is_valid)
+    }
+
+    #[inline]
+    pub fn save<F: NorFlash>(
+        &mut self,
+        flash: &mut F,
+        config: &DeviceConfig,
+    ) -> Result<(), LogError> {
+        self.log.save(flash, config)
+    }
+}
@@ -0,0 +1,139 @@
+use dyno_boot::{boot_state_log, layout, partition_crc, BootState, BootStateLog};
+use dyno_core::types::{
+    DfuCommand, DfuError, DfuResponse, FirmwareHeader, FirmwareVersion, DFU_CHUNK_SIZE,
+};
+use embedded_storage::nor_flash::NorFlash;
+
+pub const FIRMWARE_VERSION: FirmwareVersion = FirmwareVersion::parse(env!("CARGO_PKG_VERSION"));
+
+/// firmware side of the update, the image is written to [`layout::DFU`] and swapped by the
+/// bootloader after reset
+pub struct Dfu {
+    log: BootStateLog,
+    state: BootState,
+    header: Option<FirmwareHeader>,
+    written: u32,
+}
+
+impl Dfu {
+    pub fn new<F: NorFlash>(flash: &mut F) -> Self {
+        let mut log = boot_state_log();
+        let state = log.load(flash).unwrap_or_default();
+        Self {
+            log,
+            state,
+            header: None,
+            written: 0,
+        }
+    }
+
+    /// confirm the new firmware on the first command from the host, so the bootloader is
+    /// not rolling it back on the next reset
+    pub fn confirm<F: NorFlash>(&mut self, flash: &mut F) {
+        if let BootState::Testing { version, .. } = self.state {
+            match self.log.save(flash, &BootState::Boot) {
+                Ok(()) => {
+                    defmt::info!(
+                        "firmware {}.{}.{} confirmed",
+                        version.major,
+                        version.minor,
+                        version.patch
+                    );
+                    self.state = BootState::Boot;
+                }
+                Err(err) => defmt::error!("failed confirming firmware: {}", err),
+            }
+        }
+    }
+
+    /// handle the command, return the response and `true` if the device must be reset after
+    /// the response is sent
+    pub fn handle<F: NorFlash>(
+        &mut self,
+        flash: &mut F,
+        command: DfuCommand,
+    ) -> (DfuResponse, bool) {
+        let response = match command {
+            DfuCommand::GetVersion => DfuResponse::Version {
+                version: FIRMWARE_VERSION,
+                status: self.state.status(),
+            },
+            DfuCommand::Begin(header) => self.begin(flash, header),
+            DfuCommand::Write { offset, data } => self.write(flash, offset, &data),
+            DfuCommand::Finish => {
+                let response = self.finish(flash);
+                return (response, response == DfuResponse::Verified);
+            }
+            DfuCommand::Abort => {
+                self.header = None;
+                DfuResponse::Aborted
+            }
+        };
+        (response, false)
+    }
+
+    fn begin<F: NorFlash>(&mut self, flash: &mut F, header: FirmwareHeader) -> DfuResponse {
+        self.header = None;
+        if header.size > layout::DFU.size {
+            return DfuResponse::Error(DfuError::TooLarge);
+        }
+        // blocking for about a second, the watchdog timeout is longer than this
+        if flash.erase(layout::DFU.offset, layout::DFU.end()).is_err() {
+            return DfuResponse::Error(DfuError::Flash);
+        }
+        self.header = Some(header);
+        self.written = 0;
+        DfuResponse::Ready
+    }
+
+    fn write<F: NorFlash>(&mut self, flash: &mut F, offset: u32, data: &[u8]) -> DfuResponse {
+        let Some(header) = self.header else {
+            return DfuResponse::Error(DfuError::NotStarted);
+        };
+        let next = offset + data.len() as u32;
+        // retransmit of the last chunk, the response is lost
+        if next == self.written && !data.is_empty() {
+            return DfuResponse::Written { next };
+        }
+        if offset != self.written || !(offset as usize).is_multiple_of(F::WRITE_SIZE) {
+            return DfuResponse::Error(DfuError::UnexpectedOffset);
+        }
+        if next > header.size {
+            return DfuResponse::Error(DfuError::TooLarge);
+        }
+        // pad the last chunk to the flash write size
+        let mut buf = [0xFFu8; DFU_CHUNK_SIZE];
+        buf[..data.len()].copy_from_slice(data);
+        let len = data.len().next_multiple_of(F::WRITE_SIZE);
+        if flash
+            .write(layout::DFU.offset + offset, &buf[..len])
+            .is_err()
+        {
+            return DfuResponse::Error(DfuError::Flash);
+        }
+        self.written = next;
+        DfuResponse::Written { next }
+    }
+
+    fn finish<F: NorFlash>(&mut self, flash: &mut F) -> DfuResponse {
+        let Some(header) = self.header.take() else {
+            return DfuResponse::Error(DfuError::NotStarted);
+        };
+        if self.written != header.size {
+            return DfuResponse::Error(DfuError::Corrupted);
+        }
+        // read back from the flash, so the written image is checked too
+        match partition_crc(flash, layout::DFU, header.size) {
+            Ok(crc) if crc == header.crc32 => {}
+            Ok(_) => return DfuResponse::Error(DfuError::Corrupted),
+            Err(_) => return DfuResponse::Error(DfuError::Flash),
+        }
+        // the signature is checked by the bootloader
+        let state = BootState::Pending(header);
+        if self.log.save(flash, &state).is_err() {
+            return DfuResponse::Error(DfuError::Flash);
+        }
+        self.state = state;
+        DfuResponse::Verified
+    }
+}
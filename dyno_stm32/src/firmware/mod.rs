@@ -0,0 +1,240 @@
+mod config_store;
+mod dfu;
+#[cfg(not(feature = "usb"))]
+mod serial;
+mod task;
+mod thermocouple;
+mod transport;
+#[cfg(feature = "usb")]
+mod usb;
+
+use defmt_rtt as _;
+use panic_probe as _;
+
+use embassy_futures::select::{select, Either};
+use embassy_stm32::{flash, gpio, peripherals, wdg};
+
+use dyno_core::types::{
+    DeviceConfig, DeviceError, SerialCommand, SerialResponse, ThermocoupleKind,
+};
+use dyno_stm32::sampling::Sampler;
+use task::GLOBAL_DATA;
+
+type IndicatorA = gpio::Output<'static, peripherals::PB3>;
+type IndicatorB = gpio::Output<'static, peripherals::PB4>;
+
+#[cfg(not(feature = "usb"))]
+type Link = transport::Framed<serial::Serial>;
+#[cfg(feature = "usb")]
+type Link = transport::Framed<usb::UsbSerial>;
+
+type FlashDriver = flash::Flash<'static, flash::Blocking>;
+type Watchdog = wdg::IndependentWatchdog<'static, peripherals::IWDG>;
+
+/// longer than the blocking flash erase of 128K sector
+const WATCHDOG_TIMEOUT_US: u32 = 8_000_000;
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    let mut executor = ::embassy_executor::Executor::new();
+    let ph = embassy_stm32::init(config());
+    let mut indicator_a = gpio::Output::new(ph.PB3, gpio::Level::Low, gpio::Speed::Low);
+    let mut indicator_b = gpio::Output::new(ph.PB4, gpio::Level::Low, gpio::Speed::Low);
+    // the bootloader already started the watchdog when booting new firmware, keep it fed while
+    // blinking, until `watchdog_task` take over
+    let mut watchdog = wdg::IndependentWatchdog::new(ph.IWDG, WATCHDOG_TIMEOUT_US);
+    for _ in 0..3 {
+        watchdog.pet();
+        indicator_a.set_high();
+        embassy_time::block_for(embassy_time::Duration::from_millis(500));
+        indicator_a.set_low();
+        embassy_time::block_for(embassy_time::Duration::from_millis(500));
+        indicator_b.set_high();
+        embassy_time::block_for(embassy_time::Duration::from_millis(500));
+        indicator_b.set_low();
+        embassy_time::block_for(embassy_time::Duration::from_millis(500));
+    }
+
+    indicator_b.set_high();
+
+    __make_static(&mut executor).run(move |spawner| {
+        // let led_a: LedIndicatorA = gpio::Output::new(ph.PB3, Level::High, Speed::Low);
+        // let led_b: LedIndicatorB = gpio::Output::new(ph.PB4, Level::High, Speed::Low);
+        #[cfg(not(feature = "usb"))]
+        let link = match serial::Serial::new(
+            ph.USART1,   // serial instance
+            ph.PA10,     // rx
+            ph.PA9,      // tx
+            ph.PA12,     // rts
+            ph.PA11,     // cts
+            ph.DMA2_CH7, // txdma
+            ph.DMA2_CH5, // rxdma
+        ) {
+            Ok(ok) => transport::Framed::new(ok),
+            Err(err) => error_indicator(err, indicator_b),
+        };
+        #[cfg(feature = "usb")]
+        let link = {
+            let (usb_serial, device) = usb::UsbSerial::new(
+                ph.USB_OTG_FS, // usb instance
+                ph.PA12,       // d+
+                ph.PA11,       // d-
+            );
+            if let Err(err) = spawner.spawn(usb::usb_task(device)) {
+                error_indicator(err, indicator_b);
+            }
+            transport::Framed::new(usb_serial)
+        };
+        let flash = flash::Flash::new_blocking(ph.FLASH);
+
+        // the channel index follows the order of `with_chip`, keep it the same as
+        // `DEFAULT_TEMP_CHANNELS` in dyno_core
+        let thermocouples = thermocouple::Thermocouples::new(
+            ph.SPI1,     // spi instance
+            ph.PA5,      // sck
+            ph.PA6,      // miso
+            ph.DMA2_CH2, // txdma (unused b'cs thermocouple is rx only SPI)
+            ph.DMA2_CH0, // rxdma
+        )
+        .with_chip(ph.PB0, ThermocoupleKind::Max6675) // cylinder head
+        .with_chip(ph.PB1, ThermocoupleKind::Max31855) // exhaust gas
+        .with_chip(ph.PB10, ThermocoupleKind::Max6675); // oil
+
+        if let Err(err) = spawner.spawn(task::exti_input_rpm(ph.PB9, ph.EXTI9)) {
+            error_indicator(err, indicator_b);
+        }
+        if let Err(err) = spawner.spawn(task::exti_input_encoder(ph.PA2, ph.EXTI2)) {
+            error_indicator(err, indicator_b);
+        }
+        if let Err(err) = spawner.spawn(task::thermocouple_task(thermocouples)) {
+            error_indicator(err, indicator_b);
+        }
+        if let Err(err) = spawner.spawn(watchdog_task(watchdog)) {
+            error_indicator(err, indicator_b);
+        }
+        if let Err(err) = spawner.spawn(main_task(link, flash, indicator_a)) {
+            error_indicator(err, indicator_b);
+        }
+    })
+}
+
+/// also started by the bootloader when booting new firmware, so hang firmware is rolled back
+#[embassy_executor::task]
+async fn watchdog_task(mut watchdog: Watchdog) {
+    watchdog.unleash();
+    loop {
+        watchdog.pet();
+        embassy_time::Timer::after_secs(1).await;
+    }
+}
+
+#[embassy_executor::task]
+async fn main_task(mut link: Link, mut flash: FlashDriver, mut leda: IndicatorA) {
+    let mut dfu = dfu::Dfu::new(&mut flash);
+    let mut store = config_store::ConfigStore::new();
+    let mut device_config = store.load(&mut flash).unwrap_or_else(|| {
+        defmt::warn!("no valid device config in flash, using default");
+        DeviceConfig::new()
+    });
+    let mut sampler: Option<Sampler> = None;
+    loop {
+        let command = match sampler.as_mut() {
+            None => link.read_deserialize::<SerialCommand>().await,
+            Some(sampler) => {
+                let deadline = embassy_time::Instant::from_micros(sampler.deadline_us());
+                let read = link.read_deserialize::<SerialCommand>();
+                match select(read, embassy_time::Timer::at(deadline)).await {
+                    Either::First(command) => command,
+                    Either::Second(()) => {
+                        let now = embassy_time::Instant::now().as_micros();
+                        if let Some(raw_data) = sampler.poll(now, &GLOBAL_DATA) {
+                            leda.toggle();
+                            link.write_serialize(&SerialResponse::Data(raw_data)).await;
+                        }
+                        continue;
+                    }
+                }
+            }
+        };
+        dfu.confirm(&mut flash);
+        match command {
+            SerialCommand::Start(init) => {
+                let period_ms = init.period_ms_or(device_config.period_ms);
+                // drop the pulses counted while not streaming
+                GLOBAL_DATA.take_frame();
+                let now = embassy_time::Instant::now().as_micros();
+                sampler = Some(Sampler::new(now, period_ms));
+            }
+            SerialCommand::Stop => sampler = None,
+            SerialCommand::GetConfig => {
+                let response = SerialResponse::Config(device_config.clone());
+                link.write_serialize(&response).await;
+            }
+            SerialCommand::SetConfig(config) => {
+                let response = if !config.is_valid() {
+                    SerialResponse::Error(DeviceError::InvalidConfig)
+                } else if let Err(err) = store.save(&mut flash, &config) {
+                    defmt::error!("failed saving device config: {}", err);
+                    SerialResponse::Error(DeviceError::Flash)
+                } else {
+                    device_config = config;
+                    SerialResponse::ConfigSaved
+                };
+                link.write_serialize(&response).await;
+            }
+            SerialCommand::Dfu(command) => {
+                sampler = None;
+                let (response, reset) = dfu.handle(&mut flash, command);
+                link.write_serialize(&SerialResponse::Dfu(response)).await;
+                if reset {
+                    defmt::info!("firmware update is verified, resetting into bootloader");
+                    embassy_time::Timer::after_millis(100).await;
+                    cortex_m::peripheral::SCB::sys_reset();
+                }
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn error_indicator<F: defmt::Format>(err: F, mut b: IndicatorB) -> ! {
+    for _ in 0..10 {
+        b.toggle();
+        embassy_time::block_for(embassy_time::Duration::from_millis(500));
+    }
+    panic!("ERROR: {}", err)
+}
+
+#[cfg(not(feature = "usb"))]
+fn config() -> embassy_stm32::Config {
+    embassy_stm32::Config::default()
+}
+
+/// USB OTG FS need 48MHz clock, use the 25MHz HSE of the board:
+/// 25MHz / 25 * 192 = 192MHz VCO, sysclk = VCO / 2 = 96MHz, usb = VCO / 4 = 48MHz
+#[cfg(feature = "usb")]
+fn config() -> embassy_stm32::Config {
+    use embassy_stm32::{rcc::*, time::Hertz};
+    let mut config = embassy_stm32::Config::default();
+    config.rcc.hse = Some(Hse {
+        freq: Hertz(25_000_000),
+        mode: HseMode::Oscillator,
+    });
+    config.rcc.pll_src = PllSource::HSE;
+    config.rcc.pll = Some(Pll {
+        prediv: PllPreDiv::DIV25,
+        mul: PllMul::MUL192,
+        divp: Some(PllPDiv::DIV2),
+        divq: Some(PllQDiv::DIV4),
+        divr: None,
+    });
+    config.rcc.ahb_pre = AHBPrescaler::DIV1;
+    config.rcc.apb1_pre = APBPrescaler::DIV2;
+    config.rcc.apb2_pre = APBPrescaler::DIV1;
+    config.rcc.sys = Sysclk::PLL1_P;
+    config
+}
+
+fn __make_static<T>(t: &mut T) -> &'static mut T {
+    unsafe { core::mem::transmute(t) }
+}
@@ -1,5 +1,7 @@
-use dyno_core::types::RawSerialData;
 use embassy_stm32::{bind_interrupts, peripherals, usart};
+use static_cell::StaticCell;
+
+use super::transport::Transport;
 
 pub type UsartInstance = peripherals::USART1;
 pub type UsartTx = peripherals::PA9;
@@ -14,10 +16,14 @@ bind_interrupts!(struct Irqs {
     USART1 => usart::InterruptHandler<peripherals::USART1>;
 });
 
-const BUF_SIZE: usize = RawSerialData::BYTE_SIZE * 2;
+/// bytes received while the main loop is busy (sampling or writing flash) wait here
+const RX_RING_SIZE: usize = 512;
+
+/// USART1 to the external USB-serial bridge. the rx is a DMA ring buffer that keep receiving in
+/// the background, so a cancelled `read` does not lose the bytes of the frame
 pub struct Serial {
-    usart: usart::Uart<'static, UsartInstance, UsartTxDma, UsartRxDma>,
-    buffer: [u8; BUF_SIZE],
+    tx: usart::UartTx<'static, UsartInstance, UsartTxDma>,
+    rx: usart::RingBufferedUartRx<'static, UsartInstance, UsartRxDma>,
 }
 impl Serial {
     pub fn new(
@@ -40,34 +46,13 @@ impl Serial {
             rx_dma,
             Self::config(),
         )?;
-        Ok(Self {
-            usart,
-            buffer: [0u8; BUF_SIZE],
-        })
-    }
-
-    #[inline]
-    pub async fn read_deserialize<T>(&mut self) -> T
-    where
-        T: for<'de> dyno_core::serde::Deserialize<'de>,
-    {
-        let Self { usart, buffer } = self;
-        let readed = unwrap!(usart.read_until_idle(buffer).await);
-        let (t, used) = unwrap!(postcard::take_from_bytes_cobs(&mut buffer[..readed]));
-        let readed = used.len();
-        buffer.rotate_left(readed);
-        t
-    }
-    #[inline]
-    pub async fn write_serialize<T>(&mut self, value: &T)
-    where
-        T: dyno_core::serde::Serialize,
-    {
-        let Self { usart, buffer } = self;
-        let used_buffer = unwrap!(postcard::to_slice_cobs(value, buffer));
-        unwrap!(usart.write(used_buffer).await)
+        static RX_RING: StaticCell<[u8; RX_RING_SIZE]> = StaticCell::new();
+        let (tx, rx) = usart.split();
+        let rx = rx.into_ring_buffered(RX_RING.init([0; RX_RING_SIZE]));
+        Ok(Self { tx, rx })
     }
 
+    /// 8E1 with RTS/CTS, must match the default `SerialSettings` in the desktop app
     #[inline]
     fn config() -> usart::Config {
         let mut cfg = usart::Config::default();
@@ -76,3 +61,17 @@ impl Serial {
         cfg
     }
 }
+
+impl Transport for Serial {
+    type Error = usart::Error;
+
+    #[inline]
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.rx.read(buf).await
+    }
+
+    #[inline]
+    async fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.tx.write(bytes).await
+    }
+}
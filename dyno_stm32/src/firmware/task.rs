@@ -0,0 +1,45 @@
+use dyno_stm32::sampling::Counters;
+use embassy_stm32::{exti, gpio, peripherals};
+
+use super::thermocouple::Thermocouples;
+
+pub type EncoderInput = peripherals::PA2;
+pub type RpmInput = peripherals::PB9;
+pub type EncoderExtiChan = <EncoderInput as gpio::Pin>::ExtiChannel;
+pub type RpmExtiChan = <RpmInput as gpio::Pin>::ExtiChannel;
+
+pub static GLOBAL_DATA: Counters = Counters::new();
+
+#[embassy_executor::task]
+pub async fn exti_input_encoder(pin: EncoderInput, ch: EncoderExtiChan) {
+    let mut ex = exti::ExtiInput::new(gpio::Input::new(pin, gpio::Pull::Down), ch);
+    loop {
+        ex.wait_for_rising_edge().await;
+        GLOBAL_DATA.incr_encoder();
+    }
+}
+
+#[embassy_executor::task]
+pub async fn exti_input_rpm(pin: RpmInput, ch: RpmExtiChan) {
+    let mut ex = exti::ExtiInput::new(gpio::Input::new(pin, gpio::Pull::Down), ch);
+    loop {
+        ex.wait_for_rising_edge().await;
+        GLOBAL_DATA.incr_rpm();
+    }
+}
+
+#[embassy_executor::task]
+pub async fn thermocouple_task(mut ins: Thermocouples) {
+    GLOBAL_DATA.init_temps(ins.initial_temps());
+    if ins.len() == 0 {
+        return;
+    }
+    let slot = embassy_time::Duration::from_millis(ins.slot_ms());
+    let mut ticker = embassy_time::Ticker::every(slot);
+    loop {
+        ticker.next().await;
+        if let Some((idx, temp)) = ins.read_next().await {
+            GLOBAL_DATA.set_temp(idx, temp);
+        }
+    }
+}
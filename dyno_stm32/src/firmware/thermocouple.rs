@@ -0,0 +1,105 @@
+use dyno_core::types::{RawTemp, RawTemps, ThermocoupleKind, MAX_TEMP_CHANNELS};
+use dyno_stm32::sampling::ThermoSchedule;
+use embassy_stm32::{
+    gpio, peripherals,
+    spi::{self, BitOrder, MODE_0},
+    time::mhz,
+};
+pub type ThermoInstance = peripherals::SPI1;
+pub type ThermoSCK = peripherals::PA5;
+pub type ThermoMISO = peripherals::PA6;
+pub type ThermoTxDma = peripherals::DMA2_CH2;
+pub type ThermoRxDma = peripherals::DMA2_CH0;
+
+struct ThermoChip {
+    cs: gpio::Output<'static, gpio::AnyPin>,
+    kind: ThermocoupleKind,
+}
+
+/// thermocouple chips (MAX6675 / MAX31855) on shared SPI bus with separate chip select
+pub struct Thermocouples {
+    spi: spi::Spi<'static, ThermoInstance, ThermoTxDma, ThermoRxDma>,
+    chips: heapless::Vec<ThermoChip, MAX_TEMP_CHANNELS>,
+    schedule: ThermoSchedule,
+}
+
+impl Thermocouples {
+    pub fn new(
+        peri: ThermoInstance,
+        sck: ThermoSCK,
+        miso: ThermoMISO,
+        txdma: ThermoTxDma,
+        rxdma: ThermoRxDma,
+    ) -> Self {
+        let spi = spi::Spi::new_rxonly(peri, sck, miso, txdma, rxdma, Self::config());
+        Self {
+            spi,
+            chips: heapless::Vec::new(),
+            schedule: ThermoSchedule::new(0),
+        }
+    }
+
+    /// add chip on chip select `cs`, the channel index is the order of the call
+    pub fn with_chip(mut self, cs: impl gpio::Pin, kind: ThermocoupleKind) -> Self {
+        let cs = gpio::Output::new(cs.degrade(), gpio::Level::High, gpio::Speed::Low);
+        if self.chips.push(ThermoChip { cs, kind }).is_err() {
+            defmt::warn!("thermocouple chips is full, ignoring chip {}", kind as u8);
+        }
+        self.schedule = ThermoSchedule::new(self.chips.len());
+        self
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.chips.len()
+    }
+
+    /// read one chip per slot, so every chip is readed once in `THERMO_PERIOD_MS`
+    #[inline]
+    pub fn slot_ms(&self) -> u64 {
+        self.schedule.slot_ms()
+    }
+
+    /// channels with fault word, used before every chip is readed
+    pub fn initial_temps(&self) -> RawTemps {
+        let mut temps = RawTemps::new();
+        for chip in &self.chips {
+            let _ = temps.push(RawTemp::fault(chip.kind));
+        }
+        temps
+    }
+
+    /// read the next chip in round-robin order, return the channel index and its word
+    pub async fn read_next(&mut self) -> Option<(usize, RawTemp)> {
+        let idx = self.schedule.next_chip()?;
+        let Self { spi, chips, .. } = self;
+        let ThermoChip { cs, kind } = &mut chips[idx];
+        let temp = match kind {
+            ThermocoupleKind::Max6675 => {
+                let mut buf = [0u8; 2];
+                cs.set_low();
+                let res = spi.read(&mut buf).await;
+                cs.set_high();
+                res.ok().map(|_| RawTemp::max6675(u16::from_be_bytes(buf)))
+            }
+            ThermocoupleKind::Max31855 => {
+                let mut buf = [0u8; 4];
+                cs.set_low();
+                let res = spi.read(&mut buf).await;
+                cs.set_high();
+                res.ok().map(|_| RawTemp::max31855(u32::from_be_bytes(buf)))
+            }
+        };
+        Some((idx, temp.unwrap_or(RawTemp::fault(*kind))))
+    }
+
+    #[inline]
+    fn config() -> spi::Config {
+        let mut cfg = spi::Config::default();
+
+        cfg.mode = MODE_0;
+        cfg.bit_order = BitOrder::MsbFirst;
+        cfg.frequency = mhz(4);
+        cfg
+    }
+}
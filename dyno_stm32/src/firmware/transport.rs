@@ -0,0 +1,90 @@
+use dyno_core::types::MAX_FRAME_SIZE;
+
+/// byte link to the desktop app (USART bridge or native USB), framing is done in [`Framed`]
+pub trait Transport {
+    type Error: defmt::Format;
+
+    /// read available bytes into `buf`, return the length of bytes readed. must be cancel safe,
+    /// the main loop drop the read when the next sample is due
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// write all of `bytes` to the link
+    async fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+const BUF_SIZE: usize = MAX_FRAME_SIZE * 2;
+
+/// postcard COBS framed protocol on top of a [`Transport`]
+pub struct Framed<T> {
+    transport: T,
+    buffer: [u8; BUF_SIZE],
+    len: usize,
+    tx_buffer: [u8; BUF_SIZE],
+}
+
+impl<T: Transport> Framed<T> {
+    pub const fn new(transport: T) -> Self {
+        Self {
+            transport,
+            buffer: [0u8; BUF_SIZE],
+            len: 0,
+            tx_buffer: [0u8; BUF_SIZE],
+        }
+    }
+
+    /// wait for one COBS frame (delimited by `0x00`) and deserialize it
+    pub async fn read_deserialize<V>(&mut self) -> V
+    where
+        V: for<'de> dyno_core::serde::Deserialize<'de>,
+    {
+        loop {
+            if let Some(end) = self.buffer[..self.len].iter().position(|&b| b == 0) {
+                let frame_len = end + 1;
+                let value = postcard::from_bytes_cobs(&mut self.buffer[..frame_len]);
+                self.buffer.copy_within(frame_len..self.len, 0);
+                self.len -= frame_len;
+                match value {
+                    Ok(value) => return value,
+                    Err(err) => {
+                        defmt::warn!("dropping invalid frame: {}", err);
+                        continue;
+                    }
+                }
+            }
+            if self.len == BUF_SIZE {
+                defmt::warn!("frame is too long, dropping {} bytes", self.len);
+                self.len = 0;
+            }
+            let Self {
+                transport,
+                buffer,
+                len,
+                ..
+            } = self;
+            match transport.read(&mut buffer[*len..]).await {
+                Ok(read) => *len += read,
+                // bytes is lost (e.g. rx overrun), the partial frame can not be decoded anymore
+                Err(err) => {
+                    defmt::warn!("failed reading link, dropping {} bytes: {}", *len, err);
+                    *len = 0;
+                }
+            }
+        }
+    }
+
+    pub async fn write_serialize<V>(&mut self, value: &V)
+    where
+        V: dyno_core::serde::Serialize,
+    {
+        let Self {
+            transport,
+            tx_buffer,
+            ..
+        } = self;
+        let used_buffer = unwrap!(postcard::to_slice_cobs(value, tx_buffer));
+        // the frame is lost, the desktop app retry the command or wait for the next data
+        if let Err(err) = transport.write_all(used_buffer).await {
+            defmt::warn!("failed writing frame: {}", err);
+        }
+    }
+}
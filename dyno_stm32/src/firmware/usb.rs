@@ -0,0 +1,142 @@
+use dyno_core::types::{USB_MANUFACTURER, USB_PID, USB_PRODUCT, USB_VID};
+use embassy_stm32::{bind_interrupts, peripherals, uid, usb_otg};
+use embassy_usb::{
+    class::cdc_acm::{self, CdcAcmClass},
+    driver::EndpointError,
+    Builder, UsbDevice,
+};
+use static_cell::StaticCell;
+
+use super::transport::Transport;
+
+pub type UsbInstance = peripherals::USB_OTG_FS;
+pub type UsbDp = peripherals::PA12;
+pub type UsbDm = peripherals::PA11;
+
+type UsbDriver = usb_otg::Driver<'static, UsbInstance>;
+
+bind_interrupts!(struct Irqs {
+    OTG_FS => usb_otg::InterruptHandler<peripherals::USB_OTG_FS>;
+});
+
+const MAX_PACKET_SIZE: u16 = 64;
+
+/// native USB CDC-ACM device, enumerated with [`USB_VID`]:[`USB_PID`] and the chip unique id as
+/// serial number, so the desktop can tell several boards apart
+pub struct UsbSerial {
+    class: CdcAcmClass<'static, UsbDriver>,
+    // `read_packet` need buffer of full packet size, keep the rest for the next `read`
+    packet: [u8; MAX_PACKET_SIZE as usize],
+    packet_pos: usize,
+    packet_len: usize,
+}
+
+impl UsbSerial {
+    /// setup the usb device, the device itself must be run with [`usb_task`]
+    pub fn new(peri: UsbInstance, dp: UsbDp, dm: UsbDm) -> (Self, UsbDevice<'static, UsbDriver>) {
+        static EP_OUT_BUFFER: StaticCell<[u8; 256]> = StaticCell::new();
+        static DEVICE_DESCRIPTOR: StaticCell<[u8; 256]> = StaticCell::new();
+        static CONFIG_DESCRIPTOR: StaticCell<[u8; 256]> = StaticCell::new();
+        static BOS_DESCRIPTOR: StaticCell<[u8; 256]> = StaticCell::new();
+        static CONTROL_BUF: StaticCell<[u8; 64]> = StaticCell::new();
+        static STATE: StaticCell<cdc_acm::State> = StaticCell::new();
+
+        let mut driver_config = usb_otg::Config::default();
+        driver_config.vbus_detection = false;
+        let driver = usb_otg::Driver::new_fs(
+            peri,
+            Irqs,
+            dp,
+            dm,
+            EP_OUT_BUFFER.init([0; 256]),
+            driver_config,
+        );
+
+        let mut config = embassy_usb::Config::new(USB_VID, USB_PID);
+        config.manufacturer = Some(USB_MANUFACTURER);
+        config.product = Some(USB_PRODUCT);
+        config.serial_number = Some(uid::uid_hex());
+        config.max_power = 100;
+        config.max_packet_size_0 = 64;
+        // required for windows to bind the cdc-acm driver (IAD)
+        config.device_class = 0xEF;
+        config.device_sub_class = 0x02;
+        config.device_protocol = 0x01;
+        config.composite_with_iads = true;
+
+        let mut builder = Builder::new(
+            driver,
+            config,
+            DEVICE_DESCRIPTOR.init([0; 256]),
+            CONFIG_DESCRIPTOR.init([0; 256]),
+            BOS_DESCRIPTOR.init([0; 256]),
+            &mut [], // no msos descriptors
+            CONTROL_BUF.init([0; 64]),
+        );
+        let class = CdcAcmClass::new(
+            &mut builder,
+            STATE.init(cdc_acm::State::new()),
+            MAX_PACKET_SIZE,
+        );
+        let this = Self {
+            class,
+            packet: [0; MAX_PACKET_SIZE as usize],
+            packet_pos: 0,
+            packet_len: 0,
+        };
+        (this, builder.build())
+    }
+
+    async fn write_packets(&mut self, bytes: &[u8]) -> Result<(), EndpointError> {
+        let max = MAX_PACKET_SIZE as usize;
+        for chunk in bytes.chunks(max) {
+            self.class.write_packet(chunk).await?;
+        }
+        // full sized last packet need zero length packet to end the transfer
+        if bytes.len() % max == 0 {
+            self.class.write_packet(&[]).await?;
+        }
+        Ok(())
+    }
+}
+
+#[embassy_executor::task]
+pub async fn usb_task(mut device: UsbDevice<'static, UsbDriver>) -> ! {
+    device.run().await
+}
+
+impl Transport for UsbSerial {
+    type Error = EndpointError;
+
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        while self.packet_pos == self.packet_len {
+            self.class.wait_connection().await;
+            match self.class.read_packet(&mut self.packet).await {
+                Ok(len) => {
+                    self.packet_pos = 0;
+                    self.packet_len = len;
+                }
+                // host closed the port, wait for the next connection
+                Err(EndpointError::Disabled) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        let len = buf.len().min(self.packet_len - self.packet_pos);
+        buf[..len].copy_from_slice(&self.packet[self.packet_pos..self.packet_pos + len]);
+        self.packet_pos += len;
+        Ok(len)
+    }
+
+    async fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        match self.write_packets(bytes).await {
+            // host closed or unplugged the port, nobody is reading the frame so drop it and wait
+            // for the next connection
+            Err(EndpointError::Disabled) => {
+                defmt::info!("usb port is closed, waiting for the host");
+                self.class.wait_connection().await;
+                Ok(())
+            }
+            result => result,
+        }
+    }
+}
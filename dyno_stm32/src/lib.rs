@@ -0,0 +1,4 @@
+//! platform independent part of the firmware, tested on the host with `cargo test`
+#![cfg_attr(not(test), no_std)]
+
+pub mod sampling;
@@ -0,0 +1,388 @@
+//! sampling logic of the firmware, the embassy tasks only feed the edges, thermocouple words
+//! and the clock into it
+
+use core::{
+    cell::Cell,
+    sync::atomic::{AtomicU16, Ordering::Relaxed},
+};
+
+use critical_section::Mutex;
+use dyno_core::types::{RawSerialData, RawTemp, RawTemps};
+
+/// every thermocouple chip is readed once in this period, MAX6675 need at least 220ms per
+/// conversion
+pub const THERMO_PERIOD_MS: u64 = 500;
+
+/// pulse counters and the latest thermocouple words, shared between the input tasks and the
+/// sampling loop
+pub struct Counters {
+    encoder_pulse: AtomicU16,
+    rpm_pulse: AtomicU16,
+
+    temps: Mutex<Cell<RawTemps>>,
+}
+
+impl Counters {
+    pub const fn new() -> Self {
+        Self {
+            encoder_pulse: AtomicU16::new(0),
+            rpm_pulse: AtomicU16::new(0),
+            temps: Mutex::new(Cell::new(RawTemps::new())),
+        }
+    }
+
+    /// count one edge of the roller encoder
+    #[inline]
+    pub fn incr_encoder(&self) {
+        self.encoder_pulse.fetch_add(1, Relaxed);
+    }
+
+    /// count one edge of the RPM pickup
+    #[inline]
+    pub fn incr_rpm(&self) {
+        self.rpm_pulse.fetch_add(1, Relaxed);
+    }
+
+    #[inline]
+    pub fn take_encoder(&self) -> u16 {
+        self.encoder_pulse.swap(0, Relaxed)
+    }
+
+    #[inline]
+    pub fn take_rpm(&self) -> u16 {
+        self.rpm_pulse.swap(0, Relaxed)
+    }
+
+    #[inline]
+    pub fn init_temps(&self, temps: RawTemps) {
+        critical_section::with(|cs| self.temps.borrow(cs).set(temps))
+    }
+
+    #[inline]
+    pub fn set_temp(&self, idx: usize, temp: RawTemp) {
+        critical_section::with(|cs| {
+            let cell = self.temps.borrow(cs);
+            let mut temps = cell.get();
+            temps.set(idx, temp);
+            cell.set(temps);
+        })
+    }
+
+    #[inline]
+    pub fn get_temps(&self) -> RawTemps {
+        critical_section::with(|cs| self.temps.borrow(cs).get())
+    }
+
+    /// take the pulses counted since the last frame and the latest temperatures
+    pub fn take_frame(&self) -> RawSerialData {
+        RawSerialData {
+            pulse_rpm: self.take_rpm(),
+            pulse_enc: self.take_encoder(),
+            raw_temps: self.get_temps(),
+        }
+    }
+}
+
+impl Default for Counters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// cadence of the data frames, the deadlines is aligned to the start so the frames is not
+/// drifting when the loop is polled late
+pub struct Sampler {
+    period_us: u64,
+    deadline_us: u64,
+}
+
+impl Sampler {
+    pub const fn new(now_us: u64, period_ms: u16) -> Self {
+        let period_us = period_ms as u64 * 1000;
+        Self {
+            period_us,
+            deadline_us: now_us + period_us,
+        }
+    }
+
+    #[inline]
+    pub const fn deadline_us(&self) -> u64 {
+        self.deadline_us
+    }
+
+    /// take the frame if the deadline is passed. when late more than one period the missed
+    /// deadlines is skipped, so the frame contains the pulses of the missed periods too
+    pub fn poll(&mut self, now_us: u64, counters: &Counters) -> Option<RawSerialData> {
+        if now_us < self.deadline_us {
+            return None;
+        }
+        let missed = (now_us - self.deadline_us) / self.period_us;
+        self.deadline_us += (missed + 1) * self.period_us;
+        Some(counters.take_frame())
+    }
+}
+
+/// round-robin schedule of the thermocouple chips, one chip per slot
+pub struct ThermoSchedule {
+    chips: usize,
+    next: usize,
+}
+
+impl ThermoSchedule {
+    pub const fn new(chips: usize) -> Self {
+        Self { chips, next: 0 }
+    }
+
+    /// length of one slot, so every chip is readed once in [`THERMO_PERIOD_MS`]
+    #[inline]
+    pub const fn slot_ms(&self) -> u64 {
+        match self.chips {
+            0 => THERMO_PERIOD_MS,
+            n => THERMO_PERIOD_MS / n as u64,
+        }
+    }
+
+    /// chip to read in the next slot, `None` if there is no chip
+    pub fn next_chip(&mut self) -> Option<usize> {
+        if self.chips == 0 {
+            return None;
+        }
+        let idx = self.next;
+        self.next = (self.next + 1) % self.chips;
+        Some(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyno_core::types::ThermocoupleKind;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Edge {
+        Encoder,
+        Rpm,
+    }
+
+    /// simulated clock that run [`Counters`], [`Sampler`] and [`ThermoSchedule`] the same way
+    /// the firmware tasks do. events at the same time is run in order: edges, thermocouple read,
+    /// then the sampler poll, so edge at the deadline is counted in that frame
+    struct Harness<W> {
+        counters: Counters,
+        sampler: Sampler,
+        thermo: ThermoSchedule,
+        thermo_deadline_us: u64,
+        /// thermocouple word of the chip at the time
+        words: W,
+        /// extra delay of polling the sampler after the deadline
+        poll_latency_us: u64,
+        frames: Vec<(u64, RawSerialData)>,
+    }
+
+    impl<W: FnMut(usize, u64) -> RawTemp> Harness<W> {
+        fn new(period_ms: u16, kinds: &[ThermocoupleKind], words: W) -> Self {
+            let counters = Counters::new();
+            let mut temps = RawTemps::new();
+            for kind in kinds {
+                temps.push(RawTemp::fault(*kind)).unwrap();
+            }
+            counters.init_temps(temps);
+            let thermo = ThermoSchedule::new(kinds.len());
+            Self {
+                counters,
+                sampler: Sampler::new(0, period_ms),
+                thermo_deadline_us: thermo.slot_ms() * 1000,
+                thermo,
+                words,
+                poll_latency_us: 0,
+                frames: Vec::new(),
+            }
+        }
+
+        fn run(&mut self, edges: &[(u64, Edge)], until_us: u64) {
+            let mut edges = edges.iter().peekable();
+            loop {
+                let edge_us = edges.peek().map_or(u64::MAX, |(t, _)| *t);
+                let poll_us = self.sampler.deadline_us() + self.poll_latency_us;
+                let next_us = edge_us.min(poll_us).min(self.thermo_deadline_us);
+                if next_us > until_us {
+                    break;
+                }
+                if edge_us == next_us {
+                    match edges.next().unwrap().1 {
+                        Edge::Encoder => self.counters.incr_encoder(),
+                        Edge::Rpm => self.counters.incr_rpm(),
+                    }
+                } else if self.thermo_deadline_us == next_us {
+                    if let Some(chip) = self.thermo.next_chip() {
+                        self.counters.set_temp(chip, (self.words)(chip, next_us));
+                    }
+                    self.thermo_deadline_us += self.thermo.slot_ms() * 1000;
+                } else {
+                    let deadline = self.sampler.deadline_us();
+                    let frame = self.sampler.poll(next_us, &self.counters).unwrap();
+                    self.frames.push((deadline, frame));
+                }
+            }
+        }
+    }
+
+    fn no_temp(_: usize, _: u64) -> RawTemp {
+        RawTemp::max6675(0)
+    }
+
+    /// edges every `interval_us` from `start_us` until `end_us`
+    fn periodic(edge: Edge, start_us: u64, interval_us: u64, end_us: u64) -> Vec<(u64, Edge)> {
+        (start_us..=end_us)
+            .step_by(interval_us as usize)
+            .map(|t| (t, edge))
+            .collect()
+    }
+
+    fn merge(mut a: Vec<(u64, Edge)>, b: Vec<(u64, Edge)>) -> Vec<(u64, Edge)> {
+        a.extend(b);
+        a.sort_by_key(|(t, _)| *t);
+        a
+    }
+
+    /// hand written trace in the format of the logic analyzer export, `time_us,channel` per
+    /// line, the edges is placed on and around the period boundaries
+    const RECORDED_TRACE: &str = "\
+        1200,E\n 9800,E\n 20500,R\n 31000,E\n 62000,E\n 99000,R\n \
+        250000,E\n 250001,R\n 260000,E\n 499999,E\n 500000,R\n 500001,E\n";
+
+    fn parse_trace(trace: &str) -> Vec<(u64, Edge)> {
+        trace
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (time, channel) = line.split_once(',').unwrap();
+                let edge = match channel {
+                    "E" => Edge::Encoder,
+                    "R" => Edge::Rpm,
+                    other => panic!("unknown channel {other}"),
+                };
+                (time.parse().unwrap(), edge)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_counts_per_period() {
+        // encoder 4kHz, rpm 1kHz, 250ms period
+        let edges = merge(
+            periodic(Edge::Encoder, 250, 250, 1_000_000),
+            periodic(Edge::Rpm, 1000, 1000, 1_000_000),
+        );
+        let mut harness = Harness::new(250, &[], no_temp);
+        harness.run(&edges, 1_000_000);
+
+        let frames: Vec<_> = harness.frames.iter().map(|(_, f)| f).collect();
+        assert_eq!(frames.len(), 4);
+        for frame in frames {
+            assert_eq!(frame.pulse_enc, 1000, "{frame:?}");
+            assert_eq!(frame.pulse_rpm, 250, "{frame:?}");
+        }
+    }
+
+    #[test]
+    fn test_recorded_trace() {
+        let edges = parse_trace(RECORDED_TRACE);
+        let mut harness = Harness::new(250, &[], no_temp);
+        harness.run(&edges, 750_000);
+
+        let counts: Vec<_> = harness
+            .frames
+            .iter()
+            .map(|(t, f)| (*t, f.pulse_enc, f.pulse_rpm))
+            .collect();
+        assert_eq!(counts, [(250_000, 5, 2), (500_000, 2, 2), (750_000, 1, 0),]);
+    }
+
+    #[test]
+    fn test_period_alignment() {
+        let edges = periodic(Edge::Encoder, 1000, 1000, 2_010_000);
+        let mut harness = Harness::new(500, &[], no_temp);
+        // polled late, but not more than one period
+        harness.poll_latency_us = 3_000;
+        harness.run(&edges, 2_010_000);
+
+        let deadlines: Vec<_> = harness.frames.iter().map(|(t, _)| *t).collect();
+        assert_eq!(deadlines, [500_000, 1_000_000, 1_500_000, 2_000_000]);
+        let total: u32 = harness.frames.iter().map(|(_, f)| f.pulse_enc as u32).sum();
+        // the edges between the last deadline and its late poll is in the last frame
+        assert_eq!(total, 2003);
+
+        // late more than one period, the missed deadlines is skipped and stay on the grid
+        let counters = Counters::new();
+        let mut sampler = Sampler::new(0, 250);
+        assert!(sampler.poll(249_999, &counters).is_none());
+        assert!(sampler.poll(800_000, &counters).is_some());
+        assert_eq!(sampler.deadline_us(), 1_000_000);
+    }
+
+    #[test]
+    fn test_counter_overflow() {
+        // 65536 + 100 pulses in the first period, the counter wraps at `u16::MAX`
+        let edges = periodic(Edge::Encoder, 1, 3, 1 + 3 * (65_536 + 100 - 1));
+        assert_eq!(edges.len(), 65_636);
+        let mut harness = Harness::new(250, &[], no_temp);
+        harness.run(&edges, 500_000);
+
+        let counts: Vec<_> = harness.frames.iter().map(|(_, f)| f.pulse_enc).collect();
+        assert_eq!(counts, [100, 0]);
+        assert!(harness.frames.iter().all(|(_, f)| f.pulse_rpm == 0));
+    }
+
+    #[test]
+    fn test_temperature_cadence() {
+        let kinds = [
+            ThermocoupleKind::Max6675,
+            ThermocoupleKind::Max31855,
+            ThermocoupleKind::Max6675,
+        ];
+        let mut reads: Vec<(usize, u64)> = Vec::new();
+        let words = |chip: usize, t: u64| {
+            reads.push((chip, t));
+            // the word is the time in ms, so the frame show when it is readed
+            match kinds[chip] {
+                ThermocoupleKind::Max6675 => RawTemp::max6675((t / 1000) as u16),
+                ThermocoupleKind::Max31855 => RawTemp::max31855((t / 1000) as u32),
+            }
+        };
+        let mut harness = Harness::new(250, &kinds, words);
+        harness.run(&[], 5_000_000);
+
+        let frame = |t: u64| harness.frames.iter().find(|(d, _)| *d == t).unwrap().1;
+        let raw = |t: u64| -> Vec<u32> { frame(t).raw_temps.iter().map(|x| x.raw).collect() };
+        // the channel is fault until it is readed for the first time
+        let fault = |kind| RawTemp::fault(kind).raw;
+        assert_eq!(
+            raw(250_000),
+            [
+                166,
+                fault(ThermocoupleKind::Max31855),
+                fault(ThermocoupleKind::Max6675)
+            ]
+        );
+        // slot is 166ms: chip 0 at 166, chip 1 at 332, chip 2 at 498, chip 0 at 664, ...
+        assert_eq!(raw(500_000), [166, 332, 498]);
+        assert_eq!(raw(750_000), [664, 332, 498]);
+        assert_eq!(raw(1_000_000), [664, 830, 996]);
+        drop(harness);
+
+        // every chip is readed once every `THERMO_PERIOD_MS` (rounded down to the slot)
+        for chip in 0..kinds.len() {
+            let times: Vec<_> = reads
+                .iter()
+                .filter(|(c, _)| *c == chip)
+                .map(|(_, t)| t)
+                .collect();
+            assert!(times.len() >= 8, "{times:?}");
+            for pair in times.windows(2) {
+                assert_eq!(pair[1] - pair[0], 498_000);
+            }
+        }
+    }
+}
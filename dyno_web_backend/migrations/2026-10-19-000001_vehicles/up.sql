@@ -0,0 +1,20 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS vehicles (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    make TEXT NOT NULL,
+    model TEXT NOT NULL,
+    plate_number TEXT NOT NULL,
+    customer TEXT NOT NULL,
+    phone TEXT NOT NULL,
+    notes TEXT NOT NULL,
+    cc SMALLINT NOT NULL,
+    motor_kind SMALLINT NOT NULL,
+    cylinder SMALLINT NOT NULL,
+    stroke SMALLINT NOT NULL,
+    transmition SMALLINT NOT NULL,
+    deleted BOOLEAN NOT NULL DEFAULT 0,
+    updated_at DATETIME NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -29,6 +29,24 @@ pub fn is_exists(conn: &mut DynoDBPooledConnection, id: i64, user_id: i64) -> Dy
         .map(|x| x.is_some())
 }
 
+/// id of the dyno of the user with the same data checksum, the desktop app retry the upload
+/// when the response is lost
+#[inline]
+#[allow(unused)]
+pub fn select_id_by_checksum(
+    conn: &mut DynoDBPooledConnection,
+    user_id: i64,
+    checksum: &str,
+) -> DynoResult<Option<i64>> {
+    use crate::schema::dynos::dsl;
+    dsl::dynos
+        .filter(dsl::user_id.eq(user_id).and(dsl::data_checksum.eq(checksum)))
+        .select(dsl::id)
+        .first::<i64>(conn)
+        .optional()
+        .map_err(DynoErr::database_error)
+}
+
 #[inline]
 #[allow(unused)]
 pub fn select(conn: &mut DynoDBPooledConnection, id: i64, user_id: i64) -> DynoResult<Dynos> {
@@ -2,6 +2,8 @@ pub mod dyno;
 pub mod info;
 pub mod user;
 pub mod history;
+pub mod refresh_token;
+pub mod vehicle;
 
 macro_rules! query_one {
     (FIND $table:ident WHERE ($filter:expr) as $types:ty [$conn:expr]) => {{
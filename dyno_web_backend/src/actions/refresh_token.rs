@@ -0,0 +1,86 @@
+use crate::schema::refresh_tokens::dsl;
+use crate::{models::refresh_token::NewRefreshToken, DynoDBPooledConnection};
+use diesel::prelude::*;
+use dyno_core::{chrono::Utc, DynoErr, DynoResult};
+
+#[inline]
+#[allow(unused)]
+pub fn insert(conn: &mut DynoDBPooledConnection, new: NewRefreshToken) -> DynoResult<i64> {
+    diesel::insert_into(dsl::refresh_tokens)
+        .values(new)
+        .returning(dsl::id)
+        .get_result::<i64>(conn)
+        .map_err(DynoErr::database_error)
+}
+
+/// refresh token removed by [`take`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TakenToken {
+    /// not used yet and not expired, the id of the user
+    Valid(i64),
+    /// not used yet but expired, the id of the user
+    Expired(i64),
+    /// already used or revoked
+    Missing,
+}
+
+/// remove the refresh token, the token is never accepted again after this
+#[inline]
+#[allow(unused)]
+pub fn take(conn: &mut DynoDBPooledConnection, token_uuid: &str) -> DynoResult<TakenToken> {
+    let now = Utc::now().naive_utc();
+    diesel::delete(dsl::refresh_tokens.filter(dsl::token_uuid.eq(token_uuid)))
+        .returning((dsl::user_id, dsl::expires_at))
+        .get_result::<(i64, dyno_core::chrono::NaiveDateTime)>(conn)
+        .optional()
+        .map(|token| match token {
+            Some((user_id, expires_at)) if expires_at > now => TakenToken::Valid(user_id),
+            Some((user_id, _)) => TakenToken::Expired(user_id),
+            None => TakenToken::Missing,
+        })
+        .map_err(DynoErr::database_error)
+}
+
+#[inline]
+#[allow(unused)]
+pub fn revoke_user(conn: &mut DynoDBPooledConnection, user_id: i64) -> DynoResult<usize> {
+    diesel::delete(dsl::refresh_tokens.filter(dsl::user_id.eq(user_id)))
+        .execute(conn)
+        .map_err(DynoErr::database_error)
+}
+
+/// delete the expired refresh tokens, the tokens is never used again after the expiry
+#[inline]
+#[allow(unused)]
+pub fn prune_expired(conn: &mut DynoDBPooledConnection) -> DynoResult<usize> {
+    diesel::delete(dsl::refresh_tokens.filter(dsl::expires_at.le(Utc::now().naive_utc())))
+        .execute(conn)
+        .map_err(DynoErr::database_error)
+}
+
+#[cfg(all(test, feature = "db_sqlite"))]
+mod tests {
+    use super::*;
+    use crate::{DynoDBConnManager, DynoDBPool};
+
+    #[test]
+    fn refresh_token_take() {
+        let pool = DynoDBPool::builder()
+            .max_size(1)
+            .build(DynoDBConnManager::new(":memory:"))
+            .unwrap();
+        let mut conn = pool.get().unwrap();
+        diesel::sql_query(include_str!(
+            "../../migrations/2026-10-19-000000_refresh_tokens/up.sql"
+        ))
+        .execute(&mut conn)
+        .unwrap();
+
+        insert(&mut conn, NewRefreshToken::new("valid".to_owned(), 1, 10)).unwrap();
+        insert(&mut conn, NewRefreshToken::new("expired".to_owned(), 1, -10)).unwrap();
+        assert_eq!(take(&mut conn, "valid").unwrap(), TakenToken::Valid(1));
+        assert_eq!(take(&mut conn, "valid").unwrap(), TakenToken::Missing);
+        assert_eq!(take(&mut conn, "expired").unwrap(), TakenToken::Expired(1));
+        assert_eq!(take(&mut conn, "unknown").unwrap(), TakenToken::Missing);
+    }
+}
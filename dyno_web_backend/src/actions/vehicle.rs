@@ -0,0 +1,109 @@
+use crate::schema::vehicles::dsl;
+use crate::{
+    models::vehicle::{NewVehicle, VehicleRecord},
+    DynoDBPooledConnection,
+};
+use diesel::prelude::*;
+use dyno_core::{chrono::NaiveDateTime, model::vehicle::Vehicle, DynoErr, DynoResult};
+
+/// save the `changed` vehicles of the user, the record with the newer `updated_at` is kept.
+/// vehicle with the uuid owned by other user is ignored.
+/// return every vehicle of the user, the deleted one included so the deletion reach the other
+/// desktop
+#[allow(unused)]
+pub fn sync(
+    conn: &mut DynoDBPooledConnection,
+    user_id: i64,
+    changed: Vec<Vehicle>,
+) -> DynoResult<Vec<Vehicle>> {
+    conn.transaction(|conn| {
+        for vehicle in changed {
+            let new = NewVehicle::from_vehicle(user_id, vehicle);
+            let current = dsl::vehicles
+                .filter(dsl::uuid.eq(&new.uuid))
+                .select((dsl::user_id, dsl::updated_at))
+                .first::<(i64, NaiveDateTime)>(conn)
+                .optional()?;
+            match current {
+                Some((owner, _)) if owner != user_id => {}
+                Some((_, updated_at)) if updated_at >= new.updated_at => {}
+                Some(_) => {
+                    diesel::update(
+                        dsl::vehicles
+                            .filter(dsl::uuid.eq(&new.uuid))
+                            .filter(dsl::user_id.eq(user_id)),
+                    )
+                    .set(&new)
+                    .execute(conn)?;
+                }
+                None => {
+                    diesel::insert_into(dsl::vehicles)
+                        .values(&new)
+                        .execute(conn)?;
+                }
+            }
+        }
+        dsl::vehicles
+            .filter(dsl::user_id.eq(user_id))
+            .select(VehicleRecord::as_select())
+            .get_results(conn)
+    })
+    .map(|records| {
+        records
+            .into_iter()
+            .map(VehicleRecord::into_response)
+            .collect()
+    })
+    .map_err(DynoErr::database_error)
+}
+
+#[cfg(all(test, feature = "db_sqlite"))]
+mod tests {
+    use super::*;
+    use crate::{DynoDBConnManager, DynoDBPool};
+    use dyno_core::chrono::{Duration, SubsecRound, Utc};
+
+    fn vehicle(uuid: &str, customer: &str, age: i64) -> Vehicle {
+        Vehicle {
+            uuid: uuid.to_owned(),
+            make: "Honda".to_owned(),
+            customer: customer.to_owned(),
+            updated_at: Utc::now().trunc_subsecs(0) - Duration::minutes(age),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn vehicle_sync_keep_newer() {
+        let pool = DynoDBPool::builder()
+            .max_size(1)
+            .build(DynoDBConnManager::new(":memory:"))
+            .unwrap();
+        let mut conn = pool.get().unwrap();
+        diesel::sql_query(include_str!(
+            "../../migrations/2026-10-19-000001_vehicles/up.sql"
+        ))
+        .execute(&mut conn)
+        .unwrap();
+
+        let first = vec![vehicle("a", "Budi", 10), vehicle("b", "Sari", 10)];
+        let all = sync(&mut conn, 1, first.clone()).unwrap();
+        assert_eq!(all, first);
+
+        // the older change from other desktop is ignored, the newer one replace the record
+        let older = vehicle("a", "Old", 20);
+        let mut deleted = vehicle("b", "Sari", 0);
+        deleted.deleted = true;
+        let all = sync(&mut conn, 1, vec![older, deleted.clone()]).unwrap();
+        assert_eq!(all, vec![first[0].clone(), deleted.clone()]);
+
+        // other user never see nor change the vehicles of the user
+        let other = vehicle("c", "Joko", 10);
+        let mut stolen = vehicle("a", "Stolen", 0);
+        stolen.deleted = true;
+        let all = sync(&mut conn, 2, vec![stolen, other.clone()]).unwrap();
+        assert_eq!(all, vec![other]);
+        let all = sync(&mut conn, 1, vec![]).unwrap();
+        assert_eq!(all, vec![first[0].clone(), deleted]);
+    }
+}
@@ -1,5 +1,9 @@
-use crate::actions;
+use crate::actions::{
+    self,
+    refresh_token::{self as refresh_actions, TakenToken},
+};
 use crate::middlewares::JwtUserMiddleware;
+use crate::models::refresh_token::NewRefreshToken;
 use crate::{actions::user as user_actions, models::user::NewUser};
 use actix_web::cookie::{self, Cookie};
 use actix_web::http::header;
@@ -8,13 +12,91 @@ use actix_web::{web, HttpResponse};
 use dyno_core::crypto::TokenDetails;
 use dyno_core::DynoResult;
 use dyno_core::{
+    chrono::{Duration, Utc},
     crypto::verify_hash_password,
-    model::users::{UserLogin, UserRegistration},
+    model::{
+        self,
+        users::{UserLogin, UserRegistration},
+        RefreshRequest,
+    },
     ApiResponse, DynoErr, UserSession,
 };
 
 const RESET_AGE_DUR: cookie::time::Duration = cookie::time::Duration::new(-1, 0);
 
+/// new access token and refresh token of the user, the refresh token is valid once.
+/// the expired refresh tokens is pruned here, so on every login and refresh
+async fn issue_tokens(
+    data: &crate::ServerState,
+    user_session: UserSession,
+) -> DynoResult<model::TokenDetails> {
+    let jwt = &data.cfg.jwt;
+    let user_id = user_session.id;
+    let access = TokenDetails::generate(
+        user_session.clone(),
+        jwt.access_token_max_age,
+        jwt.access_token_private_key.as_bytes(),
+    )?;
+    let refresh = TokenDetails::generate(
+        user_session,
+        jwt.refresh_token_max_age,
+        jwt.refresh_token_private_key.as_bytes(),
+    )?;
+    let new_token = NewRefreshToken::new(
+        refresh.token_uuid.to_string(),
+        user_id,
+        jwt.refresh_token_max_age,
+    );
+    let db = data.db.clone();
+    web::block(move || {
+        let mut conn = db
+            .get()
+            .map_err(|_| DynoErr::database_error("Failed to get database connection"))?;
+        refresh_actions::prune_expired(&mut conn)?;
+        refresh_actions::insert(&mut conn, new_token)
+    })
+    .await
+    .map_err(DynoErr::internal_server_error)??;
+
+    Ok(model::TokenDetails {
+        token: access.token,
+        token_uuid: access.token_uuid.to_string(),
+        user_id,
+        expires_in: Some(
+            (Utc::now() + Duration::minutes(jwt.access_token_max_age)).timestamp_millis(),
+        ),
+        refresh_token: refresh.token,
+    })
+}
+
+/// delete every refresh token of the user, the user need to login again after the access token
+/// is expired
+async fn revoke_refresh_tokens(data: &crate::ServerState, user_id: i64) -> DynoResult<usize> {
+    let db = data.db.clone();
+    web::block(move || {
+        db.get()
+            .map_err(|_| DynoErr::database_error("Failed to get database connection"))
+            .and_then(|mut conn| refresh_actions::revoke_user(&mut conn, user_id))
+    })
+    .await
+    .map_err(DynoErr::internal_server_error)?
+}
+
+fn access_cookies(data: &crate::ServerState, token: &str) -> [Cookie<'static>; 2] {
+    let max_age = cookie::time::Duration::minutes(data.cfg.jwt.access_token_max_age);
+    let access_cookie = Cookie::build("access_token", token.to_owned())
+        .path("/")
+        .max_age(max_age)
+        .http_only(true)
+        .finish();
+    let logged_in_cookie = Cookie::build("logged_in", "true")
+        .path("/")
+        .max_age(max_age)
+        .http_only(false)
+        .finish();
+    [access_cookie, logged_in_cookie]
+}
+
 #[post("/auth/register")]
 pub async fn register_user(
     web::Json(user_registration): web::Json<UserRegistration>,
@@ -82,46 +164,76 @@ pub async fn login_user(
         uuid: user.uuid.into_inner(),
     };
 
-    let access_token_details = TokenDetails::generate(
-        user_session,
-        data.cfg.jwt.access_token_max_age,
-        data.cfg.jwt.access_token_private_key.as_bytes(),
-    )?;
-
-    let access_cookie = Cookie::build("access_token", access_token_details.token.clone().unwrap())
-        .path("/")
-        .max_age(cookie::time::Duration::minutes(
-            data.cfg.jwt.access_token_max_age,
-        ))
-        .http_only(true)
-        .finish();
-
-    let logged_in_cookie = Cookie::build("logged_in", "true")
-        .path("/")
-        .max_age(cookie::time::Duration::minutes(
-            data.cfg.jwt.access_token_max_age,
-        ))
-        .http_only(false)
-        .finish();
-
     if is_in_desktop {
-        data.change_active_user(user_session);
+        data.change_active_user(user_session.clone());
     }
 
+    let token_details = issue_tokens(&data, user_session).await?;
+    let [access_cookie, logged_in_cookie] =
+        access_cookies(&data, token_details.token.as_deref().unwrap_or_default());
+
     dyno_core::DynoResult::Ok(
         HttpResponse::Ok()
             .cookie(access_cookie)
             .cookie(logged_in_cookie)
-            .json(ApiResponse::success(access_token_details)),
+            .json(ApiResponse::success(token_details)),
     )
 }
 
+/// exchange the refresh token for new tokens. the used refresh token is rejected after this, using
+/// it again revoke every refresh token of the user because it may be stolen. expired token is only
+/// rejected, the other devices of the user stay logged in
+#[post("/auth/refresh")]
+pub async fn refresh_token(
+    web::Json(RefreshRequest { refresh_token }): web::Json<RefreshRequest>,
+    data: web::Data<crate::ServerState>,
+) -> DynoResult<HttpResponse> {
+    let details = TokenDetails::verify(
+        &refresh_token,
+        data.cfg.jwt.refresh_token_public_key.as_bytes(),
+    )
+    .map_err(|err| DynoErr::unauthorized_error(format!("Invalid Refresh Token - {err}")))?;
+    let user_session = details.user;
+    let token_uuid = details.token_uuid.to_string();
+    let db = data.db.clone();
+    let owner = web::block(move || {
+        db.get()
+            .map_err(|_| DynoErr::database_error("Failed to get database connection"))
+            .and_then(|mut conn| refresh_actions::take(&mut conn, &token_uuid))
+    })
+    .await
+    .map_err(DynoErr::internal_server_error)??;
+    match owner {
+        TakenToken::Valid(user_id) if user_id == user_session.id => {}
+        TakenToken::Expired(user_id) if user_id == user_session.id => {
+            return Err(DynoErr::unauthorized_error(
+                "Refresh Token is expired, please Login again",
+            ));
+        }
+        _ => {
+            revoke_refresh_tokens(&data, user_session.id).await?;
+            return Err(DynoErr::unauthorized_error(
+                "Refresh Token is already used or revoked, please Login again",
+            ));
+        }
+    }
+
+    let token_details = issue_tokens(&data, user_session).await?;
+    let [access_cookie, logged_in_cookie] =
+        access_cookies(&data, token_details.token.as_deref().unwrap_or_default());
+    Ok(HttpResponse::Ok()
+        .cookie(access_cookie)
+        .cookie(logged_in_cookie)
+        .json(ApiResponse::success(token_details)))
+}
+
 #[get("/auth/logout")]
 pub async fn logout_user(
-    JwtUserMiddleware(_d): JwtUserMiddleware,
+    JwtUserMiddleware(user_session): JwtUserMiddleware,
     data: web::Data<crate::ServerState>,
     req: HttpRequest,
 ) -> DynoResult<HttpResponse> {
+    revoke_refresh_tokens(&data, user_session.id).await?;
     let access_cookie = Cookie::build("access_token", "")
         .path("/")
         .max_age(RESET_AGE_DUR)
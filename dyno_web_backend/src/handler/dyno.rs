@@ -109,6 +109,10 @@ pub async fn add_dyno(
                 "Failed receive data, checksum of 'data' part stream is not the same",
             ));
         }
+        if let Some(dyno_id) = dyno_actions::select_id_by_checksum(&mut conn, id, &checksum)? {
+            dyno_core::log::debug!("Dyno with checksum {checksum} is already saved as {dyno_id}");
+            return Ok(dyno_id);
+        }
 
         let info_id = info_actions::insert(&mut conn, dyno_config.config.clone().into()).ok();
 
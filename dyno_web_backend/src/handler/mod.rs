@@ -8,6 +8,7 @@ pub mod dyno;
 pub mod history;
 pub mod info;
 pub mod user;
+pub mod vehicle;
 pub mod ws;
 
 #[inline]
@@ -20,6 +21,7 @@ pub fn api(conf: &mut ServiceConfig) {
             .service(auth::register_user)
             .service(auth::login_user)
             .service(auth::logout_user)
+            .service(auth::refresh_token)
             .service(user::get_user)
             .service(user::add_user)
             .service(user::update_user)
@@ -28,6 +30,7 @@ pub fn api(conf: &mut ServiceConfig) {
             .service(dyno::add_dyno)
             .service(history::history)
             .service(info::get_info)
+            .service(vehicle::sync_vehicle)
             .service(get_active)
             .service(post_active)
             .service(post_non_active),
@@ -0,0 +1,22 @@
+use actix_web::{post, web, HttpResponse};
+use dyno_core::{model::vehicle::VehicleSync, ApiResponse, DynoErr, DynoResult};
+
+use crate::{actions::vehicle as vehicle_actions, middlewares::JwtUserMiddleware};
+
+/// push the changed vehicles of the desktop app, response with every vehicle of the user
+#[post("/vehicle/sync")]
+pub async fn sync_vehicle(
+    web::Json(VehicleSync { changed }): web::Json<VehicleSync>,
+    JwtUserMiddleware(session): JwtUserMiddleware,
+    data: web::Data<crate::ServerState>,
+) -> DynoResult<HttpResponse> {
+    let vehicles = web::block(move || {
+        data.db
+            .get()
+            .map_err(DynoErr::database_error)
+            .and_then(|mut conn| vehicle_actions::sync(&mut conn, session.id, changed))
+    })
+    .await
+    .map_err(DynoErr::internal_server_error)??;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(vehicles)))
+}
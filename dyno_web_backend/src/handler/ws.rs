@@ -1,12 +1,16 @@
-use actix::{Actor, Addr, AsyncContext, Handler, StreamHandler};
+use actix::{Actor, ActorContext, Addr, AsyncContext, Handler, StreamHandler};
 use actix_web::{get, web, HttpRequest, HttpResponse};
 use actix_web_actors::ws;
 use dyno_core::crossbeam_channel::Sender;
 
-use crate::ServerState;
+use crate::{middlewares::JwtUserMiddleware, ServerState};
 
+/// broadcasted frame, the desktop app stream the run as JSON text
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Msg(Vec<u8>);
+pub enum Msg {
+    Text(String),
+    Binary(Vec<u8>),
+}
 
 impl actix::Message for Msg {
     type Result = ();
@@ -16,7 +20,8 @@ impl actix::Message for Msg {
 pub enum WsMessage {
     Conn(Addr<WsConn>),
     Disconn(Addr<WsConn>),
-    Msg(Msg),
+    /// message and the sender, the message is not sent back to the sender
+    Msg(Addr<WsConn>, Msg),
 }
 
 pub struct WsConn {
@@ -31,7 +36,10 @@ impl WsConn {
 impl Handler<Msg> for WsConn {
     type Result = ();
     fn handle(&mut self, msg: Msg, ctx: &mut Self::Context) -> Self::Result {
-        ctx.binary(msg.0)
+        match msg {
+            Msg::Text(text) => ctx.text(text),
+            Msg::Binary(bytes) => ctx.binary(bytes),
+        }
     }
 }
 
@@ -55,32 +63,36 @@ impl Actor for WsConn {
 
 /// Handler for ws::Message message
 impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsConn {
-    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, _ctx: &mut Self::Context) {
-        match msg {
-            Ok(ws::Message::Ping(msg)) => {
-                if let Err(err) = self.tx.send(WsMessage::Msg(Msg(msg.to_vec()))) {
-                    dyno_core::log::error!("MPSC SEND ERROR: {err}")
-                }
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        let msg = match msg {
+            Ok(ws::Message::Ping(msg)) => return ctx.pong(&msg),
+            Ok(ws::Message::Text(msg)) => Msg::Text(msg.to_string()),
+            Ok(ws::Message::Binary(msg)) => Msg::Binary(msg.to_vec()),
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                return ctx.stop();
             }
-            Ok(ws::Message::Text(msg)) => {
-                if let Err(err) = self.tx.send(WsMessage::Msg(Msg(msg.into_bytes().to_vec()))) {
-                    dyno_core::log::error!("MPSC SEND ERROR: {err}")
-                }
-            }
-            Ok(ws::Message::Binary(msg)) => {
-                if let Err(err) = self.tx.send(WsMessage::Msg(Msg(msg.to_vec()))) {
-                    dyno_core::log::error!("MPSC SEND ERROR: {err}")
-                }
-            }
-            Ok(_) => {}
-            Err(err) => dyno_core::log::error!("Websocket Error: {err}"),
+            Ok(_) => return,
+            Err(err) => return dyno_core::log::error!("Websocket Error: {err}"),
+        };
+        if let Err(err) = self.tx.send(WsMessage::Msg(ctx.address(), msg)) {
+            dyno_core::log::error!("MPSC SEND ERROR: {err}")
         }
     }
 }
 
+/// # Websocket Endpoint `websocket_endpoint`
+/// -----------------------------------------------------------------
+/// URL                 => `/ws`
+/// GUARD               => `GET`
+/// HEADER/COOKIES      => [`crate::middlewares::JwtUserMiddleware`]
+///
+/// the desktop app stream the recording here, every message is broadcasted to the other clients
+/// -----------------------------------------------------------------
 #[get("/ws")]
 pub async fn websocket_endpoint(
     req: HttpRequest,
+    JwtUserMiddleware(_session): JwtUserMiddleware,
     stream: web::Payload,
     data: web::Data<ServerState>,
 ) -> HttpResponse {
@@ -80,11 +80,10 @@ async fn main() -> DynoResult<()> {
                         log::info!("[MSG] websocket: {addr:?}");
                         clients.insert(addr);
                     }
-                    WsMessage::Msg(msg) => {
+                    WsMessage::Msg(sender, msg) => {
                         log::info!("[MSG] websocket: OnMessage");
-                        for client in &clients {
-                            let msg = msg.clone();
-                            client.do_send(msg);
+                        for client in clients.iter().filter(|client| **client != sender) {
+                            client.do_send(msg.clone());
                         }
                     }
                 }
@@ -6,9 +6,11 @@ use dyno_core::{
 pub mod dyno;
 pub mod history;
 pub mod info;
+pub mod refresh_token;
 pub mod role;
 pub mod user;
 pub mod uuid;
+pub mod vehicle;
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 #[serde(crate = "serde")]
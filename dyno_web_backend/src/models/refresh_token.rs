@@ -0,0 +1,37 @@
+use crate::schema::refresh_tokens;
+use dyno_core::chrono::{Duration, NaiveDateTime, Utc};
+
+/// refresh token that is not used yet, the token itself is not stored only the uuid of it
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, PartialEq, diesel::Queryable, diesel::Identifiable, diesel::Selectable)]
+#[diesel(table_name = refresh_tokens)]
+pub struct RefreshToken {
+    pub id: i64,
+    pub token_uuid: String,
+    pub user_id: i64,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, PartialEq, diesel::Insertable)]
+#[diesel(table_name = refresh_tokens)]
+pub struct NewRefreshToken {
+    pub token_uuid: String,
+    pub user_id: i64,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+impl NewRefreshToken {
+    /// `max_age` in minutes, the same as `refresh_token_max_age` in the config
+    pub fn new(token_uuid: String, user_id: i64, max_age: i64) -> Self {
+        let now = Utc::now().naive_utc();
+        Self {
+            token_uuid,
+            user_id,
+            expires_at: now + Duration::minutes(max_age),
+            created_at: now,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::schema::vehicles;
+use dyno_core::{
+    chrono::{NaiveDateTime, TimeZone, Utc},
+    model::vehicle::Vehicle,
+    types::{Cylinder, MotorInfo, MotorKind, Stroke, Transmition},
+};
+
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, PartialEq, diesel::Queryable, diesel::Identifiable, diesel::Selectable)]
+#[diesel(table_name = vehicles)]
+pub struct VehicleRecord {
+    pub id: i64,
+    pub uuid: String,
+    pub user_id: i64,
+    pub make: String,
+    pub model: String,
+    pub plate_number: String,
+    pub customer: String,
+    pub phone: String,
+    pub notes: String,
+    pub cc: i16,
+    pub motor_kind: i16,
+    pub cylinder: i16,
+    pub stroke: i16,
+    pub transmition: i16,
+    pub deleted: bool,
+    pub updated_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+impl VehicleRecord {
+    pub fn into_response(self) -> Vehicle {
+        Vehicle {
+            uuid: self.uuid,
+            make: self.make,
+            model: self.model,
+            plate_number: self.plate_number,
+            customer: self.customer,
+            phone: self.phone,
+            notes: self.notes,
+            motor_info: MotorInfo {
+                cc: self.cc as _,
+                kind: MotorKind::from(self.motor_kind as u8),
+                cylinder: Cylinder::from(self.cylinder as u8),
+                stroke: Stroke::from(self.stroke as u8),
+                transmition: Transmition::from(self.transmition as u8),
+            },
+            deleted: self.deleted,
+            updated_at: Utc.from_utc_datetime(&self.updated_at),
+        }
+    }
+}
+
+/// insert or replace the vehicle with the same uuid, `user_id` is the owner of the vehicle
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, PartialEq, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = vehicles)]
+pub struct NewVehicle {
+    pub uuid: String,
+    pub user_id: i64,
+    pub make: String,
+    pub model: String,
+    pub plate_number: String,
+    pub customer: String,
+    pub phone: String,
+    pub notes: String,
+    pub cc: i16,
+    pub motor_kind: i16,
+    pub cylinder: i16,
+    pub stroke: i16,
+    pub transmition: i16,
+    pub deleted: bool,
+    pub updated_at: NaiveDateTime,
+}
+
+impl NewVehicle {
+    pub fn from_vehicle(user_id: i64, vehicle: Vehicle) -> Self {
+        let motor = vehicle.motor_info;
+        Self {
+            uuid: vehicle.uuid,
+            user_id,
+            make: vehicle.make,
+            model: vehicle.model,
+            plate_number: vehicle.plate_number,
+            customer: vehicle.customer,
+            phone: vehicle.phone,
+            notes: vehicle.notes,
+            cc: motor.cc as _,
+            motor_kind: motor.kind as _,
+            cylinder: motor.cylinder as _,
+            stroke: motor.stroke as _,
+            transmition: motor.transmition as _,
+            deleted: vehicle.deleted,
+            updated_at: vehicle.updated_at.naive_utc(),
+        }
+    }
+}
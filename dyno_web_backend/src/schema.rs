@@ -45,6 +45,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    refresh_tokens (id) {
+        id -> BigInt,
+        token_uuid -> Text,
+        user_id -> BigInt,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> BigInt,
@@ -60,9 +70,33 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    vehicles (id) {
+        id -> BigInt,
+        uuid -> Text,
+        user_id -> BigInt,
+        make -> Text,
+        model -> Text,
+        plate_number -> Text,
+        customer -> Text,
+        phone -> Text,
+        notes -> Text,
+        cc -> SmallInt,
+        motor_kind -> SmallInt,
+        cylinder -> SmallInt,
+        stroke -> SmallInt,
+        transmition -> SmallInt,
+        deleted -> Bool,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     dyno_info,
     dynos,
     histories,
+    refresh_tokens,
     users,
+    vehicles,
 );
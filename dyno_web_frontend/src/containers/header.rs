@@ -1,7 +1,12 @@
-use dyno_core::{DynoErr, DynoResult};
+use dyno_core::{
+    i18n::{tr, Language},
+    DynoErr, DynoResult,
+};
 use gloo::net::http::Request;
-use web_sys::MouseEvent;
-use yew::{classes, function_component, html, use_effect_with_deps, AttrValue, Properties};
+use web_sys::{Event, HtmlInputElement, MouseEvent};
+use yew::{
+    classes, function_component, html, use_effect_with_deps, AttrValue, Properties, TargetCast,
+};
 use yew_icons::{Icon, IconId};
 use yew_router::prelude::use_navigator;
 use yewdux::prelude::use_store;
@@ -48,6 +53,14 @@ pub fn header(
         state.swap_theme();
     });
 
+    let language = state.language();
+    let onchange_language = dispatech.reduce_mut_callback_with(move |state, e: Event| {
+        let select = e.target_unchecked_into::<HtmlInputElement>();
+        if let Some(language) = Language::from_code(&select.value()) {
+            state.set_language(language);
+        }
+    });
+
     let onlogout = {
         let navigator = use_navigator();
         let notification = use_notification::<Notification>();
@@ -83,6 +96,11 @@ pub fn header(
                 <h1 class="text-2xl font-semibold ml-2">{title.clone()}</h1>
             </div>
             <div class="order-last">
+                <select class="select select-ghost select-sm mr-2" onchange={onchange_language}>
+                    {for Language::ALL.into_iter().map(|lang| html! {
+                        <option value={lang.code()} selected={lang == language}>{lang.name()}</option>
+                    })}
+                </select>
                 <label class="swap">
                     <input class="hidden" type="checkbox" onclick={onchange_theme}/>
                     <Icon icon_id={IconId::HeroiconsOutlineSun}
@@ -110,12 +128,12 @@ pub fn header(
                         </li>
                         <div class="divider mt-0 mb-0"></div>
                         <li class="justify-between">
-                            <LinkTag to={Route::SettingProfile}> {"Profile Settings"}</LinkTag>
+                            <LinkTag to={Route::SettingProfile}> {tr("header-profile-settings")}</LinkTag>
                         </li>
                         <div class="divider mt-0 mb-0"></div>
                         <li>
                             <a onclick={onlogout}>
-                            {"Logout"}
+                            {tr("header-logout")}
                             </a>
                         </li>
                     </ul>
@@ -4,9 +4,10 @@ use yew::{
     Children, Html, Properties,
 };
 use yew_router::prelude::use_route;
+use yewdux::prelude::use_store;
 
 use super::{header::Header, sidebar::Sidebar};
-use crate::route::Route;
+use crate::{route::Route, state::AppState};
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct LayoutProps {
@@ -16,6 +17,10 @@ pub struct LayoutProps {
 
 #[function_component(Layout)]
 pub fn layout(props: &LayoutProps) -> Html {
+    // the language is persisted in the local storage, applied before the children rendered
+    let (state, _) = use_store::<AppState>();
+    dyno_core::i18n::set_language(state.language());
+
     let title = use_state(String::default);
 
     let clicked_ref = use_node_ref();
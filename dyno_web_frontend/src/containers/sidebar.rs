@@ -1,3 +1,4 @@
+use dyno_core::i18n::tr;
 use yew::prelude::*;
 use yew_icons::{Icon, IconId};
 use yew_router::{hooks::use_location, Routable};
@@ -46,7 +47,7 @@ pub fn sidebar(props: &SideBarProps) -> Html {
                     html!{
                     <li class="" key={idx}>
                         if let Some(ref submenu) = route.submenu {
-                            <LeftSidebarSub icon={route.icon.clone()} name={route.name} submenu={submenu.clone()} location={location}/>
+                            <LeftSidebarSub icon={route.icon.clone()} name={tr(route.name)} submenu={submenu.clone()} location={location}/>
                         } else {
                             <NavLink to={route.route.unwrap()} routes={route.clone()} selected={location == route.route.unwrap()}/>
                         }
@@ -75,7 +76,7 @@ fn navlink(props: &NavLinkProps) -> Html {
             to={props.to}
             classes={classes!(if props.selected { "font-semibold  bg-base-200" } else { "font-normal" })}
         >
-            {props.routes.icon.clone()}{props.routes.name}
+            {props.routes.icon.clone()}{tr(props.routes.name)}
             if props.selected {
                 <span class="absolute inset-y-0 left-0 w-1 rounded-tr-md rounded-br-md bg-primary" aria-hidden="true"></span>
             } else {
@@ -106,7 +107,7 @@ fn sidebar_submenu(props: &LeftSidebarSubProps) -> Html {
             {for props.submenu.iter().enumerate().map(|(idx, route)| html!{
                 <li key={idx}>
                     <LinkTag to={route.route.unwrap()} >
-                        {route.icon.clone()} {route.name}
+                        {route.icon.clone()} {tr(route.name)}
                         if props.location == route.route.unwrap() {
                             <span
                                 class="absolute mt-1 mb-1 inset-y-0 left-0 w-1 rounded-tr-md rounded-br-md bg-primary"
@@ -1,7 +1,7 @@
-use std::ops::Deref;
+use std::{cell::RefCell, ops::Deref, rc::Rc};
 
-use dyno_core::DynoPlot;
-use yew::{function_component, html, use_callback, use_mut_ref, use_state, Html};
+use dyno_core::{i18n::tr, DynoPlot, PlotColor};
+use yew::{function_component, html, use_callback, use_mut_ref, use_state, Html, UseStateHandle};
 use yew_hooks::UseWebSocketReadyState;
 use yew_icons::{Icon, IconId};
 use yewdux::prelude::use_store;
@@ -11,6 +11,32 @@ use crate::{
     state::AppState,
 };
 
+/// the desktop app send the samples as JSON `Vec<Data>`, between the start and the stop marker
+fn on_frame(
+    bytes: &[u8],
+    data: &Rc<RefCell<dyno_core::BufferData>>,
+    plot: &UseStateHandle<DynoPlot>,
+    color: &PlotColor,
+) {
+    use dyno_core::{model::live::LiveEvent, serde_json};
+
+    if let Ok(event) = serde_json::from_slice::<LiveEvent>(bytes) {
+        // new run, the chart start from empty
+        if let LiveEvent::Start { .. } = event {
+            *data.borrow_mut() = dyno_core::BufferData::new();
+        }
+    } else if let Ok(de) = serde_json::from_slice::<Vec<dyno_core::Data>>(bytes) {
+        data.borrow_mut().extend_data(de);
+    } else {
+        return;
+    }
+    plot.set(
+        DynoPlot::new()
+            .set_color(color.clone())
+            .create_dyno_plot(&data.borrow()),
+    );
+}
+
 #[function_component(PageLive)]
 pub fn live() -> Html {
     let (state, _) = use_store::<AppState>();
@@ -29,30 +55,12 @@ pub fn live() -> Html {
                     let data = data.clone();
                     let plot = plot.clone();
                     Some(Box::new(move |strings| {
-                        if let Ok(de) =
-                            dyno_core::serde_json::from_str::<Vec<dyno_core::Data>>(&strings)
-                        {
-                            data.borrow_mut().extend_data(de);
-                            plot.set(
-                                DynoPlot::new()
-                                    .set_color(color.clone())
-                                    .create_dyno_plot(&data.borrow()),
-                            );
-                        }
+                        on_frame(strings.as_bytes(), &data, &plot, &color)
                     }))
                 },
                 onmessage_bytes: {
                     Some(Box::new(move |bytes| {
-                        if let Ok(de) =
-                            dyno_core::serde_json::from_slice::<Vec<dyno_core::Data>>(&bytes)
-                        {
-                            data.borrow_mut().extend_data(de);
-                            plot.set(
-                                DynoPlot::new()
-                                    .set_color(color.clone())
-                                    .create_dyno_plot(&data.borrow()),
-                            );
-                        }
+                        on_frame(&bytes, &data, &plot, &color)
                     }))
                 },
                 onerror: Some(Box::new(move |msg| {
@@ -91,7 +99,7 @@ pub fn live() -> Html {
                 disabled={*ws.ready_state == UseWebSocketReadyState::Open}
             >
                 <Icon icon_id={IconId::HeroiconsOutlineRocketLaunch} class="w-4 mr-2"/>
-                {"Start Stream"}
+                {tr("live-start")}
             </Button>
             <Button
                 class="btn px-6 btn-sm normal-case normal-case btn-primary"
@@ -99,7 +107,7 @@ pub fn live() -> Html {
                 disabled={*ws.ready_state == UseWebSocketReadyState::Closed}
             >
                 <Icon icon_id={IconId::HeroiconsOutlineStopCircle} class="w-4 mr-2"/>
-                {"Stop Stream"}
+                {tr("live-stop")}
             </Button>
         </div>
     };
@@ -108,7 +116,7 @@ pub fn live() -> Html {
     html! {
         <Chart
             id={state.me().map(|x| x.uuid.to_string()).unwrap_or("chart_live".to_owned())}
-            title="Dynotest Live Data"
+            title={tr("live-title")}
             plot={plot.clone()}
             {top_side_button}
         />
@@ -60,6 +60,7 @@ macro_rules! route_sidebar {
 #[derive(Debug, Clone, PartialEq)]
 pub struct RouteSideBar<'a> {
     pub icon: yew::Html,
+    /// message key of the name, see `dyno_core::i18n`
     pub name: &'a str,
     pub filter_role: Roles,
     pub route: Option<Route>,
@@ -68,21 +69,21 @@ pub struct RouteSideBar<'a> {
 
 thread_local! {
     pub static SIDE_BAR: std::cell::RefCell<Vec<RouteSideBar<'static>>>  = std::cell::RefCell::new(vec![
-        route_sidebar!(HeroiconsOutlineSquares2X2, "Dashboard", Dashboard, User, "h-6 w-6"),
-        route_sidebar!(HeroiconsOutlineChartBar, "Aktivitas", Activities, User, "h-6 w-6"),
-        route_sidebar!(HeroiconsOutlineUser, "Profil", SettingProfile, User, "h-5 w-5"),
+        route_sidebar!(HeroiconsOutlineSquares2X2, "nav-dashboard", Dashboard, User, "h-6 w-6"),
+        route_sidebar!(HeroiconsOutlineChartBar, "nav-activities", Activities, User, "h-6 w-6"),
+        route_sidebar!(HeroiconsOutlineUser, "nav-profile", SettingProfile, User, "h-5 w-5"),
         route_sidebar!(
             HeroiconsOutlineDocumentDuplicate,
-            "Administration",
+            "nav-administration",
             Admin,
             [
-                route_sidebar!(HeroiconsOutlineUsers, "Users", AdminUsers, Admin, "h-5 w-5"),
-                route_sidebar!(HeroiconsOutlineTableCells, "Dynos", AdminDynos, Admin, "h-5 w-5"),
-                route_sidebar!(HeroiconsOutlineCog, "Infos", AdminInfos, Admin, "h-5 w-5"),
-                route_sidebar!(HeroiconsOutlineInboxStack, "History", AdminHistory, Admin, "h-5 w-5"),
+                route_sidebar!(HeroiconsOutlineUsers, "nav-users", AdminUsers, Admin, "h-5 w-5"),
+                route_sidebar!(HeroiconsOutlineTableCells, "nav-dynos", AdminDynos, Admin, "h-5 w-5"),
+                route_sidebar!(HeroiconsOutlineCog, "nav-infos", AdminInfos, Admin, "h-5 w-5"),
+                route_sidebar!(HeroiconsOutlineInboxStack, "nav-history", AdminHistory, Admin, "h-5 w-5"),
             ]
         ),
-        route_sidebar!(HeroiconsOutlineDocumentText, "SOP", Sop, User, "h-6 w-6"),
+        route_sidebar!(HeroiconsOutlineDocumentText, "nav-sop", Sop, User, "h-6 w-6"),
     ]);
 }
 
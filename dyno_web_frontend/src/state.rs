@@ -1,8 +1,8 @@
 #![allow(unused)]
 
 use dyno_core::{
-    chrono::NaiveDateTime, crypto::TokenDetails, dynotests::DynoTest, serde, users::UserResponse,
-    uuid::Uuid, DynoConfig, HistoryResponse, PlotColor, UserSession,
+    chrono::NaiveDateTime, crypto::TokenDetails, dynotests::DynoTest, i18n::Language, serde,
+    users::UserResponse, uuid::Uuid, DynoConfig, HistoryResponse, PlotColor, UserSession,
 };
 
 use crate::Theme;
@@ -58,6 +58,8 @@ pub struct AppState {
     token_details: Option<TokenDetails>,
     data: Data,
     theme: Theme,
+    #[serde(default)]
+    language: Language,
 }
 
 impl AppState {
@@ -115,4 +117,11 @@ impl AppState {
     pub fn swap_theme(&mut self) {
         self.theme = !self.theme;
     }
+    pub const fn language(&self) -> Language {
+        self.language
+    }
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+        dyno_core::i18n::set_language(language);
+    }
 }